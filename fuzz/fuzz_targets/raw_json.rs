@@ -0,0 +1,16 @@
+//! Runs the raw JSON pass on arbitrary bytes, with and without limits, it may not panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use validation::raw_json::{check_raw_json, check_raw_json_with, RawJsonLimits};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = check_raw_json(data);
+
+    let limits = RawJsonLimits::new()
+        .with_max_size(4096)
+        .with_max_depth(8)
+        .with_max_items(16)
+        .with_max_length(64);
+    let _ = check_raw_json_with(data, &limits);
+});
@@ -0,0 +1,39 @@
+//! Runs the rules taking text on arbitrary UTF-8, none of them may panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use validation::{
+    fits_in, is_date_format, is_length, is_number_format, is_one_of, suggest_one_of, Collation,
+    DATE_STYLES, INTEGER_TYPES,
+};
+
+const LOCALES: [&str; 4] = ["en-US", "de-DE", "fr-FR", "xx"];
+const COLLATIONS: [Collation; 4] = [
+    Collation::Exact,
+    Collation::CaseFold,
+    Collation::Nfkc,
+    Collation::NfkcCaseFold,
+];
+
+fuzz_target!(|data: &[u8]| {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    // The first character picks the parameters, the rest of the text is the value
+    let selector = text.chars().next().map_or(0, |c| c as usize);
+    let value = text.get(1..).unwrap_or(text);
+
+    let _ = fits_in(value, INTEGER_TYPES[selector % INTEGER_TYPES.len()]);
+    let locale = LOCALES[selector % LOCALES.len()];
+    let _ = is_number_format(value, locale);
+    for style in DATE_STYLES.iter() {
+        let _ = is_date_format(value, style, locale);
+    }
+    let _ = is_length(&value.to_string(), Some(selector % 8), Some(selector % 64));
+
+    let allowed = value.split(',').collect::<Vec<_>>();
+    let collation = COLLATIONS[selector % COLLATIONS.len()];
+    let _ = is_one_of(value, &allowed, collation);
+    let _ = suggest_one_of(value, &allowed, collation);
+});
@@ -0,0 +1,92 @@
+//! Memory and time of the errors of a batch of a million rows, each failing on its email, kept
+//! as `ValidationErrors` and as `InternedErrors`
+//!
+//! Run with `cargo bench -p validation --bench interning`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use validation::{
+    FieldError, FieldValidation, InternedErrors, ValidationContext, ValidationError,
+    ValidationErrors, ValidationResult,
+};
+
+const ROWS: usize = 1_000_000;
+
+// Counts the bytes currently allocated
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+struct Row;
+
+impl FieldValidation for Row {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.field_errors(&ValidationContext::default())
+            .into_result()
+    }
+
+    fn field_errors(&self, _: &ValidationContext) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new(
+            "email",
+            ValidationError::FieldMismatch("regex_inline".to_string()),
+        ));
+        errors
+    }
+}
+
+fn measure<T, F: FnOnce() -> T>(name: &str, collect: F) -> T {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let errors = collect();
+    let elapsed = start.elapsed();
+    let retained = ALLOCATED.load(Ordering::Relaxed) - before;
+    println!(
+        "{:<18} {:>8.1} MiB retained, {:>5} ms",
+        name,
+        retained as f64 / (1024.0 * 1024.0),
+        elapsed.as_millis()
+    );
+    errors
+}
+
+fn main() {
+    let ctx = ValidationContext::default();
+    let rows = (0..ROWS).map(|_| Row).collect::<Vec<_>>();
+    println!("errors of {} rows", ROWS);
+
+    let plain = measure("ValidationErrors", || {
+        let mut errors = ValidationErrors::new();
+        for (row, item) in rows.iter().enumerate() {
+            errors.extend_nested(&format!("[{}]", row), item.field_errors(&ctx));
+        }
+        errors
+    });
+    let interned = measure("InternedErrors", || {
+        let mut errors = InternedErrors::new();
+        for (row, item) in rows.iter().enumerate() {
+            errors.extend_row(row, item.field_errors(&ctx));
+        }
+        errors
+    });
+
+    assert_eq!(plain.len(), interned.len());
+    assert_eq!(1, interned.distinct_errors());
+}
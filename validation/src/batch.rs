@@ -0,0 +1,220 @@
+use crate::context::ValidationContext;
+use crate::error::ValidationError;
+use crate::validation::{FieldValidation, Validation, ValidationResult};
+
+/// Outcome of the validation of a batch of items, typically the rows of a bulk import
+#[derive(Debug, PartialEq)]
+pub struct BatchReport {
+    /// Result of each validated item, by index. Items after the truncation are not validated
+    pub outcomes: Vec<ValidationResult<()>>,
+    /// Number of items that failed validation
+    pub error_count: usize,
+    /// Index of the first item that was not validated once the error budget was spent
+    pub truncated_at: Option<usize>,
+    /// Number of items in the batch
+    pub total: usize,
+}
+
+impl BatchReport {
+    /// Returns whether or not every item of the batch passed
+    pub fn passed(&self) -> bool {
+        self.error_count == 0
+    }
+
+    /// Returns whether or not the validation stopped before the end of the batch
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_at.is_some()
+    }
+
+    /// Returns the index and error of every item that failed
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &ValidationError)> {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, outcome)| outcome.as_ref().err().map(|err| (index, err)))
+    }
+}
+
+/// Validates the fields of each item, stopping once `max_errors` items failed
+///
+/// # Arguments
+///
+/// * `items` - `&[T]` batch to validate
+/// * `max_errors` - error budget, the validation stops once that many items failed, so a
+///   budget of 0 stops at the first failure
+///
+/// # Example
+/// ```
+/// use crate::validation::{validate_batch, FieldValidation, ValidationError, ValidationResult};
+///
+/// struct Row(i32);
+///
+/// impl FieldValidation for Row {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         if self.0 >= 0 {
+///             Ok(())
+///         } else {
+///             Err(ValidationError::FieldMismatch("negative".to_string()))
+///         }
+///     }
+/// }
+///
+/// let rows = vec![Row(1), Row(-1), Row(2), Row(-2), Row(-3)];
+/// let report = validate_batch(&rows, 2);
+///
+/// assert!(!report.passed());
+/// assert_eq!(2, report.error_count);
+/// assert_eq!(Some(4), report.truncated_at);
+/// assert_eq!(vec![1, 3], report.errors().map(|(i, _)| i).collect::<Vec<_>>());
+/// ```
+pub fn validate_batch<T: FieldValidation>(items: &[T], max_errors: usize) -> BatchReport {
+    validate_batch_with(items, max_errors, &ValidationContext::default())
+}
+
+/// Same as `validate_batch`, with every item validated in the given `ValidationContext`
+pub fn validate_batch_with<T: FieldValidation>(
+    items: &[T],
+    max_errors: usize,
+    ctx: &ValidationContext,
+) -> BatchReport {
+    run_batch(items, max_errors, |item| item.validate_fields_with(ctx))
+}
+
+/// Runs the `Validation` of every item, stopping once `max_errors` items failed
+///
+/// Typically used on heterogeneous payloads, like the ones of plugins, held as
+/// `Box<dyn Validation>`
+///
+/// # Example
+/// ```
+/// use crate::validation::{
+///     validate_each, FieldValidation, StateValidation, Validation, ValidationError,
+///     ValidationResult,
+/// };
+///
+/// struct Ping;
+/// struct Upload(usize);
+///
+/// impl FieldValidation for Ping {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl StateValidation for Ping {
+///     fn validate_state(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl Validation for Ping {
+///     fn validate(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl FieldValidation for Upload {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl StateValidation for Upload {
+///     fn validate_state(&self) -> ValidationResult<()> {
+///         if self.0 > 1024 {
+///             Err(ValidationError::InvalidState("too_large".to_string()))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// impl Validation for Upload {
+///     fn validate(&self) -> ValidationResult<()> {
+///         self.validate_fields()?;
+///         self.validate_state()
+///     }
+/// }
+///
+/// let payloads: Vec<Box<dyn Validation>> = vec![Box::new(Ping), Box::new(Upload(2048))];
+/// let report = validate_each(&payloads, usize::MAX);
+///
+/// assert_eq!(vec![1], report.errors().map(|(i, _)| i).collect::<Vec<_>>());
+/// ```
+pub fn validate_each<T: Validation>(items: &[T], max_errors: usize) -> BatchReport {
+    run_batch(items, max_errors, |item| item.validate())
+}
+
+fn run_batch<T, F: Fn(&T) -> ValidationResult<()>>(
+    items: &[T],
+    max_errors: usize,
+    validate: F,
+) -> BatchReport {
+    let mut report = BatchReport {
+        outcomes: Vec::with_capacity(items.len()),
+        error_count: 0,
+        truncated_at: None,
+        total: items.len(),
+    };
+
+    for (index, item) in items.iter().enumerate() {
+        if report.error_count > 0 && report.error_count >= max_errors {
+            report.truncated_at = Some(index);
+            break;
+        }
+
+        let outcome = validate(item);
+        if outcome.is_err() {
+            report.error_count += 1;
+        }
+        report.outcomes.push(outcome);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::batch::*;
+
+    struct Row(bool);
+
+    impl FieldValidation for Row {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            if self.0 {
+                Ok(())
+            } else {
+                Err(ValidationError::FieldMismatch("row".to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_passed() {
+        let report = validate_batch(&[Row(true), Row(true)], 0);
+        assert!(report.passed());
+        assert!(!report.is_truncated());
+        assert_eq!(2, report.outcomes.len());
+    }
+
+    #[test]
+    fn test_batch_zero_budget_stops_at_first_failure() {
+        let report = validate_batch(&[Row(true), Row(false), Row(false)], 0);
+        assert_eq!(1, report.error_count);
+        assert_eq!(Some(2), report.truncated_at);
+    }
+
+    #[test]
+    fn test_batch_budget_not_spent() {
+        let report = validate_batch(&[Row(false), Row(true), Row(false)], 5);
+        assert_eq!(2, report.error_count);
+        assert!(!report.is_truncated());
+        assert_eq!(3, report.total);
+    }
+
+    #[test]
+    fn test_batch_last_failure_spends_budget_without_truncating() {
+        let report = validate_batch(&[Row(true), Row(false)], 1);
+        assert!(!report.is_truncated());
+    }
+}
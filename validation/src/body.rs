@@ -0,0 +1,548 @@
+//! body
+//!
+//! Module containing the decoding of request bodies per their `Content-Type`, into a value that
+//! is validated before being returned
+//!
+//! | Content-Type                        | Decoded as                                   |
+//! |-------------------------------------|----------------------------------------------|
+//! | `application/json`, `*/*+json`      | JSON, after the `raw_json` pass              |
+//! | `application/x-www-form-urlencoded` | the fields of a struct                       |
+//! | `text/csv`, with the `csv` feature  | a sequence of rows, like `Vec<Row>`          |
+//!
+//! Form fields and CSV columns are text, they are read as the type of the field they fill
+//!
+//! Every failure is a `ValidationErrorResponse` whose message is the JSON of the
+//! `ValidationErrors`, see `ValidationErrors::to_json`:
+//!
+//! - `415` with `unsupported_media_type` for any other `Content-Type`
+//! - `400` with `invalid_body` when the body does not decode as its `Content-Type`, JSON bodies
+//!   failing with the errors of the `raw_json` pass, `invalid_json` included
+//! - `400` with the errors of the fields of the value, or `422` when the first one is an
+//!   `InvalidState`
+//!
+//! Errors of the fields a JSON object or a form sent under a serde `alias` are attributed to
+//! that alias, see `ValidationErrors::attribute_to_aliases`. Their codes are translated with the
+//! registered `ErrorCodeMap`, as for every other response
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use serde::de::value::{Error, MapDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+
+use crate::context::ValidationContext;
+use crate::error::{FieldError, ValidationError, ValidationErrorResponse, ValidationErrors};
+use crate::error_code_map;
+use crate::raw_json;
+use crate::validation::FieldValidation;
+
+const BAD_REQUEST: i32 = 400;
+const UNSUPPORTED_MEDIA_TYPE: i32 = 415;
+
+/// Decodes the body per its `Content-Type` into `T` and validates its fields
+///
+/// # Arguments
+///
+/// * `content_type` - the `Content-Type` of the request, parameters like `charset` ignored
+/// * `body` - the bytes of the body
+///
+/// # Example
+/// ```
+/// use crate::validation::{body, FieldValidation, ValidationError, ValidationResult};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Signup {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl FieldValidation for Signup {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         if self.age < 18 {
+///             Err(ValidationError::FieldMismatch("min_age".to_string()))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// let form = body::decode_body::<Signup>(
+///     "application/x-www-form-urlencoded",
+///     b"name=Joseph+Orme&age=30",
+/// );
+/// assert_eq!("Joseph Orme", form.unwrap().name);
+///
+/// let json = body::decode_body::<Signup>("application/json", br#"{"name":"Joseph","age":12}"#);
+/// assert_eq!(400, json.err().unwrap().error_code);
+///
+/// let xml = body::decode_body::<Signup>("application/xml", b"<signup/>");
+/// assert_eq!(415, xml.err().unwrap().error_code);
+/// ```
+pub fn decode_body<T>(content_type: &str, body: &[u8]) -> Result<T, ValidationErrorResponse>
+where
+    T: DeserializeOwned + FieldValidation,
+{
+    decode_body_with(content_type, body, &ValidationContext::default())
+}
+
+/// Same as `decode_body`, with the fields validated in the given `ValidationContext`
+pub fn decode_body_with<T>(
+    content_type: &str,
+    body: &[u8],
+    ctx: &ValidationContext,
+) -> Result<T, ValidationErrorResponse>
+where
+    T: DeserializeOwned + FieldValidation,
+{
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    let decoded: Result<T, ValidationErrorResponse> = match media_type.as_str() {
+        "application/json" => decode_json(body),
+        json if json.ends_with("+json") => decode_json(body),
+        "application/x-www-form-urlencoded" => decode_form(body),
+        #[cfg(feature = "csv")]
+        "text/csv" => decode_csv(body),
+        _ => {
+            return Err(translated(
+                UNSUPPORTED_MEDIA_TYPE,
+                &unattached("unsupported_media_type"),
+            ))
+        }
+    };
+
+    let attributed = |mut errors: ValidationErrors| {
+        if !T::field_aliases().is_empty() {
+            errors.attribute_to_aliases(T::field_aliases(), &sent_names(&media_type, body));
+        }
+        errors
+    };
+    let value = match decoded {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(match ValidationErrors::from_embedded(&err.error_message) {
+                Some(errors) => translated(err.error_code, &attributed(errors)),
+                None => err,
+            })
+        }
+    };
+
+    let errors = attributed(value.field_errors(ctx));
+    match errors.first() {
+        None => Ok(value),
+        Some(first) => {
+            let error_code = ValidationErrorResponse::from(first.error.clone()).error_code;
+            Err(translated(error_code, &errors))
+        }
+    }
+}
+
+// Response of `decode_body`, the codes of the errors translated with the registered
+// `ErrorCodeMap`. The decoders leave them as they are, their errors being attributed first
+fn translated(error_code: i32, errors: &ValidationErrors) -> ValidationErrorResponse {
+    let mut translated = ValidationErrors::new();
+    for error in errors.iter() {
+        translated.push(FieldError {
+            error: error_code_map::translate_registered(error.error.clone()),
+            ..error.clone()
+        });
+    }
+    response(error_code, &translated).with_retryable(errors.is_retryable())
+}
+
+fn response(error_code: i32, errors: &ValidationErrors) -> ValidationErrorResponse {
+    ValidationErrorResponse::new(error_code, errors.to_json()).with_retryable(errors.is_retryable())
+}
+
+fn unattached(code: &str) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.push(FieldError::unattached(ValidationError::FieldMismatch(
+        code.to_string(),
+    )));
+    errors
+}
+
+// The body does not decode, reported with the errors embedded in the message by
+// `#[derive(DeserializeValidated)]` when there are any
+fn invalid_body<E: Display>(err: E) -> ValidationErrorResponse {
+    let message = err.to_string();
+    let errors =
+        ValidationErrors::from_embedded(&message).unwrap_or_else(|| unattached("invalid_body"));
+    response(BAD_REQUEST, &errors)
+}
+
+fn decode_json<T: DeserializeOwned>(body: &[u8]) -> Result<T, ValidationErrorResponse> {
+    let errors = raw_json::check_raw_json(body);
+    if !errors.is_empty() {
+        return Err(response(BAD_REQUEST, &errors));
+    }
+    serde_json::from_slice(body).map_err(invalid_body)
+}
+
+fn decode_form<T: DeserializeOwned>(body: &[u8]) -> Result<T, ValidationErrorResponse> {
+    let fields = form_fields(body).map_err(invalid_body)?;
+    T::deserialize(Fields(fields)).map_err(invalid_body)
+}
+
+fn form_fields(body: &[u8]) -> Result<Vec<(String, String)>, Error> {
+    body.split(|byte| *byte == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, |byte| *byte == b'=');
+            let name = percent_decode(parts.next().unwrap_or_default())?;
+            let value = percent_decode(parts.next().unwrap_or_default())?;
+            Ok((name, value))
+        })
+        .collect()
+}
+
+// Names of the fields of a JSON object or a form, empty for the other bodies
+fn sent_names(media_type: &str, body: &[u8]) -> Vec<String> {
+    match media_type {
+        "application/x-www-form-urlencoded" => form_fields(body)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect(),
+        json if json == "application/json" || json.ends_with("+json") => {
+            serde_json::from_slice::<BTreeMap<String, de::IgnoredAny>>(body)
+                .map(|object| object.into_keys().collect())
+                .unwrap_or_default()
+        }
+        _ => vec![],
+    }
+}
+
+#[cfg(feature = "csv")]
+fn decode_csv<T: DeserializeOwned>(body: &[u8]) -> Result<T, ValidationErrorResponse> {
+    let mut reader = csv::Reader::from_reader(body);
+    let headers = reader.headers().map_err(invalid_body)?.clone();
+    let rows = reader
+        .records()
+        .map(|record| {
+            record.map(|record| {
+                Fields(
+                    headers
+                        .iter()
+                        .zip(record.iter())
+                        .map(|(header, value)| (header.to_string(), value.to_string()))
+                        .collect(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid_body)?;
+
+    T::deserialize(de::value::SeqDeserializer::new(rows.into_iter())).map_err(invalid_body)
+}
+
+// Decodes a name or a value of a form, `+` being a space
+fn percent_decode(encoded: &[u8]) -> Result<String, Error> {
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.iter();
+    while let Some(byte) = bytes.next() {
+        match *byte {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = [*bytes.next().unwrap_or(&0), *bytes.next().unwrap_or(&0)];
+                // `from_str_radix` takes a sign, `%+1` is no byte
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return Err(de::Error::custom("invalid percent encoding"));
+                }
+                let byte = std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| de::Error::custom("invalid percent encoding"))?;
+                decoded.push(byte);
+            }
+            byte => decoded.push(byte),
+        }
+    }
+    String::from_utf8(decoded).map_err(de::Error::custom)
+}
+
+// Named text values, the fields of a form or the columns of a CSV row, deserialized as a map
+struct Fields(Vec<(String, String)>);
+
+impl<'de> de::Deserializer<'de> for Fields {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut map =
+            MapDeserializer::new(self.0.into_iter().map(|(name, value)| (name, Text(value))));
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Fields {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// A text value, parsed as the type it is deserialized to
+struct Text(String);
+
+impl Text {
+    fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        T::Err: Display,
+    {
+        self.0.parse().map_err(de::Error::custom)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Text {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_parsed!(
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char
+    );
+
+    // An empty value is a missing one
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Text {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::*;
+    use crate::error_code_map::ErrorCodeMap;
+    use crate::validation::ValidationResult;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Order {
+        sku: String,
+        quantity: u32,
+        gift: Option<bool>,
+    }
+
+    impl FieldValidation for Order {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            if self.quantity == 0 {
+                Err(ValidationError::FieldMismatch("min".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn errors(response: ValidationErrorResponse) -> (i32, Vec<String>) {
+        let errors = ValidationErrors::from_embedded(&response.error_message).unwrap();
+        (
+            response.error_code,
+            errors.iter().map(|err| err.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let order = decode_body::<Order>(
+            "application/json; charset=utf-8",
+            br#"{ "sku": "A-1", "quantity": 2 }"#,
+        );
+        assert_eq!(
+            Order {
+                sku: "A-1".to_string(),
+                quantity: 2,
+                gift: None
+            },
+            order.unwrap()
+        );
+        assert!(decode_body::<Order>(
+            "application/vnd.order+json",
+            br#"{ "sku": "A-1", "quantity": 2 }"#
+        )
+        .is_ok());
+
+        let duplicated = decode_body::<Order>(
+            "application/json",
+            br#"{ "sku": "A-1", "quantity": 2, "quantity": 0 }"#,
+        );
+        assert_eq!(
+            (400, vec!["/quantity: duplicate_key".to_string()]),
+            errors(duplicated.err().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_decode_form() {
+        let order = decode_body::<Order>(
+            "Application/X-WWW-Form-Urlencoded",
+            b"sku=A%2D1+b&quantity=3&gift=true",
+        );
+        assert_eq!(
+            Order {
+                sku: "A-1 b".to_string(),
+                quantity: 3,
+                gift: Some(true)
+            },
+            order.unwrap()
+        );
+
+        let invalid =
+            decode_body::<Order>("application/x-www-form-urlencoded", b"sku=A&quantity=x");
+        assert_eq!(
+            (400, vec!["invalid_body".to_string()]),
+            errors(invalid.err().unwrap())
+        );
+        let invalid = decode_body::<Order>("application/x-www-form-urlencoded", b"sku=%zz");
+        assert_eq!(400, invalid.err().unwrap().error_code);
+    }
+
+    #[test]
+    fn test_decode_form_signed_percent_encoding() {
+        for encoded in [
+            &b"sku=%+1&quantity=3"[..],
+            b"sku=%-1&quantity=3",
+            b"sku=%1&quantity=3",
+        ] {
+            assert_eq!(
+                (400, vec!["invalid_body".to_string()]),
+                errors(
+                    decode_body::<Order>("application/x-www-form-urlencoded", encoded)
+                        .err()
+                        .unwrap()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_translated_with_registered_map() {
+        let _registry = error_code_map::REGISTRY_LOCK
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        ErrorCodeMap::new()
+            .map("unsupported_media_type", "unsupported_content_type")
+            .map("min", "too_few")
+            .register();
+        let unsupported = decode_body::<Order>("text/plain", b"sku").err().unwrap();
+        let invalid =
+            decode_body::<Order>("application/x-www-form-urlencoded", b"sku=A&quantity=0")
+                .err()
+                .unwrap();
+        ErrorCodeMap::unregister();
+
+        assert_eq!(
+            (415, vec!["unsupported_content_type".to_string()]),
+            errors(unsupported)
+        );
+        assert_eq!((400, vec!["too_few".to_string()]), errors(invalid));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_decode_csv() {
+        let orders =
+            decode_body::<Vec<Order>>("text/csv", b"sku,quantity,gift\nA-1,2,\nB-2,1,false\n");
+        assert_eq!(2, orders.unwrap().len());
+
+        let invalid = decode_body::<Vec<Order>>("text/csv", b"sku,quantity,gift\nA-1,2,\nB-2,0,\n");
+        assert_eq!(
+            (400, vec!["[1]: min".to_string()]),
+            errors(invalid.err().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_mismatched_content_type() {
+        assert_eq!(
+            (415, vec!["unsupported_media_type".to_string()]),
+            errors(decode_body::<Order>("text/plain", b"sku").err().unwrap())
+        );
+        assert_eq!(
+            (400, vec![": invalid_json".to_string()]),
+            errors(
+                decode_body::<Order>("application/json", b"sku=A-1&quantity=2")
+                    .err()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            (400, vec!["min".to_string()]),
+            errors(
+                decode_body::<Order>("application/json", br#"{ "sku": "A", "quantity": 0 }"#)
+                    .err()
+                    .unwrap()
+            )
+        );
+    }
+}
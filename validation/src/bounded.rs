@@ -0,0 +1,423 @@
+//! Newtypes encoding length limits in their type, checked whenever a value is built or
+//! deserialized: `BoundedString`, `BoundedVec` and `NonEmpty` for the common "at least one"
+//!
+//! Their failures are the `length` error of the derive's rule, so they report like any other
+//! field of a derived struct
+
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Result};
+use std::ops::Deref;
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use crate::context::ValidationContext;
+use crate::error::{FieldError, ValidationError, ValidationErrors};
+use crate::length::HasLen;
+use crate::validation::{FieldValidation, ValidationResult};
+
+fn length_error() -> ValidationError {
+    ValidationError::FieldMismatch("length".to_string())
+}
+
+/// `String` of `MIN` to `MAX` characters, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::BoundedString;
+///
+/// type Name = BoundedString<1, 8>;
+///
+/// assert_eq!("Joseph", Name::new("Joseph").unwrap().as_str());
+/// assert!(Name::new("").is_err());
+/// assert!(serde_json::from_str::<Name>("\"Bartholomew\"").is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BoundedString<const MIN: usize, const MAX: usize>(String);
+
+impl<const MIN: usize, const MAX: usize> BoundedString<MIN, MAX> {
+    // Fails the build of any use of bounds that no value can satisfy
+    const BOUNDS: () = assert!(MIN <= MAX, "`MIN` is greater than `MAX`");
+
+    /// Creates a new `BoundedString`, failing with `length` out of the bounds
+    pub fn new<S: Into<String>>(value: S) -> ValidationResult<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::BOUNDS;
+        let value = value.into();
+        if crate::is_length(&value, Some(MIN), Some(MAX)) {
+            Ok(BoundedString(value))
+        } else {
+            Err(length_error())
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Deref for BoundedString<MIN, MAX> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> AsRef<str> for BoundedString<MIN, MAX> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> HasLen for BoundedString<MIN, MAX> {
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Display for BoundedString<MIN, MAX> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> TryFrom<String> for BoundedString<MIN, MAX> {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> ValidationResult<Self> {
+        BoundedString::new(value)
+    }
+}
+
+impl<'a, const MIN: usize, const MAX: usize> TryFrom<&'a str> for BoundedString<MIN, MAX> {
+    type Error = ValidationError;
+
+    fn try_from(value: &'a str) -> ValidationResult<Self> {
+        BoundedString::new(value)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Serialize for BoundedString<MIN, MAX> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, const MIN: usize, const MAX: usize> Deserialize<'de> for BoundedString<MIN, MAX> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        BoundedString::new(value).map_err(|_| {
+            D::Error::custom(format!("length: expected {} to {} characters", MIN, MAX))
+        })
+    }
+}
+
+/// A `BoundedString` is valid once built, the bounds are checked again in case they are wrong
+impl<const MIN: usize, const MAX: usize> FieldValidation for BoundedString<MIN, MAX> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        if crate::is_length(&self.0, Some(MIN), Some(MAX)) {
+            Ok(())
+        } else {
+            Err(length_error())
+        }
+    }
+}
+
+/// `Vec` of at most `MAX` elements, each one validated as an element of a `Vec`
+///
+/// # Example
+/// ```
+/// use crate::validation::BoundedVec;
+///
+/// type Tags = BoundedVec<String, 2>;
+///
+/// assert_eq!(2, Tags::new(vec!["a".to_string(), "b".to_string()]).unwrap().len());
+/// assert!(serde_json::from_str::<Tags>(r#"["a", "b", "c"]"#).is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BoundedVec<T, const MAX: usize>(Vec<T>);
+
+impl<T, const MAX: usize> BoundedVec<T, MAX> {
+    /// Creates a new `BoundedVec`, failing with `length` over `MAX` elements
+    pub fn new(values: Vec<T>) -> ValidationResult<Self> {
+        if values.len() <= MAX {
+            Ok(BoundedVec(values))
+        } else {
+            Err(length_error())
+        }
+    }
+
+    /// Adds an element, failing with `length` and leaving the elements as they are when full
+    pub fn push(&mut self, value: T) -> ValidationResult<()> {
+        if self.0.len() < MAX {
+            self.0.push(value);
+            Ok(())
+        } else {
+            Err(length_error())
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MAX: usize> Default for BoundedVec<T, MAX> {
+    fn default() -> Self {
+        BoundedVec(Vec::new())
+    }
+}
+
+impl<T, const MAX: usize> Deref for BoundedVec<T, MAX> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const MAX: usize> AsRef<[T]> for BoundedVec<T, MAX> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const MAX: usize> HasLen for BoundedVec<T, MAX> {
+    fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T: Display, const MAX: usize> Display for BoundedVec<T, MAX> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let values = self.0.iter().map(T::to_string);
+        write!(f, "[{}]", values.collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl<T, const MAX: usize> TryFrom<Vec<T>> for BoundedVec<T, MAX> {
+    type Error = ValidationError;
+
+    fn try_from(values: Vec<T>) -> ValidationResult<Self> {
+        BoundedVec::new(values)
+    }
+}
+
+impl<T: Serialize, const MAX: usize> Serialize for BoundedVec<T, MAX> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const MAX: usize> Deserialize<'de> for BoundedVec<T, MAX> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        BoundedVec::new(values)
+            .map_err(|_| D::Error::custom(format!("length: expected at most {} elements", MAX)))
+    }
+}
+
+impl<T: FieldValidation, const MAX: usize> FieldValidation for BoundedVec<T, MAX> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.field_errors(&ValidationContext::default())
+            .into_result()
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.field_errors(ctx).into_result()
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        if self.0.len() > MAX {
+            errors.push(FieldError::unattached(length_error()));
+        }
+        self.0
+            .field_errors(ctx)
+            .into_iter()
+            .for_each(|err| errors.push(err));
+        errors
+    }
+}
+
+/// `Vec` of at least one element, each one validated as an element of a `Vec`
+///
+/// `#[validate(nested)]` walks into it like into a `Vec`, the elements reporting their errors
+/// prefixed by their index
+///
+/// # Example
+/// ```
+/// use crate::validation::NonEmpty;
+///
+/// let lines = NonEmpty::new(vec!["A-1", "A-2"]).unwrap();
+/// assert_eq!(&"A-1", lines.first());
+/// assert!(NonEmpty::<u8>::new(vec![]).is_err());
+/// assert!(serde_json::from_str::<NonEmpty<u8>>("[]").is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NonEmpty<T>(Vec<T>);
+
+impl<T> NonEmpty<T> {
+    /// Creates a new `NonEmpty`, failing with `length` without any element
+    pub fn new(values: Vec<T>) -> ValidationResult<Self> {
+        if values.is_empty() {
+            Err(length_error())
+        } else {
+            Ok(NonEmpty(values))
+        }
+    }
+
+    /// Creates a new `NonEmpty` of a single element
+    pub fn of(value: T) -> Self {
+        NonEmpty(vec![value])
+    }
+
+    pub fn first(&self) -> &T {
+        &self.0[0]
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for NonEmpty<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> AsRef<[T]> for NonEmpty<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> IntoIterator for NonEmpty<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a NonEmpty<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T> HasLen for NonEmpty<T> {
+    fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T: Display> Display for NonEmpty<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let values = self.0.iter().map(T::to_string);
+        write!(f, "[{}]", values.collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for NonEmpty<T> {
+    type Error = ValidationError;
+
+    fn try_from(values: Vec<T>) -> ValidationResult<Self> {
+        NonEmpty::new(values)
+    }
+}
+
+impl<T: Serialize> Serialize for NonEmpty<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for NonEmpty<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        NonEmpty::new(values).map_err(|_| D::Error::custom("length: expected at least 1 element"))
+    }
+}
+
+impl<T: FieldValidation> FieldValidation for NonEmpty<T> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.field_errors(&ValidationContext::default())
+            .into_result()
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.field_errors(ctx).into_result()
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        self.0.field_errors(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounded::*;
+
+    #[test]
+    fn test_bounded_string() {
+        assert!(BoundedString::<2, 3>::new("ab").is_ok());
+        assert_eq!(Err(length_error()), BoundedString::<2, 3>::new("a"));
+        assert_eq!(Err(length_error()), BoundedString::<2, 3>::try_from("abcd"));
+        assert_eq!(
+            "abc",
+            BoundedString::<2, 3>::new("abc").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_bounded_vec() {
+        let mut values = BoundedVec::<u8, 2>::new(vec![1]).unwrap();
+        assert!(values.push(2).is_ok());
+        assert_eq!(Err(length_error()), values.push(3));
+        assert_eq!("[1, 2]", values.to_string());
+        assert!(values.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn test_bounded_serde() {
+        let name: BoundedString<1, 4> = serde_json::from_str("\"Eve\"").unwrap();
+        assert_eq!("\"Eve\"", serde_json::to_string(&name).unwrap());
+
+        let err = serde_json::from_str::<BoundedVec<u8, 1>>("[1, 2]").unwrap_err();
+        assert!(err.to_string().starts_with("length"));
+    }
+
+    #[test]
+    fn test_non_empty() {
+        let mut values = NonEmpty::of(1u8);
+        values.push(2);
+        assert_eq!("[1, 2]", values.to_string());
+        assert_eq!(Err(length_error()), NonEmpty::<u8>::try_from(vec![]));
+        assert_eq!(vec![1, 2], values.into_iter().collect::<Vec<_>>());
+    }
+}
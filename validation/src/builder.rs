@@ -0,0 +1,103 @@
+//! builder
+//!
+//! Module containing the adapter validating the values of builders once they are built, so
+//! invalid values cannot be constructed through a builder either
+//!
+//! With the `derive_builder` feature the errors of a `derive_builder` builder convert to
+//! `ValidationErrors`, so the builder can use them as its error type:
+//!
+//! ```ignore
+//! #[derive(Builder, FieldValidate)]
+//! #[builder(build_fn(private, name = "build_unvalidated", error = "ValidationErrors"))]
+//! struct Order {
+//!     #[validate(length(min = 1, max = 8))]
+//!     reference: String,
+//! }
+//!
+//! impl OrderBuilder {
+//!     pub fn build(&self) -> Result<Order, ValidationErrors> {
+//!         validation::builder::validated(self.build_unvalidated()?)
+//!     }
+//! }
+//! ```
+
+use crate::context::ValidationContext;
+#[cfg(feature = "derive_builder")]
+use crate::error::ValidationError;
+use crate::error::{FieldError, ValidationErrors};
+use crate::validation::Validation;
+
+/// Returns the built value when it passes its `Validation`, every failed field rule otherwise
+///
+/// The state is validated once the fields pass, its error is unattached to any field
+///
+/// # Example
+/// ```
+/// use crate::validation::builder;
+/// use crate::validation::{
+///     FieldValidation, StateValidation, Validation, ValidationError, ValidationResult,
+/// };
+///
+/// struct Port(u16);
+///
+/// impl FieldValidation for Port {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl StateValidation for Port {
+///     fn validate_state(&self) -> ValidationResult<()> {
+///         if [80, 443].contains(&self.0) {
+///             Ok(())
+///         } else {
+///             Err(ValidationError::InvalidState("port".to_string()))
+///         }
+///     }
+/// }
+///
+/// impl Validation for Port {
+///     fn validate(&self) -> ValidationResult<()> {
+///         self.validate_fields().and_then(|_| self.validate_state())
+///     }
+/// }
+///
+/// assert!(builder::validated(Port(443)).is_ok());
+/// assert_eq!(Some(1), builder::validated(Port(22)).err().map(|e| e.len()));
+/// ```
+pub fn validated<T: Validation>(value: T) -> Result<T, ValidationErrors> {
+    validated_with(value, &ValidationContext::default())
+}
+
+/// Same as `validated`, with the field rules reading the given `ValidationContext`
+pub fn validated_with<T: Validation>(
+    value: T,
+    ctx: &ValidationContext,
+) -> Result<T, ValidationErrors> {
+    let errors = value.field_errors(ctx);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    match value.validate_state() {
+        Ok(()) => Ok(value),
+        Err(err) => {
+            let mut errors = ValidationErrors::new();
+            errors.push(FieldError::unattached(err));
+            Err(errors)
+        }
+    }
+}
+
+/// A field left unset on the builder fails like a missing value, with `not_null`
+#[cfg(feature = "derive_builder")]
+impl From<derive_builder::UninitializedFieldError> for ValidationErrors {
+    fn from(err: derive_builder::UninitializedFieldError) -> Self {
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new(
+            err.field_name(),
+            ValidationError::FieldMismatch("not_null".to_string()),
+        ));
+        errors
+    }
+}
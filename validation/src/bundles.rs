@@ -0,0 +1,114 @@
+//! bundles
+//!
+//! Vetted sets of rules for common domain fields, used by the derive through
+//! `#[validate(bundle = "address")]` or added to a `Schema` as they are
+//!
+//! Each bundle only uses rules both the derive and the `Schema` evaluate
+
+use crate::description::FieldConstraintDescription;
+
+/// Names of the bundles, as used in `#[validate(bundle = "...")]`
+pub const BUNDLES: [&str; 3] = ["address", "person_name", "contact_info"];
+
+/// A line of a postal address: letters, digits and the usual punctuation, at most 200 characters
+///
+/// # Example
+/// ```
+/// use crate::validation::bundles::address;
+/// use crate::validation::schema::Schema;
+/// use crate::validation::ValidationContext;
+/// use serde_json::json;
+///
+/// let schema = Schema::new(address("street"));
+/// let ctx = ValidationContext::default();
+/// assert!(schema.validate(&json!({ "street": "221B Baker Street" }), &ctx).is_ok());
+/// assert!(schema.validate(&json!({ "street": "<script>" }), &ctx).is_err());
+/// ```
+pub fn address(field: &str) -> Vec<FieldConstraintDescription> {
+    vec![
+        FieldConstraintDescription::new(field, "length", None)
+            .param("min", 1)
+            .param("max", 200),
+        FieldConstraintDescription::new(field, "regex_inline", None)
+            .param("pattern", r"^[\p{L}\p{N}][\p{L}\p{N}\p{M} .,'#/-]*$"),
+    ]
+}
+
+/// A given name or a family name: letters, with spaces, apostrophes, dots and hyphens in
+/// between, at most 100 characters
+pub fn person_name(field: &str) -> Vec<FieldConstraintDescription> {
+    vec![
+        FieldConstraintDescription::new(field, "length", None)
+            .param("min", 1)
+            .param("max", 100),
+        FieldConstraintDescription::new(field, "regex_inline", None)
+            .param("pattern", r"^\p{L}[\p{L}\p{M}' .-]*$"),
+    ]
+}
+
+/// An email address or a phone number, at most 254 characters as for an email address
+pub fn contact_info(field: &str) -> Vec<FieldConstraintDescription> {
+    vec![
+        FieldConstraintDescription::new(field, "length", None)
+            .param("min", 3)
+            .param("max", 254),
+        FieldConstraintDescription::new(field, "regex_inline", None).param(
+            "pattern",
+            r"^(?:[^@\s]+@[^@\s]+\.[^@\s]+|\+?[0-9][0-9 ().-]{5,18}[0-9])$",
+        ),
+    ]
+}
+
+/// Returns the rules of the bundle for the field, `None` when there is no such bundle
+pub fn find(bundle: &str, field: &str) -> Option<Vec<FieldConstraintDescription>> {
+    match bundle {
+        "address" => Some(address(field)),
+        "person_name" => Some(person_name(field)),
+        "contact_info" => Some(contact_info(field)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundles::*;
+    use crate::context::ValidationContext;
+    use crate::schema::Schema;
+    use serde_json::json;
+
+    fn is_valid(bundle: &str, value: &str) -> bool {
+        Schema::new(find(bundle, "field").unwrap())
+            .validate(&json!({ "field": value }), &ValidationContext::default())
+            .is_ok()
+    }
+
+    #[test]
+    fn test_every_bundle_found() {
+        for bundle in BUNDLES.iter() {
+            assert!(find(bundle, "field").is_some());
+        }
+        assert!(find("unknown", "field").is_none());
+    }
+
+    #[test]
+    fn test_person_name() {
+        assert!(is_valid("person_name", "Anne-Marie O'Neil"));
+        assert!(is_valid("person_name", "Zoë"));
+        assert!(!is_valid("person_name", "R2D2"));
+        assert!(!is_valid("person_name", ""));
+    }
+
+    #[test]
+    fn test_contact_info() {
+        assert!(is_valid("contact_info", "joseph@example.com"));
+        assert!(is_valid("contact_info", "+1 (555) 010-0199"));
+        assert!(!is_valid("contact_info", "joseph@"));
+        assert!(!is_valid("contact_info", "call me"));
+    }
+
+    #[test]
+    fn test_address() {
+        assert!(is_valid("address", "12, rue de l'Église"));
+        assert!(!is_valid("address", " leading space"));
+    }
+}
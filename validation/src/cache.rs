@@ -0,0 +1,368 @@
+//! cache
+//!
+//! Module containing the cache of validation outcomes, keyed by the serialized payload, for
+//! endpoints receiving the same payload again and again, like webhooks during retry storms
+//!
+//! The `OutcomeCache` of the environment is pluggable, `LruOutcomeCache` keeps the outcomes in
+//! memory behind the `cache` feature. Only `field_errors` is cached: the rules waiting on I/O
+//! are not, and outcomes that `is_retryable` are never stored
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use serde::Serialize;
+
+use crate::context::ValidationContext;
+use crate::error::ValidationErrors;
+use crate::validation::FieldValidation;
+
+/// Key of a cached outcome: the type of the payload, the parts of the context the rules read,
+/// the mode and `max_errors` of its config cutting the outcome short, and the serialized payload
+///
+/// Keys compare by their whole content, so two payloads can never share an outcome. `digest`
+/// is a hash of it, for stores addressing entries by a fixed size key
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OutcomeKey {
+    content: String,
+}
+
+impl OutcomeKey {
+    /// Creates the key of the value validated in the context, `None` when the value does not
+    /// serialize
+    pub fn new<T: Serialize>(value: &T, ctx: &ValidationContext) -> Option<Self> {
+        let payload = serde_json::to_string(value).ok()?;
+        let mut features = ctx.environment().features().collect::<Vec<_>>();
+        features.sort_unstable();
+        let content = format!(
+            "{}|{}|{}|{:?}|{}|{}|{:?}|{:?}|{}",
+            std::any::type_name::<T>(),
+            ctx.environment().locale(),
+            features.join(","),
+            ctx.api_version(),
+            ctx.tenant().unwrap_or_default(),
+            ctx.max_depth(),
+            ctx.config().mode(),
+            ctx.config().max_errors(),
+            payload
+        );
+        Some(OutcomeKey { content })
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns a hash of the key, stable within a build of the library
+    pub fn digest(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.content.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Store of validation outcomes, typically bounded in size or in time
+pub trait OutcomeCache: Send + Sync {
+    /// Returns the outcome stored for the key, if any
+    fn get(&self, key: &OutcomeKey) -> Option<ValidationErrors>;
+
+    /// Stores the outcome of the key, replacing the one stored before if any
+    fn insert(&self, key: OutcomeKey, outcome: ValidationErrors);
+}
+
+/// Returns the `field_errors` of the value, from the `OutcomeCache` of the environment when
+/// the same payload was validated in the same context before
+///
+/// Cached outcomes are returned as they were found: the observer of the environment is not
+/// told again of the warnings and deprecations, and the rules reading the time of the context
+/// (`past`, `future`, `min_age`...) are not run again. A cache for types having those keeps
+/// its outcomes for a short time only. Without a cache in the environment, or a value that
+/// does not serialize, the value is validated as usual
+///
+/// The payload is keyed as it serializes, so types with rules on fields serde skips must not be
+/// cached: two values differing only in such a field would share one outcome
+///
+/// # Arguments
+///
+/// * `value` - value to validate, serialized to key its outcome
+/// * `ctx` - `ValidationContext` of the validation, holding the cache
+pub fn validate_cached<T: FieldValidation + Serialize>(
+    value: &T,
+    ctx: &ValidationContext,
+) -> ValidationErrors {
+    let cache = match ctx.environment().outcome_cache() {
+        Some(cache) => cache,
+        None => return value.field_errors(ctx),
+    };
+    let key = match OutcomeKey::new(value, ctx) {
+        Some(key) => key,
+        None => return value.field_errors(ctx),
+    };
+    if let Some(outcome) = cache.get(&key) {
+        return outcome;
+    }
+
+    let outcome = value.field_errors(ctx);
+    if !outcome.is_retryable() {
+        cache.insert(key, outcome.clone());
+    }
+    outcome
+}
+
+#[cfg(feature = "cache")]
+pub use lru::LruOutcomeCache;
+
+#[cfg(feature = "cache")]
+mod lru {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::Mutex;
+
+    use crate::cache::{OutcomeCache, OutcomeKey};
+    use crate::error::ValidationErrors;
+
+    /// `OutcomeCache` keeping in memory the outcomes of the most recently used keys, up to
+    /// its capacity
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::cache::{LruOutcomeCache, OutcomeCache, OutcomeKey};
+    /// use crate::validation::{ValidationContext, ValidationErrors};
+    ///
+    /// let cache = LruOutcomeCache::new(2);
+    /// let ctx = ValidationContext::default();
+    /// let key = |payload: &str| OutcomeKey::new(&payload, &ctx).unwrap();
+    ///
+    /// cache.insert(key("a"), ValidationErrors::new());
+    /// cache.insert(key("b"), ValidationErrors::new());
+    /// assert!(cache.get(&key("a")).is_some());
+    ///
+    /// // "b" is the least recently used, it makes room for "c"
+    /// cache.insert(key("c"), ValidationErrors::new());
+    /// assert!(cache.get(&key("b")).is_none());
+    /// assert_eq!(2, cache.len());
+    /// ```
+    #[derive(Debug)]
+    pub struct LruOutcomeCache {
+        capacity: usize,
+        entries: Mutex<Entries>,
+    }
+
+    #[derive(Debug, Default)]
+    struct Entries {
+        // Outcomes, along with the tick of their last use
+        outcomes: HashMap<OutcomeKey, (ValidationErrors, u64)>,
+        // Keys by the tick of their last use, the least recently used first
+        uses: BTreeMap<u64, OutcomeKey>,
+        tick: u64,
+    }
+
+    impl Entries {
+        // Marks the key as the most recently used, returning its outcome
+        fn touch(&mut self, key: &OutcomeKey) -> Option<ValidationErrors> {
+            self.tick += 1;
+            let tick = self.tick;
+            let (outcome, used) = self.outcomes.get_mut(key)?;
+            if let Some(key) = self.uses.remove(used) {
+                self.uses.insert(tick, key);
+            }
+            *used = tick;
+            Some(outcome.clone())
+        }
+    }
+
+    impl LruOutcomeCache {
+        /// Creates a cache holding up to `capacity` outcomes, at least one
+        pub fn new(capacity: usize) -> Self {
+            LruOutcomeCache {
+                capacity: capacity.max(1),
+                entries: Mutex::new(Entries::default()),
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .outcomes
+                .len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+    }
+
+    impl OutcomeCache for LruOutcomeCache {
+        fn get(&self, key: &OutcomeKey) -> Option<ValidationErrors> {
+            self.entries
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .touch(key)
+        }
+
+        fn insert(&self, key: OutcomeKey, outcome: ValidationErrors) {
+            let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+            entries.tick += 1;
+            let tick = entries.tick;
+            if let Some((_, used)) = entries.outcomes.insert(key.clone(), (outcome, tick)) {
+                entries.uses.remove(&used);
+            }
+            entries.uses.insert(tick, key);
+            while entries.outcomes.len() > self.capacity {
+                let oldest = entries.uses.keys().next().copied();
+                match oldest.and_then(|used| entries.uses.remove(&used)) {
+                    Some(key) => entries.outcomes.remove(&key),
+                    None => break,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::*;
+    use crate::context::ValidationEnvironment;
+    use crate::error::{FieldError, ValidationError};
+    use crate::global_config::{ValidationConfig, ValidationMode};
+    use crate::validation::ValidationResult;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    static VALIDATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Serialize)]
+    struct Webhook {
+        event: String,
+    }
+
+    impl FieldValidation for Webhook {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            self.field_errors(&ValidationContext::default())
+                .into_result()
+        }
+
+        fn field_errors(&self, _: &ValidationContext) -> ValidationErrors {
+            VALIDATIONS.fetch_add(1, Ordering::SeqCst);
+            let mut errors = ValidationErrors::new();
+            let code = match self.event.as_str() {
+                "ok" => return errors,
+                "slow" => "validation_timeout",
+                _ => "one_of",
+            };
+            errors.push(FieldError::new(
+                "event",
+                ValidationError::InvalidState(code.to_string()),
+            ));
+            errors
+        }
+    }
+
+    // Fails both of its rules, reporting as many failures as the config allows
+    #[derive(Serialize)]
+    struct Transfer;
+
+    impl FieldValidation for Transfer {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            self.field_errors(&ValidationContext::default())
+                .into_result()
+        }
+
+        fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+            let mut errors = ValidationErrors::new();
+            for field in &["amount", "currency"] {
+                errors.push(FieldError::new(
+                    field,
+                    ValidationError::FieldMismatch("not_null".to_string()),
+                ));
+            }
+            ctx.config().limit(errors)
+        }
+    }
+
+    // `OutcomeCache` without eviction, the LRU one needs the `cache` feature
+    #[derive(Default)]
+    struct MapCache(Mutex<HashMap<OutcomeKey, ValidationErrors>>);
+
+    impl OutcomeCache for MapCache {
+        fn get(&self, key: &OutcomeKey) -> Option<ValidationErrors> {
+            self.0.lock().unwrap().get(key).cloned()
+        }
+
+        fn insert(&self, key: OutcomeKey, outcome: ValidationErrors) {
+            self.0.lock().unwrap().insert(key, outcome);
+        }
+    }
+
+    fn webhook(event: &str) -> Webhook {
+        Webhook {
+            event: event.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_outcome_key() {
+        let ctx = ValidationContext::default();
+        let key = OutcomeKey::new(&webhook("ok"), &ctx).unwrap();
+
+        assert_eq!(Some(&key), OutcomeKey::new(&webhook("ok"), &ctx).as_ref());
+        assert_eq!(key.digest(), key.clone().digest());
+        assert!(key.content().ends_with(r#"{"event":"ok"}"#));
+        assert_ne!(Some(&key), OutcomeKey::new(&webhook("ko"), &ctx).as_ref());
+        assert_ne!(
+            Some(&key),
+            OutcomeKey::new(&webhook("ok"), &ctx.clone().with_tenant("a")).as_ref()
+        );
+        assert_ne!(
+            Some(&key),
+            OutcomeKey::new(&"ok", &ctx).as_ref(),
+            "the type is part of the key"
+        );
+    }
+
+    #[test]
+    fn test_validate_cached_per_config() {
+        let env = ValidationEnvironment::new().with_outcome_cache(Arc::new(MapCache::default()));
+        let fail_fast = ValidationConfig::new().with_mode(ValidationMode::FailFast);
+
+        let first = validate_cached(
+            &Transfer,
+            &ValidationContext::new(env.clone()).with_config(fail_fast),
+        );
+        assert_eq!(1, first.len());
+        // The outcome cut short is not the one of a context collecting every failure
+        let all = validate_cached(&Transfer, &ValidationContext::new(env));
+        assert_eq!(2, all.len());
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_validate_cached() {
+        let cache = Arc::new(LruOutcomeCache::new(8));
+        let ctx =
+            ValidationContext::new(ValidationEnvironment::new().with_outcome_cache(cache.clone()));
+        let validations = || VALIDATIONS.load(Ordering::SeqCst);
+
+        let before = validations();
+        assert_eq!(
+            "event: one_of",
+            validate_cached(&webhook("ko"), &ctx).to_string()
+        );
+        assert_eq!(
+            "event: one_of",
+            validate_cached(&webhook("ko"), &ctx).to_string()
+        );
+        assert!(validate_cached(&webhook("ok"), &ctx).is_empty());
+        assert!(validate_cached(&webhook("ok"), &ctx).is_empty());
+        assert_eq!(before + 2, validations());
+
+        // Timeouts are not cached, the next try runs the rules again
+        validate_cached(&webhook("slow"), &ctx);
+        validate_cached(&webhook("slow"), &ctx);
+        assert_eq!(before + 4, validations());
+        assert_eq!(2, cache.len());
+
+        validate_cached(&webhook("ok"), &ValidationContext::default());
+        assert_eq!(before + 5, validations());
+    }
+}
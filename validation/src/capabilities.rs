@@ -0,0 +1,98 @@
+use crate::catalog;
+use crate::description::FieldConstraintDescription;
+
+/// Code of the error of a rule this build cannot evaluate, its code being unknown to it or the
+/// feature it needs not being enabled
+pub const RULE_UNAVAILABLE: &str = "rule_unavailable";
+
+/// Rules, or options of rules, compiled in per a feature of the crate
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Capability {
+    /// Name of the capability, stable across releases
+    pub name: &'static str,
+    /// Feature of the crate compiling it in
+    pub feature: &'static str,
+    /// Codes of the rules it applies to
+    pub rules: &'static [&'static str],
+    /// Whether or not this build has it
+    pub available: bool,
+}
+
+const CAPABILITIES: &[Capability] = &[
+    Capability {
+        name: "nfkc_collations",
+        feature: "unicode",
+        rules: &["one_of"],
+        available: cfg!(feature = "unicode"),
+    },
+    Capability {
+        name: "canonical_identifiers",
+        feature: "unicode",
+        rules: &["canonical_identifier"],
+        available: cfg!(feature = "unicode"),
+    },
+    Capability {
+        name: "chrono_types",
+        feature: "chrono",
+        rules: &["past", "future", "min_age", "range"],
+        available: cfg!(feature = "chrono"),
+    },
+    Capability {
+        name: "time_types",
+        feature: "time",
+        rules: &["past", "future", "min_age", "range"],
+        available: cfg!(feature = "time"),
+    },
+];
+
+/// Returns every capability gated by a feature, along with whether or not this build has it
+///
+/// Services sharing schemas across builds check them at startup rather than finding a rule
+/// missing on the first payload
+///
+/// # Example
+/// ```
+/// use crate::validation::capabilities;
+///
+/// let nfkc = capabilities().iter().find(|c| c.name == "nfkc_collations").unwrap();
+/// assert_eq!("unicode", nfkc.feature);
+/// assert_eq!(cfg!(feature = "unicode"), nfkc.available);
+/// ```
+pub fn capabilities() -> &'static [Capability] {
+    CAPABILITIES
+}
+
+/// Returns whether or not this build can evaluate the described rule: its code is a known
+/// one, and the capability it needs, if any, is compiled in
+///
+/// # Example
+/// ```
+/// use crate::validation::{is_rule_available, FieldConstraintDescription};
+///
+/// let length = FieldConstraintDescription::new("name", "length", None).param("max", 64);
+/// assert!(is_rule_available(&length));
+/// assert!(!is_rule_available(&FieldConstraintDescription::new("name", "palindrome", None)));
+///
+/// let nfkc = FieldConstraintDescription::new("unit", "one_of", None)
+///     .param("values", vec!["km"])
+///     .param("collation", "nfkc");
+/// assert_eq!(cfg!(feature = "unicode"), is_rule_available(&nfkc));
+/// ```
+pub fn is_rule_available(rule: &FieldConstraintDescription) -> bool {
+    catalog::find(&rule.rule).is_some()
+        && required_capability(rule).is_none_or(|capability| capability.available)
+}
+
+// Capability the described rule needs, the types of the chrono and time rules are not described
+fn required_capability(rule: &FieldConstraintDescription) -> Option<&'static Capability> {
+    let name = match (rule.rule.as_str(), rule.params.get("collation")) {
+        ("one_of", Some(collation)) if collation == "nfkc" || collation == "nfkc_case_fold" => {
+            "nfkc_collations"
+        }
+        ("canonical_identifier", _) => "canonical_identifiers",
+        _ => return None,
+    };
+    CAPABILITIES
+        .iter()
+        .find(|capability| capability.name == name)
+}
@@ -0,0 +1,377 @@
+//! catalog
+//!
+//! Module containing the catalog of every error code the validation rules produce, along with
+//! their default messages and the names of the parameters the rules take
+//!
+//! With the `catalog` feature the catalog can be exported as JSON, so documentation and client
+//! SDK generators can be built from the same source as the implementation
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+#[cfg(feature = "catalog")]
+use crate::description::Deprecation;
+use crate::description::FieldConstraintDescription;
+#[cfg(feature = "catalog")]
+use crate::error_code_map::{self, ErrorCodeMapping};
+
+lazy_static::lazy_static! {
+    static ref REGISTERED_CONSTRAINTS: RwLock<BTreeMap<String, Vec<FieldConstraintDescription>>> =
+        RwLock::new(BTreeMap::new());
+}
+
+/// Describes an error code produced by a validation rule
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCatalogEntry {
+    pub code: &'static str,
+    pub default_message: &'static str,
+    pub params: &'static [&'static str],
+}
+
+/// Every error code produced by the rules in this library
+///
+/// Any new rule needs an entry here so the exported catalog stays in sync
+pub const ERROR_CATALOG: &[ErrorCatalogEntry] = &[
+    ErrorCatalogEntry {
+        code: "not_null",
+        default_message: "value is required",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "regex_inline",
+        default_message: "value does not match the expected pattern",
+        params: &["pattern"],
+    },
+    ErrorCatalogEntry {
+        code: "past",
+        default_message: "value must be in the past",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "future",
+        default_message: "value must be in the future",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "min_age",
+        default_message: "value is below the minimum age",
+        params: &["years"],
+    },
+    ErrorCatalogEntry {
+        code: "date_format",
+        default_message: "value is not a date in the expected format",
+        params: &["style", "locale"],
+    },
+    ErrorCatalogEntry {
+        code: "number_format",
+        default_message: "value is not a number in the expected format",
+        params: &["locale"],
+    },
+    ErrorCatalogEntry {
+        code: "length",
+        default_message: "value does not have a valid length",
+        params: &["min", "max"],
+    },
+    ErrorCatalogEntry {
+        code: "range",
+        default_message: "value is out of the allowed range",
+        params: &["min", "max", "exclusive_min", "exclusive_max"],
+    },
+    ErrorCatalogEntry {
+        code: "finite",
+        default_message: "value is not a finite number",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "fits_in",
+        default_message: "value is not an integer",
+        params: &["type"],
+    },
+    ErrorCatalogEntry {
+        code: "canonical_identifier",
+        default_message: "value is not a canonical identifier",
+        params: &["max"],
+    },
+    ErrorCatalogEntry {
+        code: "uuid_version",
+        default_message: "value is not a UUID of the expected version",
+        params: &["version"],
+    },
+    ErrorCatalogEntry {
+        code: "uuid_not_nil",
+        default_message: "value is the nil UUID",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "integer_overflow",
+        default_message: "integer does not fit in the expected type",
+        params: &["type"],
+    },
+    ErrorCatalogEntry {
+        code: "flags_of",
+        default_message: "value sets bits of no known flag",
+        params: &["flags"],
+    },
+    ErrorCatalogEntry {
+        code: "nested",
+        default_message: "value is nested deeper than the maximum depth",
+        params: &["max_depth"],
+    },
+    ErrorCatalogEntry {
+        code: "api_version",
+        default_message: "API version is not supported",
+        params: &["latest"],
+    },
+    ErrorCatalogEntry {
+        code: "one_of",
+        default_message: "value is not one of the allowed values",
+        params: &["values", "collation"],
+    },
+    ErrorCatalogEntry {
+        code: "json_type",
+        default_message: "value is not of the expected JSON type",
+        params: &["type"],
+    },
+    ErrorCatalogEntry {
+        code: "max_depth",
+        default_message: "value is nested deeper than the maximum depth",
+        params: &["max_depth"],
+    },
+    ErrorCatalogEntry {
+        code: "max_keys",
+        default_message: "value has more keys than the maximum",
+        params: &["max_keys"],
+    },
+    ErrorCatalogEntry {
+        code: "required_keys",
+        default_message: "value is missing required keys",
+        params: &["keys"],
+    },
+    ErrorCatalogEntry {
+        code: "unknown_field",
+        default_message: "field is not known",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "duplicate_key",
+        default_message: "key appears more than once in the object",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "number_overflow",
+        default_message: "number does not fit in a 64 bit integer or a finite float",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "invalid_json",
+        default_message: "input is not valid JSON",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "invalid_body",
+        default_message: "body does not decode as its content type",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "unsupported_media_type",
+        default_message: "content type is not supported",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "max_size",
+        default_message: "input is larger than the maximum size",
+        params: &["max_size"],
+    },
+    ErrorCatalogEntry {
+        code: "max_items",
+        default_message: "array has more elements than the maximum",
+        params: &["max_items"],
+    },
+    ErrorCatalogEntry {
+        code: "max_length",
+        default_message: "string is longer than the maximum length",
+        params: &["max_length"],
+    },
+    ErrorCatalogEntry {
+        code: "signed",
+        default_message: "signature does not match the signed fields",
+        params: &["payload_fields"],
+    },
+    ErrorCatalogEntry {
+        code: "recently_seen",
+        default_message: "the same submission was already received",
+        params: &["window"],
+    },
+    ErrorCatalogEntry {
+        code: "validation_timeout",
+        default_message: "the value could not be checked in time",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "rule_unavailable",
+        default_message: "rule not available in this build",
+        params: &["rule"],
+    },
+    ErrorCatalogEntry {
+        code: "immutable",
+        default_message: "value cannot be changed",
+        params: &[],
+    },
+    ErrorCatalogEntry {
+        code: "monotonic_increase",
+        default_message: "value cannot decrease",
+        params: &[],
+    },
+];
+
+/// Returns the `ErrorCatalogEntry` for the given code, if the code is a known one
+pub fn find(code: &str) -> Option<&'static ErrorCatalogEntry> {
+    ERROR_CATALOG.iter().find(|entry| entry.code == code)
+}
+
+/// Returns the default message of the given code, if the code is a known one
+///
+/// # Example
+/// ```
+/// use crate::validation::catalog;
+///
+/// assert_eq!(Some("value is required"), catalog::default_message("not_null"));
+/// assert_eq!(None, catalog::default_message("unknown"));
+/// ```
+pub fn default_message(code: &str) -> Option<&'static str> {
+    find(code).map(|entry| entry.default_message)
+}
+
+/// Registers the constraints of a type, typically its `describe_constraints()`, so the
+/// exported catalog can list where deprecated rules are still in use
+///
+/// # Arguments
+///
+///  * `type_name` - name of the type the constraints belong to
+///  * `constraints` - the constraints of the type, replacing any registered before
+///
+/// # Example
+/// ```
+/// use crate::validation::{catalog, Deprecation, FieldConstraintDescription};
+///
+/// catalog::register_constraints(
+///     "Order",
+///     vec![FieldConstraintDescription::new("ref", "length", None)
+///         .param("max", 10)
+///         .deprecated(Deprecation::new("1.4", Some("references are 32 characters since 1.4")))],
+/// );
+/// assert_eq!(1, catalog::registered_constraints("Order").len());
+/// ```
+pub fn register_constraints(type_name: &str, constraints: Vec<FieldConstraintDescription>) {
+    REGISTERED_CONSTRAINTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(type_name.to_string(), constraints);
+}
+
+/// Returns the constraints registered for the type, empty when none were registered
+pub fn registered_constraints(type_name: &str) -> Vec<FieldConstraintDescription> {
+    REGISTERED_CONSTRAINTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(type_name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Use of a deprecated rule by a registered type
+#[cfg(feature = "catalog")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeprecatedUsage {
+    type_name: String,
+    field: String,
+    #[serde(flatten)]
+    deprecation: Deprecation,
+}
+
+// Entry as it is exported, along with how the registered `ErrorCodeMap` translates it and
+// where registered types still use it in a deprecated way
+#[cfg(feature = "catalog")]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedEntry {
+    #[serde(flatten)]
+    entry: &'static ErrorCatalogEntry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translation: Option<ErrorCodeMapping>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    deprecated_usages: Vec<DeprecatedUsage>,
+}
+
+#[cfg(feature = "catalog")]
+fn deprecated_usages(code: &str) -> Vec<DeprecatedUsage> {
+    let registered = REGISTERED_CONSTRAINTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+
+    registered
+        .iter()
+        .flat_map(|(type_name, constraints)| {
+            constraints
+                .iter()
+                .filter(move |c| c.rule == code)
+                .filter_map(move |c| {
+                    c.deprecated.clone().map(|deprecation| DeprecatedUsage {
+                        type_name: type_name.clone(),
+                        field: c.field.clone(),
+                        deprecation,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Exports the catalog as a JSON array, including how the registered `ErrorCodeMap` (if any)
+/// translates each code and the deprecated uses of each code by the registered constraints.
+/// Can be called at runtime or from a build script
+///
+/// # Example
+/// ```
+/// use crate::validation::{catalog, Deprecation, FieldConstraintDescription};
+///
+/// let json = catalog::export_catalog_json();
+/// assert!(json.contains("\"code\": \"not_null\""));
+/// assert!(json.contains("\"defaultMessage\": \"value is required\""));
+///
+/// catalog::register_constraints(
+///     "Customer",
+///     vec![FieldConstraintDescription::new("fax", "not_null", None)
+///         .deprecated(Deprecation::new("2.0", None))],
+/// );
+/// let json = catalog::export_catalog_json();
+/// assert!(json.contains("\"typeName\": \"Customer\""));
+/// assert!(json.contains("\"since\": \"2.0\""));
+/// ```
+#[cfg(feature = "catalog")]
+pub fn export_catalog_json() -> String {
+    let entries = ERROR_CATALOG
+        .iter()
+        .map(|entry| ExportedEntry {
+            entry,
+            translation: error_code_map::registered_mapping(entry.code),
+            deprecated_usages: deprecated_usages(entry.code),
+        })
+        .collect::<Vec<_>>();
+
+    // Strings, string keyed maps and `Value`s, none of which fails to serialize
+    #[allow(clippy::expect_used)]
+    let json = serde_json::to_string_pretty(&entries).expect("the catalog is always serializable");
+    json
+}
+
+/// Writes the JSON catalog of `export_catalog_json` to the given path
+///
+/// # Arguments
+///
+///  * `path` - where to write the catalog, typically somewhere under `OUT_DIR` in a build script
+#[cfg(feature = "catalog")]
+pub fn write_catalog_json<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<()> {
+    std::fs::write(path, export_catalog_json())
+}
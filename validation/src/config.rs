@@ -0,0 +1,281 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::context::ValidationContext;
+use crate::error::ValidationError;
+use crate::validation::Validation;
+
+/// Position in a configuration file, both starting at 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Failure to load a configuration file
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file is not valid TOML/YAML or does not match the config struct
+    Parse {
+        message: String,
+        location: Option<Location>,
+    },
+    /// The config struct failed its validation. The location of a field is approximated
+    /// by the first line defining a key of that name
+    Invalid {
+        error: ValidationError,
+        field: Option<String>,
+        location: Option<Location>,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let (message, location) = match *self {
+            ConfigError::Io(ref err) => return err.fmt(f),
+            ConfigError::Parse {
+                ref message,
+                location,
+            } => (message.clone(), location),
+            ConfigError::Invalid {
+                ref error,
+                ref field,
+                location,
+            } => match *field {
+                Some(ref field) => (format!("{}: {}", field, error), location),
+                None => (error.to_string(), location),
+            },
+        };
+        match location {
+            Some(location) => write!(
+                f,
+                "{} at line {} column {}",
+                message, location.line, location.column
+            ),
+            None => f.write_str(&message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(err: io::Error) -> Self {
+        ConfigError::Io(err)
+    }
+}
+
+/// Deserializes a TOML configuration and runs its `Validation`
+///
+/// # Example
+/// ```
+/// use crate::validation::config::{from_toml_str, ConfigError, Location};
+/// use crate::validation::{
+///     FieldValidation, StateValidation, Validation, ValidationError, ValidationResult,
+/// };
+///
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// impl FieldValidation for Config {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl StateValidation for Config {
+///     fn validate_state(&self) -> ValidationResult<()> {
+///         if self.port < 1024 {
+///             Err(ValidationError::InvalidState("port".to_string()))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// impl Validation for Config {
+///     fn validate(&self) -> ValidationResult<()> {
+///         self.validate_fields()?;
+///         self.validate_state()
+///     }
+/// }
+///
+/// assert!(from_toml_str::<Config>("name = \"api\"\nport = 8080\n").is_ok());
+/// assert!(matches!(
+///     from_toml_str::<Config>("name = \"api\"\nport = 80\n"),
+///     Err(ConfigError::Invalid { .. })
+/// ));
+/// match from_toml_str::<Config>("name = \"api\"\nport = \"http\"\n") {
+///     Err(ConfigError::Parse { location, .. }) => {
+///         assert_eq!(Some(2), location.map(|l| l.line))
+///     }
+///     _ => panic!("expected a parse error"),
+/// }
+/// ```
+#[cfg(feature = "toml")]
+pub fn from_toml_str<T: DeserializeOwned + Validation>(source: &str) -> Result<T, ConfigError> {
+    let value = toml::from_str::<T>(source).map_err(|err| ConfigError::Parse {
+        message: err.message().to_string(),
+        location: err
+            .span()
+            .map(|span| location_of_offset(source, span.start)),
+    })?;
+    validate_config(value, source, toml_key_location)
+}
+
+/// Reads a TOML configuration file, see `from_toml_str`
+#[cfg(feature = "toml")]
+pub fn read_toml_file<T: DeserializeOwned + Validation, P: AsRef<Path>>(
+    path: P,
+) -> Result<T, ConfigError> {
+    from_toml_str(&fs::read_to_string(path)?)
+}
+
+/// Deserializes a YAML configuration and runs its `Validation`, see `from_toml_str`
+#[cfg(feature = "yaml")]
+pub fn from_yaml_str<T: DeserializeOwned + Validation>(source: &str) -> Result<T, ConfigError> {
+    let value = serde_yaml::from_str::<T>(source).map_err(|err| ConfigError::Parse {
+        message: err.to_string(),
+        location: err.location().map(|location| Location {
+            line: location.line(),
+            column: location.column(),
+        }),
+    })?;
+    validate_config(value, source, yaml_key_location)
+}
+
+/// Reads a YAML configuration file, see `from_yaml_str`
+#[cfg(feature = "yaml")]
+pub fn read_yaml_file<T: DeserializeOwned + Validation, P: AsRef<Path>>(
+    path: P,
+) -> Result<T, ConfigError> {
+    from_yaml_str(&fs::read_to_string(path)?)
+}
+
+// Field failures are located with their key, the remaining `Validation` is run once they pass
+fn validate_config<T: Validation>(
+    value: T,
+    source: &str,
+    key_location: fn(&str, &str) -> Option<Location>,
+) -> Result<T, ConfigError> {
+    if let Some(err) = value
+        .field_errors(&ValidationContext::default())
+        .into_iter()
+        .next()
+    {
+        let location = err
+            .field
+            .as_ref()
+            .and_then(|field| key_location(source, field));
+        return Err(ConfigError::Invalid {
+            error: err.error,
+            field: err.field,
+            location,
+        });
+    }
+
+    match value.validate() {
+        Ok(()) => Ok(value),
+        Err(error) => Err(ConfigError::Invalid {
+            error,
+            field: None,
+            location: None,
+        }),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn location_of_offset(source: &str, offset: usize) -> Location {
+    // Offsets reported by the parser are on characters, any other one locates the whole source
+    let before = source.get(..offset).unwrap_or(source);
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+#[cfg(feature = "toml")]
+fn toml_key_location(source: &str, key: &str) -> Option<Location> {
+    find_key_line(source, |line| {
+        let rest = line
+            .strip_prefix(key)
+            .or_else(|| line.strip_prefix(&format!("\"{}\"", key)))?;
+        Some(rest.trim_start().starts_with('='))
+    })
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_key_location(source: &str, key: &str) -> Option<Location> {
+    find_key_line(source, |line| {
+        let line = line.strip_prefix("- ").unwrap_or(line);
+        let rest = line
+            .strip_prefix(key)
+            .or_else(|| line.strip_prefix(&format!("\"{}\"", key)))?;
+        Some(rest.trim_start().starts_with(':'))
+    })
+}
+
+fn find_key_line(source: &str, is_key: impl Fn(&str) -> Option<bool>) -> Option<Location> {
+    source.lines().enumerate().find_map(|(index, line)| {
+        let trimmed = line.trim_start();
+        if is_key(trimmed).unwrap_or(false) {
+            Some(Location {
+                line: index + 1,
+                column: line.len() - trimmed.len() + 1,
+            })
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_location_of_offset() {
+        let source = "a = 1\nbb = 2\n";
+        assert_eq!(
+            Location { line: 1, column: 1 },
+            location_of_offset(source, 0)
+        );
+        assert_eq!(
+            Location { line: 2, column: 4 },
+            location_of_offset(source, 9)
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_key_location() {
+        let source = "[server]\n  portal = 1\n  port = 80\n";
+        assert_eq!(
+            Some(Location { line: 3, column: 3 }),
+            toml_key_location(source, "port")
+        );
+        assert_eq!(None, toml_key_location(source, "host"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_key_location() {
+        let source = "server:\n  port: 80\n";
+        assert_eq!(
+            Some(Location { line: 2, column: 3 }),
+            yaml_key_location(source, "port")
+        );
+    }
+}
@@ -0,0 +1,489 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use crate::cache::OutcomeCache;
+use crate::description::Deprecation;
+use crate::error::ValidationError;
+use crate::global_config::ValidationConfig;
+use crate::recently_seen::SeenStore;
+use crate::timeout::Timer;
+
+// Environment of the contexts created with `ValidationContext::default()`, built once
+static DEFAULT_ENVIRONMENT: OnceLock<Arc<ValidationEnvironment>> = OnceLock::new();
+
+/// Source of the current time for rules that depend on it (`past`, `future`, `min_age`...)
+///
+/// Rules never read the system time themselves, they are handed the time of the
+/// `ValidationContext` so a `FixedClock` makes them deterministic under test
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// `Clock` reading the system time
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// `Clock` that always returns the same time
+///
+/// # Example
+/// ```
+/// use crate::validation::{Clock, FixedClock};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let clock = FixedClock(UNIX_EPOCH + Duration::from_secs(1_600_000_000));
+/// assert_eq!(clock.now(), clock.now());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FixedClock(pub SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// Source of randomness for rules that need it
+pub trait RandomSource: Send + Sync {
+    fn next_u64(&self) -> u64;
+}
+
+/// `RandomSource` producing the same sequence for the same seed (xorshift64*)
+///
+/// # Example
+/// ```
+/// use crate::validation::{RandomSource, SeededRandom};
+///
+/// let a = SeededRandom::new(42);
+/// let b = SeededRandom::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// ```
+#[derive(Debug)]
+pub struct SeededRandom {
+    state: AtomicU64,
+}
+
+impl SeededRandom {
+    /// Creates a new `SeededRandom`, a seed of 0 is replaced by a fixed non-zero seed
+    pub fn new(seed: u64) -> Self {
+        SeededRandom {
+            state: AtomicU64::new(if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            }),
+        }
+    }
+
+    /// Creates a new `SeededRandom` seeded from the process' random hasher keys
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        SeededRandom::new(hasher.finish())
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_u64(&self) -> u64 {
+        let mut current = self.state.load(Ordering::Relaxed);
+        loop {
+            let mut next = current;
+            next ^= next >> 12;
+            next ^= next << 25;
+            next ^= next >> 27;
+            match self.state.compare_exchange_weak(
+                current,
+                next,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return next.wrapping_mul(0x2545_F491_4F6C_DD1D),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+/// Hooks notified of what happens during a validation
+///
+/// # Example
+/// ```
+/// use crate::validation::{ValidationError, ValidationObserver};
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Warnings(Mutex<Vec<String>>);
+///
+/// impl ValidationObserver for Warnings {
+///     fn on_warning(&self, field: &str, error: &ValidationError) {
+///         self.0.lock().unwrap().push(format!("{}: {}", field, error));
+///     }
+/// }
+/// ```
+pub trait ValidationObserver: Send + Sync {
+    /// A rule in `warn` enforcement failed, the validation still succeeds
+    ///
+    /// # Arguments
+    ///
+    ///  * `field` - name of the field, as it is serialized
+    ///  * `error` - `ValidationError` the rule would have failed with
+    fn on_warning(&self, field: &str, error: &ValidationError);
+
+    /// A deprecated rule failed, whatever its enforcement
+    ///
+    /// # Arguments
+    ///
+    ///  * `field` - name of the field, as it is serialized
+    ///  * `rule` - error code of the rule
+    ///  * `deprecation` - `Deprecation` of the rule
+    fn on_deprecated(&self, field: &str, rule: &str, deprecation: &Deprecation) {
+        let _ = (field, rule, deprecation);
+    }
+}
+
+/// The environment the validation runs in: clock, randomness, locale, feature switches and
+/// the `ValidationObserver`
+///
+/// # Example
+/// ```
+/// use crate::validation::{FixedClock, ValidationEnvironment};
+/// use std::time::UNIX_EPOCH;
+///
+/// let env = ValidationEnvironment::new()
+///     .with_clock(FixedClock(UNIX_EPOCH))
+///     .with_locale("en-GB")
+///     .with_feature("strict");
+///
+/// assert_eq!(UNIX_EPOCH, env.clock().now());
+/// assert_eq!("en-GB", env.locale());
+/// assert!(env.is_enabled("strict"));
+/// assert!(!env.is_enabled("lenient"));
+/// ```
+#[derive(Clone)]
+pub struct ValidationEnvironment {
+    clock: Arc<dyn Clock>,
+    random: Arc<dyn RandomSource>,
+    locale: String,
+    features: HashSet<String>,
+    observer: Option<Arc<dyn ValidationObserver>>,
+    seen_store: Option<Arc<dyn SeenStore>>,
+    timer: Option<Arc<dyn Timer>>,
+    outcome_cache: Option<Arc<dyn OutcomeCache>>,
+}
+
+impl ValidationEnvironment {
+    /// Creates the default environment: system clock, entropy seeded randomness,
+    /// `en-US` locale and no feature switches
+    pub fn new() -> Self {
+        ValidationEnvironment {
+            clock: Arc::new(SystemClock),
+            random: Arc::new(SeededRandom::from_entropy()),
+            locale: "en-US".to_string(),
+            features: HashSet::new(),
+            observer: None,
+            seen_store: None,
+            timer: None,
+            outcome_cache: None,
+        }
+    }
+
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn with_random<R: RandomSource + 'static>(mut self, random: R) -> Self {
+        self.random = Arc::new(random);
+        self
+    }
+
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = locale.to_string();
+        self
+    }
+
+    pub fn with_feature(mut self, feature: &str) -> Self {
+        self.features.insert(feature.to_string());
+        self
+    }
+
+    /// Sets the observer, shared so the same one can be handed to every environment. Without
+    /// one, the observer of the global `ValidationConfig` is used
+    pub fn with_observer(mut self, observer: Arc<dyn ValidationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sets the store of the `recently_seen` rule, shared so every environment sees the same keys
+    pub fn with_seen_store(mut self, seen_store: Arc<dyn SeenStore>) -> Self {
+        self.seen_store = Some(seen_store);
+        self
+    }
+
+    /// Sets the timer bounding the rules waiting on I/O, per the rule timeout of the context
+    pub fn with_timer(mut self, timer: Arc<dyn Timer>) -> Self {
+        self.timer = Some(timer);
+        self
+    }
+
+    /// Sets the cache of the outcomes of `cache::validate_cached`, shared so repeated payloads
+    /// hit it whichever environment validates them
+    pub fn with_outcome_cache(mut self, outcome_cache: Arc<dyn OutcomeCache>) -> Self {
+        self.outcome_cache = Some(outcome_cache);
+        self
+    }
+
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    pub fn random(&self) -> &dyn RandomSource {
+        self.random.as_ref()
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Returns whether or not the feature switch is on
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// The observer of the environment, the one of the global `ValidationConfig` if it has none
+    pub fn observer(&self) -> Option<&dyn ValidationObserver> {
+        self.observer
+            .as_deref()
+            .or_else(|| ValidationConfig::global().observer().map(Arc::as_ref))
+    }
+
+    pub fn seen_store(&self) -> Option<&dyn SeenStore> {
+        self.seen_store.as_deref()
+    }
+
+    pub fn timer(&self) -> Option<&dyn Timer> {
+        self.timer.as_deref()
+    }
+
+    pub fn outcome_cache(&self) -> Option<&dyn OutcomeCache> {
+        self.outcome_cache.as_deref()
+    }
+
+    // Feature switches that are on, in no particular order
+    pub(crate) fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(String::as_str)
+    }
+
+    /// Reports the failure of a deprecated rule to the observer, if there is one
+    pub fn report_deprecated(&self, field: &str, rule: &str, deprecation: &Deprecation) {
+        if let Some(observer) = self.observer() {
+            observer.on_deprecated(field, rule, deprecation);
+        }
+    }
+
+    /// Reports the failure of a rule in `warn` enforcement to the observer, if there is one
+    pub fn report_warning(&self, field: &str, error: &ValidationError) {
+        if let Some(observer) = self.observer() {
+            observer.on_warning(field, error);
+        }
+    }
+}
+
+impl Default for ValidationEnvironment {
+    fn default() -> Self {
+        ValidationEnvironment::new()
+    }
+}
+
+impl Debug for ValidationEnvironment {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("ValidationEnvironment")
+            .field("now", &self.clock.now())
+            .field("locale", &self.locale)
+            .field("features", &self.features)
+            .field("observer", &self.observer.is_some())
+            .field("seen_store", &self.seen_store.is_some())
+            .field("timer", &self.timer.is_some())
+            .field("outcome_cache", &self.outcome_cache.is_some())
+            .finish()
+    }
+}
+
+/// Context threaded through a validation run
+///
+/// The time is read from the environment's clock once, when the context is created, so
+/// every rule of a request sees the same "now". The environment is shared rather than copied
+/// by the contexts of the nested values
+///
+/// # Example
+/// ```
+/// use crate::validation::{FixedClock, ValidationContext, ValidationEnvironment};
+/// use std::time::UNIX_EPOCH;
+///
+/// let ctx = ValidationContext::new(ValidationEnvironment::new().with_clock(FixedClock(UNIX_EPOCH)));
+/// assert_eq!(UNIX_EPOCH, ctx.now());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ValidationContext {
+    environment: Arc<ValidationEnvironment>,
+    now: SystemTime,
+    tenant: Option<Arc<str>>,
+    api_version: Option<u32>,
+    config: Option<ValidationConfig>,
+    depth: usize,
+    max_depth: usize,
+    rule_timeout: Option<Duration>,
+}
+
+/// Maximum nesting depth of `#[validate(nested)]` fields when none is configured
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+impl ValidationContext {
+    pub fn new(environment: ValidationEnvironment) -> Self {
+        ValidationContext::with_shared_environment(Arc::new(environment))
+    }
+
+    // Context over an environment other contexts may share
+    fn with_shared_environment(environment: Arc<ValidationEnvironment>) -> Self {
+        let now = environment.clock().now();
+        ValidationContext {
+            environment,
+            now,
+            tenant: None,
+            api_version: None,
+            config: None,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            rule_timeout: None,
+        }
+    }
+
+    /// Sets the tenant the validation runs for, used to resolve per tenant rule overrides
+    pub fn with_tenant(mut self, tenant: &str) -> Self {
+        self.tenant = Some(Arc::from(tenant));
+        self
+    }
+
+    /// Sets the API version the request asks for, choosing the rules of a struct with
+    /// `#[validation(version = N)]`. Without one, the latest version of the struct is used
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{parse_api_version, ValidationContext};
+    ///
+    /// let header = "v1";
+    /// let mut ctx = ValidationContext::default();
+    /// if let Some(version) = parse_api_version(header) {
+    ///     ctx = ctx.with_api_version(version);
+    /// }
+    /// assert_eq!(Some(1), ctx.api_version());
+    /// ```
+    pub fn with_api_version(mut self, api_version: u32) -> Self {
+        self.api_version = Some(api_version);
+        self
+    }
+
+    pub fn api_version(&self) -> Option<u32> {
+        self.api_version
+    }
+
+    /// Sets the config of this validation in place of the global one, its observer replacing
+    /// the one of the environment if it has one
+    pub fn with_config(mut self, config: ValidationConfig) -> Self {
+        if let Some(observer) = config.observer() {
+            Arc::make_mut(&mut self.environment).observer = Some(observer.clone());
+        }
+        self.config = Some(config);
+        self
+    }
+
+    /// The config of this validation, the global one unless it was given its own
+    pub fn config(&self) -> &ValidationConfig {
+        self.config
+            .as_ref()
+            .unwrap_or_else(|| ValidationConfig::global())
+    }
+
+    pub fn environment(&self) -> &ValidationEnvironment {
+        &self.environment
+    }
+
+    pub fn tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// Sets the maximum nesting depth of `#[validate(nested)]` fields. Values nested deeper
+    /// fail with the `nested` error rather than being validated
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets how long each rule waiting on I/O, like `recently_seen`, can wait before failing
+    /// with `validation_timeout`. The environment needs a `Timer` to bound them
+    pub fn with_rule_timeout(mut self, rule_timeout: Duration) -> Self {
+        self.rule_timeout = Some(rule_timeout);
+        self
+    }
+
+    pub fn rule_timeout(&self) -> Option<Duration> {
+        self.rule_timeout
+    }
+
+    /// The time of the validation run
+    pub fn now(&self) -> SystemTime {
+        self.now
+    }
+
+    /// Nesting depth of the value being validated, 0 for the value the run started with
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the context to validate a value nested one level deeper, sharing the environment
+    /// of this one
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::ValidationContext;
+    ///
+    /// let ctx = ValidationContext::default().with_max_depth(4);
+    /// assert_eq!(2, ctx.nested().nested().depth());
+    /// assert_eq!(4, ctx.nested().max_depth());
+    /// assert!(std::ptr::eq(ctx.environment(), ctx.nested().environment()));
+    /// assert!(std::ptr::eq(ctx.environment(), ValidationContext::default().environment()));
+    /// ```
+    pub fn nested(&self) -> ValidationContext {
+        let mut nested = self.clone();
+        nested.depth += 1;
+        nested
+    }
+
+    /// Returns whether or not the value being validated is nested deeper than the maximum
+    pub fn is_too_deep(&self) -> bool {
+        self.depth > self.max_depth
+    }
+}
+
+impl Default for ValidationContext {
+    fn default() -> Self {
+        let environment =
+            DEFAULT_ENVIRONMENT.get_or_init(|| Arc::new(ValidationEnvironment::new()));
+        ValidationContext::with_shared_environment(environment.clone())
+    }
+}
@@ -0,0 +1,131 @@
+use std::io;
+
+use crate::context::ValidationContext;
+use crate::error::ValidationError;
+use crate::validation::FieldValidation;
+
+/// A failure of a row of a CSV file, either to deserialize or to validate
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvRowError {
+    /// Number of the record, starting at 1 for the first record after the headers
+    pub row: usize,
+    /// Column of the failure, if known. Validation failures are reported with the name of
+    /// the field of `T`, which matches the header unless the field is renamed for serde
+    pub column: Option<String>,
+    pub error: ValidationError,
+}
+
+/// Outcome of the validation of every row of a CSV file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsvReport {
+    /// Number of records read, headers excluded
+    pub rows: usize,
+    pub errors: Vec<CsvRowError>,
+}
+
+impl CsvReport {
+    /// Returns whether or not every row deserialized and passed validation
+    pub fn passed(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the failures of the given row
+    pub fn errors_for_row(&self, row: usize) -> impl Iterator<Item = &CsvRowError> {
+        self.errors.iter().filter(move |err| err.row == row)
+    }
+}
+
+/// Deserializes every record of the CSV reader into `T` and validates its fields
+///
+/// Records that do not deserialize are reported, with the column when the deserializer
+/// knows it, and the remaining records are still read. Only I/O failures stop the reading
+///
+/// # Arguments
+///
+/// * `reader` - `csv::Reader` with headers, configured by the caller (delimiter, quoting...)
+///
+/// # Example
+/// ```
+/// use crate::validation::{validate_csv, FieldValidation, ValidationError, ValidationResult};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Row {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl FieldValidation for Row {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         if self.name.is_empty() {
+///             Err(ValidationError::FieldMismatch("not_null".to_string()))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// let data = "name,age\nJoseph,30\n,40\nAnna,abc\n";
+/// let mut reader = csv::Reader::from_reader(data.as_bytes());
+/// let report = validate_csv::<Row, _>(&mut reader).unwrap();
+///
+/// assert_eq!(3, report.rows);
+/// assert_eq!(2, report.errors.len());
+/// assert_eq!(2, report.errors[0].row);
+/// assert_eq!(3, report.errors[1].row);
+/// assert_eq!(Some("age"), report.errors[1].column.as_deref());
+/// ```
+pub fn validate_csv<T, R>(reader: &mut csv::Reader<R>) -> csv::Result<CsvReport>
+where
+    T: serde::de::DeserializeOwned + FieldValidation,
+    R: io::Read,
+{
+    validate_csv_with::<T, R>(reader, &ValidationContext::default())
+}
+
+/// Same as `validate_csv`, with every row validated in the given `ValidationContext`
+pub fn validate_csv_with<T, R>(
+    reader: &mut csv::Reader<R>,
+    ctx: &ValidationContext,
+) -> csv::Result<CsvReport>
+where
+    T: serde::de::DeserializeOwned + FieldValidation,
+    R: io::Read,
+{
+    let headers = reader.headers()?.clone();
+    let mut report = CsvReport::default();
+
+    for (index, record) in reader.deserialize::<T>().enumerate() {
+        let row = index + 1;
+        report.rows = row;
+
+        let value = match record {
+            Ok(value) => value,
+            Err(err) => {
+                let column = match err.kind() {
+                    csv::ErrorKind::Io(_) => return Err(err),
+                    csv::ErrorKind::Deserialize { err: de, .. } => de
+                        .field()
+                        .and_then(|field| headers.get(field as usize))
+                        .map(str::to_string),
+                    _ => None,
+                };
+                report.errors.push(CsvRowError {
+                    row,
+                    column,
+                    error: ValidationError::FieldMismatch(err.to_string()),
+                });
+                continue;
+            }
+        };
+
+        for err in value.field_errors(ctx) {
+            report.errors.push(CsvRowError {
+                row,
+                column: err.field,
+                error: err.error,
+            });
+        }
+    }
+
+    Ok(report)
+}
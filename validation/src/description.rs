@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+use serde_json::Value;
+
+use crate::catalog;
+use crate::version::ApiVersions;
+
+/// Deprecation of a rule, set with `deprecated(since = "...", note = "...")` on `#[validate]`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Deprecation {
+    pub since: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new(since: &str, note: Option<&str>) -> Self {
+        Deprecation {
+            since: since.to_string(),
+            note: note.map(str::to_string),
+        }
+    }
+}
+
+impl Display for Deprecation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "deprecated since {}", self.since)?;
+        match self.note {
+            Some(ref note) => write!(f, ": {}", note),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Human readable description of a single rule applied to a field
+///
+/// Generated by the `FieldValidate` derive through `describe_constraints()`, straight from the
+/// `#[validate]` annotations, so that UIs can tell users what a field expects
+///
+/// # Example
+/// ```
+/// use crate::validation::FieldConstraintDescription;
+///
+/// let description = FieldConstraintDescription::new("name", "regex_inline", None)
+///     .param("pattern", "^[a-z]+$");
+///
+/// assert_eq!("name", description.field);
+/// assert_eq!("regex_inline", description.rule);
+/// assert_eq!(Some("^[a-z]+$"), description.params["pattern"].as_str());
+/// assert_eq!(
+///     "name: value does not match the expected pattern (pattern: \"^[a-z]+$\")",
+///     description.to_string()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FieldConstraintDescription {
+    pub field: String,
+    pub rule: String,
+    pub params: BTreeMap<String, Value>,
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<ApiVersions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+}
+
+impl FieldConstraintDescription {
+    /// Creates a new `FieldConstraintDescription`
+    ///
+    /// # Arguments
+    ///
+    ///  * `field` - name of the field, as it is serialized
+    ///  * `rule` - error code of the rule
+    ///  * `message` - custom message of the rule, the catalog's default message is used if `None`
+    pub fn new(field: &str, rule: &str, message: Option<&str>) -> Self {
+        FieldConstraintDescription {
+            field: field.to_string(),
+            rule: rule.to_string(),
+            params: BTreeMap::new(),
+            message: message
+                .or_else(|| catalog::default_message(rule))
+                .map(str::to_string),
+            deprecated: None,
+            when: None,
+            versions: None,
+            groups: Vec::new(),
+            weight: None,
+        }
+    }
+
+    /// Adds a parameter of the rule to the description
+    pub fn param<V: Into<Value>>(mut self, name: &str, value: V) -> Self {
+        self.params.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Marks the rule as deprecated
+    pub fn deprecated(mut self, deprecation: Deprecation) -> Self {
+        self.deprecated = Some(deprecation);
+        self
+    }
+
+    /// Sets the condition over `self` guarding the rule, as written in its `when`
+    pub fn when(mut self, condition: &str) -> Self {
+        self.when = Some(condition.to_string());
+        self
+    }
+
+    /// Sets the API versions the rule applies to, per its `since_version` and `until_version`
+    pub fn versions(mut self, since: Option<u32>, until: Option<u32>) -> Self {
+        self.versions = Some(ApiVersions::new(since, until));
+        self
+    }
+
+    /// Sets the groups of the rule, per its `groups(...)`, labels for the tools reading the rules
+    pub fn groups(mut self, groups: &[&str]) -> Self {
+        self.groups = groups.iter().map(|group| group.to_string()).collect();
+        self
+    }
+
+    /// Sets the weight of the rule in `validate_scored()`, per its `weight = N`
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Returns whether or not the rule applies to the API version
+    pub fn applies_to(&self, api_version: u32) -> bool {
+        self.versions
+            .is_none_or(|versions| versions.contains(api_version))
+    }
+}
+
+impl Display for FieldConstraintDescription {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}: ", self.field)?;
+        match self.message {
+            Some(ref message) => write!(f, "{}", message)?,
+            None => write!(f, "{}", self.rule)?,
+        }
+
+        if !self.params.is_empty() {
+            let params = self
+                .params
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", params.join(", "))?;
+        }
+
+        if let Some(ref deprecation) = self.deprecated {
+            write!(f, " [{}]", deprecation)?;
+        }
+
+        if let Some(ref condition) = self.when {
+            write!(f, " [when {}]", condition)?;
+        }
+
+        Ok(())
+    }
+}
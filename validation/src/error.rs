@@ -1,7 +1,19 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Result};
 
+use serde_json::Value;
+
+use crate::error_code_map;
+use crate::kv::KeyValues;
+use crate::timeout::VALIDATION_TIMEOUT;
+
 const BAD_REQUEST: i32 = 400;
+const CONFLICT: i32 = 409;
 const UNPROCESSABLE_ENTITY: i32 = 422;
+const SERVICE_UNAVAILABLE: i32 = 503;
+
+/// Codes of the invalid states clashing with a request already received, sent as `409`
+const CONFLICT_CODES: [&str; 1] = ["recently_seen"];
 
 /// Describes a validation error in the system
 /// A validation error can occur in two ways
@@ -11,20 +23,137 @@ const UNPROCESSABLE_ENTITY: i32 = 422;
 ///
 /// The struct is meant to provide a common language amongst interconnected
 /// systems/microservices to describe a validation error
-#[derive(Debug, PartialOrd, PartialEq)]
+///
+/// An invalid transition between two states is an `InvalidTransition`, so clients can render
+/// it without parsing a string. It is serialized with its parts as the code:
+/// `{"kind": "invalid_transition", "code": {"entity": "order", "from": "SHIPPED", "to": "DRAFT",
+/// "allowed": ["RETURNED"]}}`
+///
+/// New kinds of errors may be added in a minor release, so matches on it need a wildcard arm.
+/// Prefer the constructors and `matches_code` to building and matching the variants
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialOrd, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "code", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum ValidationError {
     FieldMismatch(String),
     InvalidState(String),
+    /// An entity cannot move between two states, boxed to keep the error small
+    InvalidTransition(Box<StateTransition>),
+}
+
+/// The parts of an `InvalidTransition`: an entity cannot move from the state `from` to the
+/// state `to`, only to the `allowed` ones
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialOrd, PartialEq, Serialize)]
+pub struct StateTransition {
+    pub entity: String,
+    pub from: String,
+    pub to: String,
+    pub allowed: Vec<String>,
 }
 
+impl ValidationError {
+    /// Creates a new `FieldMismatch`, a value failing the rule of the code
+    pub fn field_mismatch(code: &str) -> Self {
+        ValidationError::FieldMismatch(code.to_string())
+    }
+
+    /// Creates a new `InvalidState`, a state failing the rule of the code
+    pub fn invalid_state(code: &str) -> Self {
+        ValidationError::InvalidState(code.to_string())
+    }
+
+    /// Creates a new `InvalidTransition`
+    ///
+    /// # Arguments
+    ///
+    ///  * `entity` - name of the entity, like `order`
+    ///  * `from` - the state the entity is in
+    ///  * `to` - the state the entity cannot move to
+    ///  * `allowed` - the states the entity can move to from `from`
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::ValidationError;
+    ///
+    /// let err = ValidationError::invalid_transition("order", "SHIPPED", "DRAFT", &["RETURNED"]);
+    /// assert_eq!("cannot move order from SHIPPED to DRAFT; allowed: RETURNED", err.to_string());
+    /// assert_eq!("invalid_transition", err.code());
+    /// ```
+    pub fn invalid_transition(entity: &str, from: &str, to: &str, allowed: &[&str]) -> Self {
+        ValidationError::InvalidTransition(Box::new(StateTransition {
+            entity: entity.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            allowed: allowed.iter().map(|state| state.to_string()).collect(),
+        }))
+    }
+
+    /// Returns the code of the error, `invalid_transition` for an `InvalidTransition`
+    pub fn code(&self) -> &str {
+        match *self {
+            ValidationError::FieldMismatch(ref code) | ValidationError::InvalidState(ref code) => {
+                code
+            }
+            ValidationError::InvalidTransition(_) => "invalid_transition",
+        }
+    }
+
+    /// Returns whether or not the error has the code, whatever its kind
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::ValidationError;
+    ///
+    /// assert!(ValidationError::field_mismatch("length").matches_code("length"));
+    /// assert!(ValidationError::invalid_state("immutable").matches_code("immutable"));
+    /// assert!(!ValidationError::field_mismatch("length").matches_code("range"));
+    /// ```
+    pub fn matches_code(&self, code: &str) -> bool {
+        self.code() == code
+    }
+
+    /// Returns whether or not the same value could pass on a later try, as it does when the
+    /// rule timed out with `validation_timeout` rather than rejecting it. Values failing any
+    /// other rule fail it again, until the value or the state changes
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::ValidationError;
+    ///
+    /// assert!(ValidationError::InvalidState("validation_timeout".to_string()).is_retryable());
+    /// assert!(!ValidationError::FieldMismatch("length".to_string()).is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        matches!(*self, ValidationError::InvalidState(ref code) if code == VALIDATION_TIMEOUT)
+    }
+
+    // Name of the variant, as it is serialized in `kind`
+    pub(crate) fn kind(&self) -> &'static str {
+        match *self {
+            ValidationError::FieldMismatch(_) => "field_mismatch",
+            ValidationError::InvalidState(_) => "invalid_state",
+            ValidationError::InvalidTransition(_) => "invalid_transition",
+        }
+    }
+
+    // Whether or not the error is about the state rather than a value
+    fn is_state(&self) -> bool {
+        !matches!(*self, ValidationError::FieldMismatch(_))
+    }
+}
+
+/// Response of a failed validation, its `retryable` telling clients and queues whether or not
+/// sending the same payload again can succeed
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationErrorResponse {
     pub error_code: i32,
     pub error_message: String,
+    #[serde(default)]
+    pub retryable: bool,
 }
 
-impl ValidationErrorResponse {
+impl Default for ValidationErrorResponse {
     /// A default implementation that returns a `ValidationError` with 500 - Internal Server Error
     ///
     /// # Example
@@ -34,13 +163,16 @@ impl ValidationErrorResponse {
     /// assert_eq!(500, err.error_code);
     /// assert_eq!("Internal Server Error".to_string(), err.error_message);
     /// ```
-    pub fn default() -> Self {
+    fn default() -> Self {
         ValidationErrorResponse {
             error_code: 500,
             error_message: "Internal Server Error".to_string(),
+            retryable: false,
         }
     }
+}
 
+impl ValidationErrorResponse {
     /// Creates a new `ValidationError`
     ///
     /// # Arguments
@@ -59,20 +191,63 @@ impl ValidationErrorResponse {
         ValidationErrorResponse {
             error_code,
             error_message,
+            retryable: false,
         }
     }
+
+    /// Sets whether or not the same payload can succeed when it is sent again
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
 }
 
-impl From<ValidationError> for ValidationErrorResponse {
-    fn from(e: ValidationError) -> Self {
-        match e {
-            ValidationError::FieldMismatch(ref str) => {
-                ValidationErrorResponse::new(BAD_REQUEST, str.to_string())
+impl ValidationErrorResponse {
+    // Conversion with the message `translate` gives the error, the status being the one of
+    // the original code
+    pub(crate) fn from_translated<F>(e: ValidationError, translate: F) -> Self
+    where
+        F: FnOnce(ValidationError) -> String,
+    {
+        let error_code = match e {
+            ValidationError::FieldMismatch(_) => BAD_REQUEST,
+            ValidationError::InvalidState(ref code) if CONFLICT_CODES.contains(&code.as_str()) => {
+                CONFLICT
             }
-            ValidationError::InvalidState(ref str) => {
-                ValidationErrorResponse::new(UNPROCESSABLE_ENTITY, str.to_string())
+            ValidationError::InvalidState(ref code) if code == VALIDATION_TIMEOUT => {
+                SERVICE_UNAVAILABLE
             }
-        }
+            ValidationError::InvalidState(_) | ValidationError::InvalidTransition(_) => {
+                UNPROCESSABLE_ENTITY
+            }
+        };
+        let retryable = e.is_retryable();
+        ValidationErrorResponse::new(error_code, translate(e)).with_retryable(retryable)
+    }
+}
+
+impl From<ValidationError> for ValidationErrorResponse {
+    /// Converts the error to a response, translating its code with the registered
+    /// `ErrorCodeMap` when the service registered one
+    ///
+    /// Field mismatches are sent as `400`, invalid states as `422`, or `409` for the ones
+    /// clashing with a request already received, like `recently_seen`. A rule timing out is
+    /// sent as `503`, the value may well be valid
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationError, ValidationErrorResponse};
+    ///
+    /// let err = ValidationError::InvalidState("recently_seen".to_string());
+    /// assert_eq!(409, ValidationErrorResponse::from(err).error_code);
+    ///
+    /// let err = ValidationError::InvalidState("validation_timeout".to_string());
+    /// let response = ValidationErrorResponse::from(err);
+    /// assert_eq!(503, response.error_code);
+    /// assert!(response.retryable);
+    /// ```
+    fn from(e: ValidationError) -> Self {
+        ValidationErrorResponse::from_translated(e, error_code_map::registered_message)
     }
 }
 
@@ -81,16 +256,517 @@ impl Display for ValidationError {
         match *self {
             ValidationError::FieldMismatch(ref err) => err.fmt(f),
             ValidationError::InvalidState(ref err) => err.fmt(f),
+            ValidationError::InvalidTransition(ref transition) => {
+                write!(
+                    f,
+                    "cannot move {} from {} to {}",
+                    transition.entity, transition.from, transition.to
+                )?;
+                if !transition.allowed.is_empty() {
+                    write!(f, "; allowed: {}", transition.allowed.join(", "))?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
 impl std::error::Error for ValidationError {}
 
+/// Part of a request a field is read from, set with `#[validate(source = "...")]` so errors
+/// are attributed to the part the client has to fix
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FieldSource {
+    Header,
+    Path,
+    Query,
+    Body,
+}
+
+/// Names of the sources, as written in `#[validate(source = "...")]`
+pub const FIELD_SOURCES: [&str; 4] = ["header", "path", "query", "body"];
+
+impl FieldSource {
+    /// Returns the source of the name, `None` when it is unknown
+    pub fn from_name(name: &str) -> Option<FieldSource> {
+        match name {
+            "header" => Some(FieldSource::Header),
+            "path" => Some(FieldSource::Path),
+            "query" => Some(FieldSource::Query),
+            "body" => Some(FieldSource::Body),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            FieldSource::Header => "header",
+            FieldSource::Path => "path",
+            FieldSource::Query => "query",
+            FieldSource::Body => "body",
+        }
+    }
+}
+
+/// A `ValidationError` along with the field it was found on, if known, and the params that
+/// help a client fix the value, like the `suggestion` of `one_of`
+///
+/// Serialized as `{"field": "name", "kind": "field_mismatch", "code": "length"}`, without
+/// `field` when the error is not attached to one, without `params` when it has none and
+/// without `source` when the part of the request the field is read from is not known
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FieldError {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(flatten)]
+    pub error: ValidationError,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<FieldSource>,
+}
+
+impl FieldError {
+    /// Creates a new `FieldError` for the given field
+    pub fn new(field: &str, error: ValidationError) -> Self {
+        FieldError {
+            field: Some(field.to_string()),
+            error,
+            params: BTreeMap::new(),
+            source: None,
+        }
+    }
+
+    /// Creates a new `FieldError` not attached to any field
+    pub fn unattached(error: ValidationError) -> Self {
+        FieldError {
+            field: None,
+            error,
+            params: BTreeMap::new(),
+            source: None,
+        }
+    }
+
+    /// Returns whether or not the error has the code, whatever its kind
+    pub fn matches_code(&self, code: &str) -> bool {
+        self.error.matches_code(code)
+    }
+
+    /// Returns the error read from the source
+    pub fn with_source(mut self, source: FieldSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Returns where the client has to fix the value, the source and the field like
+    /// `header:X-Tenant-Id`, the field of the body as a JSON Pointer like `body:/items/0/name`.
+    /// `None` when the source is not known
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, FieldSource, ValidationError};
+    ///
+    /// let err = FieldError::new("X-Tenant-Id", ValidationError::field_mismatch("length"))
+    ///     .with_source(FieldSource::Header);
+    /// assert_eq!(Some("header:X-Tenant-Id".to_string()), err.location());
+    ///
+    /// let err = FieldError::new("items[0].name", ValidationError::field_mismatch("length"))
+    ///     .with_source(FieldSource::Body);
+    /// assert_eq!(Some("body:/items/0/name".to_string()), err.location());
+    /// assert_eq!("body:/items/0/name: length", err.to_string());
+    /// ```
+    pub fn location(&self) -> Option<String> {
+        let source = self.source?;
+        let field = self.field.as_deref().unwrap_or_default();
+        Some(match source {
+            FieldSource::Body => format!("body:{}", json_pointer(field)),
+            _ => format!("{}:{}", source.name(), field),
+        })
+    }
+
+    /// Returns the error with the param, replacing any param of that name
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, ValidationError};
+    ///
+    /// let err = FieldError::new("currency", ValidationError::FieldMismatch("one_of".to_string()))
+    ///     .with_param("suggestion", "EUR");
+    ///
+    /// assert_eq!(
+    ///     r#"{"field":"currency","kind":"field_mismatch","code":"one_of","params":{"suggestion":"EUR"}}"#,
+    ///     serde_json::to_string(&err).unwrap()
+    /// );
+    /// ```
+    pub fn with_param<V: Into<Value>>(mut self, name: &str, value: V) -> Self {
+        self.params.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Returns the error found in a value nested in `prefix`, a field or an index like `[0]`
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, ValidationError};
+    ///
+    /// let err = FieldError::new("name", ValidationError::FieldMismatch("length".to_string()));
+    /// let err = err.prefixed("[1]").prefixed("children");
+    /// assert_eq!(Some("children[1].name"), err.field.as_deref());
+    /// ```
+    pub fn prefixed(self, prefix: &str) -> Self {
+        let field = match self.field {
+            Some(ref field) if field.starts_with('[') => format!("{}{}", prefix, field),
+            Some(ref field) => format!("{}.{}", prefix, field),
+            None => prefix.to_string(),
+        };
+        FieldError {
+            field: Some(field),
+            ..self
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if let Some(location) = self.location() {
+            return write!(f, "{}: {}", location, self.error);
+        }
+        match self.field {
+            Some(ref field) => write!(f, "{}: {}", field, self.error),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+// JSON Pointer of a field path like `items[0].name`, `~` and `/` escaped as `~0` and `~1`
+fn json_pointer(field: &str) -> String {
+    fn push(segment: &mut String, pointer: &mut String) {
+        if !segment.is_empty() {
+            pointer.push('/');
+            pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+            segment.clear();
+        }
+    }
+
+    let mut pointer = String::new();
+    let mut segment = String::new();
+    let mut in_brackets = false;
+    for c in field.chars() {
+        match c {
+            '[' if !in_brackets => {
+                push(&mut segment, &mut pointer);
+                in_brackets = true;
+            }
+            ']' if in_brackets => {
+                push(&mut segment, &mut pointer);
+                in_brackets = false;
+            }
+            '.' if !in_brackets => push(&mut segment, &mut pointer),
+            c => segment.push(c),
+        }
+    }
+    push(&mut segment, &mut pointer);
+    pointer
+}
+
+/// Every failure of a validation, in the order the rules ran
+///
+/// The order is deterministic, the same on every run for the same value: the derive validates
+/// fields in declaration order, then the rules of a field in their rule order, and the
+/// elements of collections by index or key. Hash maps are ordered by the `Display` of their
+/// keys and hash sets by the JSON of the errors of their elements, never by hash
+///
+/// # Example
+/// ```
+/// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.extend_field("name", vec![ValidationError::FieldMismatch("not_null".to_string())]);
+///
+/// assert_eq!(1, errors.len());
+/// assert_eq!(Some("name"), errors.iter().next().unwrap().field.as_deref());
+/// assert_eq!(
+///     Err(ValidationError::FieldMismatch("not_null".to_string())),
+///     errors.into_result()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        ValidationErrors { errors: Vec::new() }
+    }
+
+    pub fn push(&mut self, error: FieldError) {
+        self.errors.push(error);
+    }
+
+    /// Adds the failures of the rules of a field
+    pub fn extend_field(&mut self, field: &str, errors: Vec<ValidationError>) {
+        self.errors
+            .extend(errors.into_iter().map(|err| FieldError::new(field, err)));
+    }
+
+    /// Adds the failures of a value nested in `prefix`, see `FieldError::prefixed`
+    pub fn extend_nested(&mut self, prefix: &str, errors: ValidationErrors) {
+        self.errors
+            .extend(errors.into_iter().map(|err| err.prefixed(prefix)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FieldError> {
+        self.errors.iter()
+    }
+
+    /// Returns whether or not the same value could pass on a later try: there are failures,
+    /// and each of them `is_retryable`
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationError, ValidationErrors};
+    ///
+    /// let timeout = ValidationError::InvalidState("validation_timeout".to_string());
+    /// let mut errors = ValidationErrors::new();
+    /// errors.extend_field("email", vec![timeout]);
+    /// assert!(errors.is_retryable());
+    ///
+    /// errors.extend_field("name", vec![ValidationError::FieldMismatch("length".to_string())]);
+    /// assert!(!errors.is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        !self.errors.is_empty() && self.errors.iter().all(|err| err.error.is_retryable())
+    }
+
+    /// Returns the first failure, the one `validate_fields` reports, if any
+    pub fn first(&self) -> Option<&FieldError> {
+        self.errors.first()
+    }
+
+    /// Returns the last failure, to attach params to the failure of the rule that just ran
+    pub fn last_mut(&mut self) -> Option<&mut FieldError> {
+        self.errors.last_mut()
+    }
+
+    /// Attributes the failures of the fields sent under one of their serde `alias`es to that
+    /// alias, so clients using a legacy name get their errors under the name they sent. Failures
+    /// of fields sent under their name, or not sent at all, keep their field
+    ///
+    /// # Arguments
+    ///
+    /// * `aliases` - `(alias, name)` pairs, typically the `FieldValidation::field_aliases()`
+    /// * `sent` - the names of the fields the client sent
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationError, ValidationErrors};
+    ///
+    /// let mut errors = ValidationErrors::new();
+    /// errors.extend_field("email", vec![ValidationError::FieldMismatch("length".to_string())]);
+    /// errors.extend_field("address.city", vec![ValidationError::FieldMismatch("not_null".to_string())]);
+    ///
+    /// errors.attribute_to_aliases(&[("mail", "email"), ("addr", "address")], &["mail", "addr"]);
+    /// let fields = errors.iter().map(|err| err.field.as_deref()).collect::<Vec<_>>();
+    /// assert_eq!(vec![Some("mail"), Some("addr.city")], fields);
+    /// ```
+    pub fn attribute_to_aliases<S: AsRef<str>>(&mut self, aliases: &[(&str, &str)], sent: &[S]) {
+        let is_sent = |name: &str| sent.iter().any(|sent| sent.as_ref() == name);
+        for err in &mut self.errors {
+            let field = match err.field {
+                Some(ref mut field) => field,
+                None => continue,
+            };
+            // Nested failures are attributed per the field at the top of their path
+            let top = field.find(['.', '[']).unwrap_or(field.len());
+            if is_sent(&field[..top]) {
+                continue;
+            }
+            if let Some((alias, _)) = aliases
+                .iter()
+                .find(|(alias, name)| *name == &field[..top] && is_sent(alias))
+            {
+                field.replace_range(..top, alias);
+            }
+        }
+    }
+
+    /// Attributes the failures of the fields read from a part of the request other than the
+    /// body to that part, see `FieldError::location`. Failures that already have a source, like
+    /// the ones of a nested value, keep it
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - `(name, source)` pairs, typically the `FieldValidation::field_sources()`
+    pub fn attribute_to_sources(&mut self, sources: &[(&str, FieldSource)]) {
+        for err in &mut self.errors {
+            let field = match err.field {
+                Some(ref field) if err.source.is_none() => field,
+                _ => continue,
+            };
+            let top = field.find(['.', '[']).unwrap_or(field.len());
+            err.source = sources
+                .iter()
+                .find(|(name, _)| *name == &field[..top])
+                .map(|(_, source)| *source);
+        }
+    }
+
+    /// Returns the errors as key-value pairs with stable keys, see `KeyValues`, to log them as
+    /// structured fields rather than as a string
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationError, ValidationErrors};
+    ///
+    /// let mut errors = ValidationErrors::new();
+    /// errors.extend_field("email", vec![ValidationError::FieldMismatch("length".to_string())]);
+    ///
+    /// let kv = errors.as_kv();
+    /// assert_eq!(Some("1"), kv.get("validation.error_count"));
+    /// assert_eq!(Some("email"), kv.get("validation.errors.0.field"));
+    /// assert_eq!(Some("length"), kv.get("validation.errors.0.code"));
+    /// ```
+    pub fn as_kv(&self) -> KeyValues {
+        KeyValues::from_errors(self)
+    }
+
+    /// Returns the errors as a JSON array, see `FieldError` for the shape of an error
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Returns the errors embedded at the start of a message, typically the serde error of a
+    /// `#[derive(DeserializeValidated)]` struct, `None` when the message does not start with any
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+    ///
+    /// let mut errors = ValidationErrors::new();
+    /// errors.push(FieldError::new("name", ValidationError::FieldMismatch("length".to_string())));
+    ///
+    /// let message = format!("{} at line 1 column 16", errors.to_json());
+    /// assert_eq!(Some(errors), ValidationErrors::from_embedded(&message));
+    /// assert_eq!(None, ValidationErrors::from_embedded("missing field `name`"));
+    /// ```
+    pub fn from_embedded(message: &str) -> Option<ValidationErrors> {
+        serde_json::Deserializer::from_str(message)
+            .into_iter::<ValidationErrors>()
+            .next()
+            .and_then(|errors| errors.ok())
+    }
+
+    /// Converts to the `ValidationResult` of `validate_fields`, failing with the first error
+    pub fn into_result(self) -> crate::validation::ValidationResult<()> {
+        match self.errors.into_iter().next() {
+            Some(err) => Err(err.error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let errors = self.errors.iter().map(FieldError::to_string);
+        f.write_str(&errors.collect::<Vec<_>>().join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl From<ValidationErrors> for ValidationError {
+    /// Summarizes every failure in a single error, an `InvalidState` when all of them are
+    /// invalid states or transitions, a `FieldMismatch` otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+    ///
+    /// let mut errors = ValidationErrors::new();
+    /// errors.extend_field("name", vec![ValidationError::FieldMismatch("length".to_string())]);
+    /// errors.push(FieldError::unattached(ValidationError::InvalidState("closed".to_string())));
+    ///
+    /// assert_eq!(
+    ///     ValidationError::FieldMismatch("name: length; closed".to_string()),
+    ///     ValidationError::from(errors)
+    /// );
+    /// ```
+    fn from(errors: ValidationErrors) -> Self {
+        let summary = errors.to_string();
+        let all_state = !errors.is_empty() && errors.iter().all(|err| err.error.is_state());
+        if all_state {
+            ValidationError::InvalidState(summary)
+        } else {
+            ValidationError::FieldMismatch(summary)
+        }
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationErrors {
+    type Item = &'a FieldError;
+    type IntoIter = std::slice::Iter<'a, FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::error::*;
 
+    #[test]
+    fn test_attribute_to_sources() {
+        let mut errors = ValidationErrors::new();
+        let length = || vec![ValidationError::field_mismatch("length")];
+        errors.extend_field("X-Tenant-Id", length());
+        errors.extend_field("address.city", length());
+        errors.extend_field("a/b~c", length());
+        errors.push(
+            FieldError::new("id", ValidationError::field_mismatch("length"))
+                .with_source(FieldSource::Path),
+        );
+
+        errors.attribute_to_sources(&[
+            ("X-Tenant-Id", FieldSource::Header),
+            ("address", FieldSource::Body),
+            ("a/b~c", FieldSource::Body),
+            ("id", FieldSource::Query),
+        ]);
+        let locations = errors.iter().map(FieldError::location).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Some("header:X-Tenant-Id".to_string()),
+                Some("body:/address/city".to_string()),
+                Some("body:/a~1b~0c".to_string()),
+                Some("path:id".to_string()),
+            ],
+            locations
+        );
+        assert_eq!("/tags/a.b/0", json_pointer("tags[a.b][0]"));
+    }
+
     #[test]
     fn test_from_validation_error_field_mismatch() {
         let err = ValidationError::FieldMismatch("Bad String".to_string());
@@ -105,5 +781,69 @@ mod tests {
         let resp = ValidationErrorResponse::from(err);
         assert_eq!(UNPROCESSABLE_ENTITY, resp.error_code);
         assert_eq!("Bad Payload", resp.error_message);
+        assert!(!resp.retryable);
+    }
+
+    #[test]
+    fn test_response_retryable() {
+        let err = ValidationError::InvalidState(VALIDATION_TIMEOUT.to_string());
+        let resp = ValidationErrorResponse::from(err);
+        assert_eq!(SERVICE_UNAVAILABLE, resp.error_code);
+        assert!(resp.retryable);
+
+        let json = serde_json::to_string(&resp).unwrap();
+        assert!(json.contains("\"retryable\":true"), "{}", json);
+        let resp: ValidationErrorResponse =
+            serde_json::from_str(r#"{"errorCode": 400, "errorMessage": "length"}"#).unwrap();
+        assert!(!resp.retryable);
+    }
+
+    #[test]
+    fn test_field_error_display() {
+        let err = FieldError::new(
+            "name",
+            ValidationError::FieldMismatch("not_null".to_string()),
+        );
+        assert_eq!("name: not_null", err.to_string());
+        let err = FieldError::unattached(ValidationError::InvalidState("bad".to_string()));
+        assert_eq!("bad", err.to_string());
+    }
+
+    #[test]
+    fn test_validation_errors_summary_all_state() {
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new(
+            "status",
+            ValidationError::InvalidState("closed".to_string()),
+        ));
+        assert_eq!(
+            ValidationError::InvalidState("status: closed".to_string()),
+            ValidationError::from(errors)
+        );
+        assert_eq!(
+            ValidationError::FieldMismatch(String::new()),
+            ValidationError::from(ValidationErrors::new())
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_to_json() {
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new(
+            "name",
+            ValidationError::FieldMismatch("length".to_string()),
+        ));
+        errors.push(FieldError::unattached(ValidationError::InvalidState(
+            "closed".to_string(),
+        )));
+        assert_eq!(
+            r#"[{"field":"name","kind":"field_mismatch","code":"length"},{"kind":"invalid_state","code":"closed"}]"#,
+            errors.to_json()
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_into_result_empty() {
+        assert_eq!(Ok(()), ValidationErrors::new().into_result());
     }
 }
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::{ValidationError, ValidationErrorResponse};
+
+lazy_static::lazy_static! {
+    static ref REGISTERED_MAP: RwLock<Option<ErrorCodeMap>> = RwLock::new(None);
+}
+
+// Held by the tests registering a map, the registered one being shared by every test
+#[cfg(test)]
+pub(crate) static REGISTRY_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// The replacement for an error code, optionally with a message to send in its place
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ErrorCodeMapping {
+    pub code: String,
+    pub message: Option<String>,
+}
+
+/// Translates error codes (old code -> new code/message) when a `ValidationError` is
+/// converted to a `ValidationErrorResponse`
+///
+/// The idea is to let a service migrate its error codes in one place, rather than touching
+/// every `#[validate]` attribute at once. A service registers its map once at start up with
+/// `register`, after which every `ValidationErrorResponse::from` applies it
+///
+/// # Example
+/// ```
+/// use crate::validation::{ErrorCodeMap, ValidationError};
+///
+/// let map = ErrorCodeMap::new()
+///     .map("not_null", "required")
+///     .map_with_message("regex_inline", "invalid_format", "value has an invalid format");
+///
+/// let resp = map.to_response(ValidationError::FieldMismatch("not_null".to_string()));
+/// assert_eq!(400, resp.error_code);
+/// assert_eq!("required", resp.error_message);
+///
+/// let resp = map.to_response(ValidationError::FieldMismatch("regex_inline".to_string()));
+/// assert_eq!("value has an invalid format", resp.error_message);
+///
+/// let err = map.translate(ValidationError::FieldMismatch("regex_inline".to_string()));
+/// assert_eq!("invalid_format", err.code());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorCodeMap {
+    mappings: HashMap<String, ErrorCodeMapping>,
+}
+
+impl ErrorCodeMap {
+    /// Creates a new, empty `ErrorCodeMap`
+    pub fn new() -> Self {
+        ErrorCodeMap {
+            mappings: HashMap::new(),
+        }
+    }
+
+    /// Maps `old_code` to `new_code`
+    ///
+    /// # Arguments
+    ///
+    ///  * `old_code` - code currently produced by the validation
+    ///  * `new_code` - code to send in its place
+    pub fn map(mut self, old_code: &str, new_code: &str) -> Self {
+        self.mappings.insert(
+            old_code.to_string(),
+            ErrorCodeMapping {
+                code: new_code.to_string(),
+                message: None,
+            },
+        );
+        self
+    }
+
+    /// Maps `old_code` to `new_code`, sending `message` in the response instead of the code.
+    /// The translated error keeps `new_code`
+    ///
+    /// # Arguments
+    ///
+    ///  * `old_code` - code currently produced by the validation
+    ///  * `new_code` - code to send in its place
+    ///  * `message` - message to send in the response
+    pub fn map_with_message(mut self, old_code: &str, new_code: &str, message: &str) -> Self {
+        self.mappings.insert(
+            old_code.to_string(),
+            ErrorCodeMapping {
+                code: new_code.to_string(),
+                message: Some(message.to_string()),
+            },
+        );
+        self
+    }
+
+    /// Returns the `ErrorCodeMapping` for the given code, if there is one
+    pub fn get(&self, code: &str) -> Option<&ErrorCodeMapping> {
+        self.mappings.get(code)
+    }
+
+    /// Returns the `ValidationError` with its code translated, keeping the kind of error
+    /// An `InvalidTransition` has no code to translate, it is returned as it is
+    pub fn translate(&self, err: ValidationError) -> ValidationError {
+        match err {
+            ValidationError::FieldMismatch(code) => {
+                ValidationError::FieldMismatch(self.translate_code(code))
+            }
+            ValidationError::InvalidState(code) => {
+                ValidationError::InvalidState(self.translate_code(code))
+            }
+            err @ ValidationError::InvalidTransition(_) => err,
+        }
+    }
+
+    /// Converts the `ValidationError` to a `ValidationErrorResponse` using this map
+    /// rather than the registered one
+    pub fn to_response(&self, err: ValidationError) -> ValidationErrorResponse {
+        ValidationErrorResponse::from_translated(err, |err| self.response_message(err))
+    }
+
+    /// Registers the map for the whole service, replacing any map registered before
+    pub fn register(self) {
+        *REGISTERED_MAP.write().unwrap_or_else(|e| e.into_inner()) = Some(self);
+    }
+
+    /// Removes the registered map, responses go back to using the codes as they are
+    pub fn unregister() {
+        *REGISTERED_MAP.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn translate_code(&self, code: String) -> String {
+        match self.mappings.get(&code) {
+            Some(mapping) => mapping.code.clone(),
+            None => code,
+        }
+    }
+
+    // Message of the response of the error: the message of the mapping of its code if it has
+    // one, else its translated code
+    fn response_message(&self, err: ValidationError) -> String {
+        let message = match err {
+            ValidationError::FieldMismatch(ref code) | ValidationError::InvalidState(ref code) => {
+                self.mappings
+                    .get(code)
+                    .and_then(|mapping| mapping.message.clone())
+            }
+            ValidationError::InvalidTransition(_) => None,
+        };
+        message.unwrap_or_else(|| self.translate(err).to_string())
+    }
+}
+
+/// Translates the error with the registered `ErrorCodeMap`, if one was registered
+pub(crate) fn translate_registered(err: ValidationError) -> ValidationError {
+    match *REGISTERED_MAP.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(ref map) => map.translate(err),
+        None => err,
+    }
+}
+
+/// Returns the message of the response of the error per the registered `ErrorCodeMap`, the
+/// error as it is if none was registered
+pub(crate) fn registered_message(err: ValidationError) -> String {
+    match *REGISTERED_MAP.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(ref map) => map.response_message(err),
+        None => err.to_string(),
+    }
+}
+
+/// Returns the registered `ErrorCodeMapping` for the given code, if there is one
+#[cfg_attr(not(feature = "catalog"), allow(dead_code))]
+pub(crate) fn registered_mapping(code: &str) -> Option<ErrorCodeMapping> {
+    match *REGISTERED_MAP.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(ref map) => map.get(code).cloned(),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ValidationErrorResponse;
+    use crate::error_code_map::*;
+
+    #[test]
+    fn test_translate_keeps_unmapped_codes() {
+        let map = ErrorCodeMap::new().map("not_null", "required");
+        let err = ValidationError::InvalidState("closed".to_string());
+        assert_eq!(
+            ValidationError::InvalidState("closed".to_string()),
+            map.translate(err)
+        );
+    }
+
+    #[test]
+    fn test_translate_keeps_the_new_code_of_a_message() {
+        let map = ErrorCodeMap::new().map_with_message(
+            "regex_inline",
+            "invalid_format",
+            "value has an invalid format",
+        );
+        let err = map.translate(ValidationError::FieldMismatch("regex_inline".to_string()));
+        assert_eq!("invalid_format", err.code());
+        assert_eq!(
+            ValidationError::FieldMismatch("invalid_format".to_string()),
+            err
+        );
+    }
+
+    #[test]
+    fn test_translate_invalid_state() {
+        let map = ErrorCodeMap::new().map("closed", "order_closed");
+        let resp = map.to_response(ValidationError::InvalidState("closed".to_string()));
+        assert_eq!(422, resp.error_code);
+        assert_eq!("order_closed", resp.error_message);
+    }
+
+    #[test]
+    fn test_registered_map_applied_on_from() {
+        let _registry = REGISTRY_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        ErrorCodeMap::new()
+            .map("test_registered_old", "test_registered_new")
+            .register();
+        let resp = ValidationErrorResponse::from(ValidationError::FieldMismatch(
+            "test_registered_old".to_string(),
+        ));
+        assert_eq!("test_registered_new", resp.error_message);
+
+        ErrorCodeMap::unregister();
+        let resp = ValidationErrorResponse::from(ValidationError::FieldMismatch(
+            "test_registered_old".to_string(),
+        ));
+        assert_eq!("test_registered_old", resp.error_message);
+    }
+}
@@ -0,0 +1,24 @@
+/// Returns whether or not the float is a number other than an infinity
+///
+/// JSON cannot carry `NaN` or an infinity, other sources like protobuf or CSV can
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered, `f32` or `f64`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_finite;
+///
+/// assert_eq!(true, is_finite(&-0.5f32));
+/// assert_eq!(true, is_finite(&f64::MAX));
+/// assert_eq!(false, is_finite(&f64::NAN));
+/// assert_eq!(false, is_finite(&f32::NEG_INFINITY));
+/// assert_eq!(false, is_finite(&"inf".parse::<f64>().unwrap()));
+/// ```
+pub fn is_finite<T>(value: &T) -> bool
+where
+    T: Copy + Into<f64>,
+{
+    (*value).into().is_finite()
+}
@@ -0,0 +1,105 @@
+/// Integer types `fits_in` checks text against
+pub const INTEGER_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Returns whether or not the text is an integer that fits in the integer type, failing with
+/// the error code of the reason it does not
+///
+/// Text that is not an integer of an optional sign and decimal digits fails with `fits_in`,
+/// an integer out of the bounds of the type with `integer_overflow`, never wrapping around
+///
+/// # Arguments
+///
+/// * `value` - `&str` to be considered
+/// * `target` - name of the integer type, one of `INTEGER_TYPES`
+///
+/// # Example
+/// ```
+/// use crate::validation::fits_in;
+///
+/// assert_eq!(Ok(()), fits_in("4294967295", "u32"));
+/// assert_eq!(Err("integer_overflow"), fits_in("4294967296", "u32"));
+/// assert_eq!(Err("integer_overflow"), fits_in("-1", "u32"));
+/// assert_eq!(Ok(()), fits_in("-128", "i8"));
+/// assert_eq!(Err("fits_in"), fits_in("12.5", "i64"));
+/// assert_eq!(Err("fits_in"), fits_in(" 12", "i64"));
+/// ```
+pub fn fits_in(value: &str, target: &str) -> Result<(), &'static str> {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (min, max) = match bounds(target) {
+        Some(bounds) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => bounds,
+        _ => return Err("fits_in"),
+    };
+
+    // `u128` is the only type wider than `i128`, the text is checked against it on its own
+    let fits = if target == "u128" {
+        value.strip_prefix('-').map_or_else(
+            || value.parse::<u128>().is_ok(),
+            |digits| digits.bytes().all(|b| b == b'0'),
+        )
+    } else {
+        value.parse::<i128>().is_ok_and(|n| n >= min && n <= max)
+    };
+
+    if fits {
+        Ok(())
+    } else {
+        Err("integer_overflow")
+    }
+}
+
+fn bounds(target: &str) -> Option<(i128, i128)> {
+    let bounds = match target {
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        "u128" => (0, i128::MAX),
+        "usize" => (0, usize::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        "i128" => (i128::MIN, i128::MAX),
+        "isize" => (isize::MIN as i128, isize::MAX as i128),
+        _ => return None,
+    };
+    Some(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fits_in::{fits_in, INTEGER_TYPES};
+
+    #[test]
+    fn test_fits_in_bounds_of_every_type() {
+        for target in INTEGER_TYPES.iter() {
+            assert_eq!(Ok(()), fits_in("0", target));
+            assert_eq!(Ok(()), fits_in("+127", target));
+        }
+        assert_eq!(
+            Ok(()),
+            fits_in("340282366920938463463374607431768211455", "u128")
+        );
+        assert_eq!(
+            Err("integer_overflow"),
+            fits_in("340282366920938463463374607431768211456", "u128")
+        );
+        assert_eq!(
+            Err("integer_overflow"),
+            fits_in("-170141183460469231731687303715884105729", "i128")
+        );
+        assert_eq!(Ok(()), fits_in("-0", "u128"));
+        assert_eq!(Err("integer_overflow"), fits_in("-1", "u128"));
+    }
+
+    #[test]
+    fn test_fits_in_not_an_integer() {
+        assert_eq!(Err("fits_in"), fits_in("", "u8"));
+        assert_eq!(Err("fits_in"), fits_in("-", "u8"));
+        assert_eq!(Err("fits_in"), fits_in("1e3", "u32"));
+        assert_eq!(Err("fits_in"), fits_in("٣", "u32"));
+        assert_eq!(Err("fits_in"), fits_in("1", "f64"));
+    }
+}
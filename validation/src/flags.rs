@@ -0,0 +1,71 @@
+use std::ops::{BitAnd, Not};
+
+/// Flag types the `flags_of` rule checks integers against, like the types of the `bitflags`
+/// crate
+///
+/// # Example
+/// ```
+/// use crate::validation::{has_known_flags, KnownFlags};
+///
+/// struct Permissions;
+///
+/// impl Permissions {
+///     const READ: u8 = 0b001;
+///     const WRITE: u8 = 0b010;
+/// }
+///
+/// impl KnownFlags for Permissions {
+///     type Bits = u8;
+///
+///     fn known_bits() -> u8 {
+///         Permissions::READ | Permissions::WRITE
+///     }
+/// }
+///
+/// assert_eq!(true, has_known_flags::<Permissions>(&0b011));
+/// assert_eq!(false, has_known_flags::<Permissions>(&0b100));
+/// ```
+pub trait KnownFlags {
+    /// Integer the flags are bits of
+    type Bits;
+
+    /// Every bit one flag or another sets
+    fn known_bits() -> Self::Bits;
+}
+
+/// Returns whether or not only the bits of known flags are set, none of the flags required
+///
+/// # Arguments
+///
+/// * `bits` - `&F::Bits` to be considered, the integer of the flags
+pub fn has_known_flags<F>(bits: &F::Bits) -> bool
+where
+    F: KnownFlags + ?Sized,
+    F::Bits: Copy + BitAnd<Output = F::Bits> + Not<Output = F::Bits> + Default + PartialEq,
+{
+    *bits & !F::known_bits() == F::Bits::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flags::{has_known_flags, KnownFlags};
+
+    // Flags of a signed protocol field, the sign bit one of them
+    struct Status;
+
+    impl KnownFlags for Status {
+        type Bits = i32;
+
+        fn known_bits() -> i32 {
+            i32::MIN | 0b1
+        }
+    }
+
+    #[test]
+    fn test_has_known_flags_signed() {
+        assert!(has_known_flags::<Status>(&0));
+        assert!(has_known_flags::<Status>(&(i32::MIN | 0b1)));
+        assert!(!has_known_flags::<Status>(&-1));
+        assert!(!has_known_flags::<Status>(&0b10));
+    }
+}
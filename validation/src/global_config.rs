@@ -0,0 +1,190 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::{Arc, OnceLock};
+
+use crate::catalog;
+use crate::context::ValidationObserver;
+use crate::error::{FieldError, ValidationErrors};
+
+static GLOBAL_CONFIG: OnceLock<ValidationConfig> = OnceLock::new();
+static DEFAULT_CONFIG: OnceLock<ValidationConfig> = OnceLock::new();
+
+/// Whether a validation reports every failure or only the first one
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationMode {
+    /// Every failure is reported, up to the `max_errors` of the config
+    #[default]
+    CollectAll,
+    /// Only the first failure is reported, the rules left are not run once it is found
+    FailFast,
+}
+
+/// Resolves the message of a failure, typically from the translations of the service
+pub trait MessageResolver: Send + Sync {
+    /// Returns the message of the failure in the locale, `None` to fall back on the default
+    /// message of its code in the catalog
+    fn resolve(&self, error: &FieldError, locale: &str) -> Option<String>;
+}
+
+/// Configuration shared by every validation of a process: framework integrations and manual
+/// calls alike
+///
+/// Set once at startup with `set_global`, a `ValidationContext` uses it unless it is given
+/// one of its own with `ValidationContext::with_config`
+///
+/// # Example
+/// ```
+/// use crate::validation::{
+///     FieldError, ValidationConfig, ValidationContext, ValidationError, ValidationErrors,
+///     ValidationMode,
+/// };
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.push(FieldError::new("name", ValidationError::FieldMismatch("length".to_string())));
+/// errors.push(FieldError::new("age", ValidationError::FieldMismatch("range".to_string())));
+///
+/// let ctx = ValidationContext::default()
+///     .with_config(ValidationConfig::new().with_mode(ValidationMode::FailFast));
+/// assert_eq!(1, ctx.config().limit(errors).len());
+/// ```
+#[derive(Clone, Default)]
+pub struct ValidationConfig {
+    mode: ValidationMode,
+    max_errors: Option<usize>,
+    observer: Option<Arc<dyn ValidationObserver>>,
+    message_resolver: Option<Arc<dyn MessageResolver>>,
+}
+
+impl ValidationConfig {
+    pub fn new() -> Self {
+        ValidationConfig::default()
+    }
+
+    pub fn with_mode(mut self, mode: ValidationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the most failures a validation reports, the first ones found. The rules left are
+    /// not run once that many failed. 0 reports the first failure, as `validate_batch` stops at
+    /// the first one
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Sets the observer of the environments that do not set their own
+    pub fn with_observer(mut self, observer: Arc<dyn ValidationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn with_message_resolver(mut self, message_resolver: Arc<dyn MessageResolver>) -> Self {
+        self.message_resolver = Some(message_resolver);
+        self
+    }
+
+    /// Sets the config of every validation without one of its own, for the life of the
+    /// process. Only the first call sets it, the others return their config back
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationConfig, ValidationContext};
+    ///
+    /// assert!(ValidationConfig::new().with_max_errors(20).set_global().is_ok());
+    /// assert!(ValidationConfig::new().set_global().is_err());
+    /// assert_eq!(Some(20), ValidationContext::default().config().max_errors());
+    /// ```
+    pub fn set_global(self) -> std::result::Result<(), ValidationConfig> {
+        GLOBAL_CONFIG.set(self)
+    }
+
+    /// Returns the config set with `set_global`, the default one until it is set
+    pub fn global() -> &'static ValidationConfig {
+        GLOBAL_CONFIG
+            .get()
+            .unwrap_or_else(|| DEFAULT_CONFIG.get_or_init(ValidationConfig::new))
+    }
+
+    pub fn mode(&self) -> ValidationMode {
+        self.mode
+    }
+
+    pub fn max_errors(&self) -> Option<usize> {
+        self.max_errors
+    }
+
+    pub fn observer(&self) -> Option<&Arc<dyn ValidationObserver>> {
+        self.observer.as_ref()
+    }
+
+    /// Returns whether or not a validation that found that many failures reports no more, so
+    /// the rules left need not run
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{ValidationConfig, ValidationMode};
+    ///
+    /// let fail_fast = ValidationConfig::new().with_mode(ValidationMode::FailFast);
+    /// assert_eq!(false, fail_fast.is_exhausted(0));
+    /// assert_eq!(true, fail_fast.is_exhausted(1));
+    /// assert_eq!(false, ValidationConfig::new().is_exhausted(100));
+    /// assert_eq!(false, ValidationConfig::new().with_max_errors(0).is_exhausted(0));
+    /// ```
+    pub fn is_exhausted(&self, errors: usize) -> bool {
+        errors >= self.budget()
+    }
+
+    /// Returns the failures the config reports, the first ones per its mode and `max_errors`
+    pub fn limit(&self, errors: ValidationErrors) -> ValidationErrors {
+        let max_errors = self.budget();
+        if errors.len() <= max_errors {
+            return errors;
+        }
+
+        let mut limited = ValidationErrors::new();
+        for error in errors.into_iter().take(max_errors) {
+            limited.push(error);
+        }
+        limited
+    }
+
+    // Most failures a validation reports, never none so invalid values are not let through
+    fn budget(&self) -> usize {
+        match self.mode {
+            ValidationMode::FailFast => 1,
+            ValidationMode::CollectAll => self.max_errors.unwrap_or(usize::MAX).max(1),
+        }
+    }
+
+    /// Returns the message of the failure in the locale, per the message resolver, else the
+    /// default message of its code in the catalog, else the code itself
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::{FieldError, ValidationConfig, ValidationError};
+    ///
+    /// let error = FieldError::new("name", ValidationError::FieldMismatch("not_null".to_string()));
+    /// assert_eq!("value is required", ValidationConfig::new().message(&error, "en-US"));
+    /// ```
+    pub fn message(&self, error: &FieldError, locale: &str) -> String {
+        let code = error.error.code();
+
+        self.message_resolver
+            .as_ref()
+            .and_then(|resolver| resolver.resolve(error, locale))
+            .or_else(|| catalog::default_message(code).map(str::to_string))
+            .unwrap_or_else(|| code.to_string())
+    }
+}
+
+impl Debug for ValidationConfig {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_struct("ValidationConfig")
+            .field("mode", &self.mode)
+            .field("max_errors", &self.max_errors)
+            .field("observer", &self.observer.is_some())
+            .field("message_resolver", &self.message_resolver.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,88 @@
+/// Maximum length of `canonical_identifier` in characters, when the attribute sets none
+pub const CANONICAL_IDENTIFIER_MAX: usize = 64;
+
+/// Returns whether or not the text is a canonical identifier, the one form of it every service
+/// stores and compares:
+///
+///  * trimmed, starting and ending with a letter or a digit
+///  * lowercase, the text being its own lowercase
+///  * in NFC, so `"é"` is one character rather than `e` and a combining accent
+///  * of letters, digits and `_`, `-` or `.`, any script for the letters and digits
+///  * from 1 to `max` characters
+///
+/// Needs the `unicode` feature, for the normalization tables
+///
+/// # Arguments
+///
+/// * `value` - `&str` to be considered
+/// * `max` - maximum length in characters, typically `CANONICAL_IDENTIFIER_MAX`
+///
+/// # Example
+/// ```
+/// use crate::validation::{is_canonical_identifier, CANONICAL_IDENTIFIER_MAX};
+///
+/// assert_eq!(true, is_canonical_identifier("billing-eu.v2", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(true, is_canonical_identifier("café", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(false, is_canonical_identifier("\u{1112}\u{1161}\u{11ab}", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(false, is_canonical_identifier("Billing", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(false, is_canonical_identifier(" billing", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(false, is_canonical_identifier("billing eu", CANONICAL_IDENTIFIER_MAX));
+/// assert_eq!(false, is_canonical_identifier("billing", 4));
+/// ```
+#[cfg(feature = "unicode")]
+pub fn is_canonical_identifier(value: &str, max: usize) -> bool {
+    let is_separator = |c: char| c == '_' || c == '-' || c == '.';
+
+    crate::is_length(value, Some(1), Some(max))
+        && !value.starts_with(is_separator)
+        && !value.ends_with(is_separator)
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || is_separator(c))
+        && value.to_lowercase() == value
+        && unicode_normalization::is_nfc(value)
+}
+
+#[cfg(all(test, feature = "unicode"))]
+mod tests {
+    use crate::identifier::*;
+
+    fn is_valid(value: &str) -> bool {
+        is_canonical_identifier(value, CANONICAL_IDENTIFIER_MAX)
+    }
+
+    #[test]
+    fn test_canonical_identifier_charset() {
+        assert!(is_valid("a"));
+        assert!(is_valid("team_42"));
+        assert!(is_valid("東京"));
+        assert!(!is_valid("team/42"));
+        assert!(!is_valid("team@42"));
+        assert!(!is_valid("_team"));
+        assert!(!is_valid("team."));
+    }
+
+    #[test]
+    fn test_canonical_identifier_case() {
+        assert!(is_valid("straße"));
+        assert!(!is_valid("İstanbul"));
+        // Titlecase `ǅ`, neither upper nor lower case
+        assert!(!is_valid("ǅemal"));
+    }
+
+    #[test]
+    fn test_canonical_identifier_length_in_characters() {
+        assert!(!is_valid(""));
+        assert!(is_valid(&"é".repeat(CANONICAL_IDENTIFIER_MAX)));
+        assert!(!is_valid(&"é".repeat(CANONICAL_IDENTIFIER_MAX + 1)));
+    }
+
+    #[test]
+    fn test_canonical_identifier_nfc() {
+        // The combining accent is no letter, decomposed Hangul is letters out of NFC
+        assert!(is_valid("\u{e9}t\u{e9}"));
+        assert!(!is_valid("e\u{301}te\u{301}"));
+        assert!(is_valid("\u{d55c}"));
+        assert!(!is_valid("\u{1112}\u{1161}\u{11ab}"));
+    }
+}
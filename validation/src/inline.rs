@@ -0,0 +1,193 @@
+/// Validates values outside of a struct, for the one-off checks of a handler, returning the
+/// `ValidationErrors` the derive would for fields of the same names and rules
+///
+/// Each line names a field and its value, along with its rules. `field("name", &value)` runs
+/// the rules on the value as it is, `optional("name", &value)` takes an `&Option`: `not_null`
+/// fails on `None`, the other rules only run on the value of a `Some`. The errors are limited
+/// per the global `ValidationConfig`, as `validate_fields` limits them
+///
+/// The rules are written as on `#[validate]` attributes, when the derive has them:
+///
+///  * `not_null`
+///  * `length(min = 1, max = 64)`, either bound alone, or `length(1, 64)`
+///  * `range(min = 18, max = 120)`, either bound alone, or `range(18, 120)`, bounds included
+///  * `one_of("EUR", "USD")`, strings or any values compared with `PartialEq`
+///  * `fits_in("u16")`, failing with `integer_overflow` apart from text that is not an integer
+///  * `finite`, `uuid_version(4)` and `uuid_not_nil`
+///  * `canonical_identifier`, or `canonical_identifier(max = 32)`, with the `unicode` feature
+///
+/// # Example
+/// ```
+/// use crate::validation::{validate, ValidationError};
+///
+/// let email: Option<String> = None;
+/// let age = 16u8;
+/// let currency = "EUR";
+///
+/// let errors = validate! {
+///     optional("email", &email) => [not_null, length(max = 254)];
+///     field("age", &age) => [range(18, 120)];
+///     field("currency", &currency) => [one_of("EUR", "USD")];
+/// };
+///
+/// assert_eq!(
+///     vec!["email: not_null", "age: range"],
+///     errors.iter().map(ToString::to_string).collect::<Vec<_>>()
+/// );
+/// assert_eq!(Err(ValidationError::field_mismatch("not_null")), errors.into_result());
+/// ```
+#[macro_export]
+macro_rules! validate {
+    ($($kind:ident($name:expr, $value:expr) => [$($rules:tt)*]);* $(;)?) => {{
+        let mut field_errors = $crate::ValidationErrors::new();
+        $(
+            $crate::__validate_field!(field_errors, $kind, $name, $value, [$($rules)*]);
+        )*
+        $crate::ValidationConfig::global().limit(field_errors)
+    }};
+}
+
+// Runs the rules of a line of `validate!`, in the order they are written
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validate_field {
+    ($field_errors:ident, field, $name:expr, $value:expr, [$($rule:ident $(($($args:tt)*))?),* $(,)?]) => {{
+        let value = $value;
+        let mut errors = ::std::vec::Vec::new();
+        $(
+            if let ::std::result::Result::Err(code) = $crate::__validate_rule!($rule $(($($args)*))?, value) {
+                errors.push($crate::ValidationError::FieldMismatch(code.to_string()));
+            }
+        )*
+        $field_errors.extend_field($name, errors);
+    }};
+    ($field_errors:ident, optional, $name:expr, $value:expr, [$($rule:ident $(($($args:tt)*))?),* $(,)?]) => {{
+        let value = $value;
+        let mut errors = ::std::vec::Vec::new();
+        $(
+            if let ::std::result::Result::Err(code) = $crate::__validate_optional_rule!($rule $(($($args)*))?, value) {
+                errors.push($crate::ValidationError::FieldMismatch(code.to_string()));
+            }
+        )*
+        $field_errors.extend_field($name, errors);
+    }};
+    ($field_errors:ident, $kind:ident, $name:expr, $value:expr, [$($rules:tt)*]) => {
+        ::std::compile_error!(::std::concat!(
+            "expected `field` or `optional`, found `",
+            ::std::stringify!($kind),
+            "`"
+        ))
+    };
+}
+
+// Runs a rule on the value of an `&Option`, `not_null` on the option itself
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validate_optional_rule {
+    (not_null, $value:expr) => {
+        $crate::__validate_rule!(not_null, $value)
+    };
+    ($rule:ident $(($($args:tt)*))?, $value:expr) => {
+        match ::std::option::Option::as_ref($value) {
+            ::std::option::Option::Some(value) => $crate::__validate_rule!($rule $(($($args)*))?, value),
+            ::std::option::Option::None => ::std::result::Result::<(), &'static str>::Ok(()),
+        }
+    };
+}
+
+// Runs a rule on a reference to the value, returning the code of the error when it fails
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validate_rule {
+    (@check $code:literal, $passes:expr) => {
+        if $passes {
+            ::std::result::Result::<(), &'static str>::Ok(())
+        } else {
+            ::std::result::Result::Err($code)
+        }
+    };
+    (not_null, $value:expr) => {
+        $crate::__validate_rule!(@check "not_null", $crate::is_not_null($value))
+    };
+    (length(min = $min:expr, max = $max:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "length", $crate::is_length(
+            $value,
+            ::std::option::Option::Some($min),
+            ::std::option::Option::Some($max),
+        ))
+    };
+    (length(min = $min:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "length", $crate::is_length(
+            $value,
+            ::std::option::Option::Some($min),
+            ::std::option::Option::None,
+        ))
+    };
+    (length(max = $max:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "length", $crate::is_length(
+            $value,
+            ::std::option::Option::None,
+            ::std::option::Option::Some($max),
+        ))
+    };
+    // After the named bounds, `min = 1` parsing as an expression
+    (length($min:expr, $max:expr), $value:expr) => {
+        $crate::__validate_rule!(length(min = $min, max = $max), $value)
+    };
+    (range(min = $min:expr, max = $max:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "range", $crate::is_in_range(
+            $value,
+            ::std::option::Option::Some(&$min),
+            ::std::option::Option::Some(&$max),
+        ))
+    };
+    (range(min = $min:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "range", $crate::is_in_range(
+            $value,
+            ::std::option::Option::Some(&$min),
+            ::std::option::Option::None,
+        ))
+    };
+    (range(max = $max:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "range", $crate::is_in_range(
+            $value,
+            ::std::option::Option::None,
+            ::std::option::Option::Some(&$max),
+        ))
+    };
+    // After the named bounds, `min = 1` parsing as an expression
+    (range($min:expr, $max:expr), $value:expr) => {
+        $crate::__validate_rule!(range(min = $min, max = $max), $value)
+    };
+    (one_of($($allowed:expr),+ $(,)?), $value:expr) => {
+        $crate::__validate_rule!(@check "one_of", [$($allowed),+].iter().any(|allowed| $value == allowed))
+    };
+    (fits_in($target:expr), $value:expr) => {
+        $crate::fits_in(::std::convert::AsRef::<str>::as_ref($value), $target)
+    };
+    (finite, $value:expr) => {
+        $crate::__validate_rule!(@check "finite", $crate::is_finite($value))
+    };
+    (uuid_version($version:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "uuid_version", $crate::is_uuid_version($value, $version))
+    };
+    (uuid_not_nil, $value:expr) => {
+        $crate::__validate_rule!(@check "uuid_not_nil", $crate::is_uuid_not_nil($value))
+    };
+    (canonical_identifier(max = $max:expr), $value:expr) => {
+        $crate::__validate_rule!(@check "canonical_identifier", $crate::is_canonical_identifier(
+            ::std::convert::AsRef::<str>::as_ref($value),
+            $max,
+        ))
+    };
+    (canonical_identifier, $value:expr) => {
+        $crate::__validate_rule!(canonical_identifier(max = $crate::CANONICAL_IDENTIFIER_MAX), $value)
+    };
+    ($rule:ident $(($($args:tt)*))?, $value:expr) => {
+        ::std::compile_error!(::std::concat!(
+            "unexpected rule `",
+            ::std::stringify!($rule $(($($args)*))?),
+            "` in validate!"
+        ))
+    };
+}
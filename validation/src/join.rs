@@ -0,0 +1,293 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::error::ValidationErrors;
+use crate::validation::BoxFuture;
+
+/// How the rules waiting on I/O of a struct run, set on the derive with
+/// `#[validation(async_rules = "concurrent", max_concurrency = N, async_failures = "first")]`
+///
+/// Whichever order the rules complete in, their errors are returned in the order the rules
+/// are declared
+///
+/// # Example
+/// ```
+/// use crate::validation::JoinStrategy;
+///
+/// let sequential = JoinStrategy::sequential();
+/// assert_eq!(Some(1), sequential.max_concurrency());
+///
+/// let concurrent = JoinStrategy::concurrent(Some(4)).with_fail_fast();
+/// assert_eq!(Some(4), concurrent.max_concurrency());
+/// assert!(concurrent.is_fail_fast());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JoinStrategy {
+    max_concurrency: Option<usize>,
+    fail_fast: bool,
+}
+
+impl JoinStrategy {
+    /// Runs the rules one after the other, in the order they are declared
+    pub fn sequential() -> Self {
+        JoinStrategy::concurrent(Some(1))
+    }
+
+    /// Runs the rules at the same time, no more than `max_concurrency` of them at once when
+    /// there is a maximum
+    pub fn concurrent(max_concurrency: Option<usize>) -> Self {
+        JoinStrategy {
+            max_concurrency: max_concurrency.map(|max| max.max(1)),
+            fail_fast: false,
+        }
+    }
+
+    /// Stops at the first rule failing: the rules still running are dropped, the ones not
+    /// started never start, and only the errors of the rules done are returned
+    pub fn with_fail_fast(mut self) -> Self {
+        self.fail_fast = true;
+        self
+    }
+
+    pub fn max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
+
+    pub fn is_fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+}
+
+impl Default for JoinStrategy {
+    fn default() -> Self {
+        JoinStrategy::sequential()
+    }
+}
+
+/// Runs the rules per the strategy, returning their errors in the order the rules are given
+///
+/// # Arguments
+///
+/// * `rules` - futures of the rules, each one returning the errors it found
+/// * `strategy` - `JoinStrategy` of the run
+///
+/// # Example
+/// ```
+/// use crate::validation::{join_rules, BoxFuture, JoinStrategy, ValidationError, ValidationErrors};
+/// use std::future::Future;
+/// use std::task::{Context, Poll, Waker};
+///
+/// let rule = |field: &'static str| -> BoxFuture<'static, ValidationErrors> {
+///     Box::pin(async move {
+///         let mut errors = ValidationErrors::new();
+///         errors.extend_field(field, vec![ValidationError::InvalidState("taken".to_string())]);
+///         errors
+///     })
+/// };
+/// let mut joined = Box::pin(join_rules(
+///     vec![rule("email"), rule("username")],
+///     JoinStrategy::concurrent(None),
+/// ));
+///
+/// match joined.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+///     Poll::Ready(errors) => assert_eq!("email: taken; username: taken", errors.to_string()),
+///     Poll::Pending => unreachable!(),
+/// }
+/// ```
+pub async fn join_rules<'a>(
+    rules: Vec<BoxFuture<'a, ValidationErrors>>,
+    strategy: JoinStrategy,
+) -> ValidationErrors {
+    let count = rules.len();
+    Join {
+        waiting: rules.into_iter().enumerate().rev().collect(),
+        running: Vec::new(),
+        done: (0..count).map(|_| None).collect(),
+        strategy,
+    }
+    .await
+}
+
+// Runs the rules, keeping the errors of each one at the index of the rule
+struct Join<'a> {
+    // Rules not started yet, the next one last
+    waiting: Vec<(usize, BoxFuture<'a, ValidationErrors>)>,
+    running: Vec<(usize, BoxFuture<'a, ValidationErrors>)>,
+    done: Vec<Option<ValidationErrors>>,
+    strategy: JoinStrategy,
+}
+
+impl Join<'_> {
+    fn errors(&mut self) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        for error in self.done.iter_mut().filter_map(Option::take).flatten() {
+            errors.push(error);
+        }
+        errors
+    }
+}
+
+impl Future for Join<'_> {
+    type Output = ValidationErrors;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let join = &mut *self;
+        loop {
+            while join
+                .strategy
+                .max_concurrency
+                .is_none_or(|max| join.running.len() < max)
+            {
+                match join.waiting.pop() {
+                    Some(rule) => join.running.push(rule),
+                    None => break,
+                }
+            }
+
+            let mut completed = false;
+            let mut failed = false;
+            let mut i = 0;
+            while i < join.running.len() {
+                match join.running[i].1.as_mut().poll(cx) {
+                    Poll::Ready(errors) => {
+                        let (index, _) = join.running.swap_remove(i);
+                        failed |= !errors.is_empty();
+                        join.done[index] = Some(errors);
+                        completed = true;
+                    }
+                    Poll::Pending => i += 1,
+                }
+            }
+
+            if failed && join.strategy.fail_fast {
+                join.running.clear();
+                join.waiting.clear();
+            }
+            if join.running.is_empty() && join.waiting.is_empty() {
+                return Poll::Ready(join.errors());
+            }
+            // Rules completing make room for waiting ones, which are started right away
+            if !completed || join.waiting.is_empty() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ValidationError;
+    use crate::join::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Waker;
+
+    // Rule pending for its first `polls` polls, failing on `field` when `fails`
+    struct Rule {
+        field: &'static str,
+        polls: usize,
+        fails: bool,
+    }
+
+    impl Future for Rule {
+        type Output = ValidationErrors;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<ValidationErrors> {
+            if self.polls > 0 {
+                self.polls -= 1;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let mut errors = ValidationErrors::new();
+            if self.fails {
+                let error = ValidationError::InvalidState("taken".to_string());
+                errors.extend_field(self.field, vec![error]);
+            }
+            Poll::Ready(errors)
+        }
+    }
+
+    fn rules(
+        specs: &[(&'static str, usize, bool)],
+        started: &Arc<AtomicUsize>,
+    ) -> Vec<BoxFuture<'static, ValidationErrors>> {
+        specs
+            .iter()
+            .map(
+                |&(field, polls, fails)| -> BoxFuture<'static, ValidationErrors> {
+                    let started = started.clone();
+                    Box::pin(async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        Rule {
+                            field,
+                            polls,
+                            fails,
+                        }
+                        .await
+                    })
+                },
+            )
+            .collect()
+    }
+
+    // Polls the future to completion, returning its output and how many polls it took
+    fn run<F: Future>(future: F) -> (F::Output, usize) {
+        let mut future = Box::pin(future);
+        let mut context = Context::from_waker(Waker::noop());
+        let mut polls = 1;
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return (output, polls),
+                Poll::Pending => polls += 1,
+            }
+        }
+    }
+
+    const SPECS: [(&str, usize, bool); 3] = [
+        ("email", 3, true),
+        ("quota", 0, false),
+        ("username", 1, true),
+    ];
+
+    #[test]
+    fn test_join_strategies() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let join = |strategy| run(join_rules(rules(&SPECS, &started), strategy));
+
+        let (errors, sequential) = join(JoinStrategy::sequential());
+        assert_eq!("email: taken; username: taken", errors.to_string());
+
+        let (errors, concurrent) = join(JoinStrategy::concurrent(None));
+        assert_eq!("email: taken; username: taken", errors.to_string());
+        assert!(concurrent < sequential);
+
+        let (errors, capped) = join(JoinStrategy::concurrent(Some(2)));
+        assert_eq!("email: taken; username: taken", errors.to_string());
+        assert!(capped < sequential);
+        assert_eq!(9, started.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_join_fail_fast() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let (errors, _) = run(join_rules(
+            rules(&SPECS, &started),
+            JoinStrategy::concurrent(None).with_fail_fast(),
+        ));
+        assert_eq!("username: taken", errors.to_string());
+        assert_eq!(3, started.load(Ordering::SeqCst));
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let (errors, _) = run(join_rules(
+            rules(&SPECS, &started),
+            JoinStrategy::sequential().with_fail_fast(),
+        ));
+        assert_eq!("email: taken", errors.to_string());
+        assert_eq!(1, started.load(Ordering::SeqCst));
+
+        assert!(run(join_rules(Vec::new(), JoinStrategy::sequential()))
+            .0
+            .is_empty());
+    }
+}
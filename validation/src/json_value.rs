@@ -0,0 +1,105 @@
+use serde_json::Value;
+
+/// Names of the JSON types accepted by `is_json_type`
+pub const JSON_TYPES: [&str; 6] = ["null", "bool", "number", "string", "array", "object"];
+
+/// Returns whether or not the JSON value is of the given type
+///
+/// # Arguments
+///
+/// * `value` - `&Value` to be considered
+/// * `json_type` - `&str` one of `JSON_TYPES`, an unknown type never matches
+///
+/// # Example
+/// ```
+/// use crate::validation::is_json_type;
+/// use serde_json::json;
+///
+/// assert_eq!(true, is_json_type(&json!({ "a": 1 }), "object"));
+/// assert_eq!(false, is_json_type(&json!([1, 2]), "object"));
+/// assert_eq!(true, is_json_type(&json!(1.5), "number"));
+/// ```
+pub fn is_json_type(value: &Value, json_type: &str) -> bool {
+    match json_type {
+        "null" => value.is_null(),
+        "bool" => value.is_boolean(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+/// Returns whether or not the JSON value is nested at most `max_depth` levels deep
+/// Scalars have a depth of 0, each array or object adds a level
+///
+/// # Arguments
+///
+/// * `value` - `&Value` to be considered
+/// * `max_depth` - `usize` maximum depth, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::is_json_max_depth;
+/// use serde_json::json;
+///
+/// assert_eq!(true, is_json_max_depth(&json!("flat"), 0));
+/// assert_eq!(true, is_json_max_depth(&json!({ "a": [1] }), 2));
+/// assert_eq!(false, is_json_max_depth(&json!({ "a": { "b": {} } }), 2));
+/// ```
+pub fn is_json_max_depth(value: &Value, max_depth: usize) -> bool {
+    // Only walks down to `max_depth`, a deeper value fails without being visited
+    match *value {
+        Value::Array(ref items) => {
+            max_depth > 0 && items.iter().all(|v| is_json_max_depth(v, max_depth - 1))
+        }
+        Value::Object(ref map) => {
+            max_depth > 0 && map.values().all(|v| is_json_max_depth(v, max_depth - 1))
+        }
+        _ => true,
+    }
+}
+
+/// Returns whether or not the JSON object has at most `max_keys` keys
+/// Only the keys of the value itself are counted, values that are not objects have none
+///
+/// # Arguments
+///
+/// * `value` - `&Value` to be considered
+/// * `max_keys` - `usize` maximum number of keys, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::is_json_max_keys;
+/// use serde_json::json;
+///
+/// assert_eq!(true, is_json_max_keys(&json!({ "a": 1, "b": { "c": 2 } }), 2));
+/// assert_eq!(false, is_json_max_keys(&json!({ "a": 1, "b": 2, "c": 3 }), 2));
+/// ```
+pub fn is_json_max_keys(value: &Value, max_keys: usize) -> bool {
+    value.as_object().is_none_or(|map| map.len() <= max_keys)
+}
+
+/// Returns whether or not the JSON value is an object holding every one of the keys
+///
+/// # Arguments
+///
+/// * `value` - `&Value` to be considered
+/// * `keys` - `&[&str]` keys the object must hold, whatever their value
+///
+/// # Example
+/// ```
+/// use crate::validation::has_json_keys;
+/// use serde_json::json;
+///
+/// assert_eq!(true, has_json_keys(&json!({ "id": 1, "source": null }), &["id", "source"]));
+/// assert_eq!(false, has_json_keys(&json!({ "id": 1 }), &["id", "source"]));
+/// assert_eq!(false, has_json_keys(&json!([]), &["id"]));
+/// ```
+pub fn has_json_keys(value: &Value, keys: &[&str]) -> bool {
+    match value.as_object() {
+        Some(map) => keys.iter().all(|key| map.contains_key(*key)),
+        None => false,
+    }
+}
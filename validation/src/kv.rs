@@ -0,0 +1,180 @@
+use crate::error::ValidationErrors;
+
+/// Prefix of every key of `KeyValues`
+pub const KV_PREFIX: &str = "validation";
+
+/// Failures of a validation flattened to key-value pairs, per `ValidationErrors::as_kv`, to
+/// emit them as structured fields of a log record or a tracing span
+///
+/// Keys are stable across releases, the failures numbered in order from `0`:
+///
+/// * `validation.error_count` - the number of failures
+/// * `validation.codes` - the distinct codes, comma separated in the order they are first found
+/// * `validation.errors.N.field` - the field of the failure, left out when it has none
+/// * `validation.errors.N.kind` - `field_mismatch`, `invalid_state` or `invalid_transition`
+/// * `validation.errors.N.code` - the code of the failure, like `length`
+/// * `validation.errors.N.params.NAME` - each of its params, strings as they are and other
+///   values as JSON
+///
+/// With the `log_kv` feature, `KeyValues` is a `log::kv::Source` to attach to a log record
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyValues {
+    pairs: Vec<(String, String)>,
+}
+
+impl KeyValues {
+    pub(crate) fn from_errors(errors: &ValidationErrors) -> Self {
+        let mut codes: Vec<&str> = Vec::new();
+        for err in errors.iter() {
+            if !codes.contains(&err.error.code()) {
+                codes.push(err.error.code());
+            }
+        }
+
+        let mut pairs = vec![
+            (
+                format!("{}.error_count", KV_PREFIX),
+                errors.len().to_string(),
+            ),
+            (format!("{}.codes", KV_PREFIX), codes.join(",")),
+        ];
+        for (i, err) in errors.iter().enumerate() {
+            let key = |name: &str| format!("{}.errors.{}.{}", KV_PREFIX, i, name);
+            if let Some(ref field) = err.field {
+                pairs.push((key("field"), field.clone()));
+            }
+            pairs.push((key("kind"), err.error.kind().to_string()));
+            pairs.push((key("code"), err.error.code().to_string()));
+            for (name, value) in &err.params {
+                let value = match value.as_str() {
+                    Some(value) => value.to_string(),
+                    None => value.to_string(),
+                };
+                pairs.push((key(&format!("params.{}", name)), value));
+            }
+        }
+        KeyValues { pairs }
+    }
+
+    /// Returns the pairs in order, the counts first and then the failures
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the value of the key, if there is one
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter()
+            .find(|(candidate, _)| *candidate == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<(String, String)> {
+        self.pairs
+    }
+}
+
+#[cfg(feature = "log_kv")]
+impl log::kv::Source for KeyValues {
+    fn visit<'kvs>(
+        &'kvs self,
+        visitor: &mut dyn log::kv::VisitSource<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        for (key, value) in self.iter() {
+            visitor.visit_pair(log::kv::Key::from_str(key), log::kv::Value::from(value))?;
+        }
+        Ok(())
+    }
+
+    fn count(&self) -> usize {
+        self.pairs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{FieldError, ValidationError, ValidationErrors};
+
+    fn errors() -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        errors.push(
+            FieldError::new(
+                "currency",
+                ValidationError::FieldMismatch("one_of".to_string()),
+            )
+            .with_param("suggestion", "EUR")
+            .with_param("values", vec!["EUR", "USD"]),
+        );
+        errors.push(FieldError::new(
+            "name",
+            ValidationError::FieldMismatch("length".to_string()),
+        ));
+        errors.push(FieldError::unattached(ValidationError::invalid_transition(
+            "order",
+            "SHIPPED",
+            "DRAFT",
+            &[],
+        )));
+        errors.push(FieldError::new(
+            "alias",
+            ValidationError::FieldMismatch("length".to_string()),
+        ));
+        errors
+    }
+
+    #[test]
+    fn test_as_kv() {
+        let kv = errors().as_kv();
+        let pairs = kv.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("validation.error_count", "4"),
+                ("validation.codes", "one_of,length,invalid_transition"),
+                ("validation.errors.0.field", "currency"),
+                ("validation.errors.0.kind", "field_mismatch"),
+                ("validation.errors.0.code", "one_of"),
+                ("validation.errors.0.params.suggestion", "EUR"),
+                ("validation.errors.0.params.values", r#"["EUR","USD"]"#),
+                ("validation.errors.1.field", "name"),
+                ("validation.errors.1.kind", "field_mismatch"),
+                ("validation.errors.1.code", "length"),
+                ("validation.errors.2.kind", "invalid_transition"),
+                ("validation.errors.2.code", "invalid_transition"),
+                ("validation.errors.3.field", "alias"),
+                ("validation.errors.3.kind", "field_mismatch"),
+                ("validation.errors.3.code", "length"),
+            ],
+            pairs
+        );
+        assert_eq!(Some("name"), kv.get("validation.errors.1.field"));
+        assert_eq!(None, kv.get("validation.errors.2.field"));
+
+        let kv = ValidationErrors::new().as_kv();
+        assert_eq!(Some("0"), kv.get("validation.error_count"));
+        assert_eq!(Some(""), kv.get("validation.codes"));
+    }
+
+    #[cfg(feature = "log_kv")]
+    #[test]
+    fn test_log_kv_source() {
+        use log::kv::Source;
+
+        let kv = errors().as_kv();
+        assert_eq!(kv.len(), kv.count());
+        assert_eq!(
+            Some("length".to_string()),
+            Source::get(&kv, log::kv::Key::from_str("validation.errors.1.code"))
+                .map(|value| value.to_string())
+        );
+    }
+}
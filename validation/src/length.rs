@@ -0,0 +1,151 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::ffi::{OsStr, OsString};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Values the `length` rule applies to
+///
+/// Text is measured in characters, collections in elements. Implement it for a type of your
+/// own to use `length` on fields of that type
+///
+/// # Example
+/// ```
+/// use crate::validation::{is_length, HasLen};
+///
+/// struct Path(Vec<String>);
+///
+/// impl HasLen for Path {
+///     fn length(&self) -> usize {
+///         self.0.len()
+///     }
+/// }
+///
+/// assert_eq!(true, is_length(&Path(vec!["usr".to_string()]), Some(1), Some(8)));
+/// ```
+pub trait HasLen {
+    fn length(&self) -> usize;
+}
+
+macro_rules! impl_text_len {
+    ($($t:ty),*) => {
+        $(
+            impl HasLen for $t {
+                fn length(&self) -> usize {
+                    self.chars().count()
+                }
+            }
+        )*
+    };
+}
+
+impl_text_len!(str, String);
+
+// Not valid unicode, measured in the characters of its lossy conversion
+macro_rules! impl_os_text_len {
+    ($($t:ty),*) => {
+        $(
+            impl HasLen for $t {
+                fn length(&self) -> usize {
+                    self.to_string_lossy().chars().count()
+                }
+            }
+        )*
+    };
+}
+
+impl_os_text_len!(OsStr, OsString);
+
+impl HasLen for Cow<'_, str> {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+macro_rules! impl_collection_len {
+    ($($t:ident<$($p:ident),*>),*) => {
+        $(
+            impl<$($p),*> HasLen for $t<$($p),*> {
+                fn length(&self) -> usize {
+                    self.len()
+                }
+            }
+        )*
+    };
+}
+
+impl_collection_len!(
+    Vec<T>,
+    VecDeque<T>,
+    LinkedList<T>,
+    HashSet<T, S>,
+    BTreeSet<T>,
+    HashMap<K, V, S>,
+    BTreeMap<K, V>
+);
+
+// Objects of JSON, as the `length` of the schema driven validator measures them
+impl HasLen for serde_json::Map<String, serde_json::Value> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> HasLen for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> HasLen for [T; N] {
+    fn length(&self) -> usize {
+        N
+    }
+}
+
+macro_rules! impl_deref_len {
+    ($($t:ident),*) => {
+        $(
+            impl<T: HasLen + ?Sized> HasLen for $t<T> {
+                fn length(&self) -> usize {
+                    T::length(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_deref_len!(Box, Rc, Arc);
+
+impl<T: HasLen + ?Sized> HasLen for &T {
+    fn length(&self) -> usize {
+        T::length(*self)
+    }
+}
+
+/// Returns whether or not the length of the value is within the bounds
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered, anything with a `HasLen` length
+/// * `min` - `Option<usize>` minimum length, inclusive
+/// * `max` - `Option<usize>` maximum length, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::is_length;
+///
+/// assert_eq!(true, is_length("SQL", Some(1), Some(64)));
+/// assert_eq!(false, is_length("", Some(1), Some(64)));
+/// assert_eq!(true, is_length("Ä", None, Some(1)));
+/// assert_eq!(false, is_length(&"MongoDB".to_string(), None, Some(5)));
+/// assert_eq!(true, is_length(&vec![0u8; 16], Some(16), Some(16)));
+/// ```
+pub fn is_length<T>(value: &T, min: Option<usize>, max: Option<usize>) -> bool
+where
+    T: HasLen + ?Sized,
+{
+    let length = value.length();
+
+    min.is_none_or(|min| length >= min) && max.is_none_or(|max| length <= max)
+}
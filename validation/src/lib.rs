@@ -6,20 +6,214 @@
 //!
 //! Module `error` contains main logic for handling validation errors
 //!
-//! All other modules should be considered the validation rules
+//! Module `body` contains the decoding of request bodies per their `Content-Type`, validated
+//! once decoded
+//!
+//! Module `builder` contains the adapter validating the values of builders once built
+//!
+//! Module `bundles` contains vetted sets of rules for common domain fields
+//!
+//! Module `cache` contains the cache of validation outcomes keyed by the serialized payload,
+//! with an in memory LRU per the `cache` feature
+//!
+//! Module `catalog` contains the catalog of error codes produced by the rules
+//!
+//! Module `config` contains the loaders of TOML/YAML configuration files, per the `toml` and
+//! `yaml` features
+//!
+//! Module `raw_json` contains the pass over raw JSON input, rejecting duplicate keys and
+//! overflowing numbers before serde reads it, from a slice or streamed from a `Read`
+//!
+//! Module `reporter` contains the payloads of failures for Sentry compatible error
+//! reporters, per the `reporter` feature
+//!
+//! Module `reflect` contains the stable introspection of the rules of a type, for client SDK
+//! generators
+//!
+//! Module `schema` contains the schema driven validator, with per tenant rule overrides
+//!
+//! Module `validator_compat` contains the conversions from and to the errors of the
+//! `validator` crate, per the `validator` feature
+//!
+//! All other modules should be considered the validation rules, the `validate!` macro running
+//! them on values outside of a struct
+//!
+//! # Features
+//!
+//! Some rules, or options of rules, are only compiled in with a feature, like the NFKC
+//! collations of `one_of` with `unicode`. `capabilities()` lists them along with whether or not
+//! the build has them, and the schema driven validator fails with `rule_unavailable` on a rule
+//! the build cannot evaluate rather than skipping it
+//!
+//! The temporal rules, `past`, `future`, `min_age` and `range` on dates, take the types of
+//! `chrono` with the `chrono` feature and the types of `time` with the `time` feature. Either
+//! or both may be enabled, the rules comparing any `Temporal` the same
+//!
+//! # Panics
+//!
+//! No rule, nor the code the derives generate, panics on any input: text, numbers and JSON
+//! that fail a rule are errors of the validation, whatever their content or size. The few
+//! panics left are misuses of the API documented on the functions, like a `Clock` that panics
+//! itself. The `fuzz` directory holds the `cargo fuzz` targets checking the text rules and the
+//! raw JSON pass against arbitrary input
+//!
+//! # Compatibility
+//!
+//! From 1.0 the crate follows semantic versioning, with these additions:
+//!
+//!  - The public enums (`ValidationError`, `ValidationMode`, `Collation`, `SkipReason`,
+//!    `PlannedRuleStatus` and `ConfigError`) are `#[non_exhaustive]`: a minor release may add
+//!    variants, so matches on them need a wildcard arm
+//!  - The codes of the `catalog` and the `kind`s of `ValidationError` are never renamed nor
+//!    removed in a minor release, only deprecated, and their serialized form does not change
+//!  - New rules get new codes rather than new meanings for existing ones
+//!
+//! The `compatibility` tests of `validation_derive_test` hold the crate to this policy
+#![cfg_attr(
+    not(test),
+    deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+)]
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "derive_builder")]
+extern crate derive_builder;
+extern crate lazy_static;
+extern crate log;
+extern crate regex;
 extern crate serde;
+#[cfg(feature = "yaml")]
+extern crate serde_yaml;
+#[cfg(feature = "time")]
+extern crate time;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "unicode")]
+extern crate unicode_normalization;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
 extern crate uuid;
+#[cfg(feature = "validator")]
+extern crate validator;
 
-pub use crate::validation::{FieldValidation, StateValidation, Validation, ValidationResult};
-pub use error::{ValidationError, ValidationErrorResponse};
+pub use crate::batch::{validate_batch, validate_batch_with, validate_each, BatchReport};
+pub use crate::bounded::{BoundedString, BoundedVec, NonEmpty};
+pub use crate::capabilities::{capabilities, is_rule_available, Capability, RULE_UNAVAILABLE};
+pub use crate::context::{
+    Clock, FixedClock, RandomSource, SeededRandom, SystemClock, ValidationContext,
+    ValidationEnvironment, ValidationObserver, DEFAULT_MAX_DEPTH,
+};
+#[cfg(feature = "csv")]
+pub use crate::csv_rows::{validate_csv, validate_csv_with, CsvReport, CsvRowError};
+pub use crate::validation::{
+    AsyncFieldValidation, BoxFuture, FieldValidation, StateValidation, UpdateValidation,
+    Validation, ValidationResult, ValidationResultExt,
+};
+pub use description::{Deprecation, FieldConstraintDescription};
+pub use error::{
+    FieldError, FieldSource, StateTransition, ValidationError, ValidationErrorResponse,
+    ValidationErrors, FIELD_SOURCES,
+};
+pub use error_code_map::{ErrorCodeMap, ErrorCodeMapping};
+pub use finite::is_finite;
+pub use fits_in::{fits_in, INTEGER_TYPES};
+pub use flags::{has_known_flags, KnownFlags};
+pub use global_config::{MessageResolver, ValidationConfig, ValidationMode};
+#[cfg(feature = "unicode")]
+pub use identifier::is_canonical_identifier;
+pub use identifier::CANONICAL_IDENTIFIER_MAX;
+pub use interned::{collect_interned, InternedError, InternedErrors, Interner};
 pub use is_in_collection::is_in_collection;
+pub use join::{join_rules, JoinStrategy};
+pub use json_value::{
+    has_json_keys, is_json_max_depth, is_json_max_keys, is_json_type, JSON_TYPES,
+};
+pub use kv::{KeyValues, KV_PREFIX};
+pub use length::{is_length, HasLen};
+pub use locale_format::{is_date_format, is_number_format, DATE_STYLES};
+pub use matches_regex::matches_regex;
 pub use not_null::is_not_null;
+pub use one_of::{is_one_of, suggest_one_of, Collation, COLLATIONS};
+pub use plan::{PlannedRule, PlannedRuleStatus, SkipReason, ValidationPlan};
+pub use range::{is_in_parsed_range, is_in_range, is_within, is_within_parsed};
+pub use recently_seen::{is_recently_seen, InMemorySeenStore, SeenStore};
+pub use score::{score_plan, ScoredValidation};
+pub use signed::{constant_time_eq, SignedPayload};
+pub use sink::{
+    validate_batch_into, BatchSummary, JsonLinesSink, MemorySink, RecordReport, ReportSink,
+};
+pub use temporal::{is_future, is_min_age, is_past, is_within_temporal, ParseTemporal, Temporal};
+pub use timeout::{with_rule_timeout, Timer, VALIDATION_TIMEOUT};
+pub use transition::check_transition;
+pub use uuids::{is_uuid_not_nil, is_uuid_version};
+pub use version::{negotiate_api_version, parse_api_version, ApiVersions};
 
+pub mod body;
+pub mod builder;
+pub mod bundles;
+pub mod cache;
+pub mod catalog;
+#[cfg(any(feature = "toml", feature = "yaml"))]
+pub mod config;
 pub mod error;
+pub mod raw_json;
+pub mod reflect;
+#[cfg(feature = "reporter")]
+pub mod reporter;
+pub mod schema;
 pub mod validation;
+#[cfg(feature = "validator")]
+pub mod validator_compat;
 
+mod batch;
+mod bounded;
+mod capabilities;
+mod context;
+#[cfg(feature = "csv")]
+mod csv_rows;
+mod description;
+mod error_code_map;
+mod finite;
+mod fits_in;
+mod flags;
+mod global_config;
+mod identifier;
+mod inline;
+mod interned;
 mod is_in_collection;
+mod join;
+mod json_value;
+mod kv;
+mod length;
+mod locale_format;
+mod matches_regex;
 mod not_null;
+mod one_of;
+mod plan;
+mod range;
+mod recently_seen;
+mod score;
+mod signed;
+mod sink;
+mod std_impls;
+mod temporal;
+mod timeout;
+mod transition;
+mod uuids;
+mod version;
+
+/// Re-exports used by the code generated in `validation_derive`
+///
+/// Not part of the public API, the derive needs these so callers do not have to
+/// depend on `regex`, `lazy_static` or `serde` themselves
+#[doc(hidden)]
+pub mod __private {
+    pub use lazy_static::lazy_static;
+    pub use regex::Regex;
+    pub extern crate serde;
+    pub extern crate serde_derive;
+    pub extern crate serde_json;
+    pub extern crate uuid;
+}
@@ -0,0 +1,289 @@
+// Order in which the day, month and year appear in a numeric date
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+// How a locale writes numeric dates and numbers
+#[derive(Clone, Copy, Debug)]
+struct LocaleFormat {
+    locale: &'static str,
+    date_order: DateOrder,
+    date_separator: char,
+    decimal_separator: char,
+    group_separators: &'static [char],
+}
+
+const LOCALE_FORMATS: [LocaleFormat; 12] = [
+    LocaleFormat {
+        locale: "en-US",
+        date_order: DateOrder::MonthDayYear,
+        date_separator: '/',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+    LocaleFormat {
+        locale: "en-GB",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+    LocaleFormat {
+        locale: "en-CA",
+        date_order: DateOrder::YearMonthDay,
+        date_separator: '-',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+    LocaleFormat {
+        locale: "de-DE",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '.',
+        decimal_separator: ',',
+        group_separators: &['.'],
+    },
+    LocaleFormat {
+        locale: "fr-FR",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        decimal_separator: ',',
+        group_separators: &[' ', '\u{a0}', '\u{202f}'],
+    },
+    LocaleFormat {
+        locale: "es-ES",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        decimal_separator: ',',
+        group_separators: &['.'],
+    },
+    LocaleFormat {
+        locale: "it-IT",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        decimal_separator: ',',
+        group_separators: &['.'],
+    },
+    LocaleFormat {
+        locale: "nl-NL",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '-',
+        decimal_separator: ',',
+        group_separators: &['.'],
+    },
+    LocaleFormat {
+        locale: "pt-BR",
+        date_order: DateOrder::DayMonthYear,
+        date_separator: '/',
+        decimal_separator: ',',
+        group_separators: &['.'],
+    },
+    LocaleFormat {
+        locale: "ja-JP",
+        date_order: DateOrder::YearMonthDay,
+        date_separator: '/',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+    LocaleFormat {
+        locale: "zh-CN",
+        date_order: DateOrder::YearMonthDay,
+        date_separator: '/',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+    LocaleFormat {
+        locale: "ko-KR",
+        date_order: DateOrder::YearMonthDay,
+        date_separator: '.',
+        decimal_separator: '.',
+        group_separators: &[','],
+    },
+];
+
+/// Date styles understood by `is_date_format`
+pub const DATE_STYLES: [&str; 2] = ["short", "iso"];
+
+// Finds the format of the locale, falling back to the first locale of the same language
+// and then to en-US
+fn locale_format(locale: &str) -> &'static LocaleFormat {
+    let locale = locale.replace('_', "-");
+    let language = locale.split('-').next().unwrap_or_default();
+
+    LOCALE_FORMATS
+        .iter()
+        .find(|f| f.locale.eq_ignore_ascii_case(&locale))
+        .or_else(|| {
+            LOCALE_FORMATS
+                .iter()
+                .find(|f| f.locale.split('-').next() == Some(language))
+        })
+        .unwrap_or(&LOCALE_FORMATS[0])
+}
+
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn is_valid_date(year: u32, month: u32, day: u32) -> bool {
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    day >= 1 && day <= days_in_month
+}
+
+// Parses an all ASCII digit part of at least `min` and at most `max` digits
+fn parse_part(part: &str, min: usize, max: usize) -> Option<u32> {
+    if part.len() < min || part.len() > max || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    part.parse().ok()
+}
+
+/// Returns whether or not the value is a valid date written in the given style for the locale
+///
+/// Styles are `short`, the numeric date of the locale (`12/31/2024` for en-US,
+/// `31/12/2024` for en-GB, `31.12.2024` for de-DE...), and `iso` (`2024-12-31`) which does
+/// not depend on the locale. Unknown locales fall back to their language, then to en-US
+///
+/// # Arguments
+///
+/// * `value` - `T` to be considered, anything that can be viewed as a `str`
+/// * `style` - `&str` date style, one of `DATE_STYLES`
+/// * `locale` - `&str` BCP 47 locale, typically `ValidationEnvironment::locale()`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_date_format;
+///
+/// assert_eq!(true, is_date_format("31/12/2024", "short", "en-GB"));
+/// assert_eq!(false, is_date_format("31/12/2024", "short", "en-US"));
+/// assert_eq!(true, is_date_format("12/31/2024", "short", "en-US"));
+/// assert_eq!(true, is_date_format("29.02.2024", "short", "de-DE"));
+/// assert_eq!(false, is_date_format("29.02.2023", "short", "de-DE"));
+/// assert_eq!(true, is_date_format("2024-12-31", "iso", "en-US"));
+/// ```
+pub fn is_date_format<T>(value: T, style: &str, locale: &str) -> bool
+where
+    T: AsRef<str>,
+{
+    let value = value.as_ref();
+    let (order, separator) = match style {
+        "short" => {
+            let format = locale_format(locale);
+            (format.date_order, format.date_separator)
+        }
+        "iso" => (DateOrder::YearMonthDay, '-'),
+        _ => return false,
+    };
+
+    let parts = value.split(separator).collect::<Vec<_>>();
+    if parts.len() != 3 {
+        return false;
+    }
+
+    let (min_part, max_part) = if style == "iso" { (2, 2) } else { (1, 2) };
+    let (year, month, day) = match order {
+        DateOrder::DayMonthYear => (parts[2], parts[1], parts[0]),
+        DateOrder::MonthDayYear => (parts[2], parts[0], parts[1]),
+        DateOrder::YearMonthDay => (parts[0], parts[1], parts[2]),
+    };
+
+    match (
+        parse_part(year, 4, 4),
+        parse_part(month, min_part, max_part),
+        parse_part(day, min_part, max_part),
+    ) {
+        (Some(year), Some(month), Some(day)) => is_valid_date(year, month, day),
+        _ => false,
+    }
+}
+
+/// Returns whether or not the value is a number written as the locale writes them
+/// (`1,234.5` for en-US, `1.234,5` for de-DE...), with an optional sign and grouping
+///
+/// # Arguments
+///
+/// * `value` - `T` to be considered, anything that can be viewed as a `str`
+/// * `locale` - `&str` BCP 47 locale, typically `ValidationEnvironment::locale()`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_number_format;
+///
+/// assert_eq!(true, is_number_format("1,234.5", "en-US"));
+/// assert_eq!(true, is_number_format("-1234.5", "en-US"));
+/// assert_eq!(false, is_number_format("1.234,5", "en-US"));
+/// assert_eq!(true, is_number_format("1.234,5", "de-DE"));
+/// assert_eq!(false, is_number_format("1,23,4", "en-US"));
+/// ```
+pub fn is_number_format<T>(value: T, locale: &str) -> bool
+where
+    T: AsRef<str>,
+{
+    let format = locale_format(locale);
+    let value = value.as_ref();
+    let unsigned = value
+        .strip_prefix('-')
+        .or_else(|| value.strip_prefix('+'))
+        .unwrap_or(value);
+
+    let mut parts = unsigned.splitn(2, format.decimal_separator);
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+    }
+
+    let groups = integer
+        .split(|c| format.group_separators.contains(&c))
+        .collect::<Vec<_>>();
+    if groups
+        .iter()
+        .any(|g| g.is_empty() || !g.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return false;
+    }
+
+    // Without grouping any number of digits is fine, with grouping every group after the
+    // first one has exactly three digits
+    groups.len() == 1 || (groups[0].len() <= 3 && groups[1..].iter().all(|g| g.len() == 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale_format::*;
+
+    #[test]
+    fn test_locale_fallback() {
+        assert_eq!("en-GB", locale_format("en_gb").locale);
+        assert_eq!("de-DE", locale_format("de-AT").locale);
+        assert_eq!("en-US", locale_format("xx-YY").locale);
+    }
+
+    #[test]
+    fn test_date_format_rejects_malformed() {
+        assert!(!is_date_format("12/31/24", "short", "en-US"));
+        assert!(!is_date_format("13/31/2024", "short", "en-US"));
+        assert!(!is_date_format("2024-1-31", "iso", "en-US"));
+        assert!(!is_date_format("+1/+1/2024", "short", "en-US"));
+        assert!(!is_date_format("12/31/2024", "long", "en-US"));
+    }
+
+    #[test]
+    fn test_number_format_french_spaces() {
+        assert!(is_number_format("1 234,5", "fr-FR"));
+        assert!(is_number_format("1\u{202f}234", "fr-FR"));
+        assert!(!is_number_format("1,", "fr-FR"));
+    }
+}
@@ -0,0 +1,26 @@
+use regex::Regex;
+
+/// Returns whether or not the given value matches the given `Regex`
+///
+/// # Arguments
+///
+/// * `value` - `T` to be matched, anything that can be viewed as a `str`
+/// * `regex` - `Regex` the value must match
+///
+/// # Example
+/// ```
+/// use crate::validation::matches_regex;
+/// use regex::Regex;
+///
+/// let lowercase = Regex::new("^[a-z]+$").unwrap();
+///
+/// assert_eq!(true, matches_regex("sql", &lowercase));
+/// assert_eq!(true, matches_regex(&"mongodb".to_string(), &lowercase));
+/// assert_eq!(false, matches_regex("NoSQL", &lowercase));
+/// ```
+pub fn matches_regex<T>(value: T, regex: &Regex) -> bool
+where
+    T: AsRef<str>,
+{
+    regex.is_match(value.as_ref())
+}
@@ -0,0 +1,201 @@
+use std::borrow::Cow;
+
+/// How `one_of` compares a value with the allowed ones
+///
+/// The NFKC collations need the `unicode` feature, which brings in the normalization tables
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Collation {
+    /// Compares the values as they are
+    Exact,
+    /// Compares the lowercase of the values, so `"Ä"` matches `"ä"`
+    CaseFold,
+    /// Compares the NFKC normalizations of the values, so `"ﬁ"` matches `"fi"`
+    #[cfg(feature = "unicode")]
+    Nfkc,
+    /// Compares the NFKC normalizations of the lowercase of the values
+    #[cfg(feature = "unicode")]
+    NfkcCaseFold,
+}
+
+/// Names of the collations, as written in `#[validate(one_of(..., collation = "..."))]`
+pub const COLLATIONS: [&str; 4] = ["exact", "case_fold", "nfkc", "nfkc_case_fold"];
+
+impl Collation {
+    /// Returns the collation of the name, `None` when it is unknown or needs a feature that is
+    /// not enabled
+    pub fn from_name(name: &str) -> Option<Collation> {
+        match name {
+            "exact" => Some(Collation::Exact),
+            "case_fold" => Some(Collation::CaseFold),
+            #[cfg(feature = "unicode")]
+            "nfkc" => Some(Collation::Nfkc),
+            #[cfg(feature = "unicode")]
+            "nfkc_case_fold" => Some(Collation::NfkcCaseFold),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as the collation compares it
+    pub fn collate<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match *self {
+            Collation::Exact => Cow::Borrowed(value),
+            Collation::CaseFold => Cow::Owned(value.to_lowercase()),
+            #[cfg(feature = "unicode")]
+            Collation::Nfkc => {
+                use unicode_normalization::UnicodeNormalization;
+                Cow::Owned(value.nfkc().collect())
+            }
+            #[cfg(feature = "unicode")]
+            Collation::NfkcCaseFold => {
+                use unicode_normalization::UnicodeNormalization;
+                Cow::Owned(
+                    value
+                        .nfkc()
+                        .collect::<String>()
+                        .to_lowercase()
+                        .nfkc()
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Returns whether or not the value is one of the allowed ones, compared per the collation
+///
+/// # Arguments
+///
+/// * `value` - `&str` to find
+/// * `allowed` - `&[&str]` of the allowed values
+/// * `collation` - `Collation` of the comparison
+///
+/// # Example
+/// ```
+/// use crate::validation::{is_one_of, Collation};
+///
+/// assert_eq!(true, is_one_of("EUR", &["EUR", "USD"], Collation::Exact));
+/// assert_eq!(false, is_one_of("eur", &["EUR", "USD"], Collation::Exact));
+/// assert_eq!(true, is_one_of("Äpfel", &["äpfel", "birnen"], Collation::CaseFold));
+/// ```
+pub fn is_one_of(value: &str, allowed: &[&str], collation: Collation) -> bool {
+    let value = collation.collate(value);
+    allowed
+        .iter()
+        .any(|allowed| collation.collate(allowed) == value)
+}
+
+/// Returns the allowed value nearest to the value, for a "did you mean" hint on a failure
+/// of `one_of`
+///
+/// Values are compared in lowercase, per their collation, by the number of characters to
+/// insert, delete or replace. Only allowed values within a third of the length of the longer
+/// of the two are suggested, the first of the nearest winning a tie
+///
+/// # Arguments
+///
+/// * `value` - `&str` that is not one of the allowed values
+/// * `allowed` - `&[&str]` of the allowed values
+/// * `collation` - `Collation` of the comparison
+///
+/// # Example
+/// ```
+/// use crate::validation::{suggest_one_of, Collation};
+///
+/// let currencies = ["EUR", "USD", "GBP"];
+///
+/// assert_eq!(Some("USD"), suggest_one_of("usd", &currencies, Collation::Exact));
+/// assert_eq!(Some("EUR"), suggest_one_of("EURO", &currencies, Collation::Exact));
+/// assert_eq!(None, suggest_one_of("bitcoin", &currencies, Collation::Exact));
+/// ```
+pub fn suggest_one_of<'a>(
+    value: &str,
+    allowed: &[&'a str],
+    collation: Collation,
+) -> Option<&'a str> {
+    let value = collation
+        .collate(value)
+        .to_lowercase()
+        .chars()
+        .collect::<Vec<_>>();
+
+    allowed
+        .iter()
+        .filter_map(|allowed| {
+            let candidate = collation
+                .collate(allowed)
+                .to_lowercase()
+                .chars()
+                .collect::<Vec<_>>();
+            let distance = edit_distance(&value, &candidate);
+            let longest = value.len().max(candidate.len());
+            Some((distance, *allowed)).filter(|_| distance * 3 <= longest)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, allowed)| allowed)
+}
+
+// Levenshtein distance, a row of the table at a time
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let replaced = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = replaced.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::one_of::*;
+
+    #[test]
+    fn test_collation_names() {
+        assert_eq!(Some(Collation::Exact), Collation::from_name("exact"));
+        assert_eq!(Some(Collation::CaseFold), Collation::from_name("case_fold"));
+        assert_eq!(None, Collation::from_name("nfc"));
+        assert_eq!(
+            cfg!(feature = "unicode"),
+            Collation::from_name("nfkc").is_some()
+        );
+    }
+
+    #[test]
+    fn test_suggest_one_of_nearest() {
+        let plans = ["basic", "business", "enterprise"];
+
+        assert_eq!(
+            Some("business"),
+            suggest_one_of("buisness", &plans, Collation::Exact)
+        );
+        assert_eq!(
+            Some("basic"),
+            suggest_one_of("BASIC", &plans, Collation::Exact)
+        );
+        assert_eq!(
+            Some("basic"),
+            suggest_one_of("basi", &plans, Collation::Exact)
+        );
+        assert_eq!(None, suggest_one_of("", &plans, Collation::Exact));
+        assert_eq!(None, suggest_one_of("free", &plans, Collation::Exact));
+        assert_eq!(0, edit_distance(&[], &[]));
+        assert_eq!(3, edit_distance(&['k', 'i', 't'], &[]));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_nfkc_collations() {
+        // "Ä" composed, and as "A" followed by a combining diaeresis
+        assert!(is_one_of("\u{c4}", &["A\u{308}"], Collation::Nfkc));
+        assert!(!is_one_of("\u{c4}", &["A\u{308}"], Collation::Exact));
+        assert!(is_one_of("\u{fb01}le", &["file"], Collation::Nfkc));
+        assert!(!is_one_of("\u{fb01}le", &["FILE"], Collation::Nfkc));
+        assert!(is_one_of("\u{fb01}le", &["FILE"], Collation::NfkcCaseFold));
+        assert!(is_one_of("\u{c4}", &["a\u{308}"], Collation::NfkcCaseFold));
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Why a rule of a `ValidationPlan` would not run
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SkipReason {
+    /// The field is an `Option` holding no value, only `not_null` applies to it
+    NoValue,
+    /// The condition of the `when` of the rule does not hold
+    Condition(String),
+    /// The rule is on a variant the enum field does not hold
+    OtherVariant(String),
+    /// The rule does not apply to the API version
+    ApiVersion(u32),
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            SkipReason::NoValue => write!(f, "the field has no value"),
+            SkipReason::Condition(ref condition) => {
+                write!(f, "the condition `{}` does not hold", condition)
+            }
+            SkipReason::OtherVariant(ref variant) => {
+                write!(f, "the field does not hold the variant `{}`", variant)
+            }
+            SkipReason::ApiVersion(version) => {
+                write!(f, "the rule does not apply to API version {}", version)
+            }
+        }
+    }
+}
+
+/// Whether or not a rule of a `ValidationPlan` would run
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PlannedRuleStatus {
+    Run,
+    Skipped(SkipReason),
+}
+
+/// A rule of a `ValidationPlan`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlannedRule {
+    pub field: String,
+    pub rule: String,
+    pub status: PlannedRuleStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+}
+
+impl PlannedRule {
+    /// Creates a new `PlannedRule`
+    ///
+    /// # Arguments
+    ///
+    ///  * `field` - name of the field, as it is serialized
+    ///  * `rule` - error code of the rule
+    ///  * `status` - `PlannedRuleStatus` of the rule
+    pub fn new(field: &str, rule: &str, status: PlannedRuleStatus) -> Self {
+        PlannedRule {
+            field: field.to_string(),
+            rule: rule.to_string(),
+            status,
+            weight: None,
+        }
+    }
+
+    /// Sets the weight of the rule in `validate_scored()`, per its `weight = N`, 1 when unset
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn will_run(&self) -> bool {
+        self.status == PlannedRuleStatus::Run
+    }
+}
+
+impl Display for PlannedRule {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self.status {
+            PlannedRuleStatus::Run => write!(f, "{}: {} runs", self.field, self.rule),
+            PlannedRuleStatus::Skipped(ref reason) => {
+                write!(f, "{}: {} skipped, {}", self.field, self.rule, reason)
+            }
+        }
+    }
+}
+
+/// The rules a validation would run for a value, and why the others would be skipped
+///
+/// Generated by the `FieldValidate` derive through `explain()`, which builds the plan without
+/// running any of the rules
+///
+/// # Example
+/// ```
+/// use crate::validation::{PlannedRule, PlannedRuleStatus, SkipReason, ValidationPlan};
+///
+/// let plan = ValidationPlan::new(vec![
+///     PlannedRule::new("name", "not_null", PlannedRuleStatus::Run),
+///     PlannedRule::new("name", "length", PlannedRuleStatus::Skipped(SkipReason::NoValue)),
+/// ]);
+///
+/// assert_eq!(1, plan.to_run().count());
+/// assert_eq!(1, plan.skipped().count());
+/// assert_eq!(
+///     "name: not_null runs\nname: length skipped, the field has no value",
+///     plan.to_string()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ValidationPlan {
+    pub rules: Vec<PlannedRule>,
+}
+
+impl ValidationPlan {
+    pub fn new(rules: Vec<PlannedRule>) -> Self {
+        ValidationPlan { rules }
+    }
+
+    /// Returns the rules that would run
+    pub fn to_run(&self) -> impl Iterator<Item = &PlannedRule> {
+        self.rules.iter().filter(|rule| rule.will_run())
+    }
+
+    /// Returns the rules that would be skipped
+    pub fn skipped(&self) -> impl Iterator<Item = &PlannedRule> {
+        self.rules.iter().filter(|rule| !rule.will_run())
+    }
+
+    /// Returns the rules of the given field
+    pub fn for_field<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a PlannedRule> {
+        self.rules.iter().filter(move |rule| rule.field == field)
+    }
+}
+
+impl Display for ValidationPlan {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let rules = self
+            .rules
+            .iter()
+            .map(PlannedRule::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", rules.join("\n"))
+    }
+}
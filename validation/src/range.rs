@@ -0,0 +1,181 @@
+use std::ops::Bound;
+use std::str::FromStr;
+
+/// Returns whether or not the value is within the bounds, per `PartialOrd`
+///
+/// Numbers, chars and strings alike, strings compared lexicographically. Values not comparable
+/// with a bound, like `NaN`, are out of range
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered
+/// * `min` - `Option<&T>` minimum, inclusive
+/// * `max` - `Option<&T>` maximum, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::is_in_range;
+///
+/// assert_eq!(true, is_in_range(&42, Some(&1), Some(&100)));
+/// assert_eq!(false, is_in_range(&0.5, Some(&1.0), None));
+/// assert_eq!(false, is_in_range(&f64::NAN, None, Some(&1.0)));
+/// assert_eq!(true, is_in_range(&'q', Some(&'a'), Some(&'z')));
+/// assert_eq!(true, is_in_range::<str>(&"beta".to_string(), Some("alpha"), Some("gamma")));
+/// ```
+pub fn is_in_range<T>(value: &T, min: Option<&T>, max: Option<&T>) -> bool
+where
+    T: PartialOrd + ?Sized,
+{
+    is_within(value, included(min), included(max))
+}
+
+/// Returns whether or not the value is within the bounds, each included, excluded or
+/// unbounded, for ranges like "strictly positive" or "less than 1.0"
+///
+/// Values not comparable with a bound, like `NaN`, are out of range
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered
+/// * `min` - `Bound<&T>` lower bound
+/// * `max` - `Bound<&T>` upper bound
+///
+/// # Example
+/// ```
+/// use crate::validation::is_within;
+/// use std::ops::Bound::{Excluded, Included, Unbounded};
+///
+/// assert_eq!(true, is_within(&0.5, Excluded(&0.0), Unbounded));
+/// assert_eq!(false, is_within(&0.0, Excluded(&0.0), Unbounded));
+/// assert_eq!(false, is_within(&1.0, Included(&0.0), Excluded(&1.0)));
+/// assert_eq!(false, is_within(&f64::NAN, Unbounded, Unbounded));
+/// ```
+pub fn is_within<T>(value: &T, min: Bound<&T>, max: Bound<&T>) -> bool
+where
+    T: PartialOrd + ?Sized,
+{
+    // NaN does not even compare with itself
+    let comparable = value.partial_cmp(value).is_some();
+    let above = match min {
+        Bound::Included(min) => value >= min,
+        Bound::Excluded(min) => value > min,
+        Bound::Unbounded => comparable,
+    };
+    let below = match max {
+        Bound::Included(max) => value <= max,
+        Bound::Excluded(max) => value < max,
+        Bound::Unbounded => comparable,
+    };
+
+    above && below
+}
+
+/// Returns whether or not the value is within the bounds parsed from text, for the types
+/// written as text in attributes like the dates of chrono
+///
+/// A bound that does not parse as `T` puts every value out of range
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered
+/// * `min` - `Option<&str>` minimum, inclusive
+/// * `max` - `Option<&str>` maximum, inclusive
+///
+/// # Example
+/// ```
+/// use crate::validation::is_in_parsed_range;
+/// use std::net::Ipv4Addr;
+///
+/// let address = Ipv4Addr::new(10, 0, 0, 7);
+///
+/// assert_eq!(true, is_in_parsed_range(&address, Some("10.0.0.0"), Some("10.0.0.255")));
+/// assert_eq!(false, is_in_parsed_range(&address, Some("10.0.1.0"), None));
+/// assert_eq!(false, is_in_parsed_range(&address, Some("ten"), None));
+/// ```
+pub fn is_in_parsed_range<T>(value: &T, min: Option<&str>, max: Option<&str>) -> bool
+where
+    T: PartialOrd + FromStr,
+{
+    is_within_parsed(value, included(min), included(max))
+}
+
+/// Returns whether or not the value is within the bounds parsed from text, each included,
+/// excluded or unbounded
+///
+/// A bound that does not parse as `T` puts every value out of range
+///
+/// # Arguments
+///
+/// * `value` - `&T` to be considered
+/// * `min` - `Bound<&str>` lower bound
+/// * `max` - `Bound<&str>` upper bound
+///
+/// # Example
+/// ```
+/// use crate::validation::is_within_parsed;
+/// use std::net::Ipv4Addr;
+/// use std::ops::Bound::{Excluded, Unbounded};
+///
+/// let address = Ipv4Addr::new(10, 0, 0, 255);
+///
+/// assert_eq!(true, is_within_parsed(&address, Unbounded, Excluded("10.0.1.0")));
+/// assert_eq!(false, is_within_parsed(&address, Excluded("10.0.0.255"), Unbounded));
+/// ```
+pub fn is_within_parsed<T>(value: &T, min: Bound<&str>, max: Bound<&str>) -> bool
+where
+    T: PartialOrd + FromStr,
+{
+    let parse = |bound: Bound<&str>| match bound {
+        Bound::Included(bound) => bound.parse::<T>().ok().map(Bound::Included),
+        Bound::Excluded(bound) => bound.parse::<T>().ok().map(Bound::Excluded),
+        Bound::Unbounded => Some(Bound::Unbounded),
+    };
+
+    match (parse(min), parse(max)) {
+        (Some(min), Some(max)) => is_within(value, min.as_ref(), max.as_ref()),
+        _ => false,
+    }
+}
+
+fn included<T: ?Sized>(bound: Option<&T>) -> Bound<&T> {
+    bound.map_or(Bound::Unbounded, Bound::Included)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::range::{is_in_range, is_within};
+    use std::ops::Bound::{Excluded, Unbounded};
+
+    #[test]
+    fn test_is_in_range_one_sided() {
+        assert!(is_in_range(&-3, None, Some(&0)));
+        assert!(!is_in_range(&1, None, Some(&0)));
+        assert!(is_in_range::<str>("m", Some("m"), None));
+        assert!(!is_in_range::<str>("M", Some("m"), None));
+    }
+
+    #[test]
+    fn test_is_within_exclusive() {
+        assert!(is_within(&1, Excluded(&0), Excluded(&2)));
+        assert!(!is_within(&2, Excluded(&0), Excluded(&2)));
+        assert!(!is_within(&f32::NAN, Excluded(&0.0), Unbounded));
+        assert!(!is_within(&f32::NAN, Unbounded, Unbounded));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_is_in_parsed_range_dates() {
+        use crate::range::is_in_parsed_range;
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+
+        assert!(is_in_parsed_range(
+            &date,
+            Some("2024-01-01"),
+            Some("2024-12-31")
+        ));
+        assert!(!is_in_parsed_range(&date, Some("2024-03-01"), None));
+        assert!(!is_in_parsed_range(&date, Some("2024-13-01"), None));
+    }
+}
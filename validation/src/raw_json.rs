@@ -0,0 +1,1515 @@
+//! raw_json
+//!
+//! Module containing the pass over raw JSON input, run before it is deserialized
+//!
+//! serde keeps the last value of a duplicated key and reads numbers that do not fit as lossy
+//! floats, so two components reading the same payload can disagree on what it holds. The pass
+//! rejects those payloads, its errors are located by the JSON Pointer (RFC 6901) of the
+//! offending value, like `/lines/0/sku`
+//!
+//! `RawJsonLimits` bounds the size and shape of the input, so a hostile payload is rejected
+//! before deserializing it allocates without bound
+//!
+//! The pass also runs over a `Read` or an `AsyncRead`, a buffer at a time, so a body can be
+//! checked while it is received and rejected without buffering it whole. Simple rules of a
+//! `Schema` can be checked in the same pass, on the top level fields
+
+use std::collections::HashSet;
+use std::future;
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use serde_json::Value;
+
+use crate::capabilities::RULE_UNAVAILABLE;
+use crate::context::ValidationContext;
+use crate::description::FieldConstraintDescription;
+use crate::error::{FieldError, ValidationError, ValidationErrors};
+use crate::schema::{self, Schema};
+
+/// Checks raw JSON input for duplicate object keys and numbers that overflow, returning
+/// every failure found, empty when the input can be safely deserialized
+///
+/// Keys are compared once their escapes are decoded, `"a"` and `"\u0061"` are duplicates.
+/// Invalid JSON fails with `invalid_json` where the scan stopped
+///
+/// # Arguments
+///
+/// * `input` - the raw JSON, `&str` or `&[u8]`
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json;
+///
+/// assert!(raw_json::check_raw_json(r#"{ "role": "user" }"#).is_empty());
+///
+/// let errors = raw_json::check_raw_json(r#"{ "role": "user", "role": "admin" }"#);
+/// assert_eq!(Some("/role"), errors.first().and_then(|err| err.field.as_deref()));
+///
+/// let errors = raw_json::check_raw_json(r#"{ "amount": 1e400 }"#);
+/// assert_eq!("/amount: number_overflow", errors.to_string());
+/// ```
+pub fn check_raw_json<B: AsRef<[u8]> + ?Sized>(input: &B) -> ValidationErrors {
+    check_raw_json_with(input, &RawJsonLimits::new())
+}
+
+/// Checks raw JSON input as `check_raw_json` does, rejecting it as soon as it goes over one of
+/// the limits
+///
+/// Limits fail with their own code, `max_size`, `max_depth`, `max_items` or `max_length`, at
+/// the pointer of the value going over it. Nothing past it is scanned
+///
+/// # Arguments
+///
+/// * `input` - the raw JSON, `&str` or `&[u8]`
+/// * `limits` - the `RawJsonLimits` of the input
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json::{self, RawJsonLimits};
+///
+/// let limits = RawJsonLimits::new().with_max_depth(2).with_max_items(2);
+///
+/// assert!(raw_json::check_raw_json_with(r#"{ "tags": ["a", "b"] }"#, &limits).is_empty());
+///
+/// let errors = raw_json::check_raw_json_with(r#"{ "tags": ["a", "b", "c"] }"#, &limits);
+/// assert_eq!("/tags: max_items", errors.to_string());
+///
+/// let errors = raw_json::check_raw_json_with(r#"{ "tags": [[]] }"#, &limits);
+/// assert_eq!("/tags/0: max_depth", errors.to_string());
+/// ```
+pub fn check_raw_json_with<B: AsRef<[u8]> + ?Sized>(
+    input: &B,
+    limits: &RawJsonLimits,
+) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    let input = input.as_ref();
+    if over(limits.max_size, input.len()) {
+        errors.push(FieldError::new("", mismatch("max_size")));
+        return errors;
+    }
+    let source = SliceSource {
+        bytes: input,
+        position: 0,
+    };
+    Scanner::new(source, limits).scan(&mut errors);
+    errors
+}
+
+/// Checks raw JSON input as `check_raw_json_with` does, reading it from the reader a buffer at
+/// a time so it is never held in memory as a whole
+///
+/// The input going over `max_size` fails as soon as the bytes past the limit are read, the
+/// rest of it is never read. Only the `max_size` failure is added then, the input being cut
+/// short
+///
+/// # Arguments
+///
+/// * `reader` - `Read` of the raw JSON, typically the body of a request
+/// * `limits` - the `RawJsonLimits` of the input
+///
+/// # Errors
+///
+/// Fails with the error of the reader, when reading fails before the scan stops
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json::{self, RawJsonLimits};
+///
+/// let limits = RawJsonLimits::new().with_max_size(16);
+///
+/// let errors = raw_json::check_raw_json_reader(&b"[1, 2]"[..], &limits).unwrap();
+/// assert!(errors.is_empty());
+///
+/// let errors = raw_json::check_raw_json_reader(std::io::repeat(b' '), &limits).unwrap();
+/// assert_eq!(": max_size", errors.to_string());
+/// ```
+pub fn check_raw_json_reader<R: Read>(
+    reader: R,
+    limits: &RawJsonLimits,
+) -> io::Result<ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    let source = ReadSource::new(reader, limits.max_size);
+    match Scanner::new(source, limits).scan(&mut errors).error {
+        Some(e) => Err(e),
+        None => Ok(errors),
+    }
+}
+
+/// Checks raw JSON input read from the reader as `check_raw_json_reader` does, checking in the
+/// same pass the rules of the schema on the fields of the top level object
+///
+/// The rules are the ones `Schema::validate` resolves for the context. They are checked on
+/// the fields holding strings, numbers, booleans and `null` as the fields are read, the
+/// values of the others are not kept so their rules are skipped. The first rule to fail
+/// stops the scan, at the pointer of its field with the code of the rule, and `not_null` on
+/// a field the input does not have fails once the input ends. A rule this build cannot
+/// evaluate fails the input with `rule_unavailable` before it is read
+///
+/// # Arguments
+///
+/// * `reader` - `Read` of the raw JSON, typically the body of a request
+/// * `limits` - the `RawJsonLimits` of the input
+/// * `schema` - `Schema` of the payload
+/// * `ctx` - `ValidationContext` the rules of the schema are resolved for
+///
+/// # Errors
+///
+/// Fails with the error of the reader, when reading fails before the scan stops
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json::{self, RawJsonLimits};
+/// use crate::validation::schema::Schema;
+/// use crate::validation::{FieldConstraintDescription, ValidationContext};
+///
+/// let schema = Schema::new(vec![
+///     FieldConstraintDescription::new("name", "length", None).param("max", 8),
+///     FieldConstraintDescription::new("email", "not_null", None),
+/// ]);
+/// let check = |input: &str| {
+///     raw_json::check_raw_json_reader_with_schema(
+///         input.as_bytes(),
+///         &RawJsonLimits::new(),
+///         &schema,
+///         &ValidationContext::default(),
+///     )
+///     .unwrap()
+///     .to_string()
+/// };
+///
+/// assert_eq!("", check(r#"{ "name": "Ada", "email": "ada@example.com" }"#));
+/// assert_eq!("/name: length", check(r#"{ "name": "Ada Lovelace", "email": null }"#));
+/// assert_eq!("/email: not_null", check(r#"{ "name": "Ada" }"#));
+/// ```
+pub fn check_raw_json_reader_with_schema<R: Read>(
+    reader: R,
+    limits: &RawJsonLimits,
+    schema: &Schema,
+    ctx: &ValidationContext,
+) -> io::Result<ValidationErrors> {
+    let rules = schema.applicable_rules(ctx);
+    if let Some(errors) = unavailable_rule(&rules) {
+        return Ok(errors);
+    }
+    let mut errors = ValidationErrors::new();
+    let source = ReadSource::new(reader, limits.max_size);
+    let scanner = Scanner::new(source, limits).with_rules(rules, ctx);
+    match scanner.scan(&mut errors).error {
+        Some(e) => Err(e),
+        None => Ok(errors),
+    }
+}
+
+// Fails the input with the first rule this build cannot evaluate, before it is read
+fn unavailable_rule(rules: &[FieldConstraintDescription]) -> Option<ValidationErrors> {
+    let rule = rules.iter().find(|rule| !crate::is_rule_available(rule))?;
+    let mut errors = ValidationErrors::new();
+    errors.push(
+        FieldError::new(
+            &pointer(std::slice::from_ref(&rule.field)),
+            ValidationError::InvalidState(RULE_UNAVAILABLE.to_string()),
+        )
+        .with_param("rule", rule.rule.as_str()),
+    );
+    Some(errors)
+}
+
+/// Asynchronous reader of raw JSON, typically the body of a request being received
+///
+/// The library depends on no runtime, the trait is the one of `futures::io::AsyncRead`: the
+/// bodies of the runtime of the service implement it with a wrapper
+pub trait AsyncRead {
+    /// Reads into the buffer, returning how many bytes were read, 0 once the input ended
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+}
+
+impl AsyncRead for &[u8] {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Read::read(&mut *self, buf))
+    }
+}
+
+/// Checks raw JSON input read from the async reader as `check_raw_json_reader` does
+///
+/// The scan runs on a thread of its own, handed the buffers as they are received, so the
+/// future never blocks. Only a few buffers are held at once, and once the scan stops, on a
+/// limit or invalid JSON, the rest of the input is never read
+///
+/// # Arguments
+///
+/// * `reader` - `AsyncRead` of the raw JSON, typically the body of a request
+/// * `limits` - the `RawJsonLimits` of the input
+///
+/// # Errors
+///
+/// Fails with the error of the reader, when reading fails before the scan stops
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json::{self, RawJsonLimits};
+/// use std::future::Future;
+/// use std::task::{Context, Poll, Waker};
+///
+/// let limits = RawJsonLimits::new().with_max_depth(1);
+/// let mut check = Box::pin(raw_json::check_raw_json_async_reader(&b"[[1]]"[..], &limits));
+/// let errors = loop {
+///     match check.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+///         Poll::Ready(errors) => break errors.unwrap(),
+///         Poll::Pending => std::thread::yield_now(),
+///     }
+/// };
+/// assert_eq!("/0: max_depth", errors.to_string());
+/// ```
+pub async fn check_raw_json_async_reader<R: AsyncRead + Unpin>(
+    reader: R,
+    limits: &RawJsonLimits,
+) -> io::Result<ValidationErrors> {
+    let limits = *limits;
+    scan_async(reader, move |reader| {
+        let mut errors = ValidationErrors::new();
+        let source = ReadSource::new(reader, limits.max_size);
+        match Scanner::new(source, &limits).scan(&mut errors).error {
+            Some(e) => Err(e),
+            None => Ok(errors),
+        }
+    })
+    .await
+}
+
+/// Checks raw JSON input read from the async reader as `check_raw_json_reader_with_schema`
+/// does, the scan running as `check_raw_json_async_reader` runs it
+///
+/// # Arguments
+///
+/// * `reader` - `AsyncRead` of the raw JSON, typically the body of a request
+/// * `limits` - the `RawJsonLimits` of the input
+/// * `schema` - `Schema` of the payload
+/// * `ctx` - `ValidationContext` the rules of the schema are resolved for
+///
+/// # Errors
+///
+/// Fails with the error of the reader, when reading fails before the scan stops
+pub async fn check_raw_json_async_reader_with_schema<R: AsyncRead + Unpin>(
+    reader: R,
+    limits: &RawJsonLimits,
+    schema: &Schema,
+    ctx: &ValidationContext,
+) -> io::Result<ValidationErrors> {
+    let rules = schema.applicable_rules(ctx);
+    if let Some(errors) = unavailable_rule(&rules) {
+        return Ok(errors);
+    }
+    let (limits, ctx) = (*limits, ctx.clone());
+    scan_async(reader, move |reader| {
+        let mut errors = ValidationErrors::new();
+        let source = ReadSource::new(reader, limits.max_size);
+        let scanner = Scanner::new(source, &limits).with_rules(rules, &ctx);
+        match scanner.scan(&mut errors).error {
+            Some(e) => Err(e),
+            None => Ok(errors),
+        }
+    })
+    .await
+}
+
+// State shared by the future reading the input and the thread scanning it
+#[derive(Default)]
+struct Feed {
+    outcome: Option<io::Result<ValidationErrors>>,
+    // Waker of the future, woken once the scan takes a buffer or ends
+    waker: Option<Waker>,
+}
+
+fn lock(feed: &Mutex<Feed>) -> MutexGuard<'_, Feed> {
+    feed.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn wake(feed: &Mutex<Feed>) {
+    if let Some(waker) = lock(feed).waker.take() {
+        waker.wake();
+    }
+}
+
+// Reads the input from the reader and hands it a buffer at a time to the scan, running on a
+// thread of its own. Reading stops once the scan ends
+async fn scan_async<R, F>(mut reader: R, scan: F) -> io::Result<ValidationErrors>
+where
+    R: AsyncRead + Unpin,
+    F: FnOnce(ChannelReader) -> io::Result<ValidationErrors> + Send + 'static,
+{
+    // One buffer waits for the scan at most, so a slow scan holds the reading back
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let feed = Arc::new(Mutex::new(Feed::default()));
+    let scan_feed = feed.clone();
+    thread::spawn(move || {
+        let reader = ChannelReader {
+            receiver,
+            feed: scan_feed.clone(),
+            chunk: Vec::new(),
+            position: 0,
+        };
+        let outcome = scan(reader);
+        lock(&scan_feed).outcome = Some(outcome);
+        wake(&scan_feed);
+    });
+
+    let mut sender = Some(sender);
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    // Buffer read but not yet taken by the scan
+    let mut read: Option<io::Result<Vec<u8>>> = None;
+    future::poll_fn(|cx| loop {
+        // Registered before looking at the scan, so none of its wakes is missed
+        lock(&feed).waker = Some(cx.waker().clone());
+        if let Some(outcome) = lock(&feed).outcome.take() {
+            return Poll::Ready(outcome);
+        }
+        let to_scan = match sender {
+            Some(ref to_scan) => to_scan,
+            None => return Poll::Pending,
+        };
+        let chunk = match read.take() {
+            Some(chunk) => chunk,
+            None => match Pin::new(&mut reader).poll_read(cx, &mut buffer) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => {
+                    sender = None;
+                    continue;
+                }
+                Poll::Ready(Ok(len)) => Ok(buffer[..len].to_vec()),
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => Err(e),
+            },
+        };
+        let failed = chunk.is_err();
+        match to_scan.try_send(chunk) {
+            Ok(()) if failed => sender = None,
+            Ok(()) => {}
+            Err(TrySendError::Full(chunk)) => {
+                read = Some(chunk);
+                return Poll::Pending;
+            }
+            // The scan stopped, the rest of the input is not read
+            Err(TrySendError::Disconnected(_)) => sender = None,
+        }
+    })
+    .await
+}
+
+// `Read` of the buffers handed to the scan thread, the input ending once they stop coming
+struct ChannelReader {
+    receiver: Receiver<io::Result<Vec<u8>>>,
+    feed: Arc<Mutex<Feed>>,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.chunk.len() {
+            let received = self.receiver.recv();
+            // The channel has room again, the future can read on
+            wake(&self.feed);
+            match received {
+                Ok(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.position = 0;
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_) => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.chunk.len() - self.position);
+        buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Limits on the complexity of raw JSON input, set per endpoint, none of them set by default
+///
+/// # Example
+/// ```
+/// use crate::validation::raw_json::RawJsonLimits;
+///
+/// let upload = RawJsonLimits::new()
+///     .with_max_size(1 << 20)
+///     .with_max_depth(16)
+///     .with_max_items(1_000)
+///     .with_max_length(4_096);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RawJsonLimits {
+    max_size: Option<usize>,
+    max_depth: Option<usize>,
+    max_items: Option<usize>,
+    max_length: Option<usize>,
+}
+
+impl RawJsonLimits {
+    pub fn new() -> Self {
+        RawJsonLimits::default()
+    }
+
+    /// Maximum size of the input, in bytes
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Maximum number of objects and arrays a value can be nested in
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Maximum number of elements of an array
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Maximum length of a string, keys included, in characters, and of the text of a number
+    ///
+    /// Checked as the input is scanned, a longer string or number fails before the rest of
+    /// it is read
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+}
+
+fn over(limit: Option<usize>, value: usize) -> bool {
+    limit.is_some_and(|limit| value > limit)
+}
+
+fn mismatch(code: &str) -> ValidationError {
+    ValidationError::FieldMismatch(code.to_string())
+}
+
+/// Returns the JSON Pointer of the path, `~` and `/` escaped as `~0` and `~1`
+fn pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+// Object or array being scanned
+enum Container {
+    Object(HashSet<String>),
+    Array(usize),
+}
+
+// Reason the scan stops
+enum Stop {
+    // The input is not valid JSON
+    Invalid,
+    // The input goes over a limit, with the code of the limit
+    Limit(&'static str),
+    // A field fails one of the rules of the schema, with the code of the rule
+    Rule(String),
+}
+
+// Bytes of the input, a byte at a time
+trait Source {
+    fn peek(&mut self) -> Option<u8>;
+
+    fn advance(&mut self);
+
+    // Whether or not the input stopped as it went over its maximum size
+    fn exceeded(&self) -> bool {
+        false
+    }
+}
+
+struct SliceSource<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Source for SliceSource<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+}
+
+// Reads the input a buffer at a time, ending it early when it goes over the maximum size or
+// the reader fails
+struct ReadSource<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    position: usize,
+    end: usize,
+    read: usize,
+    max_size: Option<usize>,
+    exceeded: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> ReadSource<R> {
+    fn new(reader: R, max_size: Option<usize>) -> Self {
+        ReadSource {
+            reader,
+            buffer: vec![0; READ_BUFFER_SIZE].into_boxed_slice(),
+            position: 0,
+            end: 0,
+            read: 0,
+            max_size,
+            exceeded: false,
+            error: None,
+        }
+    }
+
+    fn fill(&mut self) -> bool {
+        if self.exceeded || self.error.is_some() {
+            return false;
+        }
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return false,
+                Ok(read) => {
+                    self.read += read;
+                    if over(self.max_size, self.read) {
+                        self.exceeded = true;
+                        return false;
+                    }
+                    self.position = 0;
+                    self.end = read;
+                    return true;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.error = Some(e);
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Source for ReadSource<R> {
+    fn peek(&mut self) -> Option<u8> {
+        if self.position == self.end && !self.fill() {
+            return None;
+        }
+        Some(self.buffer[self.position])
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+// Checks the UTF-8 of a string a byte at a time and counts its characters, so the string
+// does not need to be kept for either
+#[derive(Default)]
+struct Utf8Counter {
+    chars: usize,
+    // Continuation bytes the current character still expects
+    pending: u8,
+    // Range of the next continuation byte, narrower after some leading bytes to rule out
+    // overlong forms, surrogates and code points past U+10FFFF
+    next: (u8, u8),
+}
+
+impl Utf8Counter {
+    // Returns whether or not the byte can follow the ones before it
+    fn push(&mut self, byte: u8) -> bool {
+        if self.pending > 0 {
+            if byte < self.next.0 || byte > self.next.1 {
+                return false;
+            }
+            self.pending -= 1;
+            self.next = (0x80, 0xBF);
+            return true;
+        }
+        let (pending, next) = match byte {
+            0x00..=0x7F => (0, (0x80, 0xBF)),
+            0xC2..=0xDF => (1, (0x80, 0xBF)),
+            0xE0 => (2, (0xA0, 0xBF)),
+            0xE1..=0xEC | 0xEE..=0xEF => (2, (0x80, 0xBF)),
+            0xED => (2, (0x80, 0x9F)),
+            0xF0 => (3, (0x90, 0xBF)),
+            0xF1..=0xF3 => (3, (0x80, 0xBF)),
+            0xF4 => (3, (0x80, 0x8F)),
+            _ => return false,
+        };
+        self.chars += 1;
+        self.pending = pending;
+        self.next = next;
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        self.pending == 0
+    }
+}
+
+// Rules of a `Schema` checked on the top level fields as they are scanned
+struct FieldRules<'a> {
+    rules: Vec<FieldConstraintDescription>,
+    ctx: &'a ValidationContext,
+    // Top level fields scanned so far
+    seen: HashSet<String>,
+}
+
+struct Scanner<'a, S> {
+    limits: &'a RawJsonLimits,
+    source: S,
+    fields: Option<FieldRules<'a>>,
+    // Path of the value being scanned
+    path: Vec<String>,
+    // Containers the value being scanned is in, an explicit stack so deep input cannot
+    // overflow the call stack
+    containers: Vec<Container>,
+}
+
+impl<'a, S: Source> Scanner<'a, S> {
+    fn new(source: S, limits: &'a RawJsonLimits) -> Self {
+        Scanner {
+            limits,
+            source,
+            fields: None,
+            path: Vec::new(),
+            containers: Vec::new(),
+        }
+    }
+
+    fn with_rules(
+        mut self,
+        rules: Vec<FieldConstraintDescription>,
+        ctx: &'a ValidationContext,
+    ) -> Self {
+        self.fields = Some(FieldRules {
+            rules,
+            ctx,
+            seen: HashSet::new(),
+        });
+        self
+    }
+
+    // Scans the input, returning its source once done
+    fn scan(mut self, errors: &mut ValidationErrors) -> S {
+        let stop = match self.scan_values(errors) {
+            Ok(()) => self.check_missing(),
+            Err(stop) => Some(stop),
+        };
+        if self.source.exceeded() {
+            errors.push(FieldError::new("", mismatch("max_size")));
+        } else if let Some(stop) = stop {
+            let code = match stop {
+                Stop::Invalid => "invalid_json".to_string(),
+                Stop::Limit(code) => code.to_string(),
+                Stop::Rule(code) => code,
+            };
+            errors.push(FieldError::new(
+                &pointer(&self.path),
+                ValidationError::FieldMismatch(code),
+            ));
+        }
+        self.source
+    }
+
+    // Fails on the first `not_null` rule of a field the input does not have
+    fn check_missing(&mut self) -> Option<Stop> {
+        let fields = self.fields.as_ref()?;
+        let missing = fields
+            .rules
+            .iter()
+            .find(|rule| rule.rule == "not_null" && !fields.seen.contains(&rule.field))?;
+        self.path = vec![missing.field.clone()];
+        Some(Stop::Rule(missing.rule.clone()))
+    }
+
+    // Checks the rules of the field on a scalar value of the top level object
+    fn check_field(&mut self, value: Option<Value>) -> Result<(), Stop> {
+        let fields = match (&self.fields, self.containers.as_slice(), value) {
+            (Some(fields), [Container::Object(_)], Some(value)) => Some((fields, value)),
+            _ => None,
+        };
+        if let Some((fields, value)) = fields {
+            let field = self.path.last().map(String::as_str).unwrap_or_default();
+            let failed = fields
+                .rules
+                .iter()
+                .filter(|rule| rule.field == field)
+                .find(|rule| !schema::evaluate(rule, &value, fields.ctx));
+            if let Some(rule) = failed {
+                return Err(Stop::Rule(rule.rule.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn scan_values(&mut self, errors: &mut ValidationErrors) -> Result<(), Stop> {
+        loop {
+            let scalar = self.scan_value(errors)?;
+            self.check_field(scalar)?;
+            // Closes the containers the value ended, until one expects another value
+            loop {
+                self.skip_whitespace();
+                match self.containers.last_mut() {
+                    None => {
+                        return if self.source.peek().is_none() {
+                            Ok(())
+                        } else {
+                            Err(Stop::Invalid)
+                        };
+                    }
+                    Some(Container::Object(_)) => {
+                        self.path.pop();
+                        match self.next_byte() {
+                            Some(b',') => {
+                                self.scan_key(errors)?;
+                                break;
+                            }
+                            Some(b'}') => {
+                                self.containers.pop();
+                            }
+                            _ => return Err(Stop::Invalid),
+                        }
+                    }
+                    Some(Container::Array(ref mut index)) => {
+                        *index += 1;
+                        let index = *index;
+                        self.path.pop();
+                        match self.next_byte() {
+                            Some(b',') => {
+                                self.check_items(index)?;
+                                self.path.push(index.to_string());
+                                break;
+                            }
+                            Some(b']') => {
+                                self.containers.pop();
+                            }
+                            _ => return Err(Stop::Invalid),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Scans a scalar, or opens a container and scans up to its first value. Returns the
+    // scalar when it is checked by the rules of a field
+    fn scan_value(&mut self, errors: &mut ValidationErrors) -> Result<Option<Value>, Stop> {
+        loop {
+            self.skip_whitespace();
+            match self.source.peek() {
+                Some(b'{') => {
+                    self.source.advance();
+                    self.check_depth()?;
+                    self.skip_whitespace();
+                    if self.source.peek() == Some(b'}') {
+                        self.source.advance();
+                        return Ok(None);
+                    }
+                    self.containers.push(Container::Object(HashSet::new()));
+                    self.scan_key(errors)?;
+                }
+                Some(b'[') => {
+                    self.source.advance();
+                    self.check_depth()?;
+                    self.skip_whitespace();
+                    if self.source.peek() == Some(b']') {
+                        self.source.advance();
+                        return Ok(None);
+                    }
+                    self.check_items(0)?;
+                    self.containers.push(Container::Array(0));
+                    self.path.push("0".to_string());
+                }
+                Some(b'"') => {
+                    let keep = self.keeps_value();
+                    return Ok(self.scan_string(keep)?.map(Value::String));
+                }
+                Some(b'-') | Some(b'0'..=b'9') => {
+                    let (number, fits) = self.scan_number()?;
+                    if !fits {
+                        errors.push(FieldError::new(
+                            &pointer(&self.path),
+                            mismatch("number_overflow"),
+                        ));
+                        return Ok(None);
+                    }
+                    return Ok(serde_json::from_str(&number)
+                        .ok()
+                        .filter(|_| self.keeps_value()));
+                }
+                Some(b't') => return self.scan_literal("true", Value::Bool(true)),
+                Some(b'f') => return self.scan_literal("false", Value::Bool(false)),
+                Some(b'n') => return self.scan_literal("null", Value::Null),
+                _ => return Err(Stop::Invalid),
+            }
+        }
+    }
+
+    // Scans the key of the current object and its `:`, the key becoming the current path
+    fn scan_key(&mut self, errors: &mut ValidationErrors) -> Result<(), Stop> {
+        self.skip_whitespace();
+        if self.source.peek() != Some(b'"') {
+            return Err(Stop::Invalid);
+        }
+        let key = self.scan_string(true)?.unwrap_or_default();
+        self.path.push(key.clone());
+        if let (Some(fields), 1) = (self.fields.as_mut(), self.containers.len()) {
+            fields.seen.insert(key.clone());
+        }
+        if let Some(Container::Object(ref mut keys)) = self.containers.last_mut() {
+            if !keys.insert(key) {
+                errors.push(FieldError::new(
+                    &pointer(&self.path),
+                    mismatch("duplicate_key"),
+                ));
+            }
+        }
+        self.skip_whitespace();
+        match self.next_byte() {
+            Some(b':') => Ok(()),
+            _ => Err(Stop::Invalid),
+        }
+    }
+
+    // Fails when the container about to be opened is nested deeper than the limit
+    fn check_depth(&self) -> Result<(), Stop> {
+        if over(self.limits.max_depth, self.containers.len() + 1) {
+            Err(Stop::Limit("max_depth"))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Fails when the element at the index of the array goes over the limit
+    fn check_items(&self, index: usize) -> Result<(), Stop> {
+        if over(self.limits.max_items, index + 1) {
+            Err(Stop::Limit("max_items"))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Whether or not the scalar about to be scanned is checked by the rules of a field, the
+    // text of the others is not kept
+    fn keeps_value(&self) -> bool {
+        match (&self.fields, self.containers.as_slice()) {
+            (Some(fields), [Container::Object(_)]) => {
+                let field = self.path.last().map(String::as_str).unwrap_or_default();
+                fields.rules.iter().any(|rule| rule.field == field)
+            }
+            _ => false,
+        }
+    }
+
+    // Scans a string, returning it with its escapes decoded when it is kept. Its characters
+    // are counted as they are scanned, so a string over `max_length` fails there
+    fn scan_string(&mut self, keep: bool) -> Result<Option<String>, Stop> {
+        self.source.advance();
+        let mut bytes = Vec::new();
+        let mut utf8 = Utf8Counter::default();
+        loop {
+            while let Some(byte) = self.source.peek() {
+                if byte == b'"' || byte == b'\\' || byte < 0x20 {
+                    break;
+                }
+                if !utf8.push(byte) {
+                    return Err(Stop::Invalid);
+                }
+                self.check_length(utf8.chars)?;
+                if keep {
+                    bytes.push(byte);
+                }
+                self.source.advance();
+            }
+            if !utf8.is_complete() {
+                return Err(Stop::Invalid);
+            }
+            match self.next_byte() {
+                Some(b'"') if keep => {
+                    return String::from_utf8(bytes)
+                        .map(Some)
+                        .map_err(|_| Stop::Invalid);
+                }
+                Some(b'"') => return Ok(None),
+                Some(b'\\') => {
+                    let escaped = self.scan_escape()?;
+                    utf8.chars += 1;
+                    self.check_length(utf8.chars)?;
+                    if keep {
+                        bytes.extend_from_slice(escaped.encode_utf8(&mut [0; 4]).as_bytes());
+                    }
+                }
+                _ => return Err(Stop::Invalid),
+            }
+        }
+    }
+
+    // Fails when a string or a number is longer than the limit
+    fn check_length(&self, length: usize) -> Result<(), Stop> {
+        if over(self.limits.max_length, length) {
+            Err(Stop::Limit("max_length"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn scan_escape(&mut self) -> Result<char, Stop> {
+        let escaped = match self.next_byte() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let unit = self.scan_hex()?;
+                match unit {
+                    0xD800..=0xDBFF => {
+                        if self.next_byte() != Some(b'\\') || self.next_byte() != Some(b'u') {
+                            return Err(Stop::Invalid);
+                        }
+                        let low = self.scan_hex()?;
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err(Stop::Invalid);
+                        }
+                        let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                        std::char::from_u32(code).ok_or(Stop::Invalid)?
+                    }
+                    0xDC00..=0xDFFF => return Err(Stop::Invalid),
+                    _ => std::char::from_u32(unit).ok_or(Stop::Invalid)?,
+                }
+            }
+            _ => return Err(Stop::Invalid),
+        };
+        Ok(escaped)
+    }
+
+    fn scan_hex(&mut self) -> Result<u32, Stop> {
+        let mut unit = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next_byte()
+                .and_then(|byte| char::from(byte).to_digit(16))
+                .ok_or(Stop::Invalid)?;
+            unit = unit * 16 + digit;
+        }
+        Ok(unit)
+    }
+
+    // Scans a number, returning it along with whether or not it fits: integers in an `i64` or
+    // a `u64`, others in a finite `f64`
+    fn scan_number(&mut self) -> Result<(String, bool), Stop> {
+        let mut number = String::new();
+        if self.source.peek() == Some(b'-') {
+            self.take(&mut number)?;
+        }
+        match self.source.peek() {
+            Some(b'0') => self.take(&mut number)?,
+            Some(b'1'..=b'9') => self.take_digits(&mut number)?,
+            _ => return Err(Stop::Invalid),
+        }
+        let mut integer = true;
+        if self.source.peek() == Some(b'.') {
+            integer = false;
+            self.take(&mut number)?;
+            self.require_digits(&mut number)?;
+        }
+        if let Some(b'e') | Some(b'E') = self.source.peek() {
+            integer = false;
+            self.take(&mut number)?;
+            if let Some(b'+') | Some(b'-') = self.source.peek() {
+                self.take(&mut number)?;
+            }
+            self.require_digits(&mut number)?;
+        }
+
+        let fits = if integer {
+            number.parse::<i64>().is_ok() || number.parse::<u64>().is_ok()
+        } else {
+            number.parse::<f64>().is_ok_and(f64::is_finite)
+        };
+        Ok((number, fits))
+    }
+
+    fn require_digits(&mut self, number: &mut String) -> Result<(), Stop> {
+        match self.source.peek() {
+            Some(b'0'..=b'9') => self.take_digits(number),
+            _ => Err(Stop::Invalid),
+        }
+    }
+
+    fn take_digits(&mut self, number: &mut String) -> Result<(), Stop> {
+        while let Some(b'0'..=b'9') = self.source.peek() {
+            self.take(number)?;
+        }
+        Ok(())
+    }
+
+    // Moves the next byte, an ASCII one, to the number, failing once it is over `max_length`
+    fn take(&mut self, number: &mut String) -> Result<(), Stop> {
+        if let Some(byte) = self.next_byte() {
+            number.push(char::from(byte));
+        }
+        self.check_length(number.len())
+    }
+
+    fn scan_literal(&mut self, literal: &str, value: Value) -> Result<Option<Value>, Stop> {
+        for expected in literal.bytes() {
+            if self.next_byte() != Some(expected) {
+                return Err(Stop::Invalid);
+            }
+        }
+        Ok(Some(value).filter(|_| self.fields.is_some()))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\n') | Some(b'\r') | Some(b'\t') = self.source.peek() {
+            self.source.advance();
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.source.peek();
+        if byte.is_some() {
+            self.source.advance();
+        }
+        byte
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::raw_json::*;
+
+    fn fields(errors: ValidationErrors) -> Vec<String> {
+        errors.iter().map(|err| err.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid_input() {
+        for input in &[
+            r#"{}"#,
+            r#"[]"#,
+            r#"" \" é 😀 ""#,
+            r#"{ "a": [1, -2.5e3, true, false, null, { "b": {} }], "c": "d" }"#,
+            r#"  18446744073709551615  "#,
+            r#"-9223372036854775808"#,
+        ] {
+            assert!(check_raw_json(*input).is_empty(), "{}", input);
+        }
+        assert!(check_raw_json(b"[1, 2]".as_ref()).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        assert_eq!(
+            vec![
+                "/user/role: duplicate_key".to_string(),
+                "/lines/1/a~1b: duplicate_key".to_string(),
+            ],
+            fields(check_raw_json(
+                r#"{ "user": { "role": "a", "role": "b" }, "lines": [{}, { "a/b": 1, "a/b": 2 }] }"#
+            ))
+        );
+        assert!(check_raw_json(r#"[{ "a": 1 }, { "a": 1 }]"#).is_empty());
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        assert_eq!(
+            vec![
+                "/0: number_overflow".to_string(),
+                "/1: number_overflow".to_string(),
+                "/3: number_overflow".to_string(),
+            ],
+            fields(check_raw_json(
+                "[18446744073709551616, -9223372036854775809, 1e300, -1e400]"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert_eq!(
+            vec!["/a/1: invalid_json".to_string()],
+            fields(check_raw_json(r#"{ "a": [1, tru] }"#))
+        );
+        for input in &["", "{", r#"{ "a" 1 }"#, "[1,]", "01", "1 2", r#""\x""#] {
+            assert_eq!(1, check_raw_json(*input).len(), "{}", input);
+        }
+        assert_eq!(1, check_raw_json(b"\"\xff\"".as_ref()).len());
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = RawJsonLimits::new()
+            .with_max_size(64)
+            .with_max_depth(3)
+            .with_max_items(3)
+            .with_max_length(4);
+        let check = |input: &str| fields(check_raw_json_with(input, &limits));
+
+        assert!(check(r#"{ "a": [[1, 2, 3]], "abcd": "abcd" }"#).is_empty());
+        assert!(check("[]").is_empty());
+        assert_eq!(vec![": max_size".to_string()], check(&" ".repeat(65)));
+        assert_eq!(
+            vec!["/a/0/1: max_depth".to_string()],
+            check(r#"{ "a": [[1, {}]] }"#)
+        );
+        assert_eq!(
+            vec!["/a: max_items".to_string()],
+            check(r#"{ "a": [1, 2, 3, 4] }"#)
+        );
+        assert_eq!(
+            vec!["/a: max_length".to_string()],
+            check(r#"{ "a": "abcde" }"#)
+        );
+        assert_eq!(vec![": max_length".to_string()], check(r#"{ "abcde": 1 }"#));
+        assert_eq!(
+            vec![
+                "/a: duplicate_key".to_string(),
+                "/b/0/0: max_depth".to_string()
+            ],
+            check(r#"{ "a": 1, "a": 2, "b": [[[]]] }"#)
+        );
+        assert_eq!(
+            vec!["/a: max_items".to_string()],
+            fields(check_raw_json_with(
+                r#"{ "a": [1] }"#,
+                &RawJsonLimits::new().with_max_items(0)
+            ))
+        );
+    }
+
+    // Reader failing once the bytes before it are read
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+
+    #[test]
+    fn test_reader_matches_slices() {
+        let limits = RawJsonLimits::new().with_max_depth(3).with_max_length(4);
+        let input = format!(
+            r#"{{ "a": [1e400, "é"], "a": {{}}, "b": "{}", "c": [[[]]] }}"#,
+            "x".repeat(READ_BUFFER_SIZE)
+        );
+        for input in &[input.as_str(), r#"{ "a": 1, "a": [[[[]]]] }"#, "[tru", "01"] {
+            assert_eq!(
+                fields(check_raw_json_with(*input, &limits)),
+                fields(check_raw_json_reader(input.as_bytes(), &limits).unwrap()),
+                "{}",
+                input
+            );
+        }
+        assert!(check_raw_json_reader(&b"\"\xff\""[..], &limits)
+            .unwrap()
+            .to_string()
+            .ends_with("invalid_json"));
+    }
+
+    #[test]
+    fn test_reader_max_size() {
+        let limits = RawJsonLimits::new().with_max_size(READ_BUFFER_SIZE * 2);
+        let input = format!("[{}]", "1,".repeat(READ_BUFFER_SIZE) + "1");
+        assert_eq!(
+            vec![": max_size".to_string()],
+            fields(check_raw_json_reader(input.as_bytes(), &limits).unwrap())
+        );
+        // Nothing is read past the limit, the failing reader is never reached
+        let input = " ".repeat(READ_BUFFER_SIZE * 3);
+        assert_eq!(
+            vec![": max_size".to_string()],
+            fields(check_raw_json_reader(input.as_bytes().chain(Failing), &limits).unwrap())
+        );
+        assert_eq!(
+            io::ErrorKind::ConnectionReset,
+            check_raw_json_reader(b"[1, ".chain(Failing), &limits)
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_reader_with_schema() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("plan", "one_of", None)
+                .param("values", vec!["basic", "business"]),
+            FieldConstraintDescription::new("seats", "range", None).param("max", 10),
+            FieldConstraintDescription::new("owner", "not_null", None),
+        ]);
+        let check = |reader: &mut dyn Read| {
+            check_raw_json_reader_with_schema(
+                reader,
+                &RawJsonLimits::new(),
+                &schema,
+                &ValidationContext::default(),
+            )
+            .map(fields)
+            .map_err(|e| e.kind())
+        };
+
+        assert_eq!(
+            Ok(vec![]),
+            check(&mut &br#"{ "plan": "basic", "seats": 3, "owner": { "plan": "free" } }"#[..])
+        );
+        assert_eq!(
+            Ok(vec!["/owner: not_null".to_string()]),
+            check(&mut &br#"{ "plan": "basic", "seats": [] }"#[..])
+        );
+        assert_eq!(
+            Ok(vec!["/owner: not_null".to_string()]),
+            check(&mut &br#"{ "owner": null, "plan": "basic" }"#[..])
+        );
+        // The scan stops at the failing rule, before reaching the failing reader
+        assert_eq!(
+            Ok(vec!["/seats: range".to_string()]),
+            check(&mut br#"{ "plan": "basic", "seats": 30, "owner": "#.chain(Failing))
+        );
+        assert_eq!(
+            Err(io::ErrorKind::ConnectionReset),
+            check(&mut br#"{ "plan": "basic", "#.chain(Failing))
+        );
+    }
+
+    #[test]
+    fn test_reader_with_unavailable_rule() {
+        let schema = Schema::new(vec![FieldConstraintDescription::new(
+            "name",
+            "palindrome",
+            None,
+        )]);
+        // Not read at all, the failing reader is never reached
+        assert_eq!(
+            vec!["/name: rule_unavailable".to_string()],
+            fields(
+                check_raw_json_reader_with_schema(
+                    Failing,
+                    &RawJsonLimits::new(),
+                    &schema,
+                    &ValidationContext::default(),
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_max_length_before_the_end() {
+        let limits = RawJsonLimits::new().with_max_length(16);
+        let long = || io::repeat(b'7').take(READ_BUFFER_SIZE as u64 * 64);
+        // The scan stops within the string or the number, the failing reader is never reached
+        assert_eq!(
+            vec!["/a: max_length".to_string()],
+            fields(
+                check_raw_json_reader(b"{ \"a\": \"".chain(long()).chain(Failing), &limits)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            vec!["/a: max_length".to_string()],
+            fields(
+                check_raw_json_reader(b"{ \"a\": ".chain(long()).chain(Failing), &limits).unwrap()
+            )
+        );
+        assert_eq!(
+            vec!["/a: max_length".to_string()],
+            fields(check_raw_json_with(
+                &format!(r#"{{ "a": "{}" }}"#, "é".repeat(17)),
+                &limits
+            ))
+        );
+        assert!(
+            check_raw_json_with(&format!(r#"{{ "a": "{}" }}"#, "é".repeat(16)), &limits).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_utf8_checked_without_keeping_strings() {
+        for input in [
+            &b"[\"\xc3\"]"[..],
+            b"[\"\xed\xa0\x80\"]",
+            b"[\"\xe0\x80\xaf\"]",
+            b"[\"\xf4\x90\x80\x80\"]",
+        ] {
+            assert!(check_raw_json(input).to_string().ends_with("invalid_json"));
+        }
+        assert!(check_raw_json("[\"é€𝄞\"]").is_empty());
+    }
+
+    #[test]
+    fn test_deep_input() {
+        let input = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert!(check_raw_json(&input).is_empty());
+    }
+
+    // Async reader handing out its input a few bytes at a time, pending before each of them,
+    // then failing when `failing`
+    struct Trickle {
+        input: Vec<u8>,
+        position: usize,
+        ready: bool,
+        failing: bool,
+        // Bytes handed out, to tell how far the input was read
+        read: Arc<Mutex<usize>>,
+    }
+
+    fn trickle(input: &[u8], failing: bool) -> (Trickle, Arc<Mutex<usize>>) {
+        let read = Arc::new(Mutex::new(0));
+        let reader = Trickle {
+            input: input.to_vec(),
+            position: 0,
+            ready: false,
+            failing,
+            read: read.clone(),
+        };
+        (reader, read)
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            self.ready = !self.ready;
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let len = buf.len().min(self.input.len() - self.position).min(7);
+            if len == 0 && self.failing {
+                return Poll::Ready(Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset")));
+            }
+            buf[..len].copy_from_slice(&self.input[self.position..self.position + len]);
+            self.position += len;
+            *self.read.lock().unwrap() += len;
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    // Runs the future on the current thread, parked while it is pending
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        struct Unpark(thread::Thread);
+
+        impl std::task::Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut future = Box::pin(future);
+        loop {
+            match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_reader_matches_slices() {
+        let limits = RawJsonLimits::new().with_max_depth(3).with_max_length(4);
+        let input = format!(
+            r#"{{ "a": [1e400, "é"], "a": {{}}, "b": "{}", "c": [[[]]] }}"#,
+            "x".repeat(READ_BUFFER_SIZE)
+        );
+        for input in &[
+            input.as_str(),
+            r#"{ "a": 1, "a": [[[[]]]] }"#,
+            "[tru",
+            "01",
+            "",
+        ] {
+            let (reader, _) = trickle(input.as_bytes(), false);
+            assert_eq!(
+                fields(check_raw_json_with(*input, &limits)),
+                fields(block_on(check_raw_json_async_reader(reader, &limits)).unwrap()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_async_reader_stops_reading() {
+        let limits = RawJsonLimits::new().with_max_size(64);
+        let input = " ".repeat(READ_BUFFER_SIZE * 4);
+        let (reader, read) = trickle(input.as_bytes(), true);
+        assert_eq!(
+            vec![": max_size".to_string()],
+            fields(block_on(check_raw_json_async_reader(reader, &limits)).unwrap())
+        );
+        // The scan stopped past the limit, the reader is left with most of the input
+        assert!(*read.lock().unwrap() < READ_BUFFER_SIZE);
+
+        let (reader, _) = trickle(b"[1, ", true);
+        assert_eq!(
+            io::ErrorKind::ConnectionReset,
+            block_on(check_raw_json_async_reader(reader, &RawJsonLimits::new()))
+                .unwrap_err()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn test_async_reader_with_schema() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("plan", "one_of", None)
+                .param("values", vec!["basic", "business"]),
+            FieldConstraintDescription::new("owner", "not_null", None),
+        ]);
+        let check = |input: &str, failing: bool| {
+            let (reader, _) = trickle(input.as_bytes(), failing);
+            block_on(check_raw_json_async_reader_with_schema(
+                reader,
+                &RawJsonLimits::new(),
+                &schema,
+                &ValidationContext::default(),
+            ))
+            .map(fields)
+            .map_err(|e| e.kind())
+        };
+
+        assert_eq!(
+            Ok(vec![]),
+            check(r#"{ "plan": "basic", "owner": "ada" }"#, false)
+        );
+        assert_eq!(
+            Ok(vec!["/owner: not_null".to_string()]),
+            check(r#"{ "plan": "basic" }"#, false)
+        );
+        // The scan stops at the failing rule, before reaching the failing end of the input
+        assert_eq!(
+            Ok(vec!["/plan: one_of".to_string()]),
+            check(r#"{ "plan": "gold", "owner": "#, true)
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::context::ValidationContext;
+use crate::validation::BoxFuture;
+
+/// Store of the keys seen by the `recently_seen` rule, typically shared by every instance of a
+/// service (Redis, a database table...)
+///
+/// Checking and recording a key is one operation, so two concurrent submissions of the same
+/// key cannot both pass
+pub trait SeenStore: Send + Sync {
+    /// Records the key as seen at `now` for `window`, returning whether or not it was already
+    /// seen within a window still open at `now`. A key seen again keeps its first window
+    fn check_and_record<'a>(
+        &'a self,
+        key: &'a str,
+        window: Duration,
+        now: SystemTime,
+    ) -> BoxFuture<'a, bool>;
+}
+
+/// `SeenStore` keeping the keys in memory, for a single instance or for tests
+///
+/// # Example
+/// ```
+/// use crate::validation::{InMemorySeenStore, ValidationContext, ValidationEnvironment};
+/// use std::sync::Arc;
+///
+/// let store = Arc::new(InMemorySeenStore::new());
+/// let ctx = ValidationContext::new(ValidationEnvironment::new().with_seen_store(store.clone()));
+///
+/// assert!(ctx.environment().seen_store().is_some());
+/// assert!(store.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemorySeenStore {
+    // Keys, along with the end of their window
+    seen: Mutex<HashMap<String, SystemTime>>,
+}
+
+impl InMemorySeenStore {
+    pub fn new() -> Self {
+        InMemorySeenStore::default()
+    }
+
+    /// Returns the number of keys whose window was still open the last time one was recorded
+    pub fn len(&self) -> usize {
+        self.seen
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SeenStore for InMemorySeenStore {
+    fn check_and_record<'a>(
+        &'a self,
+        key: &'a str,
+        window: Duration,
+        now: SystemTime,
+    ) -> BoxFuture<'a, bool> {
+        let mut seen = self.seen.lock().unwrap_or_else(|err| err.into_inner());
+        seen.retain(|_, until| *until > now);
+        let was_seen = seen.contains_key(key);
+        if !was_seen {
+            seen.insert(key.to_string(), now + window);
+        }
+        Box::pin(async move { was_seen })
+    }
+}
+
+/// Returns whether or not the key was already seen within the window, recording it in the
+/// `SeenStore` of the environment at the time of the context
+///
+/// Without a store in the environment the key cannot be checked: a warning is logged and the
+/// key is reported as not seen
+///
+/// # Arguments
+///
+/// * `ctx` - `ValidationContext` holding the store and the time
+/// * `key` - the key, the derive prefixing it with the struct and the field
+/// * `window` - `Duration` for which a key is remembered
+pub async fn is_recently_seen(ctx: &ValidationContext, key: &str, window: Duration) -> bool {
+    match ctx.environment().seen_store() {
+        Some(store) => store.check_and_record(key, window, ctx.now()).await,
+        None => {
+            log::warn!(
+                "recently_seen: no SeenStore in the environment, `{}` not checked",
+                key
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::{FixedClock, ValidationEnvironment};
+    use crate::recently_seen::*;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+    use std::time::UNIX_EPOCH;
+
+    // Polls the future once, the in memory store never waits
+    fn ready<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the future is not ready"),
+        }
+    }
+
+    fn context(store: &Arc<InMemorySeenStore>, seconds: u64) -> ValidationContext {
+        ValidationContext::new(
+            ValidationEnvironment::new()
+                .with_clock(FixedClock(UNIX_EPOCH + Duration::from_secs(seconds)))
+                .with_seen_store(store.clone()),
+        )
+    }
+
+    #[test]
+    fn test_recently_seen() {
+        let store = Arc::new(InMemorySeenStore::new());
+        let window = Duration::from_secs(10);
+
+        assert!(!ready(is_recently_seen(&context(&store, 0), "a", window)));
+        assert!(ready(is_recently_seen(&context(&store, 5), "a", window)));
+        assert!(!ready(is_recently_seen(&context(&store, 5), "b", window)));
+        assert_eq!(2, store.len());
+
+        assert!(!ready(is_recently_seen(&context(&store, 10), "a", window)));
+        assert_eq!(2, store.len());
+        assert!(!ready(is_recently_seen(&context(&store, 100), "c", window)));
+        assert_eq!(1, store.len());
+    }
+
+    #[test]
+    fn test_recently_seen_without_store() {
+        let ctx = ValidationContext::default();
+        let window = Duration::from_secs(10);
+
+        assert!(!ready(is_recently_seen(&ctx, "a", window)));
+        assert!(!ready(is_recently_seen(&ctx, "a", window)));
+    }
+}
@@ -0,0 +1,161 @@
+//! reflect
+//!
+//! Module containing the stable introspection of the rules of a type, for the generators of
+//! client SDKs emitting the same rules in other languages
+//!
+//! `Reflect` is implemented by `#[derive(FieldValidate)]`, `TypeInfo::to_json` being the shape
+//! generators read:
+//!
+//! ```json
+//! {"reflectVersion": 1, "name": "Signup", "fields": [
+//!   {"name": "username", "rules": [
+//!     {"name": "length", "params": {"min": 3}, "message": "value does not have a valid length", "groups": ["signup"]}
+//!   ]}
+//! ]}
+//! ```
+//!
+//! # Stability
+//!
+//! The names of the fields, rules and params are the serialized names of the fields and the
+//! codes of `catalog::ERROR_CATALOG`, covered by semver like the rest of the public API. Within
+//! a `REFLECT_VERSION`, members are only ever added: the structs are `#[non_exhaustive]` and
+//! optional members are left out of the JSON when unset, so generators ignore what they do
+//! not know. Removing or changing the meaning of a member bumps `REFLECT_VERSION`
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::description::{Deprecation, FieldConstraintDescription};
+use crate::version::ApiVersions;
+
+/// Version of the shape of `TypeInfo`, bumped on any change that is not an addition
+pub const REFLECT_VERSION: u32 = 1;
+
+/// Types whose rules can be introspected
+pub trait Reflect {
+    fn type_info() -> TypeInfo;
+}
+
+/// Rules of a type, per field in the order the fields are declared
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct TypeInfo {
+    pub reflect_version: u32,
+    pub name: String,
+    pub fields: Vec<FieldInfo>,
+}
+
+/// Rules of a field, by its serialized name
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct FieldInfo {
+    pub name: String,
+    pub rules: Vec<RuleInfo>,
+}
+
+/// A rule of a field, named by its error code
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct RuleInfo {
+    pub name: String,
+    pub params: BTreeMap<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub groups: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
+    /// The condition of a `when`, Rust over the struct that generators can only report
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<ApiVersions>,
+}
+
+impl TypeInfo {
+    /// Groups the descriptions of the rules of a type by field, the fields in the order of their
+    /// first rule
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::reflect::TypeInfo;
+    /// use crate::validation::FieldConstraintDescription;
+    ///
+    /// let info = TypeInfo::from_constraints(
+    ///     "Signup",
+    ///     &[
+    ///         FieldConstraintDescription::new("username", "length", None).param("min", 3),
+    ///         FieldConstraintDescription::new("email", "not_null", None),
+    ///         FieldConstraintDescription::new("username", "regex_inline", None),
+    ///     ],
+    /// );
+    /// assert_eq!(2, info.fields.len());
+    /// assert_eq!(2, info.fields[0].rules.len());
+    /// assert_eq!("not_null", info.fields[1].rules[0].name);
+    /// ```
+    pub fn from_constraints(name: &str, constraints: &[FieldConstraintDescription]) -> Self {
+        let mut fields: Vec<FieldInfo> = Vec::new();
+        for constraint in constraints {
+            let rule = RuleInfo {
+                name: constraint.rule.clone(),
+                params: constraint.params.clone(),
+                message: constraint.message.clone(),
+                groups: constraint.groups.clone(),
+                deprecated: constraint.deprecated.clone(),
+                when: constraint.when.clone(),
+                versions: constraint.versions,
+            };
+            match fields
+                .iter_mut()
+                .find(|field| field.name == constraint.field)
+            {
+                Some(field) => field.rules.push(rule),
+                None => fields.push(FieldInfo {
+                    name: constraint.field.clone(),
+                    rules: vec![rule],
+                }),
+            }
+        }
+
+        TypeInfo {
+            reflect_version: REFLECT_VERSION,
+            name: name.to_string(),
+            fields,
+        }
+    }
+
+    /// Returns the rules of the type as JSON, see the module documentation for the shape
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Returns the rules in one of the groups, rules without a group being in none
+    pub fn in_group(&self, group: &str) -> TypeInfo {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| FieldInfo {
+                name: field.name.clone(),
+                rules: field
+                    .rules
+                    .iter()
+                    .filter(|rule| rule.groups.iter().any(|g| g == group))
+                    .cloned()
+                    .collect(),
+            })
+            .filter(|field| !field.rules.is_empty())
+            .collect();
+
+        TypeInfo {
+            reflect_version: self.reflect_version,
+            name: self.name.clone(),
+            fields,
+        }
+    }
+}
+
+/// Returns the rules of the type
+pub fn type_info<T: Reflect>() -> TypeInfo {
+    T::type_info()
+}
@@ -0,0 +1,180 @@
+//! reporter
+//!
+//! Module containing the payloads of `ValidationErrors` for Sentry compatible error reporters,
+//! so the failures of a validation are attached to the exceptions raised downstream of it
+//!
+//! The payloads hold the fields and codes of the failures, never their values: of the params
+//! of a failure only the ones of the rule itself, per the catalog (the `max` of a `length`...),
+//! are kept, the others, like the `suggestion` of `one_of`, may echo what the client sent
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use crate::catalog;
+use crate::error::{FieldError, ValidationErrors};
+
+/// Category of the breadcrumbs of validations
+pub const BREADCRUMB_CATEGORY: &str = "validation";
+
+/// Breadcrumb of a failed validation, shaped as the breadcrumbs of Sentry: its fields map one
+/// to one to the ones of `sentry::Breadcrumb`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReporterBreadcrumb {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub category: String,
+    pub level: String,
+    pub message: String,
+    pub data: BTreeMap<String, Value>,
+}
+
+/// Returns the breadcrumb of the failures, its message listing them as `field: code`
+///
+/// # Example
+/// ```
+/// use crate::validation::reporter;
+/// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.push(
+///     FieldError::new("currency", ValidationError::FieldMismatch("one_of".to_string()))
+///         .with_param("suggestion", "EUR"),
+/// );
+///
+/// let breadcrumb = reporter::to_breadcrumb(&errors);
+/// assert_eq!("validation", breadcrumb.category);
+/// assert_eq!("currency: one_of", breadcrumb.message);
+/// assert!(!serde_json::to_string(&breadcrumb).unwrap().contains("EUR"));
+/// ```
+pub fn to_breadcrumb(errors: &ValidationErrors) -> ReporterBreadcrumb {
+    let mut data = BTreeMap::new();
+    data.insert("error_count".to_string(), Value::from(errors.len()));
+    data.insert("errors".to_string(), Value::Array(failures(errors)));
+
+    ReporterBreadcrumb {
+        ty: "default".to_string(),
+        category: BREADCRUMB_CATEGORY.to_string(),
+        level: "warning".to_string(),
+        message: errors
+            .iter()
+            .map(|err| match err.field {
+                Some(ref field) => format!("{}: {}", field, err.error.code()),
+                None => err.error.code().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        data,
+    }
+}
+
+/// Returns the context of the failures, to set under the `validation` key of the contexts of
+/// an event
+///
+/// # Example
+/// ```
+/// use crate::validation::reporter;
+/// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.push(
+///     FieldError::new("name", ValidationError::FieldMismatch("length".to_string()))
+///         .with_param("max", 32),
+/// );
+///
+/// let context = serde_json::Value::Object(reporter::to_context(&errors));
+/// assert_eq!(1, context["error_count"]);
+/// assert_eq!("length", context["codes"][0]);
+/// assert_eq!(32, context["errors"][0]["params"]["max"]);
+/// ```
+pub fn to_context(errors: &ValidationErrors) -> Map<String, Value> {
+    let mut codes: Vec<&str> = Vec::new();
+    for err in errors.iter() {
+        if !codes.contains(&err.error.code()) {
+            codes.push(err.error.code());
+        }
+    }
+
+    let mut context = Map::new();
+    context.insert("error_count".to_string(), Value::from(errors.len()));
+    context.insert("codes".to_string(), Value::from(codes));
+    context.insert("errors".to_string(), Value::Array(failures(errors)));
+    context
+}
+
+fn failures(errors: &ValidationErrors) -> Vec<Value> {
+    errors.iter().map(failure).collect()
+}
+
+// A failure without its sensitive params
+fn failure(err: &FieldError) -> Value {
+    let rule_params = catalog::find(err.error.code()).map_or(&[][..], |entry| entry.params);
+    let params = err
+        .params
+        .iter()
+        .filter(|(name, _)| rule_params.contains(&name.as_str()))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect::<Map<_, _>>();
+
+    let mut failure = Map::new();
+    if let Some(ref field) = err.field {
+        failure.insert("field".to_string(), Value::from(field.as_str()));
+    }
+    failure.insert("kind".to_string(), Value::from(err.error.kind()));
+    failure.insert("code".to_string(), Value::from(err.error.code()));
+    if !params.is_empty() {
+        failure.insert("params".to_string(), Value::Object(params));
+    }
+    Value::Object(failure)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{FieldError, ValidationError, ValidationErrors};
+    use crate::reporter::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payloads_without_sensitive_params() {
+        let mut errors = ValidationErrors::new();
+        errors.push(
+            FieldError::new("plan", ValidationError::FieldMismatch("one_of".to_string()))
+                .with_param("values", vec!["basic", "business"])
+                .with_param("suggestion", "business")
+                .with_param("value", "buisness"),
+        );
+        errors.push(FieldError::unattached(ValidationError::invalid_transition(
+            "order",
+            "SHIPPED",
+            "DRAFT",
+            &["RETURNED"],
+        )));
+        errors.push(
+            FieldError::new("ssn", ValidationError::FieldMismatch("custom".to_string()))
+                .with_param("input", "078-05-1120"),
+        );
+
+        let expected = json!([
+            { "field": "plan", "kind": "field_mismatch", "code": "one_of",
+              "params": { "values": ["basic", "business"] } },
+            { "kind": "invalid_transition", "code": "invalid_transition" },
+            { "field": "ssn", "kind": "field_mismatch", "code": "custom" },
+        ]);
+        let context = Value::Object(to_context(&errors));
+        assert_eq!(expected, context["errors"]);
+        assert_eq!(
+            json!(["one_of", "invalid_transition", "custom"]),
+            context["codes"]
+        );
+
+        let breadcrumb = to_breadcrumb(&errors);
+        assert_eq!(Some(&expected), breadcrumb.data.get("errors"));
+        assert_eq!(
+            "plan: one_of; invalid_transition; ssn: custom",
+            breadcrumb.message
+        );
+        let serialized = serde_json::to_string(&breadcrumb).unwrap();
+        assert!(serialized.starts_with(r#"{"type":"default","category":"validation""#));
+        assert!(!serialized.contains("buisness") && !serialized.contains("078-05-1120"));
+    }
+}
@@ -0,0 +1,641 @@
+//! schema
+//!
+//! Module containing the schema driven validator: rules described at runtime by
+//! `FieldConstraintDescription`s (typically the `describe_constraints()` of a derived struct)
+//! evaluated against a JSON payload
+//!
+//! On top of the base rules, a schema can carry per tenant `RuleOverrides`, resolved through
+//! the tenant of the `ValidationContext`. This lets a multi-tenant service relax or tighten
+//! limits per tenant (128 characters names for one, 64 for another) without touching the
+//! `#[validate]` attributes
+//!
+//! Rules limited to some API versions apply per the API version of the context. Without one
+//! the payload is taken as one of the latest version, rules with an `until_version` are skipped
+//!
+//! Rules that cannot be evaluated on JSON (`past`, `future`, `min_age`, `flags_of`, `signed`) are
+//! skipped, the rules of `serde_json::Value` fields (`json_type`, `max_depth`,
+//! `max_keys`, `required_keys`), `finite`, `fits_in`, `one_of`, `range` and
+//! `canonical_identifier` apply as they do in the derive. The UUID rules (`uuid_version`,
+//! `uuid_not_nil`) apply to the text of the UUID, and `length` to text, arrays and objects as
+//! it does to text and collections
+//!
+//! A rule this build cannot evaluate, unknown to it or needing a feature that is not enabled
+//! (see `capabilities()`), fails the validation with `rule_unavailable` before any rule runs,
+//! so builds with different features never disagree silently on the same payload
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::capabilities::RULE_UNAVAILABLE;
+use crate::context::ValidationContext;
+use crate::description::FieldConstraintDescription;
+use crate::error::ValidationError;
+use crate::validation::ValidationResult;
+
+/// Runtime changes to the rules of a `Schema`, typically for one tenant
+///
+/// # Example
+/// ```
+/// use crate::validation::schema::RuleOverrides;
+/// use crate::validation::FieldConstraintDescription;
+///
+/// let overrides = RuleOverrides::new()
+///     .param("name", "length", "max", 128)
+///     .add_rule(FieldConstraintDescription::new("vat", "not_null", None));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleOverrides {
+    params: HashMap<(String, String), BTreeMap<String, Value>>,
+    added: Vec<FieldConstraintDescription>,
+}
+
+impl RuleOverrides {
+    pub fn new() -> Self {
+        RuleOverrides::default()
+    }
+
+    /// Overrides a parameter of the rule `rule` of the field `field`
+    ///
+    /// # Arguments
+    ///
+    ///  * `field` - name of the field, as it is serialized
+    ///  * `rule` - error code of the rule
+    ///  * `name` - name of the parameter
+    ///  * `value` - value replacing the base one
+    pub fn param<V: Into<Value>>(mut self, field: &str, rule: &str, name: &str, value: V) -> Self {
+        self.params
+            .entry((field.to_string(), rule.to_string()))
+            .or_default()
+            .insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Adds a rule on top of the base rules
+    pub fn add_rule(mut self, rule: FieldConstraintDescription) -> Self {
+        self.added.push(rule);
+        self
+    }
+
+    fn apply(&self, rules: &mut Vec<FieldConstraintDescription>) {
+        for rule in rules.iter_mut() {
+            if let Some(params) = self.params.get(&(rule.field.clone(), rule.rule.clone())) {
+                for (name, value) in params {
+                    rule.params.insert(name.clone(), value.clone());
+                }
+            }
+        }
+        rules.extend(self.added.iter().cloned());
+    }
+}
+
+/// Validator driven by rules described at runtime
+///
+/// # Example
+/// ```
+/// use crate::validation::schema::{RuleOverrides, Schema};
+/// use crate::validation::{FieldConstraintDescription, ValidationContext};
+/// use serde_json::json;
+///
+/// // Typically Customer::describe_constraints() from the derive
+/// let base = vec![FieldConstraintDescription::new("name", "length", None).param("max", 64)];
+/// let schema = Schema::new(base)
+///     .with_tenant("tenant-a", RuleOverrides::new().param("name", "length", "max", 128));
+///
+/// let payload = json!({ "name": "x".repeat(100) });
+/// assert!(schema.validate(&payload, &ValidationContext::default()).is_err());
+/// assert!(schema
+///     .validate(&payload, &ValidationContext::default().with_tenant("tenant-a"))
+///     .is_ok());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    base: Vec<FieldConstraintDescription>,
+    tenants: HashMap<String, RuleOverrides>,
+}
+
+impl Schema {
+    /// Creates a new `Schema` out of the base rules
+    pub fn new(base: Vec<FieldConstraintDescription>) -> Self {
+        Schema {
+            base,
+            tenants: HashMap::new(),
+        }
+    }
+
+    /// Adds the `RuleOverrides` of a tenant, replacing any overrides it had before
+    pub fn with_tenant(mut self, tenant: &str, overrides: RuleOverrides) -> Self {
+        self.tenants.insert(tenant.to_string(), overrides);
+        self
+    }
+
+    /// Returns the rules of the base, with the overrides of the tenant applied if it has any
+    pub fn rules_for(&self, tenant: Option<&str>) -> Vec<FieldConstraintDescription> {
+        let mut rules = self.base.clone();
+        if let Some(overrides) = tenant.and_then(|t| self.tenants.get(t)) {
+            overrides.apply(&mut rules);
+        }
+        rules
+    }
+
+    /// Returns the rules of the base and of every tenant this build cannot evaluate, per
+    /// `is_rule_available`, for services checking a schema when they load it
+    ///
+    /// # Example
+    /// ```
+    /// use crate::validation::schema::{RuleOverrides, Schema};
+    /// use crate::validation::FieldConstraintDescription;
+    ///
+    /// let schema = Schema::new(vec![FieldConstraintDescription::new("name", "length", None)])
+    ///     .with_tenant(
+    ///         "tenant-a",
+    ///         RuleOverrides::new().add_rule(FieldConstraintDescription::new("name", "palindrome", None)),
+    ///     );
+    ///
+    /// let unavailable = schema.unavailable_rules();
+    /// assert_eq!(1, unavailable.len());
+    /// assert_eq!("palindrome", unavailable[0].rule);
+    /// ```
+    pub fn unavailable_rules(&self) -> Vec<FieldConstraintDescription> {
+        let tenant_rules = self
+            .tenants
+            .values()
+            .flat_map(|overrides| overrides.added.iter());
+        self.base
+            .iter()
+            .chain(tenant_rules)
+            .filter(|rule| !crate::is_rule_available(rule))
+            .cloned()
+            .collect()
+    }
+
+    /// Validates the JSON payload against the rules resolved for the tenant of the context
+    ///
+    /// Fails with `InvalidState("rule_unavailable")` before any rule runs when one of them
+    /// cannot be evaluated by this build
+    ///
+    /// # Arguments
+    ///
+    ///  * `payload` - JSON object to validate, rules apply to its top level keys
+    ///  * `ctx` - `ValidationContext` providing the tenant and the locale
+    pub fn validate(&self, payload: &Value, ctx: &ValidationContext) -> ValidationResult<()> {
+        let rules = self.applicable_rules(ctx);
+        if rules.iter().any(|rule| !crate::is_rule_available(rule)) {
+            return Err(ValidationError::InvalidState(RULE_UNAVAILABLE.to_string()));
+        }
+        for rule in rules {
+            let value = payload.get(&rule.field).unwrap_or(&Value::Null);
+            if !evaluate(&rule, value, ctx) {
+                return Err(ValidationError::FieldMismatch(rule.rule));
+            }
+        }
+        Ok(())
+    }
+
+    // Rules resolved for the tenant of the context that apply to its API version
+    pub(crate) fn applicable_rules(
+        &self,
+        ctx: &ValidationContext,
+    ) -> Vec<FieldConstraintDescription> {
+        let mut rules = self.rules_for(ctx.tenant());
+        rules.retain(|rule| {
+            // The condition of a `when` is Rust over the struct, it cannot be checked on JSON
+            if rule.when.is_some() {
+                return false;
+            }
+            match (ctx.api_version(), rule.versions) {
+                (Some(api_version), _) => rule.applies_to(api_version),
+                (None, Some(versions)) => versions.until.is_none(),
+                (None, None) => true,
+            }
+        });
+        rules
+    }
+
+    /// Serializes the value to JSON and validates it with `validate`
+    pub fn validate_serialized<T: Serialize>(
+        &self,
+        value: &T,
+        ctx: &ValidationContext,
+    ) -> ValidationResult<()> {
+        match serde_json::to_value(value) {
+            Ok(payload) => self.validate(&payload, ctx),
+            Err(e) => Err(ValidationError::FieldMismatch(e.to_string())),
+        }
+    }
+}
+
+// Returns whether or not the value passes the rule, rules that cannot be evaluated pass
+pub(crate) fn evaluate(
+    rule: &FieldConstraintDescription,
+    value: &Value,
+    ctx: &ValidationContext,
+) -> bool {
+    let string_param = |name: &str| rule.params.get(name).and_then(Value::as_str);
+    let usize_param = |name: &str| {
+        rule.params
+            .get(name)
+            .and_then(Value::as_u64)
+            .map(|n| n as usize)
+    };
+
+    if rule.rule == "not_null" {
+        return !value.is_null();
+    }
+    if value.is_null() {
+        return true;
+    }
+
+    match rule.rule.as_str() {
+        "regex_inline" => match (value.as_str(), string_param("pattern")) {
+            (Some(s), Some(pattern)) => Regex::new(pattern)
+                .map(|regex| crate::matches_regex(s, &regex))
+                .unwrap_or(false),
+            _ => false,
+        },
+        // Collections are described with `length` as well, arrays and objects by their elements
+        "length" => {
+            let (min, max) = (usize_param("min"), usize_param("max"));
+            match *value {
+                Value::String(ref s) => crate::is_length(s, min, max),
+                Value::Array(ref items) => crate::is_length(items, min, max),
+                Value::Object(ref map) => crate::is_length(map, min, max),
+                _ => false,
+            }
+        }
+        "date_format" => match (value.as_str(), string_param("style")) {
+            (Some(s), Some(style)) => crate::is_date_format(
+                s,
+                style,
+                string_param("locale").unwrap_or_else(|| ctx.environment().locale()),
+            ),
+            _ => false,
+        },
+        "number_format" => match value.as_str() {
+            Some(s) => crate::is_number_format(
+                s,
+                string_param("locale").unwrap_or_else(|| ctx.environment().locale()),
+            ),
+            None => false,
+        },
+        #[cfg(feature = "unicode")]
+        "canonical_identifier" => match value.as_str() {
+            Some(s) => crate::is_canonical_identifier(
+                s,
+                usize_param("max").unwrap_or(crate::CANONICAL_IDENTIFIER_MAX),
+            ),
+            None => false,
+        },
+        "fits_in" => match (value.as_str(), string_param("type")) {
+            (Some(s), Some(target)) => crate::fits_in(s, target).is_ok(),
+            _ => false,
+        },
+        "uuid_version" => match (
+            value.as_str().map(uuid::Uuid::parse_str),
+            usize_param("version"),
+        ) {
+            (Some(Ok(uuid)), Some(version)) => crate::is_uuid_version(&uuid, version),
+            _ => false,
+        },
+        "uuid_not_nil" => match value.as_str().map(uuid::Uuid::parse_str) {
+            Some(Ok(uuid)) => crate::is_uuid_not_nil(&uuid),
+            _ => false,
+        },
+        // Always true of JSON numbers, JSON has no `NaN` or infinity
+        "finite" => value.as_f64().is_some_and(f64::is_finite),
+        // Chars and dates are described as text, ISO 8601 dates compare as their text does
+        "range" => match (value.as_f64(), value.as_str()) {
+            (Some(n), _) => match (
+                range_bound(rule, "min", "exclusive_min", Value::as_f64),
+                range_bound(rule, "max", "exclusive_max", Value::as_f64),
+            ) {
+                (Some(min), Some(max)) => crate::is_within(&n, min.as_ref(), max.as_ref()),
+                _ => false,
+            },
+            (_, Some(s)) => match (
+                range_bound(rule, "min", "exclusive_min", Value::as_str),
+                range_bound(rule, "max", "exclusive_max", Value::as_str),
+            ) {
+                (Some(min), Some(max)) => crate::is_within(s, min, max),
+                _ => false,
+            },
+            _ => false,
+        },
+        "json_type" => match string_param("type") {
+            Some(json_type) => crate::is_json_type(value, json_type),
+            None => false,
+        },
+        "max_depth" => match usize_param("max_depth") {
+            Some(max_depth) => crate::is_json_max_depth(value, max_depth),
+            None => false,
+        },
+        "max_keys" => match usize_param("max_keys") {
+            Some(max_keys) => crate::is_json_max_keys(value, max_keys),
+            None => false,
+        },
+        "required_keys" => match rule.params.get("keys").and_then(Value::as_array) {
+            Some(keys) => {
+                let keys = keys.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+                crate::has_json_keys(value, &keys)
+            }
+            None => false,
+        },
+        "one_of" => match rule.params.get("values").and_then(Value::as_array) {
+            Some(values) => match value.as_str() {
+                Some(s) => {
+                    let values = values.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+                    crate::Collation::from_name(string_param("collation").unwrap_or("exact"))
+                        .is_some_and(|collation| crate::is_one_of(s, &values, collation))
+                }
+                None => values.contains(value),
+            },
+            None => false,
+        },
+        _ => true,
+    }
+}
+
+// Returns the bound of `range`, included, excluded or unbounded, `None` when the value cannot
+// be compared with it
+fn range_bound<'a, T>(
+    rule: &'a FieldConstraintDescription,
+    included: &str,
+    excluded: &str,
+    as_kind: fn(&'a Value) -> Option<T>,
+) -> Option<Bound<T>> {
+    match (rule.params.get(included), rule.params.get(excluded)) {
+        (Some(bound), _) => as_kind(bound).map(Bound::Included),
+        (None, Some(bound)) => as_kind(bound).map(Bound::Excluded),
+        (None, None) => Some(Bound::Unbounded),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::*;
+    use serde_json::json;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            FieldConstraintDescription::new("name", "not_null", None),
+            FieldConstraintDescription::new("name", "length", None)
+                .param("min", 1)
+                .param("max", 64),
+            FieldConstraintDescription::new("born", "past", None),
+        ])
+        .with_tenant(
+            "tenant-a",
+            RuleOverrides::new().param("name", "length", "max", 128),
+        )
+        .with_tenant(
+            "tenant-b",
+            RuleOverrides::new()
+                .param("name", "length", "max", 16)
+                .add_rule(FieldConstraintDescription::new("vat", "not_null", None)),
+        )
+    }
+
+    #[test]
+    fn test_rules_for_tenant() {
+        let rules = schema().rules_for(Some("tenant-a"));
+        assert_eq!(Some(&json!(128)), rules[1].params.get("max"));
+        assert_eq!(Some(&json!(1)), rules[1].params.get("min"));
+        assert_eq!(
+            schema().rules_for(None),
+            schema().rules_for(Some("unknown"))
+        );
+    }
+
+    #[test]
+    fn test_validate_per_tenant() {
+        let payload = json!({ "name": "x".repeat(100), "born": "2000-01-01" });
+        let ctx = ValidationContext::default();
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            schema().validate(&payload, &ctx)
+        );
+        assert!(schema()
+            .validate(&payload, &ctx.clone().with_tenant("tenant-a"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_added_rule() {
+        let payload = json!({ "name": "short" });
+        let ctx = ValidationContext::default().with_tenant("tenant-b");
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("not_null".to_string())),
+            schema().validate(&payload, &ctx)
+        );
+        assert!(schema()
+            .validate(&json!({ "name": "short", "vat": "FR1" }), &ctx)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_not_null() {
+        let ctx = ValidationContext::default();
+        assert!(schema().validate(&json!({}), &ctx).is_err());
+        assert!(schema().validate(&json!({ "name": null }), &ctx).is_err());
+    }
+
+    #[test]
+    fn test_validate_json_rules() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("metadata", "json_type", None).param("type", "object"),
+            FieldConstraintDescription::new("metadata", "max_keys", None).param("max_keys", 2),
+            FieldConstraintDescription::new("metadata", "required_keys", None)
+                .param("keys", vec!["source"]),
+        ]);
+        let ctx = ValidationContext::default();
+
+        assert!(schema
+            .validate(&json!({ "metadata": { "source": "web" } }), &ctx)
+            .is_ok());
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("json_type".to_string())),
+            schema.validate(&json!({ "metadata": ["source"] }), &ctx)
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("required_keys".to_string())),
+            schema.validate(&json!({ "metadata": { "id": 1 } }), &ctx)
+        );
+    }
+
+    #[test]
+    fn test_validate_range() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("quantity", "range", None)
+                .param("min", 1)
+                .param("max", 99),
+            FieldConstraintDescription::new("shipped", "range", None).param("min", "2024-01-01"),
+        ]);
+        let ctx = ValidationContext::default();
+
+        assert!(schema
+            .validate(&json!({ "quantity": 99, "shipped": "2024-06-30" }), &ctx)
+            .is_ok());
+        assert!(schema.validate(&json!({ "quantity": 0 }), &ctx).is_err());
+        assert!(schema
+            .validate(&json!({ "shipped": "2023-12-31" }), &ctx)
+            .is_err());
+        assert!(schema
+            .validate(&json!({ "quantity": "ten" }), &ctx)
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_one_of() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("currency", "one_of", None)
+                .param("values", vec!["EUR", "USD"])
+                .param("collation", "case_fold"),
+            FieldConstraintDescription::new("priority", "one_of", None).param("values", vec![1, 2]),
+        ]);
+        let ctx = ValidationContext::default();
+
+        assert!(schema
+            .validate(&json!({ "currency": "eur", "priority": 2 }), &ctx)
+            .is_ok());
+        assert!(schema
+            .validate(&json!({ "currency": "GBP" }), &ctx)
+            .is_err());
+        assert!(schema.validate(&json!({ "priority": 3 }), &ctx).is_err());
+    }
+
+    #[test]
+    fn test_validate_skips_conditional_rules() {
+        let schema = Schema::new(vec![FieldConstraintDescription::new(
+            "body", "length", None,
+        )
+        .param("max", 2)
+        .when("self.sms")]);
+        assert!(schema
+            .validate(&json!({ "body": "long" }), &ValidationContext::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_unavailable_rule() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("name", "length", None).param("max", 2),
+            FieldConstraintDescription::new("name", "palindrome", None),
+        ]);
+        assert_eq!(
+            Err(ValidationError::InvalidState(
+                "rule_unavailable".to_string()
+            )),
+            schema.validate(&json!({ "name": "long" }), &ValidationContext::default())
+        );
+        assert_eq!(1, schema.unavailable_rules().len());
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn test_validate_rule_of_missing_feature() {
+        let schema = Schema::new(vec![FieldConstraintDescription::new(
+            "unit", "one_of", None,
+        )
+        .param("values", vec!["km"])
+        .param("collation", "nfkc")]);
+        assert_eq!(
+            Err(ValidationError::InvalidState(
+                "rule_unavailable".to_string()
+            )),
+            schema.validate(&json!({ "unit": "km" }), &ValidationContext::default())
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_validate_canonical_identifier() {
+        let schema = Schema::new(vec![FieldConstraintDescription::new(
+            "team",
+            "canonical_identifier",
+            None,
+        )
+        .param("max", 8)]);
+        let ctx = ValidationContext::default();
+
+        assert!(schema.validate(&json!({ "team": "billing" }), &ctx).is_ok());
+        assert!(schema
+            .validate(&json!({ "team": "Billing" }), &ctx)
+            .is_err());
+        assert!(schema
+            .validate(&json!({ "team": "billing-eu" }), &ctx)
+            .is_err());
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn test_validate_canonical_identifier_unavailable() {
+        let schema = Schema::new(vec![FieldConstraintDescription::new(
+            "team",
+            "canonical_identifier",
+            None,
+        )]);
+        assert_eq!(
+            Err(ValidationError::InvalidState(
+                "rule_unavailable".to_string()
+            )),
+            schema.validate(&json!({ "team": "billing" }), &ValidationContext::default())
+        );
+    }
+
+    #[test]
+    fn test_validate_length_of_collections() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("tags", "length", None)
+                .param("min", 1)
+                .param("max", 2),
+            FieldConstraintDescription::new("labels", "length", None).param("max", 1),
+        ]);
+        let ctx = ValidationContext::default();
+
+        assert!(schema
+            .validate(&json!({ "tags": ["a", "b"], "labels": { "a": 1 } }), &ctx)
+            .is_ok());
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            schema.validate(&json!({ "tags": [] }), &ctx)
+        );
+        assert!(schema
+            .validate(&json!({ "tags": ["a", "b", "c"] }), &ctx)
+            .is_err());
+        assert!(schema
+            .validate(
+                &json!({ "tags": ["a"], "labels": { "a": 1, "b": 2 } }),
+                &ctx
+            )
+            .is_err());
+        assert!(schema.validate(&json!({ "tags": 2 }), &ctx).is_err());
+    }
+
+    #[test]
+    fn test_validate_per_api_version() {
+        let schema = Schema::new(vec![
+            FieldConstraintDescription::new("name", "length", None)
+                .param("max", 8)
+                .versions(None, Some(1)),
+            FieldConstraintDescription::new("name", "length", None)
+                .param("max", 16)
+                .versions(Some(2), None),
+        ]);
+        let name = json!({ "name": "Ada Lovelace" });
+
+        assert!(schema
+            .validate(&name, &ValidationContext::default().with_api_version(1))
+            .is_err());
+        assert!(schema
+            .validate(&name, &ValidationContext::default().with_api_version(2))
+            .is_ok());
+        assert!(schema
+            .validate(&name, &ValidationContext::default())
+            .is_ok());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::{FieldError, ValidationErrors};
+use crate::plan::{PlannedRule, ValidationPlan};
+
+/// Outcome of a scored validation: how much of the weight of the rules that ran passed, along
+/// with the failures
+///
+/// Generated by the `FieldValidate` derive through `validate_scored()`, for pipelines that
+/// accept imperfect records but rank them. Each rule weighs 1 unless `#[validate]` sets a
+/// `weight = N`, and skipped rules do not count
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredValidation {
+    /// Weight of the rules that passed over the weight of the rules that ran, from `0.0` to
+    /// `1.0`, and `1.0` when no rule ran
+    pub score: f64,
+    pub passed_weight: u64,
+    pub total_weight: u64,
+    pub errors: ValidationErrors,
+}
+
+impl ScoredValidation {
+    /// Returns whether or not every rule that ran passed
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Scores the failures of a validation against its plan
+///
+/// A rule fails when there is an error of its code on its field, or when it is `nested` and
+/// there is any error under its field. With a `ValidationConfig` limiting the errors, only
+/// the failures kept count against the score
+///
+/// # Arguments
+///
+///  * `plan` - `ValidationPlan` of the value, from `explain()`
+///  * `errors` - `ValidationErrors` of the value, from `field_errors()`
+///
+/// # Example
+/// ```
+/// use crate::validation::{
+///     score_plan, FieldError, PlannedRule, PlannedRuleStatus, ValidationError,
+///     ValidationErrors, ValidationPlan,
+/// };
+///
+/// let plan = ValidationPlan::new(vec![
+///     PlannedRule::new("name", "not_null", PlannedRuleStatus::Run).weight(3),
+///     PlannedRule::new("nickname", "length", PlannedRuleStatus::Run),
+/// ]);
+/// let mut errors = ValidationErrors::new();
+/// errors.push(FieldError::new("nickname", ValidationError::field_mismatch("length")));
+///
+/// let scored = score_plan(&plan, errors);
+/// assert_eq!(0.75, scored.score);
+/// assert_eq!((3, 4), (scored.passed_weight, scored.total_weight));
+/// ```
+pub fn score_plan(plan: &ValidationPlan, errors: ValidationErrors) -> ScoredValidation {
+    let (passed_weight, total_weight) =
+        plan.to_run()
+            .fold((0, 0), |(passed, total), rule: &PlannedRule| {
+                let weight = u64::from(rule.weight.unwrap_or(1));
+                if errors.iter().any(|error| fails(rule, error)) {
+                    (passed, total + weight)
+                } else {
+                    (passed + weight, total + weight)
+                }
+            });
+    let score = if total_weight == 0 {
+        1.0
+    } else {
+        passed_weight as f64 / total_weight as f64
+    };
+
+    ScoredValidation {
+        score,
+        passed_weight,
+        total_weight,
+        errors,
+    }
+}
+
+// Whether or not the error is a failure of the rule
+fn fails(rule: &PlannedRule, error: &FieldError) -> bool {
+    let field = match error.field {
+        Some(ref field) => field,
+        None => return false,
+    };
+    if rule.rule == "nested" {
+        return field
+            .strip_prefix(rule.field.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with('['));
+    }
+    *field == rule.field && error.matches_code(&rule.rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{FieldError, ValidationError, ValidationErrors};
+    use crate::plan::{PlannedRule, PlannedRuleStatus, SkipReason, ValidationPlan};
+    use crate::score::score_plan;
+
+    #[test]
+    fn test_score_plan() {
+        let plan = ValidationPlan::new(vec![
+            PlannedRule::new("address", "nested", PlannedRuleStatus::Run).weight(2),
+            PlannedRule::new("addressee", "length", PlannedRuleStatus::Run),
+            PlannedRule::new(
+                "phone",
+                "length",
+                PlannedRuleStatus::Skipped(SkipReason::NoValue),
+            ),
+        ]);
+
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new(
+            "address.street",
+            ValidationError::field_mismatch("length"),
+        ));
+        errors.push(FieldError::new(
+            "phone",
+            ValidationError::field_mismatch("length"),
+        ));
+        let scored = score_plan(&plan, errors);
+        assert_eq!((1, 3), (scored.passed_weight, scored.total_weight));
+        assert!(!scored.is_valid());
+
+        let scored = score_plan(&ValidationPlan::default(), ValidationErrors::new());
+        assert_eq!(1.0, scored.score);
+        assert!(scored.is_valid());
+    }
+}
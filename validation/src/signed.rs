@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Payload the signature of a struct with `#[validate(signed(...))]` is computed over
+///
+/// The signed fields as a compact JSON object, keys sorted, so the sender and every service
+/// checking the signature compute it over the same bytes
+///
+/// # Example
+/// ```
+/// use crate::validation::SignedPayload;
+///
+/// let payload = SignedPayload::new()
+///     .with_field("currency", "EUR")
+///     .with_field("amount", &1250);
+///
+/// assert_eq!(
+///     Some(r#"{"amount":1250,"currency":"EUR"}"#.as_bytes().to_vec()),
+///     payload.to_bytes()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignedPayload {
+    fields: BTreeMap<String, Value>,
+    // Whether or not a field could not be serialized, no signature matches the payload then
+    invalid: bool,
+}
+
+impl SignedPayload {
+    pub fn new() -> Self {
+        SignedPayload::default()
+    }
+
+    /// Returns the payload with the field, serialized as JSON
+    pub fn with_field<T: Serialize + ?Sized>(mut self, name: &str, value: &T) -> Self {
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                self.fields.insert(name.to_string(), value);
+            }
+            Err(_) => self.invalid = true,
+        }
+        self
+    }
+
+    /// Returns the bytes of the payload, `None` when a field could not be serialized
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        if self.invalid {
+            return None;
+        }
+        serde_json::to_vec(&self.fields).ok()
+    }
+
+    /// Returns whether or not the verifier accepts the bytes of the payload, never when a
+    /// field could not be serialized
+    pub fn verify<F: FnOnce(&[u8]) -> bool>(&self, verifier: F) -> bool {
+        self.to_bytes().is_some_and(|bytes| verifier(&bytes))
+    }
+}
+
+/// Returns whether or not the bytes are the same, in a time that only depends on their lengths
+///
+/// Comparing an expected HMAC with `==` returns at the first difference, the time it takes
+/// tells an attacker how much of a forged signature is right
+///
+/// # Example
+/// ```
+/// use crate::validation::constant_time_eq;
+///
+/// assert_eq!(true, constant_time_eq(b"8f14e45f", b"8f14e45f"));
+/// assert_eq!(false, constant_time_eq(b"8f14e45f", b"8f14e45e"));
+/// assert_eq!(false, constant_time_eq(b"8f14e45f", b"8f14"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signed::SignedPayload;
+    use serde::ser::{Error, Serialize, Serializer};
+
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(S::Error::custom("unserializable"))
+        }
+    }
+
+    #[test]
+    fn test_signed_payload_invalid_field() {
+        let payload = SignedPayload::new()
+            .with_field("id", "evt_1")
+            .with_field("data", &Unserializable);
+
+        assert_eq!(None, payload.to_bytes());
+        assert!(!payload.verify(|_| true));
+    }
+}
@@ -0,0 +1,297 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::context::ValidationContext;
+use crate::error::ValidationErrors;
+use crate::validation::FieldValidation;
+
+/// Outcome of the validation of a record of a batch, as it is given to a `ReportSink`
+///
+/// Serialized as `{"index":1,"valid":false,"errors":[{"field":"name","kind":"field_mismatch",
+/// "code":"length"}]}`
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecordReport {
+    /// Index of the record in the batch
+    pub index: usize,
+    pub valid: bool,
+    pub errors: ValidationErrors,
+}
+
+impl RecordReport {
+    pub fn new(index: usize, errors: ValidationErrors) -> Self {
+        RecordReport {
+            index,
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
+/// Destination of the `RecordReport`s of `validate_batch_into`, persisting the outcome of each
+/// record as it is validated, typically for import jobs too long to keep every outcome in memory
+pub trait ReportSink {
+    /// Persists the report of a record, a failure stopping the batch
+    fn record(&mut self, report: &RecordReport) -> io::Result<()>;
+
+    /// Persists whatever the sink buffers, once the batch is done
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: ReportSink + ?Sized> ReportSink for &mut S {
+    fn record(&mut self, report: &RecordReport) -> io::Result<()> {
+        S::record(*self, report)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        S::flush(*self)
+    }
+}
+
+/// `ReportSink` keeping the reports in memory, for tests and small batches
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemorySink {
+    pub reports: Vec<RecordReport>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    /// Returns the reports of the records that failed
+    pub fn failures(&self) -> impl Iterator<Item = &RecordReport> {
+        self.reports.iter().filter(|report| !report.valid)
+    }
+}
+
+impl ReportSink for MemorySink {
+    fn record(&mut self, report: &RecordReport) -> io::Result<()> {
+        self.reports.push(report.clone());
+        Ok(())
+    }
+}
+
+/// `ReportSink` writing each report as a line of JSON, the JSON Lines format most log
+/// pipelines and `jq` read
+pub struct JsonLinesSink<W: Write> {
+    writer: W,
+    failures_only: bool,
+}
+
+impl JsonLinesSink<BufWriter<File>> {
+    /// Creates the file at the path, truncating it if it exists, and writes the reports to it
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(JsonLinesSink::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesSink {
+            writer,
+            failures_only: false,
+        }
+    }
+
+    /// Only writes the reports of the records that failed
+    pub fn failures_only(mut self) -> Self {
+        self.failures_only = true;
+        self
+    }
+
+    /// Returns the writer, once the batch is done
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> ReportSink for JsonLinesSink<W> {
+    fn record(&mut self, report: &RecordReport) -> io::Result<()> {
+        if self.failures_only && report.valid {
+            return Ok(());
+        }
+        serde_json::to_writer(&mut self.writer, report)?;
+        self.writer.write_all(b"\n")
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Counts of a batch streamed into a `ReportSink` by `validate_batch_into`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BatchSummary {
+    /// Number of records validated
+    pub validated: usize,
+    /// Number of records that failed validation
+    pub error_count: usize,
+    /// Index of the first record that was not validated once the error budget was spent
+    pub truncated_at: Option<usize>,
+}
+
+impl BatchSummary {
+    /// Returns whether or not every record validated passed
+    pub fn passed(&self) -> bool {
+        self.error_count == 0
+    }
+
+    /// Returns whether or not the validation stopped before the end of the batch
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_at.is_some()
+    }
+}
+
+/// Validates the fields of each record, streaming the report of each one into the sink and
+/// stopping once `max_errors` records failed, as `validate_batch` does
+///
+/// The records are read one at a time, so the batch can be an iterator over a file or a
+/// database cursor. The sink is flushed once the batch is done
+///
+/// # Arguments
+///
+/// * `records` - records to validate, any `IntoIterator` of `FieldValidation` values
+/// * `max_errors` - error budget, the validation stops once that many records failed
+/// * `ctx` - `ValidationContext` every record is validated in
+/// * `sink` - `ReportSink` receiving the report of each record
+///
+/// # Errors
+///
+/// The first failure of the sink, the records after it are not validated
+///
+/// # Example
+/// ```
+/// use crate::validation::{
+///     validate_batch_into, FieldValidation, JsonLinesSink, ValidationContext, ValidationError,
+///     ValidationResult,
+/// };
+///
+/// struct Row(i32);
+///
+/// impl FieldValidation for Row {
+///     fn validate_fields(&self) -> ValidationResult<()> {
+///         if self.0 >= 0 {
+///             Ok(())
+///         } else {
+///             Err(ValidationError::field_mismatch("negative"))
+///         }
+///     }
+/// }
+///
+/// let mut sink = JsonLinesSink::new(Vec::new()).failures_only();
+/// let rows = (-1..2).map(Row);
+/// let summary = validate_batch_into(rows, usize::MAX, &ValidationContext::default(), &mut sink)
+///     .unwrap();
+///
+/// assert_eq!((3, 1), (summary.validated, summary.error_count));
+/// assert_eq!(
+///     "{\"index\":0,\"valid\":false,\"errors\":[{\"kind\":\"field_mismatch\",\"code\":\"negative\"}]}\n",
+///     String::from_utf8(sink.into_inner()).unwrap()
+/// );
+/// ```
+pub fn validate_batch_into<I, S>(
+    records: I,
+    max_errors: usize,
+    ctx: &ValidationContext,
+    sink: &mut S,
+) -> io::Result<BatchSummary>
+where
+    I: IntoIterator,
+    I::Item: FieldValidation,
+    S: ReportSink + ?Sized,
+{
+    let mut summary = BatchSummary::default();
+
+    for (index, record) in records.into_iter().enumerate() {
+        if summary.error_count > 0 && summary.error_count >= max_errors {
+            summary.truncated_at = Some(index);
+            break;
+        }
+
+        let report = RecordReport::new(index, record.field_errors(ctx));
+        if !report.valid {
+            summary.error_count += 1;
+        }
+        summary.validated += 1;
+        sink.record(&report)?;
+    }
+
+    sink.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::context::ValidationContext;
+    use crate::error::ValidationError;
+    use crate::sink::*;
+    use crate::validation::ValidationResult;
+
+    struct Row(bool);
+
+    impl FieldValidation for Row {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            if self.0 {
+                Ok(())
+            } else {
+                Err(ValidationError::field_mismatch("row"))
+            }
+        }
+    }
+
+    struct Full;
+
+    impl ReportSink for Full {
+        fn record(&mut self, _: &RecordReport) -> io::Result<()> {
+            Err(io::Error::other("disk full"))
+        }
+    }
+
+    #[test]
+    fn test_memory_sink_budget() {
+        let ctx = ValidationContext::default();
+        let rows = [Row(true), Row(false), Row(false), Row(true)];
+
+        let mut sink = MemorySink::new();
+        let summary = validate_batch_into(&rows, 2, &ctx, &mut sink).unwrap();
+        assert_eq!(3, sink.reports.len());
+        assert_eq!(
+            vec![1, 2],
+            sink.failures().map(|r| r.index).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(3), summary.truncated_at);
+        assert!(!summary.passed());
+    }
+
+    #[test]
+    fn test_json_lines_sink_round_trip() {
+        let ctx = ValidationContext::default();
+        let mut sink = JsonLinesSink::new(Vec::new());
+        let summary = validate_batch_into(vec![Row(true), Row(false)], 5, &ctx, &mut sink).unwrap();
+        assert!(!summary.is_truncated());
+
+        let written = String::from_utf8(sink.into_inner()).unwrap();
+        let reports = written
+            .lines()
+            .map(|line| serde_json::from_str::<RecordReport>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(2, reports.len());
+        assert!(reports[0].valid);
+        assert_eq!(
+            Some("row"),
+            reports[1].errors.first().map(|e| e.error.code())
+        );
+    }
+
+    #[test]
+    fn test_sink_failure_stops_batch() {
+        let ctx = ValidationContext::default();
+        let err = validate_batch_into(vec![Row(true), Row(true)], 5, &ctx, &mut Full).unwrap_err();
+        assert_eq!("disk full", err.to_string());
+    }
+}
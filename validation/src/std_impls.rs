@@ -0,0 +1,472 @@
+//! `FieldValidation` for the primitive and std types, so generic code can require
+//! `T: FieldValidation` of any field
+//!
+//! Leaf types are always valid. Wrappers are validated as the value they hold, collections
+//! validate every element with the errors of an element prefixed by its index or key
+//!
+//! The errors of a collection are in a deterministic order: by index for sequences, by key for
+//! maps, hash maps included, and by their JSON for hash sets, so they are the same whatever the
+//! iteration order of a hashed collection. `validate_fields` fails with the first of them
+//!
+//! Smart pointers also forward `StateValidation` and `Validation`, so `Box<dyn Validation>`
+//! can be validated like the value it holds
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::fmt::Display;
+use std::hash::BuildHasher;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::context::ValidationContext;
+use crate::error::ValidationErrors;
+use crate::validation::{FieldValidation, StateValidation, Validation, ValidationResult};
+
+macro_rules! impl_always_valid {
+    ($($t:ty),*) => {
+        $(
+            impl FieldValidation for $t {
+                fn validate_fields(&self) -> ValidationResult<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_always_valid!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    str,
+    String,
+    Duration,
+    SystemTime
+);
+
+macro_rules! impl_deref {
+    ($($t:ident),*) => {
+        $(
+            impl<T: FieldValidation + ?Sized> FieldValidation for $t<T> {
+                fn validate_fields(&self) -> ValidationResult<()> {
+                    T::validate_fields(self)
+                }
+
+                fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+                    T::validate_fields_with(self, ctx)
+                }
+
+                fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+                    T::field_errors(self, ctx)
+                }
+            }
+
+            impl<T: StateValidation + ?Sized> StateValidation for $t<T> {
+                fn validate_state(&self) -> ValidationResult<()> {
+                    T::validate_state(self)
+                }
+            }
+
+            impl<T: Validation + ?Sized> Validation for $t<T> {
+                fn validate(&self) -> ValidationResult<()> {
+                    T::validate(self)
+                }
+            }
+        )*
+    };
+}
+
+// A boxed value is validated as the value itself, which allows recursive types
+impl_deref!(Box, Rc, Arc);
+
+impl<'a, T: FieldValidation + ToOwned + ?Sized> FieldValidation for Cow<'a, T> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        T::validate_fields(self)
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        T::validate_fields_with(self, ctx)
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        T::field_errors(self, ctx)
+    }
+}
+
+/// A missing value is valid, use `not_null` to require it
+impl<T: FieldValidation> FieldValidation for Option<T> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.as_ref().map_or(Ok(()), T::validate_fields)
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.as_ref()
+            .map_or(Ok(()), |value| value.validate_fields_with(ctx))
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        self.as_ref()
+            .map(|value| value.field_errors(ctx))
+            .unwrap_or_default()
+    }
+}
+
+// Errors of an element are prefixed with the given path, `None` keeping them as they are
+fn elements_errors<'a, T, I>(elements: I, ctx: &ValidationContext) -> ValidationErrors
+where
+    T: FieldValidation + ?Sized + 'a,
+    I: Iterator<Item = (Option<String>, &'a T)>,
+{
+    let mut errors = ValidationErrors::new();
+    for (path, element) in elements {
+        let element_errors = element.field_errors(ctx);
+        match path {
+            Some(path) => errors.extend_nested(&path, element_errors),
+            None => element_errors.into_iter().for_each(|err| errors.push(err)),
+        }
+    }
+    errors
+}
+
+macro_rules! impl_sequence {
+    ($($t:ident),*) => {
+        $(
+            impl<T: FieldValidation> FieldValidation for $t<T> {
+                fn validate_fields(&self) -> ValidationResult<()> {
+                    self.iter().try_for_each(T::validate_fields)
+                }
+
+                fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+                    self.iter().try_for_each(|value| value.validate_fields_with(ctx))
+                }
+
+                fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+                    elements_errors(
+                        self.iter()
+                            .enumerate()
+                            .map(|(index, value)| (Some(format!("[{}]", index)), value)),
+                        ctx,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+// Every element is validated, the errors of an element are prefixed by its index like `[0]`
+impl_sequence!(Vec, VecDeque, LinkedList);
+
+impl<T: FieldValidation> FieldValidation for [T] {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.iter().try_for_each(T::validate_fields)
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.iter()
+            .try_for_each(|value| value.validate_fields_with(ctx))
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        elements_errors(
+            self.iter()
+                .enumerate()
+                .map(|(index, value)| (Some(format!("[{}]", index)), value)),
+            ctx,
+        )
+    }
+}
+
+impl<T: FieldValidation, const N: usize> FieldValidation for [T; N] {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self[..].validate_fields()
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self[..].validate_fields_with(ctx)
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        self[..].field_errors(ctx)
+    }
+}
+
+// Elements of a set have no stable position, their errors are not prefixed. The errors of a
+// hash set are ordered by the JSON of the errors of each element, every element is validated
+// to find the first of them
+impl<T: FieldValidation, S: BuildHasher> FieldValidation for HashSet<T, S> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.validate_fields_with(&ValidationContext::default())
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.field_errors(ctx).into_result()
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        let mut elements = self
+            .iter()
+            .map(|value| value.field_errors(ctx))
+            .filter(|errors| !errors.is_empty())
+            .map(|errors| (errors.to_json(), errors))
+            .collect::<Vec<_>>();
+        elements.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut errors = ValidationErrors::new();
+        elements
+            .into_iter()
+            .flat_map(|(_, element_errors)| element_errors)
+            .for_each(|err| errors.push(err));
+        errors
+    }
+}
+
+impl<T: FieldValidation> FieldValidation for BTreeSet<T> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.iter().try_for_each(T::validate_fields)
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.iter()
+            .try_for_each(|value| value.validate_fields_with(ctx))
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        elements_errors(self.iter().map(|value| (None, value)), ctx)
+    }
+}
+
+// Values of a map are validated, the errors of a value are prefixed by its key like `[key]`.
+// The values of a hash map are validated in the order of their keys, as in a `BTreeMap`
+impl<K: Display + Ord, V: FieldValidation, S: BuildHasher> FieldValidation for HashMap<K, V, S> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        sorted_by_key(self)
+            .into_iter()
+            .try_for_each(|(_, value)| value.validate_fields())
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        sorted_by_key(self)
+            .into_iter()
+            .try_for_each(|(_, value)| value.validate_fields_with(ctx))
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        elements_errors(
+            sorted_by_key(self)
+                .into_iter()
+                .map(|(key, value)| (Some(format!("[{}]", key)), value)),
+            ctx,
+        )
+    }
+}
+
+fn sorted_by_key<K: Ord, V, S: BuildHasher>(map: &HashMap<K, V, S>) -> Vec<(&K, &V)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
+
+impl<K: Display, V: FieldValidation> FieldValidation for BTreeMap<K, V> {
+    fn validate_fields(&self) -> ValidationResult<()> {
+        self.values().try_for_each(V::validate_fields)
+    }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        self.values()
+            .try_for_each(|value| value.validate_fields_with(ctx))
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        elements_errors(
+            self.iter()
+                .map(|(key, value)| (Some(format!("[{}]", key)), value)),
+            ctx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{FieldError, ValidationError};
+    use crate::std_impls::*;
+
+    struct Positive(i32);
+
+    impl FieldValidation for Positive {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            if self.0 > 0 {
+                Ok(())
+            } else {
+                Err(ValidationError::FieldMismatch("positive".to_string()))
+            }
+        }
+
+        fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+            let mut errors = ValidationErrors::new();
+            if let Err(err) = self.validate_fields_with(ctx) {
+                errors.push(FieldError::new("value", err));
+            }
+            errors
+        }
+    }
+
+    // Always invalid, on the field of its name
+    #[derive(Eq, Hash, PartialEq)]
+    struct Labelled(&'static str);
+
+    impl FieldValidation for Labelled {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            Err(ValidationError::FieldMismatch("labelled".to_string()))
+        }
+
+        fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+            let mut errors = ValidationErrors::new();
+            if let Err(err) = self.validate_fields_with(ctx) {
+                errors.push(FieldError::new(self.0, err));
+            }
+            errors
+        }
+    }
+
+    // Always invalid, with the code of its name
+    #[derive(Eq, Hash, PartialEq)]
+    struct Code(&'static str);
+
+    impl FieldValidation for Code {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            Err(ValidationError::FieldMismatch(self.0.to_string()))
+        }
+
+        fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+            let mut errors = ValidationErrors::new();
+            if let Err(err) = self.validate_fields_with(ctx) {
+                errors.push(FieldError::new("value", err));
+            }
+            errors
+        }
+    }
+
+    fn requires_field_validation<T: FieldValidation + ?Sized>(value: &T) -> ValidationResult<()> {
+        value.validate_fields()
+    }
+
+    fn fields(errors: ValidationErrors) -> Vec<String> {
+        errors.into_iter().filter_map(|err| err.field).collect()
+    }
+
+    #[test]
+    fn test_leaf_types_valid() {
+        assert!(requires_field_validation(&1u8).is_ok());
+        assert!(requires_field_validation(&-1.5f64).is_ok());
+        assert!(requires_field_validation("").is_ok());
+        assert!(requires_field_validation(&String::new()).is_ok());
+        assert!(requires_field_validation(&vec![Some("a".to_string()), None]).is_ok());
+        assert!(requires_field_validation(&Arc::new(Cow::Borrowed("a"))).is_ok());
+    }
+
+    #[test]
+    fn test_sequences_prefixed_by_index() {
+        let ctx = ValidationContext::default();
+        let values = vec![Positive(1), Positive(0), Positive(-1)];
+
+        assert!(values.validate_fields().is_err());
+        assert_eq!(
+            vec!["[1].value", "[2].value"],
+            fields(values.field_errors(&ctx))
+        );
+        assert_eq!(vec!["[0].value"], fields([Positive(0)].field_errors(&ctx)));
+        assert_eq!(
+            vec!["[0].value"],
+            fields(VecDeque::from(vec![Positive(0)]).field_errors(&ctx))
+        );
+    }
+
+    #[test]
+    fn test_maps_prefixed_by_key() {
+        let mut values = BTreeMap::new();
+        values.insert("a", Positive(1));
+        values.insert("b", Positive(0));
+
+        assert_eq!(
+            vec!["[b].value"],
+            fields(values.field_errors(&ValidationContext::default()))
+        );
+    }
+
+    #[test]
+    fn test_hashed_collections_ordered() {
+        let ctx = ValidationContext::default();
+        let values = (0..32)
+            .map(|key| (key, Positive(key % 2)))
+            .collect::<HashMap<_, _>>();
+
+        let expected = (0..32)
+            .filter(|key| key % 2 == 0)
+            .map(|key| format!("[{}].value", key))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, fields(values.field_errors(&ctx)));
+
+        let mut values = HashSet::new();
+        values.insert(Labelled("b"));
+        values.insert(Labelled("a"));
+        values.insert(Labelled("c"));
+        assert_eq!(vec!["a", "b", "c"], fields(values.field_errors(&ctx)));
+    }
+
+    #[test]
+    fn test_hashed_collections_first_error() {
+        let ctx = ValidationContext::default();
+        // By key rather than by the text of the key, where `[10]` comes before `[2]`
+        let values = vec![(10, Code("a")), (2, Code("b")), (7, Code("c"))]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            vec!["[2].value", "[7].value", "[10].value"],
+            fields(values.field_errors(&ctx))
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("b".to_string())),
+            values.validate_fields()
+        );
+        assert_eq!(
+            values.field_errors(&ctx).into_result(),
+            values.validate_fields_with(&ctx)
+        );
+
+        let values = vec![Code("c"), Code("a"), Code("b")]
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("a".to_string())),
+            values.validate_fields()
+        );
+        assert_eq!(
+            values.field_errors(&ctx).into_result(),
+            values.validate_fields_with(&ctx)
+        );
+    }
+
+    #[test]
+    fn test_sets_not_prefixed() {
+        let mut values = HashSet::new();
+        values.insert(0u8);
+        assert!(values.validate_fields().is_ok());
+    }
+}
@@ -0,0 +1,363 @@
+use std::ops::Bound;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A point in time the temporal rules can compare against the time of the validation
+///
+/// Implemented for `SystemTime`, for the `chrono` types with the `chrono` feature and for the
+/// `time` types with the `time` feature, either or both
+pub trait Temporal {
+    /// Seconds since the Unix epoch, negative before it
+    fn unix_timestamp(&self) -> i64;
+}
+
+impl Temporal for SystemTime {
+    fn unix_timestamp(&self) -> i64 {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        }
+    }
+}
+
+impl<T: Temporal + ?Sized> Temporal for &T {
+    fn unix_timestamp(&self) -> i64 {
+        T::unix_timestamp(*self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Temporal for chrono::DateTime<Tz> {
+    fn unix_timestamp(&self) -> i64 {
+        self.timestamp()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Temporal for chrono::NaiveDateTime {
+    fn unix_timestamp(&self) -> i64 {
+        self.and_utc().timestamp()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Temporal for chrono::NaiveDate {
+    fn unix_timestamp(&self) -> i64 {
+        self.and_hms_opt(0, 0, 0)
+            .map(|d| d.and_utc().timestamp())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::OffsetDateTime {
+    fn unix_timestamp(&self) -> i64 {
+        time::OffsetDateTime::unix_timestamp(*self)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::PrimitiveDateTime {
+    fn unix_timestamp(&self) -> i64 {
+        self.assume_utc().unix_timestamp()
+    }
+}
+
+#[cfg(feature = "time")]
+impl Temporal for time::Date {
+    fn unix_timestamp(&self) -> i64 {
+        self.midnight().assume_utc().unix_timestamp()
+    }
+}
+
+/// A `Temporal` written as ISO 8601 text in attributes, like the bounds of `range`
+///
+/// Implemented for the dates, date times and offset date times of `chrono` and `time`, so the
+/// derive compares them the same whichever crate the field comes from
+pub trait ParseTemporal: Temporal + PartialOrd + Sized {
+    /// Parses the ISO 8601 text, `None` when it is not one of this type
+    fn parse_iso8601(text: &str) -> Option<Self>;
+}
+
+#[cfg(feature = "chrono")]
+impl ParseTemporal for chrono::NaiveDate {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ParseTemporal for chrono::NaiveDateTime {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ParseTemporal for chrono::DateTime<chrono::Utc> {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ParseTemporal for chrono::DateTime<chrono::FixedOffset> {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+#[cfg(feature = "time")]
+impl ParseTemporal for time::Date {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        time::Date::parse(
+            text,
+            &time::format_description::well_known::Iso8601::DEFAULT,
+        )
+        .ok()
+    }
+}
+
+#[cfg(feature = "time")]
+impl ParseTemporal for time::PrimitiveDateTime {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        time::PrimitiveDateTime::parse(
+            text,
+            &time::format_description::well_known::Iso8601::DEFAULT,
+        )
+        .ok()
+    }
+}
+
+#[cfg(feature = "time")]
+impl ParseTemporal for time::OffsetDateTime {
+    fn parse_iso8601(text: &str) -> Option<Self> {
+        time::OffsetDateTime::parse(text, &time::format_description::well_known::Rfc3339).ok()
+    }
+}
+
+/// Returns whether or not the value is within the bounds written in ISO 8601, each included,
+/// excluded or unbounded
+///
+/// A bound that does not parse as `T` puts every value out of range
+///
+/// # Arguments
+///
+/// * `value` - `ParseTemporal` to be considered
+/// * `min` - `Bound<&str>` lower bound
+/// * `max` - `Bound<&str>` upper bound
+pub fn is_within_temporal<T: ParseTemporal>(value: &T, min: Bound<&str>, max: Bound<&str>) -> bool {
+    let parse = |bound: Bound<&str>| match bound {
+        Bound::Included(bound) => T::parse_iso8601(bound).map(Bound::Included),
+        Bound::Excluded(bound) => T::parse_iso8601(bound).map(Bound::Excluded),
+        Bound::Unbounded => Some(Bound::Unbounded),
+    };
+
+    match (parse(min), parse(max)) {
+        (Some(min), Some(max)) => crate::is_within(value, min.as_ref(), max.as_ref()),
+        _ => false,
+    }
+}
+
+/// Returns whether or not the value is strictly before `now`
+///
+/// # Arguments
+///
+/// * `value` - `Temporal` to be considered
+/// * `now` - `SystemTime` of the validation, typically `ValidationContext::now()`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_past;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+///
+/// assert_eq!(true, is_past(&(now - Duration::from_secs(1)), now));
+/// assert_eq!(false, is_past(&now, now));
+/// ```
+pub fn is_past<T: Temporal>(value: &T, now: SystemTime) -> bool {
+    value.unix_timestamp() < now.unix_timestamp()
+}
+
+/// Returns whether or not the value is strictly after `now`
+///
+/// # Arguments
+///
+/// * `value` - `Temporal` to be considered
+/// * `now` - `SystemTime` of the validation, typically `ValidationContext::now()`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_future;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+///
+/// assert_eq!(true, is_future(&(now + Duration::from_secs(1)), now));
+/// assert_eq!(false, is_future(&now, now));
+/// ```
+pub fn is_future<T: Temporal>(value: &T, now: SystemTime) -> bool {
+    value.unix_timestamp() > now.unix_timestamp()
+}
+
+/// Returns whether or not at least `years` full years (UTC calendar) passed between the
+/// value and `now`, typically a birth date
+///
+/// # Arguments
+///
+/// * `value` - `Temporal` to be considered
+/// * `years` - minimum age in years
+/// * `now` - `SystemTime` of the validation, typically `ValidationContext::now()`
+///
+/// # Example
+/// ```
+/// use crate::validation::is_min_age;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// // 2000-01-01 and 2018-01-01, both midnight UTC
+/// let born = UNIX_EPOCH + Duration::from_secs(946_684_800);
+/// let eighteenth_birthday = UNIX_EPOCH + Duration::from_secs(1_514_764_800);
+///
+/// assert_eq!(true, is_min_age(&born, 18, eighteenth_birthday));
+/// assert_eq!(false, is_min_age(&born, 18, eighteenth_birthday - Duration::from_secs(1)));
+/// ```
+pub fn is_min_age<T: Temporal>(value: &T, years: u32, now: SystemTime) -> bool {
+    let (born_year, born_month, born_day) = civil_from_timestamp(value.unix_timestamp());
+    let (year, month, day) = civil_from_timestamp(now.unix_timestamp());
+
+    let mut age = year - born_year;
+    if (month, day) < (born_month, born_day) {
+        age -= 1;
+    }
+
+    age >= i64::from(years)
+}
+
+// Converts a Unix timestamp to a (year, month, day) UTC civil date
+// Howard Hinnant's `civil_from_days` algorithm
+fn civil_from_timestamp(timestamp: i64) -> (i64, u32, u32) {
+    let days = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::temporal::*;
+
+    #[test]
+    fn test_civil_from_timestamp() {
+        assert_eq!((1970, 1, 1), civil_from_timestamp(0));
+        assert_eq!((1969, 12, 31), civil_from_timestamp(-1));
+        assert_eq!((2000, 2, 29), civil_from_timestamp(951_782_400));
+        assert_eq!((2024, 12, 31), civil_from_timestamp(1_735_689_599));
+    }
+
+    #[test]
+    fn test_min_age_leap_day() {
+        // 2004-02-29 and 2022-02-28 / 2022-03-01, midnight UTC
+        let born = UNIX_EPOCH + std::time::Duration::from_secs(1_078_012_800);
+        let feb_28 = UNIX_EPOCH + std::time::Duration::from_secs(1_646_006_400);
+        let mar_1 = UNIX_EPOCH + std::time::Duration::from_secs(1_646_092_800);
+
+        assert!(!is_min_age(&born, 18, feb_28));
+        assert!(is_min_age(&born, 18, mar_1));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_min_age() {
+        let born = chrono::NaiveDate::from_ymd_opt(2004, 2, 29).unwrap();
+        let mar_1 = UNIX_EPOCH + std::time::Duration::from_secs(1_646_092_800);
+
+        assert!(is_min_age(&born, 18, mar_1));
+        assert!(is_past(&born, mar_1));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_within_temporal() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+
+        assert!(is_within_temporal(&date, Included("2024-02-29"), Unbounded));
+        assert!(!is_within_temporal(
+            &date,
+            Excluded("2024-02-29"),
+            Unbounded
+        ));
+        assert!(!is_within_temporal(
+            &date,
+            Included("2024-13-01"),
+            Unbounded
+        ));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_timestamps() {
+        let date = time::Date::from_calendar_date(2004, time::Month::February, 29).unwrap();
+        let at = date.with_hms(12, 0, 0).unwrap();
+
+        assert_eq!(1_078_012_800, date.unix_timestamp());
+        assert_eq!(1_078_056_000, Temporal::unix_timestamp(&at));
+        assert_eq!(1_078_056_000, Temporal::unix_timestamp(&at.assume_utc()));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_min_age() {
+        let born = time::Date::from_calendar_date(2004, time::Month::February, 29).unwrap();
+        let mar_1 = UNIX_EPOCH + std::time::Duration::from_secs(1_646_092_800);
+
+        assert!(is_min_age(&born, 18, mar_1));
+        assert!(is_past(&born, mar_1));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_within_temporal() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        let date = time::Date::from_calendar_date(2024, time::Month::February, 29).unwrap();
+        let at = date.with_hms(8, 30, 0).unwrap();
+
+        assert!(is_within_temporal(
+            &date,
+            Included("2024-01-01"),
+            Included("2024-12-31")
+        ));
+        assert!(!is_within_temporal(
+            &date,
+            Excluded("2024-02-29"),
+            Unbounded
+        ));
+        assert!(!is_within_temporal(
+            &date,
+            Included("2024-13-01"),
+            Unbounded
+        ));
+        assert!(is_within_temporal(
+            &at,
+            Unbounded,
+            Excluded("2024-02-29T08:30:01")
+        ));
+        assert!(is_within_temporal(
+            &at.assume_utc(),
+            Included("2024-02-29T09:00:00+01:00"),
+            Unbounded
+        ));
+    }
+}
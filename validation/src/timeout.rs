@@ -0,0 +1,174 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::context::ValidationContext;
+use crate::error::ValidationError;
+use crate::validation::{BoxFuture, ValidationResult};
+
+/// Code of the error of a rule waiting on I/O for longer than the rule timeout of its context
+pub const VALIDATION_TIMEOUT: &str = "validation_timeout";
+
+/// Timer of the async runtime of the service, used to bound the rules waiting on I/O
+///
+/// The library depends on no runtime, the service hands the sleep of its own, like
+/// `tokio::time::sleep`
+pub trait Timer: Send + Sync {
+    /// Returns a future completing once the duration has elapsed
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Awaits the rule, failing with `InvalidState("validation_timeout")` when it is still waiting
+/// once the rule timeout of the context has elapsed
+///
+/// Without a rule timeout in the context the rule is awaited as long as it takes. Without a
+/// `Timer` in the environment it cannot be bounded: a warning is logged and the rule is
+/// awaited as long as it takes
+///
+/// # Arguments
+///
+/// * `ctx` - `ValidationContext` holding the rule timeout and the timer
+/// * `rule` - future of the rule, typically a store or a service being queried
+///
+/// # Example
+/// ```
+/// use crate::validation::{with_rule_timeout, BoxFuture, Timer, ValidationContext, ValidationEnvironment};
+/// use std::future::Future;
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll, Waker};
+/// use std::time::Duration;
+///
+/// // Timer whose every sleep has already elapsed
+/// struct Elapsed;
+///
+/// impl Timer for Elapsed {
+///     fn sleep(&self, _: Duration) -> BoxFuture<'static, ()> {
+///         Box::pin(async {})
+///     }
+/// }
+///
+/// let ctx = ValidationContext::new(ValidationEnvironment::new().with_timer(Arc::new(Elapsed)))
+///     .with_rule_timeout(Duration::from_millis(200));
+/// let mut check = Box::pin(with_rule_timeout(&ctx, std::future::pending::<bool>()));
+///
+/// match check.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+///     Poll::Ready(Err(err)) => assert_eq!("validation_timeout", err.code()),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub async fn with_rule_timeout<F: Future>(
+    ctx: &ValidationContext,
+    rule: F,
+) -> ValidationResult<F::Output> {
+    let timeout = match ctx.rule_timeout() {
+        Some(timeout) => timeout,
+        None => return Ok(rule.await),
+    };
+    match ctx.environment().timer() {
+        Some(timer) => Race {
+            rule: Box::pin(rule),
+            sleep: timer.sleep(timeout),
+        }
+        .await
+        .ok_or_else(|| ValidationError::InvalidState(VALIDATION_TIMEOUT.to_string())),
+        None => {
+            log::warn!(
+                "rule timeout of {:?}: no Timer in the environment, the rule is not bounded",
+                timeout
+            );
+            Ok(rule.await)
+        }
+    }
+}
+
+// Completes with the output of the rule, or with `None` once the sleep completes first
+struct Race<F> {
+    rule: Pin<Box<F>>,
+    sleep: BoxFuture<'static, ()>,
+}
+
+impl<F: Future> Future for Race<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.rule.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        self.sleep.as_mut().poll(cx).map(|()| None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::ValidationEnvironment;
+    use crate::timeout::*;
+    use std::sync::Arc;
+    use std::task::Waker;
+
+    // Timer whose sleeps have elapsed once they are longer than a second
+    struct Threshold;
+
+    impl Timer for Threshold {
+        fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+            if duration < Duration::from_secs(1) {
+                Box::pin(std::future::pending())
+            } else {
+                Box::pin(async {})
+            }
+        }
+    }
+
+    fn poll<F: Future>(future: F) -> Poll<F::Output> {
+        Box::pin(future)
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    fn context(timeout: Option<u64>) -> ValidationContext {
+        let ctx =
+            ValidationContext::new(ValidationEnvironment::new().with_timer(Arc::new(Threshold)));
+        match timeout {
+            Some(seconds) => ctx.with_rule_timeout(Duration::from_secs(seconds)),
+            None => ctx,
+        }
+    }
+
+    #[test]
+    fn test_rule_timeout() {
+        let timeout = ValidationError::InvalidState(VALIDATION_TIMEOUT.to_string());
+
+        assert_eq!(
+            Poll::Ready(Err(timeout)),
+            poll(with_rule_timeout(
+                &context(Some(5)),
+                std::future::pending::<bool>()
+            ))
+        );
+        assert_eq!(
+            Poll::Ready(Ok(true)),
+            poll(with_rule_timeout(&context(Some(5)), async { true }))
+        );
+        assert_eq!(
+            Poll::Pending,
+            poll(with_rule_timeout(
+                &context(Some(0)),
+                std::future::pending::<bool>()
+            ))
+        );
+        assert_eq!(
+            Poll::Pending,
+            poll(with_rule_timeout(
+                &context(None),
+                std::future::pending::<bool>()
+            ))
+        );
+        assert_eq!(
+            Poll::Pending,
+            poll(with_rule_timeout(
+                &ValidationContext::default().with_rule_timeout(Duration::from_secs(5)),
+                std::future::pending::<bool>()
+            ))
+        );
+    }
+}
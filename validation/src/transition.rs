@@ -0,0 +1,80 @@
+use crate::error::ValidationError;
+use crate::validation::ValidationResult;
+
+/// Returns whether or not an entity can move from a state to another, per its transitions,
+/// failing with an `InvalidTransition` listing the states it can move to otherwise
+///
+/// Staying in the same state is not a transition, it always succeeds
+///
+/// # Arguments
+///
+/// * `entity` - name of the entity, like `order`
+/// * `from` - the state the entity is in
+/// * `to` - the state the entity moves to
+/// * `transitions` - the states each state can move to, a state not listed moving to none
+///
+/// # Example
+/// ```
+/// use crate::validation::{check_transition, ValidationError};
+///
+/// let transitions: &[(&str, &[&str])] = &[
+///     ("DRAFT", &["PLACED"]),
+///     ("PLACED", &["SHIPPED", "CANCELLED"]),
+///     ("SHIPPED", &["RETURNED"]),
+/// ];
+///
+/// assert!(check_transition("order", "PLACED", "SHIPPED", transitions).is_ok());
+/// assert_eq!(
+///     Err(ValidationError::invalid_transition("order", "SHIPPED", "DRAFT", &["RETURNED"])),
+///     check_transition("order", "SHIPPED", "DRAFT", transitions)
+/// );
+/// ```
+pub fn check_transition(
+    entity: &str,
+    from: &str,
+    to: &str,
+    transitions: &[(&str, &[&str])],
+) -> ValidationResult<()> {
+    let allowed = transitions
+        .iter()
+        .find(|(state, _)| *state == from)
+        .map_or(&[][..], |(_, allowed)| *allowed);
+    if from == to || allowed.contains(&to) {
+        Ok(())
+    } else {
+        Err(ValidationError::invalid_transition(
+            entity, from, to, allowed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{FieldError, ValidationErrorResponse, ValidationErrors};
+    use crate::transition::*;
+
+    #[test]
+    fn test_invalid_transition_serialization() {
+        let err =
+            check_transition("order", "RETURNED", "DRAFT", &[("DRAFT", &["PLACED"])]).unwrap_err();
+        assert_eq!("cannot move order from RETURNED to DRAFT", err.to_string());
+        assert_eq!(422, ValidationErrorResponse::from(err.clone()).error_code);
+
+        let mut errors = ValidationErrors::new();
+        errors.push(FieldError::new("status", err));
+        assert_eq!(
+            r#"[{"field":"status","kind":"invalid_transition","code":{"entity":"order","from":"RETURNED","to":"DRAFT","allowed":[]}}]"#,
+            errors.to_json()
+        );
+        assert_eq!(
+            Some(errors.clone()),
+            ValidationErrors::from_embedded(&errors.to_json())
+        );
+        assert_eq!(
+            ValidationError::InvalidState(
+                "status: cannot move order from RETURNED to DRAFT".to_string()
+            ),
+            ValidationError::from(errors)
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use uuid::Uuid;
+
+/// Returns whether or not the UUID is of the given version, like 4 for a random UUID
+///
+/// # Arguments
+///
+/// * `value` - `&Uuid` to be considered
+/// * `version` - the version the UUID must be of, from 1 to 5
+///
+/// # Example
+/// ```
+/// use crate::validation::is_uuid_version;
+/// use uuid::Uuid;
+///
+/// assert_eq!(true, is_uuid_version(&Uuid::new_v4(), 4));
+/// assert_eq!(false, is_uuid_version(&Uuid::new_v4(), 1));
+/// assert_eq!(false, is_uuid_version(&Uuid::nil(), 4));
+/// ```
+pub fn is_uuid_version(value: &Uuid, version: usize) -> bool {
+    value.get_version_num() == version
+}
+
+/// Returns whether or not the UUID is other than the nil UUID, the one with every bit unset
+/// that a missing value often defaults to
+///
+/// # Arguments
+///
+/// * `value` - `&Uuid` to be considered
+///
+/// # Example
+/// ```
+/// use crate::validation::is_uuid_not_nil;
+/// use uuid::Uuid;
+///
+/// assert_eq!(true, is_uuid_not_nil(&Uuid::new_v4()));
+/// assert_eq!(false, is_uuid_not_nil(&Uuid::nil()));
+/// assert_eq!(false, is_uuid_not_nil(&Uuid::default()));
+/// ```
+pub fn is_uuid_not_nil(value: &Uuid) -> bool {
+    !value.is_nil()
+}
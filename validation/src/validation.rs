@@ -1,7 +1,63 @@
-use crate::error::ValidationError;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::context::ValidationContext;
+use crate::error::{
+    FieldError, FieldSource, ValidationError, ValidationErrorResponse, ValidationErrors,
+};
 
 pub type ValidationResult<T> = std::result::Result<T, ValidationError>;
 
+/// Future returned by the async validation, boxed so the traits stay object safe
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Helpers to hand a `ValidationResult` to a service handler
+///
+/// `ValidationError` implements `std::error::Error`, so `?` already converts it to a
+/// `Box<dyn Error>` in handlers returning one
+///
+/// # Example
+/// ```
+/// use crate::validation::{ValidationError, ValidationResult, ValidationResultExt};
+///
+/// let result: ValidationResult<u32> = Err(ValidationError::FieldMismatch("Bad".to_string()));
+/// let response = result.into_response().unwrap_err();
+/// assert_eq!(400, response.error_code);
+///
+/// let result: ValidationResult<u32> = Err(ValidationError::InvalidState("Bad".to_string()));
+/// assert_eq!(None, result.log_and_ignore());
+///
+/// fn handler() -> Result<u32, Box<dyn std::error::Error>> {
+///     let result: ValidationResult<u32> = Ok(1);
+///     Ok(result?)
+/// }
+/// assert_eq!(1, handler().unwrap());
+/// ```
+pub trait ValidationResultExt<T> {
+    /// Converts the error to the `ValidationErrorResponse` to send back
+    #[doc(alias = "ok_or_response")]
+    fn into_response(self) -> std::result::Result<T, ValidationErrorResponse>;
+
+    /// Logs the error as a warning and drops it, for validations that must not fail a request
+    fn log_and_ignore(self) -> Option<T>;
+}
+
+impl<T> ValidationResultExt<T> for ValidationResult<T> {
+    fn into_response(self) -> std::result::Result<T, ValidationErrorResponse> {
+        self.map_err(ValidationErrorResponse::from)
+    }
+
+    fn log_and_ignore(self) -> Option<T> {
+        match self {
+            Ok(value) => Some(value),
+            Err(err) => {
+                log::warn!("ignored validation error: {}", err);
+                None
+            }
+        }
+    }
+}
+
 /// Trait that implements a validation routine for a system/subsystem
 ///
 /// The idea behind it is to
@@ -33,11 +89,19 @@ pub type ValidationResult<T> = std::result::Result<T, ValidationError>;
 /// will be based on the passed by rules from the caller
 ///
 /// TODO: Update as lib is fixed and tweaked
+///
+/// <br>
+///
+/// # Trait objects
+///
+/// `Validation`, `StateValidation` and `FieldValidation` are object safe, and references and
+/// boxes of them implement them too, so heterogeneous payloads can be validated uniformly as
+/// `Box<dyn Validation>`, see `validate_each`
 #[cfg_attr(doc_fc, doc(cfg(any(feature = "full"))))]
 pub trait Validation: StateValidation + FieldValidation {
     fn validate(&self) -> ValidationResult<()>;
 }
-impl<T: Validation> Validation for &T {
+impl<T: Validation + ?Sized> Validation for &T {
     fn validate(&self) -> ValidationResult<()> {
         T::validate(*self)
     }
@@ -49,7 +113,7 @@ pub trait StateValidation {
     fn validate_state(&self) -> ValidationResult<()>;
 }
 
-impl<T: StateValidation> StateValidation for &T {
+impl<T: StateValidation + ?Sized> StateValidation for &T {
     fn validate_state(&self) -> ValidationResult<()> {
         T::validate_state(*self)
     }
@@ -60,10 +124,161 @@ pub trait FieldValidation {
     /// Typically this is custom per the structure's field, some structs will require
     /// different validation (or, none at all should the user wish it)
     fn validate_fields(&self) -> ValidationResult<()>;
+
+    /// Provides the same validation as `validate_fields`, with the rules reading the
+    /// environment (clock, locale, feature switches) from the given `ValidationContext`
+    ///
+    /// The derive implements both, `validate_fields` running with a default context. Manual
+    /// implementations that do not depend on the context can rely on this default
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        let _ = ctx;
+        self.validate_fields()
+    }
+
+    /// Provides every failure of the validation instead of the first one, each one with the
+    /// field it was found on
+    ///
+    /// The derive knows the field of every rule. Manual implementations can rely on this
+    /// default, reporting the error of `validate_fields_with` without a field
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        if let Err(err) = self.validate_fields_with(ctx) {
+            errors.push(FieldError::unattached(err));
+        }
+        errors
+    }
+
+    /// Provides the `(alias, name)` pairs of the fields that also deserialize under a serde
+    /// `alias`, see `ValidationErrors::attribute_to_aliases`
+    ///
+    /// The derive reads them from the `#[serde(alias = "...")]` of the fields. References and
+    /// boxes of a type have none of its aliases
+    fn field_aliases() -> &'static [(&'static str, &'static str)]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Provides the `(name, source)` pairs of the fields read from a part of the request, see
+    /// `ValidationErrors::attribute_to_sources`
+    ///
+    /// The derive reads them from the `#[validate(source = "...")]` of the fields and
+    /// attributes the errors of `field_errors` to them
+    fn field_sources() -> &'static [(&'static str, FieldSource)]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
 }
 
-impl<T: FieldValidation> FieldValidation for &T {
+impl<T: FieldValidation + ?Sized> FieldValidation for &T {
     fn validate_fields(&self) -> ValidationResult<()> {
         T::validate_fields(*self)
     }
+
+    fn validate_fields_with(&self, ctx: &ValidationContext) -> ValidationResult<()> {
+        T::validate_fields_with(*self, ctx)
+    }
+
+    fn field_errors(&self, ctx: &ValidationContext) -> ValidationErrors {
+        T::field_errors(*self, ctx)
+    }
+}
+
+/// Field validation including the rules waiting on I/O, like `recently_seen`
+///
+/// `field_errors` skips those rules, `field_errors_async` runs every other rule first and then
+/// them. The derive implements it for the structs that have any
+pub trait AsyncFieldValidation: FieldValidation + Sync {
+    /// Provides every failure of the validation, the rules waiting on I/O included
+    fn field_errors_async<'a>(
+        &'a self,
+        ctx: &'a ValidationContext,
+    ) -> BoxFuture<'a, ValidationErrors>;
+
+    /// Provides the first failure of `field_errors_async`
+    fn validate_fields_async<'a>(
+        &'a self,
+        ctx: &'a ValidationContext,
+    ) -> BoxFuture<'a, ValidationResult<()>> {
+        Box::pin(async move { self.field_errors_async(ctx).await.into_result() })
+    }
+}
+
+pub trait UpdateValidation {
+    /// Provides a `ValidationResult` of the validity of an update of a struct from its
+    /// previous value: fields that cannot change, counters that cannot decrease...
+    /// Invalid updates are reported as a `ValidationError::InvalidState`
+    fn validate_update(&self, previous: &Self) -> ValidationResult<()>;
+}
+
+impl<T: UpdateValidation> UpdateValidation for &T {
+    fn validate_update(&self, previous: &Self) -> ValidationResult<()> {
+        T::validate_update(*self, *previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::*;
+
+    struct Payload(bool);
+
+    impl StateValidation for Payload {
+        fn validate_state(&self) -> ValidationResult<()> {
+            if self.0 {
+                Ok(())
+            } else {
+                Err(ValidationError::InvalidState("payload".to_string()))
+            }
+        }
+    }
+
+    impl FieldValidation for Payload {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            Ok(())
+        }
+    }
+
+    impl Validation for Payload {
+        fn validate(&self) -> ValidationResult<()> {
+            self.validate_fields()?;
+            self.validate_state()
+        }
+    }
+
+    struct Empty;
+
+    impl FieldValidation for Empty {
+        fn validate_fields(&self) -> ValidationResult<()> {
+            Err(ValidationError::FieldMismatch("empty".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_dyn_field_validation() {
+        let values: Vec<Box<dyn FieldValidation>> = vec![Box::new(Payload(false)), Box::new(Empty)];
+        assert!(values[0].validate_fields().is_ok());
+        assert!(values[1].validate_fields().is_err());
+        assert!(values.validate_fields().is_err());
+
+        let value: &dyn FieldValidation = &Empty;
+        assert_eq!(1, value.field_errors(&ValidationContext::default()).len());
+    }
+
+    #[test]
+    fn test_dyn_validation() {
+        let values: Vec<Box<dyn Validation>> =
+            vec![Box::new(Payload(true)), Box::new(Payload(false))];
+        assert!(values[0].validate().is_ok());
+        assert_eq!(
+            Err(ValidationError::InvalidState("payload".to_string())),
+            values[1].validate()
+        );
+
+        let value: &dyn StateValidation = &Payload(false);
+        assert!(value.validate_state().is_err());
+    }
 }
@@ -0,0 +1,362 @@
+//! validator_compat
+//!
+//! Module containing the conversions between the errors of this crate and the ones of the
+//! `validator` crate, per the `validator` feature, so services can migrate one at a time while
+//! sharing the same error contracts
+//!
+//! The errors keep their codes, but for the rules the two crates name differently:
+//! `not_null` is `required` and `regex_inline` is `regex` in `validator`, see `CODE_NAMES`.
+//! Fields nest as they do in `validator`: `address.city` is the `city` of the `Struct` of
+//! `address`, `items[0].name` the `name` of the element 0 of the `List` of `items`. Errors
+//! attached to no field, or to an element rather than a field of it, are under `__all__`, the
+//! key `validator` uses for the errors of a whole struct
+//!
+//! The derive also accepts the names of the attributes of `validator` for the rules both crates
+//! have: `required` for `not_null` and `length(equal = N)` for `length(min = N, max = N)`.
+//! `length`, `range` and `nested` are written the same in both
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::Value;
+
+use crate::error::{FieldError, ValidationError, ValidationErrors};
+
+/// Codes of this crate along with the ones of the same rules in `validator`, for the rules
+/// named differently
+pub const CODE_NAMES: &[(&str, &str)] = &[("not_null", "required"), ("regex_inline", "regex")];
+
+/// Key of the errors attached to no field, as `validator` names it
+pub const ALL_FIELDS: &str = "__all__";
+
+// Param carrying the kind of errors other than a field mismatch, which `validator` does not have
+const KIND_PARAM: &str = "kind";
+// Param carrying the message of an error of `validator`, which `FieldError` does not have
+const MESSAGE_PARAM: &str = "message";
+
+/// Returns the code `validator` gives to the rule of the code of this crate
+///
+/// # Example
+/// ```
+/// use crate::validation::validator_compat;
+///
+/// assert_eq!("required", validator_compat::validator_code("not_null"));
+/// assert_eq!("length", validator_compat::validator_code("length"));
+/// ```
+pub fn validator_code(code: &str) -> &str {
+    CODE_NAMES
+        .iter()
+        .find(|(ours, _)| *ours == code)
+        .map_or(code, |(_, theirs)| theirs)
+}
+
+/// Returns the code of this crate for the rule of the code of `validator`
+///
+/// # Example
+/// ```
+/// use crate::validation::validator_compat;
+///
+/// assert_eq!("regex_inline", validator_compat::code_of_validator("regex"));
+/// assert_eq!("range", validator_compat::code_of_validator("range"));
+/// ```
+pub fn code_of_validator(code: &str) -> &str {
+    CODE_NAMES
+        .iter()
+        .find(|(_, theirs)| *theirs == code)
+        .map_or(code, |(ours, _)| ours)
+}
+
+/// The code converts per `CODE_NAMES`, the kind is the `kind` param unless it is a field
+/// mismatch. An `InvalidTransition` keeps its parts as params
+impl From<ValidationError> for validator::ValidationError {
+    fn from(err: ValidationError) -> Self {
+        let code = validator_code(err.code()).to_string();
+        let mut converted = validator::ValidationError {
+            code: Cow::Owned(code),
+            message: None,
+            params: HashMap::new(),
+        };
+        if let ValidationError::FieldMismatch(_) = err {
+            return converted;
+        }
+        converted
+            .params
+            .insert(Cow::Borrowed(KIND_PARAM), Value::from(err.kind()));
+        if let ValidationError::InvalidTransition(ref transition) = err {
+            for (name, value) in [
+                ("entity", &transition.entity),
+                ("from", &transition.from),
+                ("to", &transition.to),
+            ] {
+                converted
+                    .params
+                    .insert(Cow::Borrowed(name), Value::from(value.as_str()));
+            }
+            converted.params.insert(
+                Cow::Borrowed("allowed"),
+                Value::from(transition.allowed.clone()),
+            );
+        }
+        converted
+    }
+}
+
+/// A field mismatch unless the `kind` param says otherwise, the code converts per `CODE_NAMES`
+impl From<validator::ValidationError> for ValidationError {
+    fn from(err: validator::ValidationError) -> Self {
+        let param = |name: &str| {
+            err.params
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        };
+        let code = code_of_validator(&err.code).to_string();
+        match param(KIND_PARAM) {
+            "invalid_state" => ValidationError::InvalidState(code),
+            "invalid_transition" => {
+                let allowed = err
+                    .params
+                    .get("allowed")
+                    .and_then(Value::as_array)
+                    .map(|allowed| allowed.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                ValidationError::invalid_transition(
+                    param("entity"),
+                    param("from"),
+                    param("to"),
+                    &allowed,
+                )
+            }
+            _ => ValidationError::FieldMismatch(code),
+        }
+    }
+}
+
+/// Nests the errors per their field, their params along with them and the `message` param as
+/// the message
+///
+/// # Example
+/// ```
+/// use crate::validation::{FieldError, ValidationError, ValidationErrors};
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.push(FieldError::new("email", ValidationError::field_mismatch("not_null")));
+/// errors.push(FieldError::new("lines[1].sku", ValidationError::field_mismatch("length")));
+///
+/// let converted = validator::ValidationErrors::from(errors);
+/// assert_eq!("required", converted.field_errors()["email"][0].code);
+/// assert!(matches!(
+///     converted.errors()["lines"],
+///     validator::ValidationErrorsKind::List(_)
+/// ));
+/// ```
+impl From<ValidationErrors> for validator::ValidationErrors {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut converted = validator::ValidationErrors::new();
+        for field_error in errors {
+            let path = field_error
+                .field
+                .as_deref()
+                .map(parse_path)
+                .unwrap_or_default();
+            let mut error = validator::ValidationError::from(field_error.error);
+            for (name, value) in field_error.params {
+                match (name.as_str(), value) {
+                    (MESSAGE_PARAM, Value::String(message)) => {
+                        error.message = Some(Cow::Owned(message))
+                    }
+                    (_, value) => {
+                        error.params.insert(Cow::Owned(name), value);
+                    }
+                }
+            }
+            insert(&mut converted, &path, field_error.field.as_deref(), error);
+        }
+        converted
+    }
+}
+
+/// Flattens the errors into fields like `lines[1].sku`, ordered by field then as they were
+/// added, the errors under `__all__` attached to no field
+///
+/// # Example
+/// ```
+/// use crate::validation::{ValidationError, ValidationErrors};
+///
+/// let mut errors = validator::ValidationErrors::new();
+/// errors.add("email", validator::ValidationError::new("required"));
+///
+/// let converted = ValidationErrors::from(errors);
+/// assert_eq!(Some("email"), converted.iter().next().unwrap().field.as_deref());
+/// assert_eq!(Err(ValidationError::field_mismatch("not_null")), converted.into_result());
+/// ```
+impl From<validator::ValidationErrors> for ValidationErrors {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut converted = ValidationErrors::new();
+        flatten(errors, None, &mut converted);
+        converted
+    }
+}
+
+// Segment of the path of a field, a field of a struct or the index of an element
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+// Splits a path like `items[0].name`, keys of maps being fields of a struct in `validator`
+fn parse_path(field: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    for part in field.split('.').filter(|part| !part.is_empty()) {
+        let mut brackets = part.split('[');
+        if let Some(name) = brackets.next().filter(|name| !name.is_empty()) {
+            segments.push(Segment::Field(name.to_string()));
+        }
+        for key in brackets {
+            let key = key.trim_end_matches(']');
+            segments.push(match key.parse() {
+                Ok(index) => Segment::Index(index),
+                Err(_) => Segment::Field(key.to_string()),
+            });
+        }
+    }
+    segments
+}
+
+// Adds the error at the path. `validator` has no place for an error on an element, nor for a
+// field with errors of its own and of its fields, those go under `__all__` with their `field`
+fn insert(
+    errors: &mut validator::ValidationErrors,
+    path: &[Segment],
+    field: Option<&str>,
+    error: validator::ValidationError,
+) {
+    use validator::ValidationErrorsKind as Kind;
+
+    let misplaced = |errors: &mut validator::ValidationErrors,
+                     mut error: validator::ValidationError| {
+        if let Some(field) = field {
+            error
+                .params
+                .insert(Cow::Borrowed("field"), Value::from(field));
+        }
+        push_field(errors, ALL_FIELDS.to_string(), error);
+    };
+    let (name, rest) = match path {
+        [] => return push_field(errors, ALL_FIELDS.to_string(), error),
+        [Segment::Field(name), rest @ ..] => (name.clone(), rest),
+        // Only the top level of a batch is an element, named by its index
+        [Segment::Index(index), rest @ ..] => (index.to_string(), rest),
+    };
+    match rest {
+        [] => match errors.0.get_mut(name.as_str()) {
+            Some(Kind::Struct(nested)) => insert(nested, &[], field, error),
+            Some(Kind::List(_)) => misplaced(errors, error),
+            _ => push_field(errors, name, error),
+        },
+        [Segment::Index(index), rest @ ..] => {
+            let list = errors
+                .0
+                .entry(Cow::Owned(name))
+                .or_insert_with(|| Kind::List(BTreeMap::new()));
+            match *list {
+                Kind::List(ref mut elements) => {
+                    insert(elements.entry(*index).or_default(), rest, field, error)
+                }
+                _ => misplaced(errors, error),
+            }
+        }
+        rest => {
+            let nested = errors
+                .0
+                .entry(Cow::Owned(name))
+                .or_insert_with(|| Kind::Struct(Box::default()));
+            match *nested {
+                Kind::Struct(ref mut nested) => insert(nested, rest, field, error),
+                _ => misplaced(errors, error),
+            }
+        }
+    }
+}
+
+// Adds the error to the errors of the field, which has none of the nested kinds
+fn push_field(
+    errors: &mut validator::ValidationErrors,
+    name: String,
+    error: validator::ValidationError,
+) {
+    if let validator::ValidationErrorsKind::Field(ref mut field_errors) = *errors
+        .0
+        .entry(Cow::Owned(name))
+        .or_insert_with(|| validator::ValidationErrorsKind::Field(vec![]))
+    {
+        field_errors.push(error);
+    }
+}
+
+// Adds the errors, their fields prefixed by the path of the struct they were found in
+fn flatten(
+    errors: validator::ValidationErrors,
+    prefix: Option<&str>,
+    converted: &mut ValidationErrors,
+) {
+    let mut fields = errors.into_errors().into_iter().collect::<Vec<_>>();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (name, kind) in fields {
+        let path = match (prefix, name.as_ref()) {
+            (prefix, ALL_FIELDS) => prefix.map(str::to_string),
+            (Some(prefix), name) => Some(format!("{}.{}", prefix, name)),
+            (None, name) => Some(name.to_string()),
+        };
+        match kind {
+            validator::ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    converted.push(field_error(path.as_deref(), error));
+                }
+            }
+            validator::ValidationErrorsKind::Struct(nested) => {
+                flatten(*nested, path.as_deref(), converted)
+            }
+            validator::ValidationErrorsKind::List(elements) => {
+                for (index, element) in elements {
+                    let path = format!("{}[{}]", path.as_deref().unwrap_or_default(), index);
+                    flatten(*element, Some(&path), converted);
+                }
+            }
+        }
+    }
+}
+
+// Converts an error of `validator` found on the field, the `field` param of a misplaced one
+// being the field it was found on
+fn field_error(field: Option<&str>, mut error: validator::ValidationError) -> FieldError {
+    let misplaced = error.params.remove("field");
+    let field = match misplaced {
+        Some(Value::String(ref field)) => Some(field.as_str()),
+        _ => field,
+    };
+    // The params of the kind are part of the converted error
+    let kind_params: &[&str] = match error.params.get(KIND_PARAM).and_then(Value::as_str) {
+        Some("invalid_transition") => &[KIND_PARAM, "entity", "from", "to", "allowed"],
+        _ => &[KIND_PARAM],
+    };
+    let mut params = error
+        .params
+        .iter()
+        .filter(|(name, _)| !kind_params.contains(&name.as_ref()))
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect::<Vec<_>>();
+    if let Some(ref message) = error.message {
+        params.push((MESSAGE_PARAM.to_string(), Value::from(message.as_ref())));
+    }
+    let converted = ValidationError::from(error);
+    let mut field_error = match field {
+        Some(field) => FieldError::new(field, converted),
+        None => FieldError::unattached(converted),
+    };
+    for (name, value) in params {
+        field_error = field_error.with_param(&name, value);
+    }
+    field_error
+}
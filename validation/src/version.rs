@@ -0,0 +1,83 @@
+use crate::error::ValidationError;
+
+/// API versions a rule applies to, per `since_version` and `until_version` on `#[validate]`
+///
+/// Both ends are inclusive, a missing end leaves the range open on that side
+///
+/// # Example
+/// ```
+/// use crate::validation::ApiVersions;
+///
+/// let v1_only = ApiVersions::new(None, Some(1));
+/// assert_eq!(true, v1_only.contains(1));
+/// assert_eq!(false, v1_only.contains(2));
+/// assert_eq!(true, ApiVersions::new(Some(2), None).contains(5));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ApiVersions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u32>,
+}
+
+impl ApiVersions {
+    pub fn new(since: Option<u32>, until: Option<u32>) -> Self {
+        ApiVersions { since, until }
+    }
+
+    /// Returns whether or not the version is in the range
+    pub fn contains(&self, version: u32) -> bool {
+        self.since.is_none_or(|since| since <= version)
+            && self.until.is_none_or(|until| version <= until)
+    }
+}
+
+/// Returns the API version of a header value like `2` or `v2`, `None` when it is not one
+///
+/// # Example
+/// ```
+/// use crate::validation::parse_api_version;
+///
+/// assert_eq!(Some(2), parse_api_version("v2"));
+/// assert_eq!(Some(1), parse_api_version(" 1 "));
+/// assert_eq!(None, parse_api_version("latest"));
+/// assert_eq!(None, parse_api_version("v0"));
+/// ```
+pub fn parse_api_version(header: &str) -> Option<u32> {
+    let header = header.trim();
+    let version = header
+        .strip_prefix('v')
+        .or_else(|| header.strip_prefix('V'))
+        .unwrap_or(header);
+    version.parse().ok().filter(|version| *version > 0)
+}
+
+/// Returns the version of a schema to validate a request with, the one it asks for, or the
+/// latest when it asks for none
+///
+/// A version of 0 or above the latest fails with `InvalidState("api_version")`
+///
+/// # Arguments
+///
+/// * `requested` - the API version of the `ValidationContext`, if any
+/// * `latest` - the latest version of the schema, per `#[validation(version = N)]`
+///
+/// # Example
+/// ```
+/// use crate::validation::{negotiate_api_version, ValidationError};
+///
+/// assert_eq!(Ok(1), negotiate_api_version(Some(1), 2));
+/// assert_eq!(Ok(2), negotiate_api_version(None, 2));
+/// assert_eq!(
+///     Err(ValidationError::InvalidState("api_version".to_string())),
+///     negotiate_api_version(Some(3), 2)
+/// );
+/// ```
+pub fn negotiate_api_version(requested: Option<u32>, latest: u32) -> Result<u32, ValidationError> {
+    match requested {
+        None => Ok(latest),
+        Some(version) if version > 0 && version <= latest => Ok(version),
+        Some(_) => Err(ValidationError::InvalidState("api_version".to_string())),
+    }
+}
@@ -0,0 +1,585 @@
+//! container
+//!
+//! Module containing the options set on the struct itself through the `#[validation]`
+//! container attribute, as opposed to the `#[validate]` attributes of the fields, and the
+//! rules of the struct as a whole set through a `#[validate]` attribute on the struct
+
+use std::convert::TryFrom;
+
+use proc_macro2::Span;
+use syn::{parse_quote, spanned::Spanned};
+
+use crate::field_validation::Enforcement;
+use crate::lit::{lit_to_string, lit_to_u64};
+
+///Policy for keys captured by a `#[serde(flatten)]` map, the fields the struct does not know
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnknownFields {
+    //Unknown keys are accepted
+    Allow,
+    //Unknown keys are reported to the observer, the validation still succeeds
+    Warn,
+    //Unknown keys fail the validation with `unknown_field`
+    Deny,
+}
+
+impl UnknownFields {
+    pub fn from_name(name: &str) -> Option<UnknownFields> {
+        match name {
+            "allow" => Some(UnknownFields::Allow),
+            "warn_unknown" => Some(UnknownFields::Warn),
+            "deny_unknown" => Some(UnknownFields::Deny),
+            _ => None,
+        }
+    }
+}
+
+///How the rules waiting on I/O of the struct run, per `async_rules`, `max_concurrency` and
+///`async_failures`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AsyncStrategy {
+    //Whether or not the rules run at the same time rather than one after the other
+    pub concurrent: bool,
+    //Maximum number of rules running at once, when they run concurrently
+    pub max_concurrency: Option<u64>,
+    //Whether or not the first failure stops the rules not done yet
+    pub fail_fast: bool,
+}
+
+impl AsyncStrategy {
+    /// Generates the `JoinStrategy` the rules run with
+    pub fn quote(&self) -> proc_macro2::TokenStream {
+        let strategy = if self.concurrent {
+            let max_concurrency = match self.max_concurrency {
+                Some(max) => {
+                    let max = max as usize;
+                    quote!(::std::option::Option::Some(#max))
+                }
+                None => quote!(::std::option::Option::None),
+            };
+            quote!(_validation::JoinStrategy::concurrent(#max_concurrency))
+        } else {
+            quote!(_validation::JoinStrategy::sequential())
+        };
+
+        if self.fail_fast {
+            quote!(#strategy.with_fail_fast())
+        } else {
+            strategy
+        }
+    }
+}
+
+///Case convention of the names of the fields, per the `#[serde(rename_all = "...")]` of the
+///struct
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub fn from_name(name: &str) -> Option<RenameRule> {
+        match name {
+            "lowercase" => Some(RenameRule::Lower),
+            "UPPERCASE" => Some(RenameRule::Upper),
+            "PascalCase" => Some(RenameRule::Pascal),
+            "camelCase" => Some(RenameRule::Camel),
+            "snake_case" => Some(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnake),
+            "kebab-case" => Some(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the field as serde serializes it, the field being in snake case
+    pub fn apply(&self, field: &str) -> String {
+        match *self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                match pascal.chars().next() {
+                    Some(first) => {
+                        first.to_ascii_lowercase().to_string() + &pascal[first.len_utf8()..]
+                    }
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Finds the `#[serde(rename_all = "...")]` of the struct, the one of `serialize` when it is
+/// split in `rename_all(serialize = "...", deserialize = "...")`. Rules serde does not know are
+/// left for serde to report
+///
+/// # Arguments
+/// * `syntax` - the `DeriveInput` of the struct
+pub fn find_serde_rename_all(syntax: &syn::DeriveInput) -> Option<RenameRule> {
+    let mut rename_all = None;
+    for attr in syntax
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(serde))
+    {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { nested, .. })) => nested,
+            _ => continue,
+        };
+        for meta in &nested {
+            let name = match *meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("rename_all") => lit_to_string(lit),
+                syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                    ref path,
+                    ref nested,
+                    ..
+                })) if path.is_ident("rename_all") => nested.iter().find_map(|meta| match *meta {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        ref path,
+                        ref lit,
+                        ..
+                    })) if path.is_ident("serialize") => lit_to_string(lit),
+                    _ => None,
+                }),
+                _ => None,
+            };
+            if let Some(rule) = name.and_then(|name| RenameRule::from_name(&name)) {
+                rename_all = Some(rule);
+            }
+        }
+    }
+    rename_all
+}
+
+///Options of the `#[validation(...)]` container attribute
+#[derive(Debug)]
+pub struct ContainerOptions {
+    //Enforcement of the rules that do not set their own
+    pub enforcement: Enforcement,
+    //Path of the `validation` crate the generated code refers to, per `crate = "..."`
+    pub crate_path: syn::Path,
+    //Name of the raw twin struct generated along with its `TryFrom`, per `raw = "..."`
+    pub raw: Option<syn::Ident>,
+    //Policy for the keys of `#[serde(flatten)]` maps, per `unknown_fields = "..."`
+    pub unknown_fields: UnknownFields,
+    //Latest API version of the rules, per `version = N`, rules may then set the versions they
+    //apply to
+    pub version: Option<u32>,
+    //Whether or not the derive generates `invalid_examples()`, per `invalid_examples`
+    pub invalid_examples: bool,
+    //How the rules waiting on I/O run
+    pub async_strategy: AsyncStrategy,
+    //Whether the serde configuration of the struct is checked against its validation policy,
+    //its drifts being compile warnings or errors, per `strict_serde = "..."`
+    pub strict_serde: Option<Enforcement>,
+}
+
+impl Default for ContainerOptions {
+    fn default() -> Self {
+        ContainerOptions {
+            enforcement: Enforcement::Deny,
+            crate_path: parse_quote!(::validation),
+            raw: None,
+            unknown_fields: UnknownFields::Allow,
+            version: None,
+            invalid_examples: false,
+            async_strategy: AsyncStrategy::default(),
+            strict_serde: None,
+        }
+    }
+}
+
+impl ContainerOptions {
+    /// Wraps the generated items in an anonymous `const` importing the `validation` crate as
+    /// `_validation`, the name every generated path starts with, so the crate can be renamed
+    pub fn wrap_in_crate_scope(
+        &self,
+        tokens: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let crate_path = &self.crate_path;
+
+        quote!(
+            const _: () = {
+                #[allow(unused_imports)]
+                use #crate_path as _validation;
+                #tokens
+            };
+        )
+    }
+}
+
+/// Finds the `#[validation(...)]` options of the struct
+///
+/// # Arguments
+/// * `syntax` - the `DeriveInput` of the struct
+pub fn find_container_options(syntax: &syn::DeriveInput) -> ContainerOptions {
+    let mut options = ContainerOptions::default();
+
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(span, "Invalid attribute #[validation]: {}", msg);
+    };
+
+    for attr in syntax
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(validation))
+    {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { nested, .. })) => nested,
+            _ => error(attr.span(), "expected #[validation(...)]"),
+        };
+
+        for meta in &nested {
+            match *meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("enforcement") => {
+                    options.enforcement = enforcement_from_lit(lit, "#[validation]");
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("strict_serde") => {
+                    options.strict_serde = Some(enforcement_from_lit(lit, "#[validation]"));
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("crate") => {
+                    options.crate_path = match *lit {
+                        syn::Lit::Str(ref lit_str) => match lit_str.parse::<syn::Path>() {
+                            Ok(crate_path) => crate_path,
+                            Err(_) => error(lit.span(), "`crate` expects the path of a crate"),
+                        },
+                        _ => error(lit.span(), "`crate` expects a string literal"),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("raw") => {
+                    options.raw = match *lit {
+                        syn::Lit::Str(ref lit_str) => match lit_str.parse::<syn::Ident>() {
+                            Ok(raw) => Some(raw),
+                            Err(_) => error(lit.span(), "`raw` expects the name of a struct"),
+                        },
+                        _ => error(lit.span(), "`raw` expects a string literal"),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("version") => {
+                    options.version =
+                        match lit_to_u64(lit).and_then(|version| u32::try_from(version).ok()) {
+                            Some(version) if version > 0 => Some(version),
+                            _ => error(lit.span(), "`version` expects a positive integer"),
+                        };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("unknown_fields") => {
+                    options.unknown_fields =
+                        match lit_to_string(lit).and_then(|name| UnknownFields::from_name(&name)) {
+                            Some(unknown_fields) => unknown_fields,
+                            None => abort!(
+                                lit.span(),
+                                "Invalid attribute #[validation]: unknown `unknown_fields` policy";
+                                help = "expected one of: allow, warn_unknown, deny_unknown";
+                            ),
+                        };
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                    if path.is_ident("invalid_examples") =>
+                {
+                    options.invalid_examples = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("async_rules") => {
+                    options.async_strategy.concurrent = match lit_to_string(lit).as_deref() {
+                        Some("sequential") => false,
+                        Some("concurrent") => true,
+                        _ => abort!(
+                            lit.span(),
+                            "Invalid attribute #[validation]: unknown `async_rules` strategy";
+                            help = "expected one of: sequential, concurrent";
+                        ),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("max_concurrency") => {
+                    options.async_strategy.max_concurrency = match lit_to_u64(lit) {
+                        Some(max) if max > 0 => Some(max),
+                        _ => error(lit.span(), "`max_concurrency` expects a positive integer"),
+                    };
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) if path.is_ident("async_failures") => {
+                    options.async_strategy.fail_fast = match lit_to_string(lit).as_deref() {
+                        Some("collect") => false,
+                        Some("first") => true,
+                        _ => abort!(
+                            lit.span(),
+                            "Invalid attribute #[validation]: unknown `async_failures` policy";
+                            help = "expected one of: collect, first";
+                        ),
+                    };
+                }
+                _ => error(meta.span(), "unexpected option"),
+            }
+        }
+    }
+
+    if options.async_strategy.max_concurrency.is_some() && !options.async_strategy.concurrent {
+        error(
+            Span::call_site(),
+            "`max_concurrency` requires `async_rules = \"concurrent\"`",
+        );
+    }
+
+    options
+}
+
+/// Finds where the serde configuration of the struct drifts from a strict validation policy:
+/// unknown fields accepted, as neither `#[serde(deny_unknown_fields)]` nor
+/// `unknown_fields = "deny_unknown"` is set, and fields with rules that serde never
+/// deserializes, per their `#[serde(skip)]` or `#[serde(skip_deserializing)]`
+///
+/// # Arguments
+/// * `syntax` - the `DeriveInput` of the struct
+/// * `options` - the `#[validation(...)]` options of the struct
+pub fn find_serde_drift(
+    syntax: &syn::DeriveInput,
+    options: &ContainerOptions,
+) -> Vec<(Span, String)> {
+    let mut drift = vec![];
+
+    if !has_serde_flag(&syntax.attrs, &["deny_unknown_fields"])
+        && options.unknown_fields != UnknownFields::Deny
+    {
+        drift.push((
+            syntax.ident.span(),
+            format!(
+                "`{}` accepts unknown fields, add #[serde(deny_unknown_fields)] or deny them with `unknown_fields = \"deny_unknown\"`",
+                syntax.ident
+            ),
+        ));
+    }
+
+    if let syn::Data::Struct(syn::DataStruct { ref fields, .. }) = syntax.data {
+        for field in fields {
+            let validated = field
+                .attrs
+                .iter()
+                .any(|attr| attr.path == parse_quote!(validate));
+            if validated && has_serde_flag(&field.attrs, &["skip", "skip_deserializing"]) {
+                let name = field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default();
+                drift.push((
+                    field.span(),
+                    format!("field `{}` has rules but serde never deserializes it", name),
+                ));
+            }
+        }
+    }
+
+    drift
+}
+
+/// Returns whether or not one of the `#[serde(...)]` attributes sets one of the flags, like
+/// `flatten` or `skip`
+pub fn has_serde_flag(attrs: &[syn::Attribute], flags: &[&str]) -> bool {
+    attrs.iter().filter(|attr| attr.path == parse_quote!(serde)).any(|attr| {
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { ref nested, .. })) => nested.iter().any(|meta| {
+                matches!(*meta, syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if flags.iter().any(|flag| path.is_ident(flag)))
+            }),
+            _ => false,
+        }
+    })
+}
+
+///Rule `signed` of a struct, the signature field checked over the payload fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signed {
+    //Field holding the signature, the rule is reported on it
+    pub field: syn::Ident,
+    //Fields the signature is computed over
+    pub payload_fields: Vec<syn::Ident>,
+    //Path of the `fn(&[u8], &Signature, &ValidationContext) -> bool` checking the signature
+    pub verifier: String,
+}
+
+/// Finds the `#[validate(signed(field = "..", payload_fields(..), verifier = ".."))]` rule of
+/// the struct, the only rule a struct takes as a whole
+///
+/// # Arguments
+/// * `syntax` - the `DeriveInput` of the struct
+pub fn find_signed(syntax: &syn::DeriveInput) -> Option<Signed> {
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(span, "Invalid attribute #[validate] on the struct: {}", msg);
+    };
+
+    let mut signed = None;
+    for attr in syntax
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(validate))
+    {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { nested, .. })) => nested,
+            _ => error(attr.span(), "expected #[validate(signed(...))]"),
+        };
+
+        for meta in &nested {
+            let items = match *meta {
+                syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                    ref path,
+                    ref nested,
+                    ..
+                })) if path.is_ident("signed") => nested,
+                _ => error(meta.span(), "a struct only takes the rule `signed`"),
+            };
+            if signed.is_some() {
+                error(meta.span(), "`signed` is set twice");
+            }
+
+            let (mut field, mut payload_fields, mut verifier) = (None, None, None);
+            for item in items {
+                match *item {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        ref path,
+                        ref lit,
+                        ..
+                    })) if path.is_ident("field") => {
+                        field = match lit_to_string(lit)
+                            .map(|name| syn::parse_str::<syn::Ident>(&name))
+                        {
+                            Some(Ok(ident)) => Some(ident),
+                            _ => error(lit.span(), "`field` expects the name of a field"),
+                        };
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        ref path,
+                        ref lit,
+                        ..
+                    })) if path.is_ident("verifier") => {
+                        verifier = match lit_to_string(lit) {
+                            Some(path) if syn::parse_str::<syn::Path>(&path).is_ok() => Some(path),
+                            _ => error(lit.span(), "`verifier` expects the path of a function"),
+                        };
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::List(syn::MetaList {
+                        ref path,
+                        ref nested,
+                        ..
+                    })) if path.is_ident("payload_fields") => {
+                        payload_fields = Some(
+                            nested
+                                .iter()
+                                .map(|name| match *name {
+                                    syn::NestedMeta::Meta(syn::Meta::Path(ref path)) => {
+                                        match path.get_ident() {
+                                            Some(ident) => ident.clone(),
+                                            None => error(
+                                                name.span(),
+                                                "`payload_fields` expects the names of fields",
+                                            ),
+                                        }
+                                    }
+                                    _ => error(
+                                        name.span(),
+                                        "`payload_fields` expects the names of fields",
+                                    ),
+                                })
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                    _ => error(
+                        item.span(),
+                        "`signed` expects `field`, `payload_fields` and `verifier`",
+                    ),
+                }
+            }
+
+            signed = match (field, payload_fields, verifier) {
+                (Some(field), Some(payload_fields), Some(verifier)) if !payload_fields.is_empty() => Some(Signed {
+                    field,
+                    payload_fields,
+                    verifier,
+                }),
+                _ => error(
+                    meta.span(),
+                    "`signed` requires a `field`, at least one of the `payload_fields` and a `verifier`",
+                ),
+            };
+        }
+    }
+
+    signed
+}
+
+/// Returns the `Enforcement` named by the literal, aborting at it when it is not a known one
+///
+/// # Arguments
+/// * `lit` - the `Lit` naming the enforcement
+/// * `attribute` - the attribute being parsed, for the error message
+pub fn enforcement_from_lit(lit: &syn::Lit, attribute: &str) -> Enforcement {
+    match lit_to_string(lit).and_then(|name| Enforcement::from_name(&name)) {
+        Some(enforcement) => enforcement,
+        None => abort!(
+            lit.span(),
+            "Invalid attribute {}: unknown enforcement", attribute;
+            help = "expected one of: deny, warn";
+        ),
+    }
+}
@@ -0,0 +1,94 @@
+//! deserialize
+//!
+//! Module generating the `Deserialize` of `#[derive(DeserializeValidated)]`: serde derives
+//! the deserializer of a shadow struct with the same fields and `#[serde]` attributes, the
+//! struct is then built from it and only returned once its `FieldValidation` passes
+
+use syn::parse_quote;
+
+/// Generates the shadow struct and the `Deserialize` of the struct, to be scoped with
+/// `ContainerOptions::wrap_in_crate_scope`
+///
+/// # Arguments
+///
+/// * `syntax` - the `DeriveInput` of the struct
+/// * `fields` - the named fields of the struct
+pub fn create_validated_deserialize(
+    syntax: &syn::DeriveInput,
+    fields: &[syn::Field],
+) -> proc_macro2::TokenStream {
+    let identity = &syntax.ident;
+    let generics = &syntax.generics;
+    let where_clause = &syntax.generics.where_clause;
+    let (_, type_generics, _) = syntax.generics.split_for_impl();
+    let container_serde = serde_attributes(&syntax.attrs);
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let shadow_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let field_type = &field.ty;
+        let field_serde = serde_attributes(&field.attrs);
+
+        quote!(
+            #(#field_serde)*
+            #ident: #field_type
+        )
+    });
+
+    // The deserializer borrows for `'de`, on top of the generics of the struct
+    let mut deserialize_generics = syntax.generics.clone();
+    deserialize_generics.params.insert(0, parse_quote!('de));
+    {
+        let predicates = &mut deserialize_generics.make_where_clause().predicates;
+        predicates.push(parse_quote!(
+            Shadow #type_generics: _validation::__private::serde::Deserialize<'de>
+        ));
+        predicates.push(parse_quote!(
+            #identity #type_generics: _validation::FieldValidation
+        ));
+    }
+    let (deserialize_impl_generics, _, deserialize_where_clause) =
+        deserialize_generics.split_for_impl();
+
+    quote!(
+        #[derive(_validation::__private::serde_derive::Deserialize)]
+        #[serde(crate = "_validation::__private::serde")]
+        #(#container_serde)*
+        struct Shadow #generics #where_clause {
+            #(#shadow_fields),*
+        }
+
+        impl #deserialize_impl_generics _validation::__private::serde::Deserialize<'de> for #identity #type_generics #deserialize_where_clause {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: _validation::__private::serde::Deserializer<'de>,
+            {
+                let Shadow { #(#idents),* } =
+                    <Shadow #type_generics as _validation::__private::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                let value = #identity { #(#idents),* };
+                let errors = _validation::FieldValidation::field_errors(
+                    &value,
+                    &_validation::ValidationContext::default(),
+                );
+                if errors.is_empty() {
+                    Ok(value)
+                } else {
+                    Err(<D::Error as _validation::__private::serde::de::Error>::custom(
+                        errors.to_json(),
+                    ))
+                }
+            }
+        }
+    )
+}
+
+// Returns the `#[serde]` attributes, applying to the shadow struct as they do to the struct
+fn serde_attributes(attrs: &[syn::Attribute]) -> Vec<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(serde))
+        .collect()
+}
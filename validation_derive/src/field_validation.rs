@@ -5,12 +5,82 @@
 
 use crate::types::ValidationType;
 
+///How a failure of a rule is handled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Enforcement {
+    //The failure is an error of the validation
+    Deny,
+    //The failure is only reported to the observer, the validation still succeeds
+    Warn,
+}
+
+impl Enforcement {
+    pub fn from_name(name: &str) -> Option<Enforcement> {
+        match name {
+            "deny" => Some(Enforcement::Deny),
+            "warn" => Some(Enforcement::Warn),
+            _ => None,
+        }
+    }
+}
+
 ///Helper struct to allow generation of a new validation rule
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FieldValidation {
     pub code: String,
     pub message: Option<String>,
     pub validator: ValidationType,
+    //Enforcement set on the `#[validate]` attribute of the rule, if any
+    pub enforcement: Option<Enforcement>,
+    //Deprecation set on the `#[validate]` attribute of the rule, if any
+    pub deprecation: Option<Deprecation>,
+    //Condition set on the `#[validate]` attribute of the rule with `when`, if any
+    pub condition: Option<Box<Condition>>,
+    //Order set on the `#[validate]` attribute of the rule with `order = N`, if any
+    pub order: Option<u64>,
+    //Weight set on the `#[validate]` attribute of the rule with `weight = N`, if any, for
+    //`validate_scored()`
+    pub weight: Option<u32>,
+    //API versions set on the `#[validate]` attribute of the rule with `since_version` and
+    //`until_version`, if any
+    pub versions: Option<Versions>,
+    //Groups set on the `#[validate]` attribute of the rule with `groups(...)`, labels for the
+    //tools reading the rules
+    pub groups: Vec<String>,
+    //Predicate of the `cfg(...)` set on the `#[validate]` attribute of the rule, if any, the
+    //rule is only compiled into the builds it holds for
+    pub cfg: Option<String>,
+}
+
+///Condition over `self` guarding a rule, per `when = "..."`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    //The condition as written, for descriptions and plans
+    pub source: String,
+    pub expr: syn::Expr,
+}
+
+///API versions a rule applies to, both ends included
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Versions {
+    pub since: Option<u32>,
+    pub until: Option<u32>,
+    //Latest version of the struct, per `#[validation(version = N)]`, used without an API version
+    pub latest: u32,
+}
+
+impl Versions {
+    pub fn contains(&self, version: u32) -> bool {
+        self.since.is_none_or(|since| since <= version)
+            && self.until.is_none_or(|until| version <= until)
+    }
+}
+
+///Deprecation of a rule, per `deprecated(since = "...", note = "...")`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deprecation {
+    pub since: String,
+    pub note: Option<String>,
 }
 
 impl FieldValidation {
@@ -19,6 +89,14 @@ impl FieldValidation {
             code: validator.code().to_string(),
             validator,
             message: None,
+            enforcement: None,
+            deprecation: None,
+            condition: None,
+            order: None,
+            weight: None,
+            versions: None,
+            groups: vec![],
+            cfg: None,
         }
     }
 }
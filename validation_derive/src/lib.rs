@@ -11,20 +11,26 @@ extern crate syn;
 extern crate validation;
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use proc_macro2::Span;
 use proc_macro_error::proc_macro_error;
-use syn::export::ToTokens;
-use syn::{parse_quote, spanned::Spanned};
+use quote::ToTokens;
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned};
 
-use crate::field_validation::FieldValidation;
-use crate::lit::lit_to_string;
+use crate::container::{enforcement_from_lit, RenameRule, UnknownFields};
+use crate::field_validation::{Condition, Deprecation, Enforcement, FieldValidation, Versions};
+use crate::lit::{lit_to_string, lit_to_u64};
 use crate::quotation::FieldQuoter;
-use crate::types::ValidationType;
+use crate::raw::RawField;
+use crate::types::{LengthBound, RangeBound, RuleCost, ValidationType};
 
+mod container;
+mod deserialize;
 mod field_validation;
 mod lit;
 mod quotation;
+mod raw;
 mod types;
 
 /// Derives and generates a rule (or later, a series or rules) that
@@ -44,81 +50,1008 @@ mod types;
 ///
 /// <br>
 /// Refer to the individual rules that are part of the `validation` module for further rules
-#[proc_macro_derive(FieldValidate, attributes(validate))]
+/// <br>
+///
+/// # Enforcement
+///
+/// A failing rule is an error by default. With `enforcement = "warn"`, either on a `#[validate]`
+/// attribute for its rules or on the struct with `#[validation(enforcement = "warn")]` for every
+/// rule that does not set its own, failures are only reported to the `ValidationObserver` of the
+/// environment and the validation still succeeds. This allows rolling out new rules against
+/// production traffic before they become hard failures
+///
+/// # Rule order
+///
+/// Rules run in a documented order, which decides the error `validate_fields` reports and the
+/// order of `field_errors`, the same for every run whatever the values. Fields are validated in
+/// the order they are declared. Within a field, the rules with `order = N` on their
+/// `#[validate]` attribute run first, by ascending `N`. The others then run from the cheapest
+/// to the most expensive: rules checking the value as it is (`not_null`, `length`,
+/// `regex_inline`, the JSON rules), then the ones parsing it or reading the context (formats,
+/// `past`, `future`, `min_age`), then `nested`. Rules of the same cost run in the order they
+/// are declared. The errors of a collection follow the order of its elements, by index for
+/// sequences and by key for maps. Rules waiting on I/O run after every other rule, in the
+/// order they are declared
+///
+/// # Migrating from `validator`
+///
+/// The attributes of the `validator` crate for the rules both crates have are accepted as they
+/// are: `required` is `not_null`, `length(equal = N)` is `length(min = N, max = N)`, and
+/// `length`, `range` and `nested` are written the same. With the `validator` feature of
+/// `validation` the errors convert to and from the `ValidationErrors` of `validator`, see
+/// `validation::validator_compat`
+///
+/// # Associated consts
+///
+/// The bounds of `length` are integers or the path of a const as a string, read when the rule
+/// runs: `#[validate(length(max = "Self::MAX_NAME"))]` reads an associated const of the struct,
+/// and `length(max = "Username::MAX_LEN")` the one of a library type, so the DTOs using it
+/// honor the limits it ships with. The consts can be of any integer type
+///
+/// # Scoring
+///
+/// Besides `validate_fields`, the derive generates `validate_scored()` and
+/// `validate_scored_with(ctx)`, returning a `ScoredValidation`: the errors along with the
+/// weight of the rules that passed over the weight of the rules that ran, for data quality
+/// pipelines ranking imperfect records rather than rejecting them. Each rule weighs 1, or `N`
+/// with `weight = N` on its `#[validate]` attribute, and skipped rules do not count
+///
+/// # Conditional compilation
+///
+/// `cfg(feature = "strict")` on a `#[validate]` attribute compiles its rules only into the
+/// builds the predicate holds for, like the `#[cfg]` of an item. In the other builds they are
+/// neither checked nor described, so `#[validate(cfg(feature = "strict"), length(max = 32))]`
+/// tightens a limit in some builds only. Any `cfg` predicate is accepted, `all`, `any` and
+/// `not` included
+///
+/// # Async rules
+///
+/// `recently_seen(window = 600)` rejects a value seen less than `window` seconds before, per
+/// the `SeenStore` of the environment, with `InvalidState("recently_seen")` sent as `409`. The
+/// key is the value of the field, or the result of `key_fn = "path"` called with `&self`.
+/// Rules waiting on I/O like this one are skipped by `FieldValidation`: the derive implements
+/// `AsyncFieldValidation`, whose `field_errors_async` runs them after every other rule
+///
+/// Each of them waits for no longer than the rule timeout of the context, set with
+/// `ValidationContext::with_rule_timeout` and bounded by the `Timer` of the environment. A
+/// rule still waiting then fails with `InvalidState("validation_timeout")`, sent as `503`
+///
+/// The rules run one after the other by default. With
+/// `#[validation(async_rules = "concurrent")]` they run at the same time, no more than
+/// `max_concurrency = N` of them at once when it is set, so checks against several services
+/// do not wait on each other. With `async_failures = "first"` the first of them to fail stops
+/// the ones not done yet, `"collect"`, the default, waits for every one of them. Either way
+/// their errors are in the order the rules are declared
+///
+/// # Unknown fields
+///
+/// A map field with `#[serde(flatten)]` captures the keys no other field has. With
+/// `#[validation(unknown_fields = "deny_unknown")]` each of them fails with `unknown_field`,
+/// with `"warn_unknown"` they are reported to the `ValidationObserver`, and `"allow"`, the
+/// default, accepts them
+///
+/// # Sources
+///
+/// `#[validate(source = "header")]` tells the part of the request a field is read from:
+/// `"header"`, `"path"`, `"query"` or `"body"`. The errors of the field then carry it, and
+/// `FieldError::location()` attributes them to that part, like `header:X-Tenant-Id` or
+/// `body:/address/city`, so clients show them where the value was sent. The sources are listed
+/// by `FieldValidation::field_sources()`. A field without rules takes the `source` alone
+///
+/// # Strict serde
+///
+/// With `#[validation(strict_serde = "warn")]` the derive checks the serde configuration of the
+/// struct against a strict validation policy: unknown fields must be rejected, by
+/// `#[serde(deny_unknown_fields)]` or `unknown_fields = "deny_unknown"`, and no field with rules
+/// may be `#[serde(skip)]` or `#[serde(skip_deserializing)]`. Each drift is a compile warning,
+/// or a compile error with `strict_serde = "deny"`, and the generated `serde_drift()` lists
+/// them for contract tests
+///
+/// # Serde aliases
+///
+/// Fields are named as they are serialized, per their `#[serde(rename)]` or the
+/// `#[serde(rename_all)]` of the struct, and the errors of a `#[serde(flatten)]` nested value
+/// are named as its fields are, at the top level. Their
+/// `#[serde(alias = "...")]` are listed by `FieldValidation::field_aliases()` and accepted by
+/// `assert_unchanged_except`, and `body::decode_body` attributes the errors of a field sent
+/// under an alias to that alias, so clients using a legacy name see the name they sent
+///
+/// # Raw twin
+///
+/// With `#[validation(raw = "RawOrder")]` the derive also generates `RawOrder`, the same fields
+/// each wrapped in an `Option` and deserializable, and `TryFrom<RawOrder>` for the struct. The
+/// conversion fails with the `ValidationErrors` of the missing fields (`not_null`) or of the
+/// rules of the struct, so a deserialized `RawOrder` can only become a valid `Order`
+///
+/// # Invalid examples
+///
+/// With `#[validation(invalid_examples)]` on a struct implementing `Default`, the derive also
+/// generates `invalid_examples()`: the `Default` with a single field changed to fail a rule,
+/// along with the code of the rule, for each rule such a value can be made for. `not_null`,
+/// `length`, `range` and `finite` on numbers, the UUID rules, and the text rules `fits_in`,
+/// `one_of`, `canonical_identifier` and the formats have one, contract tests can then check
+/// every code without hand-written fixtures
+///
+/// # Groups and reflection
+///
+/// `groups("signup", "admin")` on a `#[validate]` attribute labels its rules for the tools
+/// reading them, the rules run whatever their groups. The derive implements
+/// `validation::reflect::Reflect`, the stable introspection of the rules client SDK
+/// generators read, groups included
+///
+/// # API versions
+///
+/// With `#[validation(version = 2)]` a struct serves every API version up to 2, and a
+/// `#[validate]` attribute can limit its rules to some of them with `since_version = N` and
+/// `until_version = N`, both included. `#[validate(length(max = 64), until_version = 1)]` next to
+/// `#[validate(length(max = 128), since_version = 2)]` relaxes a limit in v2 while v1 clients
+/// keep theirs. The version is the `api_version` of the `ValidationContext`, typically the
+/// `parse_api_version` of a request header, the latest when it has none. A version the struct
+/// does not have fails with `InvalidState("api_version")`, and `explain()` plans the latest
+///
+/// # Signed payloads
+///
+/// `#[validate(signed(field = "signature", payload_fields(event, amount), verifier = "path"))]`
+/// on the struct checks the signature field against the `SignedPayload` of the payload fields,
+/// a compact JSON object of their serialized names and values. The verifier is a
+/// `fn(&[u8], &Signature, &ValidationContext) -> bool`, comparing an expected HMAC with
+/// `constant_time_eq`. A mismatch, or a `None` signature, fails the signature field with `signed`
+///
+/// # Canonical identifiers
+///
+/// `#[validate(canonical_identifier)]` holds a text field to the one form of an identifier
+/// every service stores: trimmed, lowercase, in NFC, of letters, digits and `_`, `-` or `.`
+/// starting and ending with a letter or a digit, and from 1 to 64 characters, or to `max`
+/// with `canonical_identifier(max = 32)`. Any of them failing is a single `canonical_identifier`
+/// error. The rule needs the `unicode` feature of `validation`, for the normalization tables
+#[proc_macro_derive(FieldValidate, attributes(validate, validation))]
 #[proc_macro_error]
 pub fn derive_field_validation(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let syntax = syn::parse(input).unwrap();
+    let syntax = parse_derive_input(input);
     impl_field_validation(&syntax).into()
 }
 
-fn impl_field_validation(syntax: &syn::DeriveInput) -> proc_macro2::TokenStream {
-    // Validate that we can make the derive function. This can only be done when we have
-    // a valid struct and the struct does not have any tuple fields (aka (a,b): Blah)
-    let fields = match syntax.data {
+/// Derives the `UpdateValidation` trait, validating an update of a struct from its previous value
+///
+/// <br>
+///
+/// # Usage
+///
+/// Shares the `validate` keyword with `FieldValidate`, only the update rules are implemented:
+///
+///  * `immutable` - the field cannot change
+///  * `monotonic_increase` - the field cannot decrease, typically a counter or a version
+///
+/// Any violation is reported as a `ValidationError::InvalidState`
+///
+/// The derive also generates `assert_unchanged_except(&self, previous, allowed)`, failing with
+/// `immutable` when any field with an update rule missing from `allowed` differs from the one of
+/// `previous`, so a PATCH handler only accepts changes to the fields it lists. Fields are named as
+/// they are serialized and compared with `PartialEq`, fields without an update rule are neither
+/// compared nor bound by it
+#[proc_macro_derive(UpdateValidate, attributes(validate, validation))]
+#[proc_macro_error]
+pub fn derive_update_validation(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let syntax = parse_derive_input(input);
+    impl_update_validation(&syntax).into()
+}
+
+/// Derives `Deserialize` for a struct that also derives `FieldValidate`, running its
+/// `FieldValidation` before the value is returned
+///
+/// <br>
+///
+/// # Usage
+///
+/// Takes the place of serde's `Deserialize` derive, the `#[serde]` attributes apply as they do
+/// with serde. A value failing its rules is never returned, deserializing fails with a serde
+/// error whose message is the JSON of the `ValidationErrors`, see
+/// `ValidationErrors::from_embedded` to get them back
+#[proc_macro_derive(DeserializeValidated, attributes(serde, validation))]
+#[proc_macro_error]
+pub fn derive_deserialize_validated(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let syntax = parse_derive_input(input);
+    impl_deserialize_validated(&syntax).into()
+}
+
+// Validate that we can make the derive function. This can only be done when we have
+// a valid struct and the struct does not have any tuple fields (aka (a,b): Blah)
+fn get_named_fields(syntax: &syn::DeriveInput, derive: &str) -> Vec<syn::Field> {
+    match syntax.data {
         syn::Data::Struct(syn::DataStruct { ref fields, .. }) => {
             if fields.iter().any(|field| field.ident.is_none()) {
                 abort!(
                     fields.span(),
                     "struct has unnamed fields";
-                    help = "#[derive(FieldValidate)] can only be used on structs with named fields";
+                    help = "#[derive({})] can only be used on structs with named fields", derive;
                 );
             }
             fields.iter().cloned().collect::<Vec<_>>()
         }
         _ => abort!(
             syntax.span(),
-            "#[derive(FieldValidation)] can only be used with structs"
+            "#[derive({})] can only be used with structs",
+            derive
         ),
-    };
+    }
+}
+
+// Derives only get the items they are declared on, an item that does not parse is reported
+// where it stops parsing rather than panicking
+fn parse_derive_input(input: proc_macro::TokenStream) -> syn::DeriveInput {
+    match syn::parse(input) {
+        Ok(syntax) => syntax,
+        Err(err) => abort!(err.span(), "{}", err),
+    }
+}
+
+fn impl_field_validation(syntax: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let fields = get_named_fields(syntax, "FieldValidate");
+    let container_options = container::find_container_options(syntax);
 
-    // List of the validation rules to implement at the end once fields and rules are mapped
+    // List of the validation rules to implement at the end once fields and rules are mapped,
+    // in the order they are declared until they are sorted
     // TODO: error or abort if the derive has none?
-    let mut validation_rules = vec![];
+    let mut ordered_rules = vec![];
+    // Inline regex patterns used across every field, each one is generated once as a static
+    let mut regex_patterns = vec![];
+    // Descriptions of every rule, returned by the generated `describe_constraints()`
+    let mut descriptions = vec![];
+    // Rules waiting on I/O, run by the generated `AsyncFieldValidation`
+    let mut async_rules = vec![];
+    // Fields of the raw twin, when the struct has one
+    let mut raw_fields = vec![];
+    // Whether or not a `#[serde(flatten)]` map captures the unknown fields
+    let mut has_unknown_fields = false;
+    // Values failing a rule each, returned by the generated `invalid_examples()`
+    let mut invalid_examples = vec![];
+    // `(alias, name)` of the fields deserializing from a serde `alias`, per `field_aliases()`
+    let mut field_aliases = vec![];
+    // `(name, source)` of the fields read from a part of the request, per `field_sources()`
+    let mut field_sources = vec![];
 
     // Check the field type
     let field_types = get_field_types(&fields);
+    let rename_all = container::find_serde_rename_all(syntax);
 
-    for field in &fields {
+    let mut field_validations = fields
+        .iter()
+        .map(|field| find_validations_for_field(field, &field_types, rename_all))
+        .collect::<Vec<_>>();
+    // The struct rule `signed` is checked as a rule of its signature field
+    if let Some(signed) = container::find_signed(syntax) {
+        let find_field = |ident: &syn::Ident| match fields
+            .iter()
+            .position(|field| field.ident.as_ref() == Some(ident))
+        {
+            Some(position) => position,
+            None => abort!(
+                ident.span(),
+                "Invalid attribute #[validate] on the struct: `{}` is not a field",
+                ident
+            ),
+        };
+        let payload_fields = signed
+            .payload_fields
+            .iter()
+            .map(|ident| {
+                if *ident == signed.field {
+                    abort!(ident.span(), "Invalid attribute #[validate] on the struct: the signature field can not be signed");
+                }
+                (field_validations[find_field(ident)].0.clone(), ident.clone())
+            })
+            .collect();
+        field_validations[find_field(&signed.field)]
+            .1
+            .push(FieldValidation::new(ValidationType::Signed {
+                payload_fields,
+                verifier: signed.verifier,
+            }));
+    }
+
+    // Rules limited to some API versions need the latest version of the struct
+    for (field, (_, validations)) in fields.iter().zip(&mut field_validations) {
+        let rules = validations.iter_mut().flat_map(|validation| {
+            let variant_rules = match validation.validator {
+                ValidationType::OnVariant { ref mut rules, .. } => Some(rules.iter_mut()),
+                _ => None,
+            };
+            variant_rules
+                .into_iter()
+                .flatten()
+                .map(|rule| &mut rule.versions)
+                .chain(Some(&mut validation.versions))
+        });
+        for versions in rules.flatten() {
+            let latest = match container_options.version {
+                Some(latest) => latest,
+                None => abort!(
+                    field.span(),
+                    "Invalid attribute #[validate] on field `{}`: `since_version` and `until_version` require the latest version of the struct",
+                    field.ident.as_ref().unwrap();
+                    help = "add #[validation(version = N)] to the struct";
+                ),
+            };
+            let since = versions.since.unwrap_or(1);
+            let until = versions.until.unwrap_or(latest);
+            if since > until || until > latest {
+                abort!(
+                    field.span(),
+                    "Invalid attribute #[validate] on field `{}`: no version from {} to {} is a version of the struct, the latest is {}",
+                    field.ident.as_ref().unwrap(), since, until, latest
+                );
+            }
+            versions.latest = latest;
+        }
+        if let Some(validation) = validations.iter().find(|validation| {
+            validation.versions.is_some() && validation.validator.is_update_rule()
+        }) {
+            abort!(
+                field.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` applies to every API version",
+                field.ident.as_ref().unwrap(),
+                validation.code
+            );
+        }
+    }
+
+    for (field_index, (field, (name, validations))) in
+        fields.iter().zip(field_validations).enumerate()
+    {
         let field_identity = field.ident.clone().unwrap();
-        let (name, validations) = find_validations_for_field(field, &field_types);
         let field_type = field_types
             .get(&field_identity.to_string())
             .cloned()
             .unwrap();
+        if container_options.raw.is_some() {
+            raw_fields.push(RawField {
+                field: field.clone(),
+                name: name.clone(),
+                optional: field_type.starts_with("Option<"),
+            });
+        }
+        for alias in serde_aliases(field) {
+            field_aliases.push(quote!((#alias, #name)));
+        }
+        if let Some(source) = field_source(field) {
+            let source = syn::Ident::new(source_variant(&source), Span::call_site());
+            field_sources.push(quote!((#name, _validation::FieldSource::#source)));
+        }
         let field_quoter = FieldQuoter::new(field_identity, name, field_type);
 
         for validation in &validations {
-            quotation::create_field_validation(&field_quoter, validation, &mut validation_rules);
+            // Update rules are described, but only checked by `UpdateValidate`
+            if validation.validator.is_update_rule() {
+                descriptions.push(push_for_rule(
+                    validation,
+                    "constraints",
+                    quotation::create_constraint_description(&field_quoter, validation),
+                ));
+                continue;
+            }
+            if let ValidationType::RegexInline(ref pattern) = validation.validator {
+                if !regex_patterns.contains(pattern) {
+                    regex_patterns.push(pattern.clone());
+                }
+            }
+            let enforcement = validation
+                .enforcement
+                .unwrap_or(container_options.enforcement);
+            let key = RuleKey::new(
+                field_index,
+                validation.order,
+                validation.validator.cost(),
+                ordered_rules.len(),
+            );
+            // Rules waiting on I/O only run in `field_errors_async`, after every other rule, each
+            // one a future run per the `JoinStrategy` of the struct
+            if validation.validator.cost() == RuleCost::Async {
+                let rule = quotation::wrap_in_field(
+                    &field_quoter,
+                    vec![quote_rule(&field_quoter, validation, enforcement)],
+                );
+                async_rules.push(push_for_rule(
+                    validation,
+                    "async_rules",
+                    quote!(::std::boxed::Box::pin(async move {
+                        let mut field_errors = _validation::ValidationErrors::new();
+                        #rule
+                        field_errors
+                    })),
+                ));
+                descriptions.push(push_for_rule(
+                    validation,
+                    "constraints",
+                    quotation::create_constraint_description(&field_quoter, validation),
+                ));
+                continue;
+            }
+            // Nested errors keep the path they were found on, they are collected by the rule
+            if let ValidationType::Nested { max_depth } = validation.validator {
+                let rule = quotation::create_nested_validation(
+                    &field_quoter,
+                    validation,
+                    enforcement,
+                    max_depth,
+                    has_serde_flatten(field),
+                );
+                descriptions.push(push_for_rule(
+                    validation,
+                    "constraints",
+                    quotation::create_constraint_description(&field_quoter, validation),
+                ));
+                ordered_rules.push(OrderedRule {
+                    key,
+                    rule: quotation::wrap_in_cfg(
+                        validation,
+                        quotation::wrap_in_versions(
+                            validation,
+                            vec![quotation::wrap_in_condition(validation, vec![rule])],
+                        ),
+                    ),
+                    planned: vec![push_for_rule(
+                        validation,
+                        "planned",
+                        quotation::create_planned_rule(&field_quoter, validation),
+                    )],
+                });
+                continue;
+            }
+            // The rules of a variant run on its value, bound when the field holds the variant
+            if let ValidationType::OnVariant {
+                ref variant,
+                ref rules,
+            } = validation.validator
+            {
+                let variant_quoter = field_quoter.variant_quoter();
+                let mut variant_rules = vec![];
+                let mut planned = vec![];
+                for rule in rules {
+                    if let ValidationType::RegexInline(ref pattern) = rule.validator {
+                        if !regex_patterns.contains(pattern) {
+                            regex_patterns.push(pattern.clone());
+                        }
+                    }
+                    let enforcement = rule.enforcement.unwrap_or(container_options.enforcement);
+                    variant_rules.push(quote_rule(&variant_quoter, rule, enforcement));
+                    descriptions.push(push_for_rule(
+                        rule,
+                        "constraints",
+                        quotation::create_variant_constraint_description(
+                            &variant_quoter,
+                            variant,
+                            rule,
+                        ),
+                    ));
+                    planned.push(push_for_rule(
+                        rule,
+                        "planned",
+                        quotation::create_variant_planned_rule(
+                            &field_quoter,
+                            &variant_quoter,
+                            variant,
+                            rule,
+                        ),
+                    ));
+                }
+                ordered_rules.push(OrderedRule {
+                    key,
+                    rule: quotation::wrap_in_cfg(
+                        validation,
+                        quotation::wrap_in_field(
+                            &field_quoter,
+                            vec![field_quoter.wrap_in_variant(variant, variant_rules)],
+                        ),
+                    ),
+                    planned,
+                });
+                continue;
+            }
+            descriptions.push(push_for_rule(
+                validation,
+                "constraints",
+                quotation::create_constraint_description(&field_quoter, validation),
+            ));
+            invalid_examples.extend(
+                quotation::create_invalid_example(&field_quoter, validation, enforcement)
+                    .map(|example| quotation::wrap_in_cfg(validation, example)),
+            );
+            let mut rule = quotation::wrap_in_field(
+                &field_quoter,
+                vec![quote_rule(&field_quoter, validation, enforcement)],
+            );
+            if let ValidationType::OneOf {
+                ref values,
+                ref collation,
+                suggest: true,
+            } = validation.validator
+            {
+                rule = quotation::wrap_in_suggestion(&field_quoter, values, collation, rule);
+            }
+            ordered_rules.push(OrderedRule {
+                key,
+                rule: quotation::wrap_in_cfg(validation, rule),
+                planned: vec![push_for_rule(
+                    validation,
+                    "planned",
+                    quotation::create_planned_rule(&field_quoter, validation),
+                )],
+            });
+        }
+
+        if has_serde_flatten(field) && field_quoter.is_map() {
+            has_unknown_fields = true;
+            ordered_rules.push(OrderedRule {
+                key: RuleKey::new(field_index, None, RuleCost::Syntactic, ordered_rules.len()),
+                rule: quotation::create_unknown_fields_validation(
+                    &field_quoter,
+                    container_options.unknown_fields,
+                ),
+                planned: vec![],
+            });
         }
     }
 
+    // Rules run, and are planned, in the order of their keys
+    ordered_rules.sort_by_key(|ordered_rule| ordered_rule.key);
+    let validation_rules = ordered_rules.iter().map(|ordered_rule| &ordered_rule.rule);
+    let planned_rules = ordered_rules
+        .iter()
+        .flat_map(|ordered_rule| &ordered_rule.planned);
+
+    if container_options.unknown_fields != UnknownFields::Allow && !has_unknown_fields {
+        abort!(
+            syntax.ident.span(),
+            "Invalid attribute #[validation]: `unknown_fields` requires a field capturing them";
+            help = "add a map field with #[serde(flatten)], like `extra: HashMap<String, Value>`";
+        );
+    }
+
     //Field validations are found and quoted
     //Generate the field validation code here
     let identity = &syntax.ident;
+    let regex_statics = quotation::create_regex_statics(&regex_patterns);
 
     //Syn library provides generics to help with generation
     //Use them per the generics of the implementation
     let (implementation_generics, type_generics, where_clause) = syntax.generics.split_for_impl();
+    //The raw twin is named by the caller, it cannot be scoped with the implementations
+    let (raw_struct, raw_conversion) = match container_options.raw {
+        Some(ref raw) => (
+            raw::create_raw_struct(syntax, raw, &raw_fields, &container_options),
+            raw::create_raw_conversion(syntax, raw, &raw_fields),
+        ),
+        None => (quote!(), quote!()),
+    };
+    // A struct with versions rejects the API versions it does not have before any rule runs
+    let (api_version_check, unsupported_api_version) = match container_options.version {
+        Some(latest) => (
+            quote!(
+                if let Err(err) = _validation::negotiate_api_version(ctx.api_version(), #latest) {
+                    field_errors.push(_validation::FieldError::unattached(err).with_param("latest", #latest));
+                    return field_errors;
+                }
+            ),
+            quote!(|| _validation::negotiate_api_version(ctx.api_version(), #latest).is_err()),
+        ),
+        None => (quote!(), quote!()),
+    };
+    let type_name = identity.to_string();
+    let attribute_to_sources = if field_sources.is_empty() {
+        quote!()
+    } else {
+        quote!(field_errors.attribute_to_sources(
+            <Self as _validation::FieldValidation>::field_sources(),
+        );)
+    };
+    // Drifts of the serde configuration are compile errors or, as the uses of deprecated
+    // items, compile warnings
+    let (serde_drift, serde_drift_warnings) = match container_options.strict_serde {
+        Some(enforcement) => {
+            let drift = container::find_serde_drift(syntax, &container_options);
+            if enforcement == Enforcement::Deny {
+                for (span, message) in &drift {
+                    emit_error!(
+                        *span,
+                        "Invalid attribute #[validation(strict_serde)]: {}",
+                        message
+                    );
+                }
+            }
+            let messages = drift.iter().map(|(_, message)| message);
+            let warnings = drift
+                .iter()
+                .filter(|_| enforcement == Enforcement::Warn)
+                .map(|(span, message)| {
+                    quote_spanned!(*span=>
+                        #[allow(dead_code)]
+                        fn serde_drift() {
+                            #[deprecated(note = #message)]
+                            struct SerdeDrift;
+                            let _ = SerdeDrift;
+                        }
+                    )
+                });
+            (
+                quote!(
+                    /// Returns where the serde configuration of the struct drifts from its
+                    /// `strict_serde` policy, empty when it matches
+                    pub fn serde_drift() -> &'static [&'static str] {
+                        &[#(#messages),*]
+                    }
+                ),
+                quote!(#(const _: () = { #warnings };)*),
+            )
+        }
+        None => (quote!(), quote!()),
+    };
+    let invalid_examples = if container_options.invalid_examples {
+        quote!(
+            /// Returns a value failing each rule an invalid value can be made for, along with the
+            /// code of the rule. Each one is the `Default` of the struct with a single field changed
+            pub fn invalid_examples() -> ::std::vec::Vec<(Self, &'static str)> {
+                let mut examples = ::std::vec::Vec::new();
+                #({ #invalid_examples })*
+                examples
+            }
+        )
+    } else {
+        quote!()
+    };
+    let async_validation = if async_rules.is_empty() {
+        quote!()
+    } else {
+        let seen_key_prefix = identity.to_string();
+        let strategy = container_options.async_strategy.quote();
+        quote!(
+            impl #implementation_generics _validation::AsyncFieldValidation for #identity #type_generics #where_clause {
+                fn field_errors_async<'validation>(
+                    &'validation self,
+                    ctx: &'validation _validation::ValidationContext,
+                ) -> _validation::BoxFuture<'validation, _validation::ValidationErrors> {
+                    const SEEN_KEY_PREFIX: &str = #seen_key_prefix;
+
+                    ::std::boxed::Box::pin(async move {
+                        let mut field_errors = _validation::FieldValidation::field_errors(self, ctx);
+                        if ctx.is_too_deep()
+                            || ctx.config().is_exhausted(field_errors.len())
+                            #unsupported_api_version
+                        {
+                            return field_errors;
+                        }
+
+                        #[allow(unused_mut)]
+                        let mut async_rules: ::std::vec::Vec<
+                            _validation::BoxFuture<'validation, _validation::ValidationErrors>,
+                        > = ::std::vec::Vec::new();
+                        #(#async_rules)*
+                        for error in _validation::join_rules(async_rules, #strategy).await {
+                            field_errors.push(error);
+                        }
+                        #attribute_to_sources
+
+                        ctx.config().limit(field_errors)
+                    })
+                }
+            }
+        )
+    };
     let implemented_syntax = quote!(
-        impl #implementation_generics ::validation::FieldValidation for #identity #type_generics #where_clause {
-            fn validate_fields(&self) -> ::validation::ValidationResult<()> {
+        impl #implementation_generics _validation::FieldValidation for #identity #type_generics #where_clause {
+            fn validate_fields(&self) -> _validation::ValidationResult<()> {
+                self.validate_fields_with(&_validation::ValidationContext::default())
+            }
+
+            fn validate_fields_with(
+                &self,
+                ctx: &_validation::ValidationContext,
+            ) -> _validation::ValidationResult<()> {
+                _validation::FieldValidation::field_errors(self, ctx).into_result()
+            }
+
+            #[allow(unused_variables, unused_mut, unused_labels)]
+            fn field_errors(
+                &self,
+                ctx: &_validation::ValidationContext,
+            ) -> _validation::ValidationErrors {
+                #regex_statics
+
+                let mut field_errors = _validation::ValidationErrors::new();
+
+                if ctx.is_too_deep() {
+                    field_errors.push(_validation::FieldError::unattached(
+                        _validation::ValidationError::InvalidState("nested".to_string()),
+                    ));
+                    return field_errors;
+                }
+
+                #api_version_check
+
+                // The rules left are not run once the config reports no more failures
+                'rules: {
+                    #(
+                        #validation_rules
+                        if ctx.config().is_exhausted(field_errors.len()) {
+                            break 'rules;
+                        }
+                    )*
+                }
+                #attribute_to_sources
+
+                ctx.config().limit(field_errors)
+            }
+
+            fn field_aliases() -> &'static [(&'static str, &'static str)] {
+                &[#(#field_aliases),*]
+            }
+
+            fn field_sources() -> &'static [(&'static str, _validation::FieldSource)] {
+                &[#(#field_sources),*]
+            }
+        }
+
+        impl #implementation_generics #identity #type_generics #where_clause {
+            /// Describes every rule of the `#[validate]` annotations of the struct
+            #[allow(unused_mut)]
+            pub fn describe_constraints() -> ::std::vec::Vec<_validation::FieldConstraintDescription> {
+                let mut constraints = ::std::vec::Vec::new();
+                #(#descriptions)*
+                constraints
+            }
+
+            /// Lists the rules `validate_fields` would run for this value and why the others
+            /// would be skipped, without running any of them
+            #[allow(unused_mut)]
+            pub fn explain(&self) -> _validation::ValidationPlan {
+                let mut planned = ::std::vec::Vec::new();
+                #(#planned_rules)*
+                _validation::ValidationPlan::new(planned)
+            }
+
+            /// Validates the struct, scoring the rules that ran by their weight on top of the
+            /// errors
+            pub fn validate_scored(&self) -> _validation::ScoredValidation {
+                self.validate_scored_with(&_validation::ValidationContext::default())
+            }
+
+            /// Provides the same scoring as `validate_scored`, with the rules reading the
+            /// environment from the given `ValidationContext`
+            pub fn validate_scored_with(
+                &self,
+                ctx: &_validation::ValidationContext,
+            ) -> _validation::ScoredValidation {
+                _validation::score_plan(&self.explain(), _validation::FieldValidation::field_errors(self, ctx))
+            }
+
+            #invalid_examples
+
+            #serde_drift
+        }
+
+        #serde_drift_warnings
+
+        impl #implementation_generics _validation::reflect::Reflect for #identity #type_generics #where_clause {
+            fn type_info() -> _validation::reflect::TypeInfo {
+                _validation::reflect::TypeInfo::from_constraints(#type_name, &Self::describe_constraints())
+            }
+        }
+
+        #async_validation
+
+        #raw_conversion
+    );
+
+    let scoped_syntax = container_options.wrap_in_crate_scope(implemented_syntax);
+    quote!(
+        #raw_struct
+        #scoped_syntax
+    )
+}
+
+/// Position of a rule in the generated code: fields in declaration order and, within a field,
+/// rules with an `order` first, by ascending order, then the others from the cheapest to the
+/// most expensive, ties in declaration order
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct RuleKey {
+    field: usize,
+    unordered: bool,
+    order: u64,
+    cost: RuleCost,
+    declaration: usize,
+}
+
+impl RuleKey {
+    fn new(field: usize, order: Option<u64>, cost: RuleCost, declaration: usize) -> Self {
+        RuleKey {
+            field,
+            unordered: order.is_none(),
+            order: order.unwrap_or_default(),
+            cost,
+            declaration,
+        }
+    }
+}
+
+/// A generated rule, along with its `PlannedRule`s for `explain()`
+struct OrderedRule {
+    key: RuleKey,
+    rule: proc_macro2::TokenStream,
+    planned: Vec<proc_macro2::TokenStream>,
+}
+
+/// Generates the statement pushing an item generated for a rule to the given collection of the
+/// generated code, compiled per the `cfg` of the rule
+fn push_for_rule(
+    validation: &FieldValidation,
+    collection: &str,
+    item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let collection = syn::Ident::new(collection, Span::call_site());
+    quotation::wrap_in_cfg(validation, quote!(#collection.push(#item);))
+}
+
+/// Generates a rule of a field, handling the options of its `#[validate]` attribute
+fn quote_rule(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    enforcement: Enforcement,
+) -> proc_macro2::TokenStream {
+    let mut rules = vec![];
+    quotation::create_field_validation(field_quoter, validation, &mut rules);
+    if let Some(ref deprecation) = validation.deprecation {
+        rules = vec![quotation::wrap_in_deprecation(
+            field_quoter,
+            validation,
+            deprecation,
+            rules,
+        )];
+    }
+    let rules = match enforcement {
+        Enforcement::Deny => rules,
+        Enforcement::Warn => vec![quotation::wrap_in_warning(field_quoter, rules)],
+    };
+    quotation::wrap_in_versions(
+        validation,
+        vec![quotation::wrap_in_condition(validation, rules)],
+    )
+}
+
+fn impl_update_validation(syntax: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let fields = get_named_fields(syntax, "UpdateValidate");
+    let container_options = container::find_container_options(syntax);
+    let mut update_rules = vec![];
+    // Every field with an update rule, compared by `assert_unchanged_except`
+    let mut compared_fields = vec![];
+    let field_types = get_field_types(&fields);
+    let rename_all = container::find_serde_rename_all(syntax);
+
+    for field in &fields {
+        let field_identity = field.ident.clone().unwrap();
+        let (name, validations) = find_validations_for_field(field, &field_types, rename_all);
+        let field_type = field_types
+            .get(&field_identity.to_string())
+            .cloned()
+            .unwrap();
+        let names = Some(name.clone())
+            .into_iter()
+            .chain(serde_aliases(field))
+            .collect();
+        if validations.iter().any(|v| v.validator.is_update_rule()) {
+            compared_fields.push((field_identity.clone(), names, field.ty.clone()));
+        }
+        let field_quoter = FieldQuoter::new(field_identity, name, field_type);
+
+        for validation in validations.iter().filter(|v| v.validator.is_update_rule()) {
+            update_rules.push(quotation::wrap_in_cfg(
+                validation,
+                quotation::create_update_validation(&field_quoter, validation),
+            ));
+        }
+    }
+
+    let unchanged_except = quotation::create_unchanged_except(&compared_fields);
+
+    let identity = &syntax.ident;
+    let (implementation_generics, type_generics, where_clause) = syntax.generics.split_for_impl();
+    container_options.wrap_in_crate_scope(quote!(
+        impl #implementation_generics _validation::UpdateValidation for #identity #type_generics #where_clause {
+            #[allow(unused_variables)]
+            fn validate_update(&self, previous: &Self) -> _validation::ValidationResult<()> {
                 let mut errors = ::std::vec::Vec::new();
 
-                #(#validation_rules)*
+                #(#update_rules)*
 
                 if errors.is_empty() {
                     Ok(())
                 } else {
-                    let mut err = ::validation::ValidationError::FieldMismatch("not_null".to_string());
-                    Err(err)
+                    Err(errors.remove(0))
                 }
             }
         }
-    );
 
-    //TODO: Debug statement, remove later
-    println!("{}", implemented_syntax.to_string());
-    implemented_syntax
+        impl #implementation_generics #identity #type_generics #where_clause {
+            #unchanged_except
+        }
+    ))
+}
+
+fn impl_deserialize_validated(syntax: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let fields = get_named_fields(syntax, "DeserializeValidated");
+    let container_options = container::find_container_options(syntax);
+
+    container_options
+        .wrap_in_crate_scope(deserialize::create_validated_deserialize(syntax, &fields))
+}
+
+//The part of the request the field is read from, per the `source` of its `#[validate]` attributes
+fn field_source(field: &syn::Field) -> Option<String> {
+    let mut sources = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(validate))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|item| match item {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(ref name_value))
+                if name_value.path.is_ident("source") =>
+            {
+                lit_to_string(&name_value.lit)
+            }
+            _ => None,
+        });
+    let source = sources.next()?;
+    if sources.any(|other| other != source) {
+        abort!(
+            field.span(),
+            "Invalid attribute #[validate] on field `{}`: a field is read from a single `source`",
+            field.ident.as_ref().unwrap()
+        );
+    }
+    Some(source)
+}
+
+//The variant of `FieldSource` of a name of `validation::FIELD_SOURCES`
+fn source_variant(source: &str) -> &'static str {
+    match source {
+        "header" => "Header",
+        "path" => "Path",
+        "query" => "Query",
+        _ => "Body",
+    }
+}
+
+//Whether or not the field is `#[serde(flatten)]`, capturing the keys no other field has
+fn has_serde_flatten(field: &syn::Field) -> bool {
+    container::has_serde_flag(&field.attrs, &["flatten"])
+}
+
+//The names of the `#[serde(alias = "...")]` of the field, the other names it deserializes from
+fn serde_aliases(field: &syn::Field) -> Vec<String> {
+    let mut aliases = vec![];
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(serde))
+    {
+        if let Ok(syn::Meta::List(syn::MetaList { ref nested, .. })) = attr.parse_meta() {
+            for meta in nested {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    ref lit,
+                    ..
+                })) = *meta
+                {
+                    if path.is_ident("alias") {
+                        aliases.extend(lit_to_string(lit));
+                    }
+                }
+            }
+        }
+    }
+    aliases
 }
 
 //Finds the field types for each field of the struct [string, i32, etc...]
@@ -160,9 +1093,6 @@ fn get_field_types(fields: &[syn::Field]) -> HashMap<String, String> {
             }
         };
 
-        //TODO: Debugging statement, remove this later
-        println!("{:?}", field_type);
-
         types.insert(field_identity, field_type);
     }
 
@@ -172,11 +1102,18 @@ fn get_field_types(fields: &[syn::Field]) -> HashMap<String, String> {
 fn find_validations_for_field(
     field: &syn::Field,
     field_types: &HashMap<String, String>,
+    rename_all: Option<RenameRule>,
 ) -> (String, Vec<FieldValidation>) {
     // Cloning the field ident twice to helps with a case where a struct has
     // renamed the field and allows us to compare it with what Rust compile a different name
     let rust_identity = field.ident.clone().unwrap().to_string();
-    let mut field_identity = field.ident.clone().unwrap().to_string();
+    // Fields are named as serde names them, `r#type` as `type`, and the `rename_all` of the
+    // struct names the fields without a `rename` of their own
+    let unraw_identity = field.ident.as_ref().unwrap().unraw().to_string();
+    let mut field_identity = match rename_all {
+        Some(rule) => rule.apply(&unraw_identity),
+        None => unraw_identity,
+    };
 
     //anonymous fn to handle any errors on invalid [validate] attributes
     let error = |span: Span, msg: &str| -> ! {
@@ -188,10 +1125,12 @@ fn find_validations_for_field(
         );
     };
 
-    let _field_type = field_types.get(&field_identity).unwrap();
+    let _field_type = field_types.get(&rust_identity).unwrap();
 
     let mut validators = vec![];
     let mut has_validate = false;
+    // A `source` alone tells where a field without rules is read from
+    let mut has_source = false;
 
     for attr in &field.attrs {
         if attr.path != parse_quote!(validate) && attr.path != parse_quote!(serde) {
@@ -203,7 +1142,7 @@ fn find_validations_for_field(
         }
 
         match attr.parse_meta() {
-            Ok(syn::Meta::List(syn::MetaList { ref nested, ..})) => {
+            Ok(syn::Meta::List(syn::MetaList { ref nested, .. })) => {
                 let meta_items = nested.iter().collect::<Vec<_>>();
                 //For the case of a serde rename check to see if we need to map to the Rust field name
                 if attr.path == parse_quote!(serde) {
@@ -213,43 +1152,75 @@ fn find_validations_for_field(
                     continue;
                 }
 
+                //Options like `enforcement` apply to every rule of their attribute
+                let attr_start = validators.len();
+                let mut attr_enforcement = None;
+                let mut attr_deprecation = None;
+                let mut attr_condition = None;
+                let mut attr_order = None;
+                let mut attr_weight = None;
+                let mut attr_versions = None;
+                let mut attr_groups = vec![];
+                let mut attr_cfg = None;
+
                 //We have a field and a valid validation, find the rule to match it to
                 for meta_item in meta_items {
-                    match *meta_item {
-                        syn::NestedMeta::Meta(ref item) => match *item {
-                            //not_null
-                            syn::Meta::Path(ref name) => {
-                                match name.get_ident().unwrap().to_string().as_ref() {
-                                    "not_null" => {
-                                        validators.push(FieldValidation::new(ValidationType::NotNull))
-                                    }
-                                    _ => {
-                                        let mut ident = proc_macro2::TokenStream::new();
-                                        name.to_tokens(&mut ident);
-                                        abort!(name.span(), "Unexpected Validation: {}", ident)
-                                    }
-                                }
-                            }
-                            syn::Meta::NameValue(syn::MetaNameValue { ref path, lit: _, ..}) => {
-                                let ident = path.get_ident().unwrap();
-                                abort!(path.span(), "Unexpected Validation: {:?}", ident)
-                            }
-                            syn::Meta::List(syn::MetaList { ref path, nested: _, ..}) => {
-                                let ident = path.get_ident().unwrap();
-                                abort!(path.span(), "Unexpected Validation: {:?}", ident)
-                            }
+                    match find_attr_item(&rust_identity, meta_item) {
+                        AttrItem::Rule(rule) => validators.push(rule),
+                        AttrItem::Bundle(rules) => validators.extend(rules),
+                        AttrItem::Enforcement(enforcement) => attr_enforcement = Some(enforcement),
+                        AttrItem::Deprecation(deprecation) => attr_deprecation = Some(deprecation),
+                        AttrItem::Condition(condition) => {
+                            attr_condition = Some(Box::new(condition))
                         }
-                        _ => unreachable!("Found a non Meta while looking for Validators"),
-                    };
+                        AttrItem::Order(order) => attr_order = Some(order),
+                        AttrItem::Weight(weight) => attr_weight = Some(weight),
+                        AttrItem::Source => has_source = true,
+                        AttrItem::Groups(groups) => attr_groups = groups,
+                        AttrItem::Cfg(predicate) => attr_cfg = Some(predicate),
+                        AttrItem::SinceVersion(since) => {
+                            attr_versions.get_or_insert_with(Versions::default).since = Some(since)
+                        }
+                        AttrItem::UntilVersion(until) => {
+                            attr_versions.get_or_insert_with(Versions::default).until = Some(until)
+                        }
+                    }
+                }
+
+                for validator in &mut validators[attr_start..] {
+                    validator.enforcement = attr_enforcement;
+                    validator.deprecation = attr_deprecation.clone();
+                    validator.condition = attr_condition.clone();
+                    validator.order = attr_order;
+                    validator.weight = attr_weight;
+                    validator.versions = attr_versions;
+                    validator.groups = attr_groups.clone();
+                    validator.cfg = attr_cfg.clone();
+                    if let ValidationType::OnVariant { ref mut rules, .. } = validator.validator {
+                        for rule in rules {
+                            rule.enforcement = attr_enforcement;
+                            rule.deprecation = attr_deprecation.clone();
+                            rule.condition = attr_condition.clone();
+                            rule.weight = attr_weight;
+                            rule.versions = attr_versions;
+                            rule.groups = attr_groups.clone();
+                            rule.cfg = attr_cfg.clone();
+                        }
+                    }
                 }
             }
             Ok(syn::Meta::Path(_)) => abort!(attr.span(), "Unexpected nested value"),
             Ok(syn::Meta::NameValue(_)) => abort!(attr.span(), "Unexpected name=value argument"),
-            Err(e) => unreachable!(
-                "Received something other than a list of attributes while checking field `{}`: {:?}", field_identity, e),
+            //Other derives report their own attributes, `#[validate]` is reported where it stops parsing
+            Err(_) if attr.path == parse_quote!(serde) => continue,
+            Err(e) => abort!(
+                e.span(),
+                "Invalid attribute #[validate] on field `{}`: {}", rust_identity, e;
+                note = "parameters are literals, like `min = 1` or `pattern = \"^[a-z]+$\"`";
+            ),
         }
 
-        if has_validate && validators.is_empty() {
+        if has_validate && validators.is_empty() && !has_source {
             error(attr.span(), "there must be at least one validation rule");
         }
     }
@@ -257,6 +1228,904 @@ fn find_validations_for_field(
     (field_identity, validators)
 }
 
+/// An item of a `#[validate]` attribute: a rule, or an option applying to every rule of the attribute
+enum AttrItem {
+    Rule(FieldValidation),
+    Bundle(Vec<FieldValidation>),
+    Enforcement(Enforcement),
+    Deprecation(Deprecation),
+    Condition(Condition),
+    Order(u64),
+    Weight(u32),
+    //The source itself is read by `field_source()`, over every attribute of the field
+    Source,
+    SinceVersion(u32),
+    UntilVersion(u32),
+    Groups(Vec<String>),
+    Cfg(String),
+}
+
+/// Finds the rule or the option of an item of a `#[validate]` attribute
+fn find_attr_item(rust_identity: &str, meta_item: &syn::NestedMeta) -> AttrItem {
+    //anonymous fn to handle any errors on invalid [validate] attributes
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(
+            span,
+            "Invalid attribute #[validate] on field `{}`: {}",
+            rust_identity,
+            msg
+        );
+    };
+
+    match *meta_item {
+        syn::NestedMeta::Meta(ref item) => match *item {
+            //not_null
+            syn::Meta::Path(ref name) => {
+                let ident = match name.get_ident() {
+                    Some(ident) => ident,
+                    None => error(name.span(), "expected the name of a rule or an option"),
+                };
+                match ident.to_string().as_ref() {
+                    // `required` as the `validator` crate names it
+                    "not_null" | "required" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::NotNull))
+                    }
+                    "past" => AttrItem::Rule(FieldValidation::new(ValidationType::Past)),
+                    "finite" => AttrItem::Rule(FieldValidation::new(ValidationType::Finite)),
+                    "canonical_identifier" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::CanonicalIdentifier {
+                            max: validation::CANONICAL_IDENTIFIER_MAX as u64,
+                        }))
+                    }
+                    "uuid_not_nil" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::UuidNotNil))
+                    }
+                    "immutable" => AttrItem::Rule(FieldValidation::new(ValidationType::Immutable)),
+                    "monotonic_increase" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::MonotonicIncrease))
+                    }
+                    "future" => AttrItem::Rule(FieldValidation::new(ValidationType::Future)),
+                    "nested" => AttrItem::Rule(FieldValidation::new(ValidationType::Nested {
+                        max_depth: None,
+                    })),
+                    "number_format" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::NumberFormat {
+                            locale: None,
+                        }))
+                    }
+                    _ => unexpected_validation(name.span(), ident),
+                }
+            }
+            syn::Meta::NameValue(syn::MetaNameValue {
+                ref path, ref lit, ..
+            }) => {
+                let ident = match path.get_ident() {
+                    Some(ident) => ident,
+                    None => error(path.span(), "expected the name of a rule or an option"),
+                };
+                match ident.to_string().as_ref() {
+                    "enforcement" => {
+                        AttrItem::Enforcement(enforcement_from_lit(lit, "#[validate]"))
+                    }
+                    "when" => AttrItem::Condition(condition_from_lit(rust_identity, lit)),
+                    "order" => match lit_to_u64(lit) {
+                        Some(order) => AttrItem::Order(order),
+                        None => error(lit.span(), "`order` expects a positive integer"),
+                    },
+                    "source" => match lit_to_string(lit) {
+                        Some(ref source)
+                            if validation::FIELD_SOURCES.contains(&source.as_str()) =>
+                        {
+                            AttrItem::Source
+                        }
+                        _ => abort!(
+                            lit.span(),
+                            "Invalid attribute #[validate] on field `{}`: unknown `source`", rust_identity;
+                            help = "expected one of: {}", validation::FIELD_SOURCES.join(", ");
+                        ),
+                    },
+                    "weight" => {
+                        match lit_to_u64(lit).filter(|w| (1..=u64::from(u32::MAX)).contains(w)) {
+                            Some(weight) => AttrItem::Weight(weight as u32),
+                            None => error(lit.span(), "`weight` expects an integer from 1"),
+                        }
+                    }
+                    "since_version" => {
+                        AttrItem::SinceVersion(api_version_from_lit(rust_identity, lit))
+                    }
+                    "until_version" => {
+                        AttrItem::UntilVersion(api_version_from_lit(rust_identity, lit))
+                    }
+                    "bundle" => AttrItem::Bundle(bundle_from_lit(rust_identity, lit)),
+                    "regex_inline" => AttrItem::Rule(FieldValidation::new(
+                        ValidationType::RegexInline(regex_inline_from_lit(rust_identity, lit)),
+                    )),
+                    "date_format" => {
+                        AttrItem::Rule(FieldValidation::new(ValidationType::DateFormat {
+                            style: date_style_from_lit(rust_identity, lit),
+                            locale: None,
+                        }))
+                    }
+                    "fits_in" => AttrItem::Rule(FieldValidation::new(ValidationType::FitsIn(
+                        integer_type_from_lit(rust_identity, lit),
+                    ))),
+                    "flags_of" => match lit_to_string(lit) {
+                        Some(ref flags) if syn::parse_str::<syn::Path>(flags).is_ok() => {
+                            AttrItem::Rule(FieldValidation::new(ValidationType::FlagsOf(
+                                flags.clone(),
+                            )))
+                        }
+                        _ => error(
+                            lit.span(),
+                            "`flags_of` expects the path of a `KnownFlags` type",
+                        ),
+                    },
+                    "json_type" => AttrItem::Rule(FieldValidation::new(ValidationType::JsonType(
+                        json_type_from_lit(rust_identity, lit),
+                    ))),
+                    "max_depth" => match lit_to_u64(lit) {
+                        Some(max_depth) => AttrItem::Rule(FieldValidation::new(
+                            ValidationType::JsonMaxDepth(max_depth),
+                        )),
+                        None => error(lit.span(), "`max_depth` expects a positive integer"),
+                    },
+                    "max_keys" => match lit_to_u64(lit) {
+                        Some(max_keys) => AttrItem::Rule(FieldValidation::new(
+                            ValidationType::JsonMaxKeys(max_keys),
+                        )),
+                        None => error(lit.span(), "`max_keys` expects a positive integer"),
+                    },
+                    "uuid_version" => match lit_to_u64(lit)
+                        .filter(|version| (1..=5).contains(version))
+                    {
+                        Some(version) => AttrItem::Rule(FieldValidation::new(
+                            ValidationType::UuidVersion(version as u8),
+                        )),
+                        None => error(lit.span(), "`uuid_version` expects a version from 1 to 5"),
+                    },
+                    "min_age" => match lit_to_u64(lit).filter(|y| *y <= u64::from(u32::MAX)) {
+                        Some(years) => AttrItem::Rule(FieldValidation::new(
+                            ValidationType::MinAge(years as u32),
+                        )),
+                        None => error(lit.span(), "`min_age` expects a number of years"),
+                    },
+                    _ => unexpected_validation(path.span(), ident),
+                }
+            }
+            syn::Meta::List(syn::MetaList {
+                ref path,
+                ref nested,
+                ..
+            }) => {
+                let ident = match path.get_ident() {
+                    Some(ident) => ident,
+                    None => error(path.span(), "expected the name of a rule or an option"),
+                };
+                if ident == "on_variant" {
+                    return AttrItem::Rule(FieldValidation::new(on_variant_from_meta(
+                        rust_identity,
+                        path,
+                        nested,
+                    )));
+                }
+                if ident == "one_of" {
+                    return AttrItem::Rule(FieldValidation::new(one_of_from_meta(
+                        rust_identity,
+                        path,
+                        nested,
+                    )));
+                }
+                if ident == "required_keys" {
+                    let keys = string_lits_from_meta(rust_identity, path, nested);
+                    return AttrItem::Rule(FieldValidation::new(ValidationType::RequiredKeys(
+                        keys,
+                    )));
+                }
+                if ident == "groups" {
+                    return AttrItem::Groups(string_lits_from_meta(rust_identity, path, nested));
+                }
+                if ident == "cfg" {
+                    return match nested.first() {
+                        Some(predicate @ syn::NestedMeta::Meta(_)) if nested.len() == 1 => {
+                            AttrItem::Cfg(predicate.to_token_stream().to_string())
+                        }
+                        _ => error(
+                            path.span(),
+                            "`cfg` expects a single predicate, like `cfg(feature = \"strict\")`",
+                        ),
+                    };
+                }
+                let params = find_params(rust_identity, ident, nested);
+                match ident.to_string().as_ref() {
+                    "deprecated" => {
+                        check_known_params(rust_identity, &params, &["since", "note"]);
+                        let since = match find_string_param(rust_identity, &params, "since") {
+                            Some(since) => since,
+                            None => error(path.span(), "`deprecated` requires a `since`"),
+                        };
+                        let note = find_string_param(rust_identity, &params, "note");
+                        AttrItem::Deprecation(Deprecation { since, note })
+                    }
+                    "date_format" => {
+                        let style = match params.iter().find(|(name, _)| name == "style") {
+                            Some((_, lit)) => date_style_from_lit(rust_identity, lit),
+                            None => error(path.span(), "`date_format` requires a `style`"),
+                        };
+                        let locale = find_string_param(rust_identity, &params, "locale");
+                        check_known_params(rust_identity, &params, &["style", "locale"]);
+                        AttrItem::Rule(FieldValidation::new(ValidationType::DateFormat {
+                            style,
+                            locale,
+                        }))
+                    }
+                    "length" => {
+                        check_known_params(rust_identity, &params, &["min", "max", "equal"]);
+                        let mut min = find_length_bound_param(rust_identity, &params, "min");
+                        let mut max = find_length_bound_param(rust_identity, &params, "max");
+                        // `equal` as the `validator` crate writes an exact length
+                        if let Some(equal) =
+                            find_length_bound_param(rust_identity, &params, "equal")
+                        {
+                            if min.is_some() || max.is_some() {
+                                error(
+                                    find_param_span(&params, "equal"),
+                                    "`length` takes an `equal` or bounds, not both",
+                                );
+                            }
+                            min = Some(equal.clone());
+                            max = Some(equal);
+                        }
+                        match (&min, &max) {
+                            (None, None) => {
+                                error(path.span(), "`length` requires a `min` or a `max`")
+                            }
+                            (Some(LengthBound::Value(min)), Some(LengthBound::Value(max)))
+                                if min > max =>
+                            {
+                                error(
+                                    find_param_span(&params, "min"),
+                                    "`length` has a `min` greater than its `max`",
+                                )
+                            }
+                            _ => AttrItem::Rule(FieldValidation::new(ValidationType::Length {
+                                min,
+                                max,
+                            })),
+                        }
+                    }
+                    "range" => {
+                        check_known_params(
+                            rust_identity,
+                            &params,
+                            &["min", "max", "exclusive_min", "exclusive_max"],
+                        );
+                        AttrItem::Rule(FieldValidation::new(range_from_params(
+                            rust_identity,
+                            path,
+                            &params,
+                        )))
+                    }
+                    "recently_seen" => {
+                        check_known_params(rust_identity, &params, &["window", "key_fn"]);
+                        let window = match find_u64_param(rust_identity, &params, "window") {
+                            Some(window) => window,
+                            None => error(
+                                path.span(),
+                                "`recently_seen` requires a `window` in seconds",
+                            ),
+                        };
+                        let key_fn = find_string_param(rust_identity, &params, "key_fn");
+                        if let Some(ref key_fn) = key_fn {
+                            if syn::parse_str::<syn::ExprPath>(key_fn).is_err() {
+                                error(
+                                    find_param_span(&params, "key_fn"),
+                                    "`key_fn` expects the path of a function",
+                                );
+                            }
+                        }
+                        AttrItem::Rule(FieldValidation::new(ValidationType::RecentlySeen {
+                            window,
+                            key_fn,
+                        }))
+                    }
+                    "nested" => {
+                        check_known_params(rust_identity, &params, &["max_depth"]);
+                        let max_depth = match find_u64_param(rust_identity, &params, "max_depth") {
+                            Some(max_depth) => max_depth,
+                            None => error(path.span(), "`nested` requires a `max_depth`"),
+                        };
+                        AttrItem::Rule(FieldValidation::new(ValidationType::Nested {
+                            max_depth: Some(max_depth),
+                        }))
+                    }
+                    "canonical_identifier" => {
+                        check_known_params(rust_identity, &params, &["max"]);
+                        match find_u64_param(rust_identity, &params, "max") {
+                            Some(max) if max > 0 => {
+                                AttrItem::Rule(FieldValidation::new(ValidationType::CanonicalIdentifier { max }))
+                            }
+                            Some(_) => error(find_param_span(&params, "max"), "`canonical_identifier` expects a `max` of at least 1"),
+                            None => error(path.span(), "`canonical_identifier` requires a `max`, or no parentheses for the default"),
+                        }
+                    }
+                    "number_format" => {
+                        let locale = find_string_param(rust_identity, &params, "locale");
+                        check_known_params(rust_identity, &params, &["locale"]);
+                        AttrItem::Rule(FieldValidation::new(ValidationType::NumberFormat {
+                            locale,
+                        }))
+                    }
+                    _ => unexpected_validation(path.span(), ident),
+                }
+            }
+        },
+        syn::NestedMeta::Lit(ref lit) => error(lit.span(), "expected a rule, found a literal"),
+    }
+}
+
+/// Aborts on a name that is no rule, pointing out the rules of the `validator` crate the
+/// derive does not have
+fn unexpected_validation(span: Span, ident: &syn::Ident) -> ! {
+    const VALIDATOR_ONLY_RULES: &[&str] = &[
+        "email",
+        "url",
+        "ip",
+        "credit_card",
+        "non_control_character",
+        "must_match",
+        "contains",
+        "does_not_contain",
+        "regex",
+        "custom",
+        "schema",
+    ];
+
+    if VALIDATOR_ONLY_RULES.contains(&ident.to_string().as_str()) {
+        abort!(
+            span,
+            "Unexpected Validation: {}", ident;
+            help = "`{}` is a rule of the `validator` crate only, the derive takes its `required`, `length`, `range` and `nested`, and `regex_inline` for patterns", ident;
+        );
+    }
+    abort!(span, "Unexpected Validation: {}", ident)
+}
+
+/// Returns the rule `on_variant(Variant, rules...)`, the rules applying to the value of the
+/// variant when the field holds it
+fn on_variant_from_meta(
+    rust_identity: &str,
+    path: &syn::Path,
+    nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::token::Comma>,
+) -> ValidationType {
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(
+            span,
+            "Invalid attribute #[validate] on field `{}`: {}",
+            rust_identity,
+            msg
+        );
+    };
+
+    let mut items = nested.iter();
+    let variant = match items.next() {
+        Some(item) => match *item {
+            syn::NestedMeta::Meta(syn::Meta::Path(ref variant)) => match variant.get_ident() {
+                Some(variant) => variant.to_string(),
+                None => error(
+                    item.span(),
+                    "`on_variant` expects the name of a variant first",
+                ),
+            },
+            _ => error(
+                item.span(),
+                "`on_variant` expects the name of a variant first",
+            ),
+        },
+        None => error(path.span(), "`on_variant` requires a variant and its rules"),
+    };
+
+    let mut rules = vec![];
+    for item in items {
+        match find_attr_item(rust_identity, item) {
+            AttrItem::Rule(rule) => match rule.validator {
+                ValidationType::NotNull
+                | ValidationType::Nested { .. }
+                | ValidationType::OnVariant { .. }
+                | ValidationType::RecentlySeen { .. }
+                | ValidationType::Immutable
+                | ValidationType::MonotonicIncrease => error(
+                    item.span(),
+                    &format!("`{}` cannot apply to a variant", rule.code),
+                ),
+                ValidationType::OneOf { suggest: true, .. } => {
+                    error(item.span(), "`one_of` cannot suggest a value on a variant")
+                }
+                _ => rules.push(rule),
+            },
+            AttrItem::Bundle(bundle) => rules.extend(bundle),
+            _ => error(
+                item.span(),
+                "`on_variant` only takes rules, options go on the attribute",
+            ),
+        }
+    }
+    if rules.is_empty() {
+        error(path.span(), "`on_variant` requires at least one rule");
+    }
+
+    ValidationType::OnVariant { variant, rules }
+}
+
+/// Returns the rule `range(min = 1, exclusive_max = 10)`, bounds of the same kind among
+/// numbers, chars and strings, text compared as it is written
+fn range_from_params(
+    rust_identity: &str,
+    path: &syn::Path,
+    params: &[(String, syn::Lit)],
+) -> ValidationType {
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(
+            span,
+            "Invalid attribute #[validate] on field `{}`: {}",
+            rust_identity,
+            msg
+        );
+    };
+
+    let bound = |name: &str| {
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, lit)| lit.clone())
+        };
+        let exclusive_name = format!("exclusive_{}", name);
+        match (param(name), param(&exclusive_name)) {
+            (Some(_), Some(exclusive)) => error(
+                exclusive.span(),
+                &format!(
+                    "`range` takes a `{}` or an `{}`, not both",
+                    name, exclusive_name
+                ),
+            ),
+            (Some(value), None) => Some(RangeBound {
+                value,
+                exclusive: false,
+            }),
+            (None, Some(value)) => Some(RangeBound {
+                value,
+                exclusive: true,
+            }),
+            (None, None) => None,
+        }
+    };
+    let (min, max) = (bound("min"), bound("max"));
+    for bound in min.iter().chain(max.iter()) {
+        if !matches!(
+            bound.value,
+            syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Char(_) | syn::Lit::Str(_)
+        ) {
+            error(
+                bound.value.span(),
+                "`range` expects a number, a char or a string",
+            );
+        }
+    }
+
+    let (lower, upper) = match (&min, &max) {
+        (None, None) => error(
+            path.span(),
+            "`range` requires a bound, a `min` or a `max` inclusive or exclusive",
+        ),
+        (Some(min), Some(max)) => (min, max),
+        _ => return ValidationType::Range { min, max },
+    };
+    let ordering = match (&lower.value, &upper.value) {
+        (syn::Lit::Char(min), syn::Lit::Char(max)) => min.value().partial_cmp(&max.value()),
+        (syn::Lit::Str(min), syn::Lit::Str(max)) => min.value().partial_cmp(&max.value()),
+        (min @ syn::Lit::Int(_), max) | (min @ syn::Lit::Float(_), max)
+            if matches!(max, syn::Lit::Int(_) | syn::Lit::Float(_)) =>
+        {
+            lit_to_f64(min).partial_cmp(&lit_to_f64(max))
+        }
+        (_, max) => error(max.span(), "`range` expects bounds of the same kind"),
+    };
+    let empty = match ordering {
+        Some(std::cmp::Ordering::Greater) => true,
+        Some(std::cmp::Ordering::Equal) => lower.exclusive || upper.exclusive,
+        _ => false,
+    };
+    if empty {
+        error(
+            lower.value.span(),
+            "`range` has a lower bound no value is within",
+        );
+    }
+
+    ValidationType::Range { min, max }
+}
+
+/// Returns the value of a number literal, negative ones included
+fn lit_to_f64(lit: &syn::Lit) -> f64 {
+    match *lit {
+        syn::Lit::Int(ref n) => n.base10_parse().unwrap_or_default(),
+        syn::Lit::Float(ref n) => n.base10_parse().unwrap_or_default(),
+        _ => 0.0,
+    }
+}
+
+/// Returns the rule `one_of("a", "b", collation = "case_fold")`, the literals the field must be
+/// one of, all strings or all integers
+fn one_of_from_meta(
+    rust_identity: &str,
+    path: &syn::Path,
+    nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::token::Comma>,
+) -> ValidationType {
+    let error = |span: Span, msg: &str| -> ! {
+        abort!(
+            span,
+            "Invalid attribute #[validate] on field `{}`: {}",
+            rust_identity,
+            msg
+        );
+    };
+
+    let mut values = vec![];
+    let mut collation = None;
+    let mut suggest = None;
+    for item in nested {
+        match *item {
+            syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("suggest") => suggest = Some(path.span()),
+            syn::NestedMeta::Lit(ref lit @ syn::Lit::Str(_)) | syn::NestedMeta::Lit(ref lit @ syn::Lit::Int(_)) => {
+                values.push(lit.clone())
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue { ref path, ref lit, .. }))
+                if path.is_ident("collation") =>
+            {
+                match lit_to_string(lit) {
+                    Some(ref name) if validation::COLLATIONS.contains(&name.as_str()) => collation = Some((name.clone(), lit.span())),
+                    _ => error(
+                        lit.span(),
+                        &format!("unknown collation, expected one of {}", validation::COLLATIONS.join(", ")),
+                    ),
+                }
+            }
+            _ => error(
+                item.span(),
+                "`one_of` expects string or integer literals, an optional `collation` and `suggest`",
+            ),
+        }
+    }
+    if values.is_empty() {
+        error(path.span(), "`one_of` requires at least one value");
+    }
+    let strings = matches!(values[0], syn::Lit::Str(_));
+    if let Some(value) = values
+        .iter()
+        .find(|value| matches!(value, syn::Lit::Str(_)) != strings)
+    {
+        error(
+            value.span(),
+            "`one_of` expects values of the same kind, strings or integers",
+        );
+    }
+    let collation = match collation {
+        Some((_, span)) if !strings => error(span, "`collation` only applies to strings"),
+        Some((name, _)) => name,
+        None => "exact".to_string(),
+    };
+    if let (Some(span), false) = (suggest, strings) {
+        error(span, "`suggest` only applies to strings");
+    }
+
+    ValidationType::OneOf {
+        values,
+        collation,
+        suggest: suggest.is_some(),
+    }
+}
+
+/// Returns the strings of a list like `required_keys("a", "b")` or `groups("signup")`,
+/// aborting when it holds anything else or nothing
+fn string_lits_from_meta(
+    rust_identity: &str,
+    path: &syn::Path,
+    nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::token::Comma>,
+) -> Vec<String> {
+    let name = path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let values = nested
+        .iter()
+        .map(|item| match *item {
+            syn::NestedMeta::Lit(ref lit) => match lit_to_string(lit) {
+                Some(value) => value,
+                None => abort!(
+                    lit.span(),
+                    "Invalid attribute #[validate] on field `{}`: `{}` expects string literals",
+                    rust_identity,
+                    name
+                ),
+            },
+            _ => abort!(
+                item.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` expects string literals",
+                rust_identity,
+                name
+            ),
+        })
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        abort!(
+            path.span(),
+            "Invalid attribute #[validate] on field `{}`: `{}` requires at least one value",
+            rust_identity,
+            name
+        );
+    }
+
+    values
+}
+
+/// Returns the type of a `json_type` literal, aborting at the literal when it is not a known
+/// JSON type
+fn json_type_from_lit(field: &str, lit: &syn::Lit) -> String {
+    match lit_to_string(lit) {
+        Some(ref json_type) if validation::JSON_TYPES.contains(&json_type.as_str()) => {
+            json_type.clone()
+        }
+        _ => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: unknown JSON type", field;
+            help = "expected one of: {}", validation::JSON_TYPES.join(", ");
+        ),
+    }
+}
+
+/// Returns the type of a `fits_in` literal, aborting at the literal when it is not an integer
+/// type
+fn integer_type_from_lit(field: &str, lit: &syn::Lit) -> String {
+    match lit_to_string(lit) {
+        Some(ref target) if validation::INTEGER_TYPES.contains(&target.as_str()) => target.clone(),
+        _ => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: unknown integer type", field;
+            help = "expected one of: {}", validation::INTEGER_TYPES.join(", ");
+        ),
+    }
+}
+
+/// Returns the pattern of a `regex_inline` literal, aborting at the literal when
+/// it is not a string or is not a valid regex so the error is caught at compile time
+fn regex_inline_from_lit(field: &str, lit: &syn::Lit) -> String {
+    let pattern = match lit_to_string(lit) {
+        Some(s) => s,
+        None => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: `regex_inline` expects a string literal",
+            field
+        ),
+    };
+
+    if let Err(e) = regex::Regex::new(&pattern) {
+        abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: invalid regex `{}`", field, pattern;
+            note = "{}", e;
+        );
+    }
+
+    pattern
+}
+
+/// Returns the API version of a `since_version` or `until_version` literal, aborting at the
+/// literal when it is not a positive integer
+fn api_version_from_lit(field: &str, lit: &syn::Lit) -> u32 {
+    match lit_to_u64(lit).and_then(|version| u32::try_from(version).ok()) {
+        Some(version) if version > 0 => version,
+        _ => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: API versions are positive integers",
+            field
+        ),
+    }
+}
+
+/// Returns the condition of a `when` literal, aborting at the literal when it is not a
+/// string holding a Rust expression. The expression keeps the span of the literal so type
+/// errors in it point at the attribute
+fn condition_from_lit(field: &str, lit: &syn::Lit) -> Condition {
+    let lit_str = match *lit {
+        syn::Lit::Str(ref lit_str) => lit_str,
+        _ => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: `when` expects a string literal",
+            field
+        ),
+    };
+
+    match lit_str.parse::<syn::Expr>() {
+        Ok(expr) => Condition {
+            source: lit_str.value(),
+            expr,
+        },
+        Err(e) => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: `when` is not an expression", field;
+            note = "{}", e;
+        ),
+    }
+}
+
+/// Returns the rules of a `bundle` literal, per `validation::bundles`
+fn bundle_from_lit(field: &str, lit: &syn::Lit) -> Vec<FieldValidation> {
+    let bundle = match lit_to_string(lit) {
+        Some(bundle) => bundle,
+        None => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: `bundle` expects a string literal",
+            field
+        ),
+    };
+
+    let rules = match validation::bundles::find(&bundle, field) {
+        Some(rules) => rules,
+        None => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: unknown bundle `{}`", field, bundle;
+            help = "known bundles are {}", validation::bundles::BUNDLES.join(", ");
+        ),
+    };
+
+    rules
+        .iter()
+        .map(|rule| {
+            let param = |name: &str| rule.params.get(name);
+            let validator = match rule.rule.as_ref() {
+                "length" => ValidationType::Length {
+                    min: param("min").and_then(|min| min.as_u64()).map(LengthBound::Value),
+                    max: param("max").and_then(|max| max.as_u64()).map(LengthBound::Value),
+                },
+                "regex_inline" => match param("pattern").and_then(|pattern| pattern.as_str()) {
+                    Some(pattern) => ValidationType::RegexInline(pattern.to_string()),
+                    None => abort!(
+                        lit.span(),
+                        "Invalid attribute #[validate] on field `{}`: the `regex_inline` rule of bundle `{}` has no `pattern`",
+                        field,
+                        bundle
+                    ),
+                },
+                _ => abort!(
+                    lit.span(),
+                    "Invalid attribute #[validate] on field `{}`: bundle `{}` uses the rule `{}` the derive does not map",
+                    field,
+                    bundle,
+                    rule.rule
+                ),
+            };
+            FieldValidation::new(validator)
+        })
+        .collect()
+}
+
+/// Returns the `name = value` parameters of a rule like `date_format(style = "short")`,
+/// aborting on anything that is not a `name = value` pair
+fn find_params(
+    field: &str,
+    rule: &syn::Ident,
+    nested: &syn::punctuated::Punctuated<syn::NestedMeta, syn::token::Comma>,
+) -> Vec<(String, syn::Lit)> {
+    nested
+        .iter()
+        .map(|meta| match *meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                ref path,
+                ref lit,
+                ..
+            })) => match path.get_ident() {
+                Some(ident) => (ident.to_string(), lit.clone()),
+                None => abort!(path.span(), "Invalid attribute #[validate] on field `{}`: unexpected parameter of `{}`", field, rule),
+            },
+            _ => abort!(
+                meta.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` expects `name = value` parameters",
+                field,
+                rule
+            ),
+        })
+        .collect()
+}
+
+/// Aborts at the first parameter whose name is not one of `known`
+fn check_known_params(field: &str, params: &[(String, syn::Lit)], known: &[&str]) {
+    if let Some((name, lit)) = params
+        .iter()
+        .find(|(name, _)| !known.contains(&name.as_str()))
+    {
+        abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: unknown parameter `{}`", field, name;
+            help = "expected one of: {}", known.join(", ");
+        );
+    }
+}
+
+/// Returns the span of the value of the named parameter, for errors about its value
+fn find_param_span(params: &[(String, syn::Lit)], name: &str) -> Span {
+    params
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, lit)| lit.span())
+        .unwrap_or_else(Span::call_site)
+}
+
+/// Returns the string value of the named parameter, aborting at it when it is not a string
+fn find_string_param(field: &str, params: &[(String, syn::Lit)], name: &str) -> Option<String> {
+    params
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, lit)| match lit_to_string(lit) {
+            Some(s) => s,
+            None => abort!(
+                lit.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` expects a string literal",
+                field,
+                name
+            ),
+        })
+}
+
+/// Returns the number value of the named parameter, aborting at it when it is not a number
+fn find_u64_param(field: &str, params: &[(String, syn::Lit)], name: &str) -> Option<u64> {
+    params
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, lit)| match lit_to_u64(lit) {
+            Some(n) => n,
+            None => abort!(
+                lit.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` expects a positive integer",
+                field,
+                name
+            ),
+        })
+}
+
+/// Returns the named bound of `length`, a positive integer or the path of a const as a string,
+/// like `max = "Self::MAX_NAME"`
+fn find_length_bound_param(
+    field: &str,
+    params: &[(String, syn::Lit)],
+    name: &str,
+) -> Option<LengthBound> {
+    params.iter().find(|(n, _)| n == name).map(|(_, lit)| {
+        if let Some(n) = lit_to_u64(lit) {
+            return LengthBound::Value(n);
+        }
+        match lit_to_string(lit).and_then(|path| syn::parse_str::<syn::ExprPath>(&path).ok()) {
+            Some(path) => LengthBound::Const(Box::new(path)),
+            None => abort!(
+                lit.span(),
+                "Invalid attribute #[validate] on field `{}`: `{}` expects a positive integer or the path of a const", field, name;
+                help = "like `{} = \"Self::MAX_NAME\"` for an associated const of the struct", name;
+            ),
+        }
+    })
+}
+
+/// Returns the style of a `date_format`, aborting at the literal when it is not a known style
+fn date_style_from_lit(field: &str, lit: &syn::Lit) -> String {
+    match lit_to_string(lit) {
+        Some(ref style) if validation::DATE_STYLES.contains(&style.as_str()) => style.clone(),
+        _ => abort!(
+            lit.span(),
+            "Invalid attribute #[validate] on field `{}`: unknown date style", field;
+            help = "expected one of: {}", validation::DATE_STYLES.join(", ");
+        ),
+    }
+}
+
 fn find_original_name(meta_items: &[&syn::NestedMeta]) -> Option<String> {
     let mut original_name = None;
 
@@ -264,21 +2133,73 @@ fn find_original_name(meta_items: &[&syn::NestedMeta]) -> Option<String> {
         match **meta_item {
             syn::NestedMeta::Meta(ref item) => match *item {
                 syn::Meta::Path(_) => continue,
+                // serde reports a `rename` that is not a string itself
                 syn::Meta::NameValue(syn::MetaNameValue {
                     ref path, ref lit, ..
                 }) => {
-                    let ident = path.get_ident().unwrap();
-                    if ident == "rename" {
-                        original_name = Some(lit_to_string(lit).unwrap());
+                    if path.is_ident("rename") {
+                        original_name = lit_to_string(lit).or(original_name);
                     }
                 }
-                syn::Meta::List(syn::MetaList { ref nested, .. }) => {
-                    return find_original_name(&nested.iter().collect::<Vec<_>>());
+                // `rename(serialize = "...", deserialize = "...")`, errors are named as serialized
+                syn::Meta::List(syn::MetaList {
+                    ref path,
+                    ref nested,
+                    ..
+                }) if path.is_ident("rename") => {
+                    for meta in nested {
+                        if let syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            ref path,
+                            ref lit,
+                            ..
+                        })) = *meta
+                        {
+                            if path.is_ident("serialize") {
+                                original_name = lit_to_string(lit).or(original_name);
+                            }
+                        }
+                    }
                 }
+                syn::Meta::List(_) => continue,
             },
-            _ => unreachable!(),
+            syn::NestedMeta::Lit(_) => continue,
         };
     }
 
     original_name
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{find_param_span, find_params};
+    use proc_macro2::Span;
+
+    fn params(rule: &str) -> Vec<(String, syn::Lit)> {
+        match syn::parse_str::<syn::Meta>(rule) {
+            Ok(syn::Meta::List(list)) => {
+                find_params("name", list.path.get_ident().unwrap(), &list.nested)
+            }
+            _ => panic!("`{}` is no rule with parameters", rule),
+        }
+    }
+
+    #[test]
+    fn find_param_span_of_the_value() {
+        let params = params("length(min = 8, max = 4)");
+        let (min, max) = (
+            find_param_span(&params, "min"),
+            find_param_span(&params, "max"),
+        );
+        assert_eq!((1, 13), (min.start().line, min.start().column));
+        assert_eq!((1, 22), (max.start().line, max.start().column));
+        assert_eq!(23, max.end().column);
+    }
+
+    #[test]
+    fn find_param_span_of_a_missing_param() {
+        let params = params("range(exclusive_min = 1)");
+        let missing = find_param_span(&params, "min");
+        assert_eq!(Span::call_site().start(), missing.start());
+        assert_eq!(Span::call_site().end(), missing.end());
+    }
+}
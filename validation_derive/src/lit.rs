@@ -14,3 +14,14 @@ pub fn lit_to_string(lit: &syn::Lit) -> Option<String> {
         _ => None,
     }
 }
+
+/// Converts the given `Lit` to a `Option<u64>`
+///
+/// # Arguments
+/// * `lit` - the `Lit` to convert
+pub fn lit_to_u64(lit: &syn::Lit) -> Option<u64> {
+    match *lit {
+        syn::Lit::Int(ref i) => i.base10_parse().ok(),
+        _ => None,
+    }
+}
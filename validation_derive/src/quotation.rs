@@ -10,8 +10,14 @@
 //! Options and other fields are handled via the `COW_TYPE` and `NUMBER_TYPES` to prevent any
 //! complications with trying to figure out types wrapped in `Option`
 
-use crate::field_validation::FieldValidation;
-use crate::types::ValidationType;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::container::UnknownFields;
+use crate::field_validation::{Deprecation, Enforcement, FieldValidation, Versions};
+use crate::lit::lit_to_string;
+use crate::types::{LengthBound, RangeBound, ValidationType};
+use proc_macro2::Span;
 use regex::Regex;
 
 lazy_static! {
@@ -58,12 +64,40 @@ pub const NUMBER_TYPES: [&str; 36] = [
     "Option<Option<f64>>",
 ];
 
+/// Constant to handle the maps a `#[serde(flatten)]` field can capture the unknown keys in
+pub const MAP_TYPES: [&str; 4] = ["HashMap", "BTreeMap", "Map", "IndexMap"];
+
+/// Constant to handle the chrono and time types `range` parses its bounds as, with the text
+/// they parse
+pub const DATE_TYPES: [(&str, &str); 6] = [
+    ("NaiveDate", r"^\d{4}-\d{2}-\d{2}$"),
+    (
+        "NaiveDateTime",
+        r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?$",
+    ),
+    (
+        "DateTime",
+        r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})$",
+    ),
+    ("Date", r"^\d{4}-\d{2}-\d{2}$"),
+    (
+        "PrimitiveDateTime",
+        r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?$",
+    ),
+    (
+        "OffsetDateTime",
+        r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})$",
+    ),
+];
+
 /// Struct helper to allow storing variables used in the generation of quoted code
 #[derive(Debug)]
 pub struct FieldQuoter {
     ident: syn::Ident,
     name: String,
     _type: String,
+    //Whether or not `ident` is a reference bound to the value of a variant, not a field of `self`
+    binding: bool,
 }
 
 impl FieldQuoter {
@@ -75,20 +109,175 @@ impl FieldQuoter {
     /// * `name` - Field name
     /// * `_type` - `String` representation of the field type
     pub fn new(ident: syn::Ident, name: String, _type: String) -> FieldQuoter {
-        FieldQuoter { ident, name, _type }
+        FieldQuoter {
+            ident,
+            name,
+            _type,
+            binding: false,
+        }
+    }
+
+    /// Returns the `FieldQuoter` of the value of a variant of the field, bound by `wrap_in_variant`
+    pub fn variant_quoter(&self) -> FieldQuoter {
+        FieldQuoter {
+            ident: syn::Ident::new("variant_value", Span::call_site()),
+            name: self.name.clone(),
+            _type: String::new(),
+            binding: true,
+        }
     }
 
+    /// Returns whether or not the field is a map a `#[serde(flatten)]` field can capture the
+    /// unknown keys in
+    pub fn is_map(&self) -> bool {
+        let type_name = self._type.split('<').next().unwrap_or(&self._type);
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        MAP_TYPES.contains(&type_name)
+    }
+
+    /// Returns whether or not the field is a `uuid::Uuid`, wrapped in `Option`s or not
+    pub fn is_uuid(&self) -> bool {
+        self.type_name() == "Uuid"
+    }
+
+    /// Returns the parameter to hand to the rule for this field
+    /// Options are expected to be unwrapped first with `wrap_if_option`
     pub fn quote_validate_parameter(&self) -> proc_macro2::TokenStream {
         let ident = &self.ident;
 
-        if self._type.starts_with("Option<") {
+        if self.binding || self._type.starts_with("Option<") {
             quote!(#ident)
-        } else if COW_TYPE.is_match(&self._type.as_ref()) {
-            quote!(&self.#ident.as_ref())
+        } else if COW_TYPE.is_match(self._type.as_ref()) {
+            quote!(self.#ident.as_ref())
         } else if self._type.starts_with('&') || NUMBER_TYPES.contains(&self._type.as_ref()) {
+            quote!(self.#ident)
+        } else {
             quote!(&self.#ident)
+        }
+    }
+
+    /// Returns the parameter to hand to the rule as a reference, numbers included
+    pub fn quote_validate_reference(&self) -> proc_macro2::TokenStream {
+        let validate_parameter = self.quote_validate_parameter();
+
+        if !self.binding && NUMBER_TYPES.contains(&self._type.as_ref()) {
+            quote!(&#validate_parameter)
         } else {
-            quote!(self.#ident)
+            validate_parameter
+        }
+    }
+
+    /// Returns the name of the type of the value, without its `Option`s, path or generics
+    /// Empty for the value of a variant, its type being unknown to the derive
+    pub fn type_name(&self) -> &str {
+        let mut type_name = self._type.as_str();
+        while let Some(inner) = type_name.strip_prefix("Option<") {
+            type_name = inner.strip_suffix('>').unwrap_or(inner);
+        }
+        let type_name = type_name.split('<').next().unwrap_or(type_name);
+        type_name.rsplit("::").next().unwrap_or(type_name)
+    }
+
+    /// Wraps the generated rule in an `if let Some(..)` when the field is an `Option`
+    /// Rules other than `not_null` only apply once a value is present
+    pub fn wrap_if_option(&self, tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let pattern = self.quote_optional_pattern();
+
+        if self.binding {
+            tokens
+        } else if self._type.starts_with("Option<Option<") {
+            quote!(
+                if let Some(Some(#pattern)) = self.#ident {
+                    #tokens
+                }
+            )
+        } else if self._type.starts_with("Option<") {
+            quote!(
+                if let Some(#pattern) = self.#ident {
+                    #tokens
+                }
+            )
+        } else {
+            tokens
+        }
+    }
+
+    /// Returns the condition for the field to hold a value, `None` when it is not an `Option`
+    pub fn quote_has_value(&self) -> Option<proc_macro2::TokenStream> {
+        let ident = &self.ident;
+
+        if self._type.starts_with("Option<Option<") {
+            Some(quote!(matches!(self.#ident, Some(Some(_)))))
+        } else if self._type.starts_with("Option<") {
+            Some(quote!(self.#ident.is_some()))
+        } else {
+            None
+        }
+    }
+
+    /// Wraps the rules of a variant in an `if let` binding the value of the variant, the
+    /// enum being the type of the field, wrapped in `Option`s or not
+    pub fn wrap_in_variant(
+        &self,
+        variant: &str,
+        rules: Vec<proc_macro2::TokenStream>,
+    ) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let pattern = self.quote_variant_pattern(variant, quote!(ref variant_value));
+
+        quote!(
+            if let #pattern = self.#ident {
+                #(#rules)*
+            }
+        )
+    }
+
+    /// Returns the condition for the field to hold the variant
+    pub fn quote_has_variant(&self, variant: &str) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+        let pattern = self.quote_variant_pattern(variant, quote!(..));
+
+        quote!(matches!(self.#ident, #pattern))
+    }
+
+    fn quote_variant_pattern(
+        &self,
+        variant: &str,
+        inner: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let mut enum_type = self._type.as_str();
+        let mut options = 0;
+        while let Some(inner_type) = enum_type
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            enum_type = inner_type;
+            options += 1;
+        }
+        // Generic arguments are left to inference, a pattern cannot hold them without a turbofish
+        let enum_type = enum_type.split('<').next().unwrap_or(enum_type);
+        let enum_path = syn::parse_str::<syn::Path>(enum_type)
+            .unwrap_or_else(|_| abort!(self.ident.span(), "`on_variant` requires an enum field"));
+        let variant = syn::Ident::new(variant, Span::call_site());
+
+        let mut pattern = quote!(#enum_path::#variant(#inner));
+        for _ in 0..options {
+            pattern = quote!(Some(#pattern));
+        }
+        pattern
+    }
+
+    fn quote_optional_pattern(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+
+        if self._type.starts_with("Option<&")
+            || self._type.starts_with("Option<Option<&")
+            || NUMBER_TYPES.contains(&self._type.as_ref())
+        {
+            quote!(#ident)
+        } else {
+            quote!(ref #ident)
         }
     }
 }
@@ -106,9 +295,660 @@ pub fn create_field_validation(
     validations: &mut Vec<proc_macro2::TokenStream>,
 ) {
     match validation.validator {
-        ValidationType::NotNull { .. } => {
-            validations.push(create_not_null_validation(&field_quoter, validation))
+        ValidationType::NotNull => {
+            validations.push(create_not_null_validation(field_quoter, validation))
+        }
+        ValidationType::RegexInline(ref pattern) => validations.push(
+            create_regex_inline_validation(field_quoter, validation, pattern),
+        ),
+        ValidationType::Past | ValidationType::Future | ValidationType::MinAge(_) => {
+            validations.push(create_temporal_validation(field_quoter, validation))
+        }
+        ValidationType::DateFormat { .. } | ValidationType::NumberFormat { .. } => {
+            validations.push(create_locale_format_validation(field_quoter, validation))
+        }
+        ValidationType::Length { ref min, ref max } => validations.push(create_length_validation(
+            field_quoter,
+            validation,
+            min.as_ref(),
+            max.as_ref(),
+        )),
+        ValidationType::JsonType(_)
+        | ValidationType::JsonMaxDepth(_)
+        | ValidationType::JsonMaxKeys(_)
+        | ValidationType::RequiredKeys(_) => {
+            validations.push(create_json_value_validation(field_quoter, validation))
+        }
+        ValidationType::Finite => {
+            validations.push(create_finite_validation(field_quoter, validation))
+        }
+        ValidationType::UuidVersion(_) | ValidationType::UuidNotNil => {
+            validations.push(create_uuid_validation(field_quoter, validation))
+        }
+        ValidationType::FitsIn(ref target) => {
+            validations.push(create_fits_in_validation(field_quoter, validation, target))
+        }
+        ValidationType::CanonicalIdentifier { max } => validations.push(
+            create_canonical_identifier_validation(field_quoter, validation, max),
+        ),
+        ValidationType::Signed {
+            ref payload_fields,
+            ref verifier,
+        } => validations.push(create_signed_validation(
+            field_quoter,
+            validation,
+            payload_fields,
+            verifier,
+        )),
+        ValidationType::FlagsOf(ref flags) => {
+            validations.push(create_flags_of_validation(field_quoter, validation, flags))
+        }
+        ValidationType::Range { ref min, ref max } => validations.push(create_range_validation(
+            field_quoter,
+            validation,
+            min.as_ref(),
+            max.as_ref(),
+        )),
+        ValidationType::OneOf {
+            ref values,
+            ref collation,
+            ..
+        } => validations.push(create_one_of_validation(
+            field_quoter,
+            validation,
+            values,
+            collation,
+        )),
+        ValidationType::RecentlySeen { window, ref key_fn } => validations.push(
+            create_recently_seen_validation(field_quoter, validation, window, key_fn),
+        ),
+        // Generated by `create_nested_validation`, its errors keep their path
+        ValidationType::Nested { .. } => {}
+        // The rules of the variant are generated with `FieldQuoter::wrap_in_variant`
+        ValidationType::OnVariant { .. } => {}
+        // Generated by `create_update_validation` for `UpdateValidate`
+        ValidationType::Immutable | ValidationType::MonotonicIncrease => {}
+    }
+}
+
+/// Wraps rules in `warn` enforcement: their failures are reported to the observer of the
+/// environment rather than collected as errors
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `rules` - `Vec<TokenStream>` of the generated rules to wrap
+pub fn wrap_in_warning(
+    field_quoter: &FieldQuoter,
+    rules: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+
+    // The rules push to `errors`, shadowed here so their failures do not reach the result
+    quote!(
+        {
+            let mut errors = ::std::vec::Vec::new();
+            #(#rules)*
+            for err in errors {
+                ctx.environment().report_warning(#field_name, &err);
+            }
+        }
+    )
+}
+
+/// Wraps the rules of a field so their failures are collected along with the field name
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `rules` - `Vec<TokenStream>` of the generated rules of the field
+pub fn wrap_in_field(
+    field_quoter: &FieldQuoter,
+    rules: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+
+    quote!(
+        {
+            let mut errors = ::std::vec::Vec::new();
+            #(#rules)*
+            field_errors.extend_field(#field_name, errors);
+        }
+    )
+}
+
+/// Wraps rules in the condition of their `when`, they only run when it holds
+/// Rules without a condition are returned as they are
+///
+/// # Arguments
+///
+/// * `validation` - `FieldValidation` of the rules
+/// * `rules` - `Vec<TokenStream>` of the generated rules to wrap
+pub fn wrap_in_condition(
+    validation: &FieldValidation,
+    rules: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    match validation.condition {
+        Some(ref condition) => {
+            let expr = &condition.expr;
+            quote!(
+                if #expr {
+                    #(#rules)*
+                }
+            )
+        }
+        None => quote!(#(#rules)*),
+    }
+}
+
+/// Wraps rules in the check of the API versions they apply to, per `since_version` and
+/// `until_version`. The latest version applies when the context has no API version
+/// Rules without versions are returned as they are
+pub fn wrap_in_versions(
+    validation: &FieldValidation,
+    rules: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    match validation.versions {
+        Some(Versions {
+            since,
+            until,
+            latest,
+        }) => {
+            let since = quote_option(since);
+            let until = quote_option(until);
+            quote!(
+                if _validation::ApiVersions::new(#since, #until)
+                    .contains(ctx.api_version().unwrap_or(#latest))
+                {
+                    #(#rules)*
+                }
+            )
+        }
+        None => quote!(#(#rules)*),
+    }
+}
+
+/// Wraps statements generated for a rule in the `cfg` of the rule, they are only compiled into
+/// the builds it holds for. Statements of rules without a `cfg` are returned as they are
+///
+/// # Arguments
+///
+/// * `validation` - `FieldValidation` of the rule
+/// * `statements` - `TokenStream` of the generated statements to wrap
+pub fn wrap_in_cfg(
+    validation: &FieldValidation,
+    statements: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match validation.cfg {
+        Some(ref predicate) => {
+            let predicate = syn::parse_str::<syn::NestedMeta>(predicate).unwrap();
+            quote!(
+                #[cfg(#predicate)]
+                {
+                    #statements
+                }
+            )
+        }
+        None => statements,
+    }
+}
+
+/// Wraps a deprecated rule so its failures are reported to the observer of the environment,
+/// the failures are still handled per the enforcement of the rule
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` of the rule
+/// * `deprecation` - `Deprecation` of the rule
+/// * `rules` - `Vec<TokenStream>` of the generated rules to wrap
+pub fn wrap_in_deprecation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    deprecation: &Deprecation,
+    rules: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let code = &validation.code;
+    let deprecation = quote_deprecation(deprecation);
+
+    quote!(
+        {
+            let errors_before = errors.len();
+            #(#rules)*
+            if errors.len() > errors_before {
+                ctx.environment().report_deprecated(#field_name, #code, &#deprecation);
+            }
+        }
+    )
+}
+
+fn quote_deprecation(deprecation: &Deprecation) -> proc_macro2::TokenStream {
+    let since = &deprecation.since;
+    let note = match deprecation.note {
+        Some(ref note) => quote!(Some(#note)),
+        None => quote!(None),
+    };
+
+    quote!(_validation::Deprecation::new(#since, #note))
+}
+
+// Quotes an API version of `since_version` or `until_version`, `None` when it is not set
+fn quote_option(version: Option<u32>) -> proc_macro2::TokenStream {
+    match version {
+        Some(version) => quote!(::std::option::Option::Some(#version)),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+/// Generates the `FieldConstraintDescription` of the rule, for `describe_constraints()`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to describe
+pub fn create_constraint_description(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let code = &validation.code;
+    let message = match validation.message {
+        Some(ref m) => quote!(Some(#m)),
+        None => quote!(None),
+    };
+    let params = match validation.validator {
+        ValidationType::NotNull => quote!(),
+        ValidationType::RegexInline(ref pattern) => quote!(.param("pattern", #pattern)),
+        ValidationType::Past
+        | ValidationType::Future
+        | ValidationType::Finite
+        | ValidationType::UuidNotNil => quote!(),
+        ValidationType::UuidVersion(version) => quote!(.param("version", #version)),
+        ValidationType::MinAge(years) => quote!(.param("years", #years)),
+        ValidationType::FitsIn(ref target) => quote!(.param("type", #target)),
+        ValidationType::CanonicalIdentifier { max } => quote!(.param("max", #max)),
+        ValidationType::Signed {
+            ref payload_fields, ..
+        } => {
+            let names = payload_fields.iter().map(|(name, _)| name);
+            quote!(.param("payload_fields", vec![#(#names),*]))
+        }
+        ValidationType::FlagsOf(ref flags) => quote!(.param("flags", #flags)),
+        ValidationType::DateFormat {
+            ref style,
+            ref locale,
+        } => {
+            let locale = quote_param_option("locale", locale);
+            quote!(.param("style", #style)#locale)
+        }
+        ValidationType::NumberFormat { ref locale } => quote_param_option("locale", locale),
+        ValidationType::Length { ref min, ref max } => {
+            let min = min.as_ref().map(|min| {
+                let min = min.quote_u64();
+                quote!(.param("min", #min))
+            });
+            let max = max.as_ref().map(|max| {
+                let max = max.quote_u64();
+                quote!(.param("max", #max))
+            });
+            quote!(#min #max)
+        }
+        ValidationType::Range { ref min, ref max } => {
+            let min = min
+                .as_ref()
+                .map(|min| quote_range_param(&min.param_name("min"), &min.value));
+            let max = max
+                .as_ref()
+                .map(|max| quote_range_param(&max.param_name("max"), &max.value));
+            quote!(#min #max)
+        }
+        ValidationType::Nested { max_depth } => max_depth
+            .map(|max_depth| quote!(.param("max_depth", #max_depth)))
+            .unwrap_or_default(),
+        ValidationType::JsonType(ref json_type) => quote!(.param("type", #json_type)),
+        ValidationType::JsonMaxDepth(max_depth) => quote!(.param("max_depth", #max_depth)),
+        ValidationType::JsonMaxKeys(max_keys) => quote!(.param("max_keys", #max_keys)),
+        ValidationType::RequiredKeys(ref keys) => quote!(.param("keys", ::std::vec![#(#keys),*])),
+        ValidationType::OneOf {
+            ref values,
+            ref collation,
+            ..
+        } => {
+            let collation = Some(collation)
+                .filter(|collation| *collation != "exact")
+                .map(|collation| quote!(.param("collation", #collation)));
+            quote!(.param("values", ::std::vec![#(#values),*])#collation)
+        }
+        ValidationType::RecentlySeen { window, .. } => quote!(.param("window", #window)),
+        ValidationType::OnVariant { .. }
+        | ValidationType::Immutable
+        | ValidationType::MonotonicIncrease => {
+            quote!()
+        }
+    };
+
+    let deprecated = validation.deprecation.as_ref().map(|deprecation| {
+        let deprecation = quote_deprecation(deprecation);
+        quote!(.deprecated(#deprecation))
+    });
+
+    let when = validation.condition.as_ref().map(|condition| {
+        let source = &condition.source;
+        quote!(.when(#source))
+    });
+
+    let versions = validation.versions.map(|versions| {
+        let since = quote_option(versions.since);
+        let until = quote_option(versions.until);
+        quote!(.versions(#since, #until))
+    });
+
+    let groups = if validation.groups.is_empty() {
+        quote!()
+    } else {
+        let groups = &validation.groups;
+        quote!(.groups(&[#(#groups),*]))
+    };
+
+    let weight = validation.weight.map(|weight| quote!(.weight(#weight)));
+
+    quote!(
+        _validation::FieldConstraintDescription::new(#field_name, #code, #message)#params#deprecated#when#versions#groups#weight
+    )
+}
+
+/// Generates the `FieldConstraintDescription` of a rule of a variant, for `describe_constraints()`
+///
+/// # Arguments
+///
+/// * `variant_quoter` - `FieldQuoter` of the value of the variant
+/// * `variant` - `&str` name of the variant
+/// * `validation` - `FieldValidation` to describe
+pub fn create_variant_constraint_description(
+    variant_quoter: &FieldQuoter,
+    variant: &str,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let description = create_constraint_description(variant_quoter, validation);
+
+    quote!(#description.param("variant", #variant))
+}
+
+/// Generates the statement adding a value failing the rule to the examples of
+/// `invalid_examples()`, the `Default` of the struct with the field changed
+/// Returns `None` when no such value can be made from the rule alone, or the rule may not fail
+/// `validate_fields`: it warns, is deprecated, has a condition or not for the latest API version
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` of the rule
+/// * `enforcement` - `Enforcement` of the rule
+pub fn create_invalid_example(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    enforcement: Enforcement,
+) -> Option<proc_macro2::TokenStream> {
+    let in_latest = validation
+        .versions
+        .is_none_or(|versions| versions.contains(versions.latest));
+    if field_quoter.binding
+        || enforcement == Enforcement::Warn
+        || validation.deprecation.is_some()
+        || validation.condition.is_some()
+        || !in_latest
+    {
+        return None;
+    }
+
+    let type_name = field_quoter.type_name();
+    let is_text = type_name == "String";
+    let value = match validation.validator {
+        ValidationType::NotNull if field_quoter._type.starts_with("Option<") => {
+            let ident = &field_quoter.ident;
+            let code = &validation.code;
+            return Some(quote!(
+                let mut example = <Self as ::std::default::Default>::default();
+                example.#ident = ::std::option::Option::None;
+                examples.push((example, #code));
+            ));
+        }
+        ValidationType::Length {
+            min: Some(LengthBound::Value(min)),
+            ..
+        } if min > 0 && (is_text || type_name == "Vec") => {
+            quote!(::std::default::Default::default())
+        }
+        // The value of a const is only known once compiled, the example is chosen then
+        ValidationType::Length {
+            min: Some(LengthBound::Const(ref min)),
+            ref max,
+        } if is_text || type_name == "Vec" => {
+            let empty = quote_example_field(
+                field_quoter,
+                validation,
+                quote!(::std::default::Default::default()),
+            );
+            let too_long = max.as_ref().map(|max| {
+                quote_example_field(field_quoter, validation, quote_too_long(is_text, max))
+            });
+            return Some(quote!(
+                if (#min as usize) > 0 {
+                    #empty
+                } else {
+                    #too_long
+                }
+            ));
+        }
+        ValidationType::Length {
+            max: Some(ref max), ..
+        } if is_text || type_name == "Vec" => quote_too_long(is_text, max),
+        ValidationType::Range { ref min, ref max } if NUMBER_TYPES.contains(&type_name) => {
+            let ty = syn::parse_str::<syn::Type>(type_name).unwrap();
+            let is_float = type_name.starts_with('f');
+            // The bound itself when excluded, else the next value past it, if the type has one
+            let outside = |bound: &RangeBound, below: bool| {
+                let value = &bound.value;
+                match (bound.exclusive, is_float, below) {
+                    (true, _, _) => quote!(::std::option::Option::Some(#value as #ty)),
+                    (false, true, true) => quote!(::std::option::Option::Some(#value as #ty - 1.0)),
+                    (false, true, false) => {
+                        quote!(::std::option::Option::Some(#value as #ty + 1.0))
+                    }
+                    (false, false, true) => quote!((#value as #ty).checked_sub(1)),
+                    (false, false, false) => quote!((#value as #ty).checked_add(1)),
+                }
+            };
+            let outside = match (min, max) {
+                (Some(min), _) => outside(min, true),
+                (None, Some(max)) => outside(max, false),
+                (None, None) => return None,
+            };
+            let example = quote_example_field(field_quoter, validation, quote!(value));
+            return Some(quote!(
+                if let ::std::option::Option::Some(value) = #outside {
+                    #example
+                }
+            ));
+        }
+        ValidationType::Finite => {
+            let ty = syn::parse_str::<syn::Type>(type_name).unwrap();
+            quote!(#ty::NAN)
+        }
+        ValidationType::UuidVersion(_) | ValidationType::UuidNotNil => {
+            quote!(_validation::__private::uuid::Uuid::nil())
+        }
+        ValidationType::CanonicalIdentifier { .. } if is_text => {
+            quote!(::std::default::Default::default())
+        }
+        ValidationType::FitsIn(_)
+        | ValidationType::DateFormat { .. }
+        | ValidationType::NumberFormat { .. }
+            if is_text =>
+        {
+            quote!("?".to_string())
+        }
+        ValidationType::OneOf { ref values, .. } if is_text => {
+            let values = values
+                .iter()
+                .filter_map(lit_to_string)
+                .map(|value| value.to_lowercase())
+                .collect::<Vec<_>>();
+            let mut outside = "invalid".to_string();
+            while values.contains(&outside) {
+                outside.push('_');
+            }
+            quote!(#outside.to_string())
+        }
+        ValidationType::JsonType(ref json_type) if type_name == "Value" => {
+            if json_type == "null" {
+                quote!(_validation::__private::serde_json::Value::Bool(false))
+            } else {
+                quote!(_validation::__private::serde_json::Value::Null)
+            }
+        }
+        _ => return None,
+    };
+
+    Some(quote_example_field(field_quoter, validation, value))
+}
+
+// Quotes a value one element longer than the `max` of `length`, text or a `Vec`
+fn quote_too_long(is_text: bool, max: &LengthBound) -> proc_macro2::TokenStream {
+    let max = max.quote_usize();
+    if is_text {
+        quote!("a".repeat(#max + 1))
+    } else {
+        quote!(::std::iter::repeat_with(::std::default::Default::default).take(#max + 1).collect())
+    }
+}
+
+// Quotes the example of the rule with the field set to the value, in the `Some`s of its type
+fn quote_example_field(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let ident = &field_quoter.ident;
+    let code = &validation.code;
+    let value = if field_quoter._type.starts_with("Option<Option<") {
+        quote!(::std::option::Option::Some(::std::option::Option::Some(#value)))
+    } else if field_quoter._type.starts_with("Option<") {
+        quote!(::std::option::Option::Some(#value))
+    } else {
+        value
+    };
+
+    quote!(
+        let mut example = <Self as ::std::default::Default>::default();
+        example.#ident = #value;
+        examples.push((example, #code));
+    )
+}
+
+/// Generates the `PlannedRule` of the rule, for `explain()`
+/// The rule is not run, only the reasons it would be skipped are checked
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to plan
+pub fn create_planned_rule(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let code = &validation.code;
+    let status = quote_planned_status(field_quoter, validation);
+    let weight = validation.weight.map(|weight| quote!(.weight(#weight)));
+
+    quote!(
+        _validation::PlannedRule::new(#field_name, #code, #status)#weight
+    )
+}
+
+/// Generates the `PlannedRule` of a rule of a variant, for `explain()`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` of the field
+/// * `variant_quoter` - `FieldQuoter` of the value of the variant
+/// * `variant` - `&str` name of the variant
+/// * `validation` - `FieldValidation` to plan
+pub fn create_variant_planned_rule(
+    field_quoter: &FieldQuoter,
+    variant_quoter: &FieldQuoter,
+    variant: &str,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let code = &validation.code;
+    let has_variant = field_quoter.quote_has_variant(variant);
+    let status = quote_planned_status(variant_quoter, validation);
+    let other_status = match field_quoter.quote_has_value() {
+        Some(has_value) => quote!(
+            if #has_value {
+                _validation::PlannedRuleStatus::Skipped(
+                    _validation::SkipReason::OtherVariant(#variant.to_string()),
+                )
+            } else {
+                _validation::PlannedRuleStatus::Skipped(_validation::SkipReason::NoValue)
+            }
+        ),
+        None => quote!(_validation::PlannedRuleStatus::Skipped(
+            _validation::SkipReason::OtherVariant(#variant.to_string()),
+        )),
+    };
+
+    let weight = validation.weight.map(|weight| quote!(.weight(#weight)));
+
+    quote!(
+        _validation::PlannedRule::new(#field_name, #code, if #has_variant { #status } else { #other_status })#weight
+    )
+}
+
+fn quote_planned_status(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let status = match (&validation.validator, field_quoter.quote_has_value()) {
+        (ValidationType::NotNull, _) | (_, None) => quote!(_validation::PlannedRuleStatus::Run),
+        (_, Some(has_value)) => quote!(
+            if #has_value {
+                _validation::PlannedRuleStatus::Run
+            } else {
+                _validation::PlannedRuleStatus::Skipped(_validation::SkipReason::NoValue)
+            }
+        ),
+    };
+
+    let status = match validation.condition {
+        Some(ref condition) => {
+            let expr = &condition.expr;
+            let source = &condition.source;
+            quote!(
+                if #expr {
+                    #status
+                } else {
+                    _validation::PlannedRuleStatus::Skipped(
+                        _validation::SkipReason::Condition(#source.to_string()),
+                    )
+                }
+            )
+        }
+        None => status,
+    };
+
+    // Plans are for the latest API version, the rules of older versions only are skipped
+    match validation.versions {
+        Some(versions) if !versions.contains(versions.latest) => {
+            let latest = versions.latest;
+            quote!(_validation::PlannedRuleStatus::Skipped(
+                _validation::SkipReason::ApiVersion(#latest),
+            ))
         }
+        _ => status,
     }
 }
 
@@ -127,9 +967,9 @@ pub fn create_not_null_validation(
     let ident = &field_quoter.ident;
     let validate_parameter = quote!(&self.#ident);
 
-    let quoted_error = quote_err(&validation);
+    let quoted_error = quote_err(validation);
     let quoted = quote!(
-        if !::validation::is_not_null(#validate_parameter) {
+        if !_validation::is_not_null(#validate_parameter) {
             #quoted_error
             errors.push(err)
         }
@@ -138,15 +978,832 @@ pub fn create_not_null_validation(
     quoted
 }
 
-fn quote_err(validation: &FieldValidation) -> proc_macro2::TokenStream {
-    let code = &validation.code;
-    // let _add_message_quote = if let Some(ref m) = validation.message {
-    //     quote!(err.message = Some(::std::borrow::Cow::from(#m));)
-    // } else {
-    //     quote!()
-    // };
+/// Generates the validation rule `nested`
+/// The field is validated with its own `FieldValidation` one level deeper, its errors are
+/// collected with the field name prepended to their path
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `enforcement` - `Enforcement` of the rule, in `warn` the errors are reported instead
+/// * `max_depth` - `Option<u64>` maximum depth, lowering the one of the context
+/// * `flattened` - whether or not the field is `#[serde(flatten)]`, its errors then keep their
+///   path as it is
+pub fn create_nested_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    enforcement: Enforcement,
+    max_depth: Option<u64>,
+    flattened: bool,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let ident = &field_quoter.ident;
+    let nested_ctx = match max_depth {
+        Some(max_depth) => {
+            let max_depth = max_depth as usize;
+            quote!(ctx.nested().with_max_depth(::std::cmp::min(ctx.max_depth(), #max_depth)))
+        }
+        None => quote!(ctx.nested()),
+    };
+    let collect = match enforcement {
+        // The fields of a `#[serde(flatten)]` value are fields of the struct itself
+        Enforcement::Deny if flattened => quote!(for err in nested_errors {
+            field_errors.push(err);
+        }),
+        Enforcement::Warn if flattened => quote!(
+            for err in nested_errors {
+                ctx.environment()
+                    .report_warning(err.field.as_deref().unwrap_or(#field_name), &err.error);
+            }
+        ),
+        Enforcement::Deny => quote!(field_errors.extend_nested(#field_name, nested_errors);),
+        Enforcement::Warn => quote!(
+            for err in nested_errors {
+                let err = err.prefixed(#field_name);
+                ctx.environment()
+                    .report_warning(err.field.as_deref().unwrap_or(#field_name), &err.error);
+            }
+        ),
+    };
+    let deprecated = validation.deprecation.as_ref().map(|deprecation| {
+        let code = &validation.code;
+        let deprecation = quote_deprecation(deprecation);
+        quote!(
+            if !nested_errors.is_empty() {
+                ctx.environment().report_deprecated(#field_name, #code, &#deprecation);
+            }
+        )
+    });
+
+    quote!(
+        {
+            let nested_errors =
+                _validation::FieldValidation::field_errors(&self.#ident, &#nested_ctx);
+            #deprecated
+            #collect
+        }
+    )
+}
+
+/// Generates the check of the keys captured by a `#[serde(flatten)]` map, per the
+/// `unknown_fields` policy of the struct. The keys are checked in order so the errors are
+/// the same whatever the map
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` of the map field
+/// * `unknown_fields` - `UnknownFields` policy, nothing is generated to `Allow`
+pub fn create_unknown_fields_validation(
+    field_quoter: &FieldQuoter,
+    unknown_fields: UnknownFields,
+) -> proc_macro2::TokenStream {
+    let ident = &field_quoter.ident;
+    let report = match unknown_fields {
+        UnknownFields::Allow => return quote!(),
+        UnknownFields::Warn => quote!(ctx.environment().report_warning(&key, &err)),
+        UnknownFields::Deny => quote!(field_errors.push(_validation::FieldError::new(&key, err))),
+    };
+
+    quote!(
+        {
+            let mut unknown_keys = self.#ident.keys().map(|key| key.to_string()).collect::<::std::vec::Vec<_>>();
+            unknown_keys.sort();
+            for key in unknown_keys {
+                let err = _validation::ValidationError::FieldMismatch("unknown_field".to_string());
+                #report;
+            }
+        }
+    )
+}
+
+/// Generates the validation rule `regex_inline`
+/// Returns the `TokenStream` of the generated rule, matching against the shared static
+/// generated by `create_regex_statics` for the pattern
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `pattern` - `&str` the inline regex literal
+pub fn create_regex_inline_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    pattern: &str,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let regex_static = regex_static_ident(pattern);
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !_validation::matches_regex(#validate_parameter, &#regex_static) {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the temporal validation rules `past`, `future` and `min_age`
+/// The rules compare against the time of the `ValidationContext`, never the system time
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_temporal_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let check = match validation.validator {
+        ValidationType::Past => quote!(_validation::is_past(#validate_parameter, ctx.now())),
+        ValidationType::Future => quote!(_validation::is_future(#validate_parameter, ctx.now())),
+        ValidationType::MinAge(years) => {
+            quote!(_validation::is_min_age(#validate_parameter, #years, ctx.now()))
+        }
+        _ => unreachable!("Only temporal rules are generated here"),
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `length`
+/// Returns the `TokenStream` of the generated rule
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `min` - `Option<&LengthBound>` minimum length, per the `HasLen` of the field
+/// * `max` - `Option<&LengthBound>` maximum length, per the `HasLen` of the field
+pub fn create_length_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    min: Option<&LengthBound>,
+    max: Option<&LengthBound>,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let quote_bound = |bound: Option<&LengthBound>| match bound {
+        Some(b) => {
+            let b = b.quote_usize();
+            quote!(Some(#b))
+        }
+        None => quote!(None),
+    };
+    let min = quote_bound(min);
+    let max = quote_bound(max);
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !_validation::is_length(#validate_parameter, #min, #max) {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `canonical_identifier`, on text
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `max` - maximum length of the identifier in characters
+pub fn create_canonical_identifier_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    max: u64,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let max = max as usize;
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !_validation::is_canonical_identifier(::std::convert::AsRef::<str>::as_ref(#validate_parameter), #max) {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `fits_in`, the error code being the one of the reason the
+/// text does not fit unless the rule has a message
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `target` - name of the integer type, one of `validation::INTEGER_TYPES`
+pub fn create_fits_in_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    target: &str,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let message = match validation.message {
+        Some(ref message) => quote!(#message),
+        None => quote!(code),
+    };
+
+    let quoted = quote!(
+        if let Err(code) = _validation::fits_in(#validate_parameter, #target) {
+            let err = _validation::ValidationError::FieldMismatch(#message.to_string());
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `signed`, a field without a signature failing it
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` of the signature field
+/// * `validation` - `FieldValidation` to add
+/// * `payload_fields` - serialized names and identities of the signed fields
+/// * `verifier` - path of the `fn(&[u8], &Signature, &ValidationContext) -> bool`
+pub fn create_signed_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    payload_fields: &[(String, syn::Ident)],
+    verifier: &str,
+) -> proc_macro2::TokenStream {
+    let ident = &field_quoter.ident;
+    let verifier = syn::parse_str::<syn::Path>(verifier).unwrap();
+    let quoted_error = quote_err(validation);
+    let (names, identities): (Vec<_>, Vec<_>) = payload_fields.iter().cloned().unzip();
+
+    let signature = if field_quoter._type.starts_with("Option<Option<") {
+        abort!(
+            ident.span(),
+            "Invalid attribute #[validate] on the struct: the signature field of `signed` can not be an `Option<Option<..>>`"
+        )
+    } else if field_quoter._type.starts_with("Option<") {
+        quote!(self.#ident.as_ref())
+    } else {
+        quote!(Some(&self.#ident))
+    };
+
+    quote!(
+        let payload = _validation::SignedPayload::new()
+            #(.with_field(#names, &self.#identities))*;
+        let valid = match #signature {
+            Some(signature) => payload.verify(|payload| #verifier(payload, signature, ctx)),
+            None => false,
+        };
+        if !valid {
+            #quoted_error
+            errors.push(err);
+        }
+    )
+}
+
+/// Generates the validation rule `flags_of`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `flags` - path of the `validation::KnownFlags` type
+pub fn create_flags_of_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    flags: &str,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_reference();
+    let flags = syn::parse_str::<syn::Path>(flags).unwrap();
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !_validation::has_known_flags::<#flags>(#validate_parameter) {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `finite`, aborting on fields that are not `f32` or `f64`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_finite_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let type_name = field_quoter.type_name();
+    if !field_quoter.binding && type_name != "f32" && type_name != "f64" {
+        abort!(
+            field_quoter.ident.span(),
+            "`finite` only applies to `f32` and `f64` fields, not `{}`",
+            type_name
+        );
+    }
+    let validate_parameter = field_quoter.quote_validate_reference();
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !_validation::is_finite(#validate_parameter) {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rules `uuid_version` and `uuid_not_nil`, aborting on fields that
+/// are not `Uuid`s
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_uuid_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    if !field_quoter.binding && !field_quoter.is_uuid() {
+        abort!(
+            field_quoter.ident.span(),
+            "`{}` only applies to `Uuid` fields, not `{}`",
+            validation.code,
+            field_quoter.type_name()
+        );
+    }
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let check = match validation.validator {
+        ValidationType::UuidVersion(version) => {
+            let version = usize::from(version);
+            quote!(_validation::is_uuid_version(#validate_parameter, #version))
+        }
+        _ => quote!(_validation::is_uuid_not_nil(#validate_parameter)),
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Wraps the rule `one_of` of a field so its failure has the allowed value nearest to the
+/// value as the `suggestion` param, when one is near enough
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `values` - the string literals the field must be one of
+/// * `collation` - name of the `validation::Collation` of the values
+/// * `rule` - `TokenStream` of the rule wrapped in its field
+pub fn wrap_in_suggestion(
+    field_quoter: &FieldQuoter,
+    values: &[syn::Lit],
+    collation: &str,
+    rule: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let collation = quote_collation(collation);
+    let suggest = field_quoter.wrap_if_option(quote!(
+        let suggestion = _validation::suggest_one_of(#validate_parameter, &[#(#values),*], #collation);
+        if let (Some(suggestion), Some(err)) = (suggestion, field_errors.last_mut()) {
+            err.params.insert("suggestion".to_string(), suggestion.into());
+        }
+    ));
+
+    // Failures reported as warnings never reach `field_errors`, nor do their suggestions
+    quote!(
+        {
+            let errors_before = field_errors.len();
+            #rule
+            if field_errors.len() > errors_before {
+                #suggest
+            }
+        }
+    )
+}
+
+// Returns the `validation::Collation` of its name in an attribute, `case_fold` as `CaseFold`
+fn quote_collation(collation: &str) -> proc_macro2::TokenStream {
+    let collation = syn::Ident::new(
+        &collation
+            .split('_')
+            .map(|word| word[..1].to_uppercase() + &word[1..])
+            .collect::<String>(),
+        Span::call_site(),
+    );
+    quote!(_validation::Collation::#collation)
+}
+
+/// Generates the validation rule `range`, the comparison picked by the class of the type of
+/// the field: text parsed as the chrono or time types, numbers, chars and strings compared as they are
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `min` - `Option<&RangeBound>` lower bound, included or excluded
+/// * `max` - `Option<&RangeBound>` upper bound, included or excluded
+pub fn create_range_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    min: Option<&RangeBound>,
+    max: Option<&RangeBound>,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_reference();
+    let type_name = field_quoter.type_name();
+    let date = DATE_TYPES.iter().find(|(name, _)| *name == type_name);
+    let float = type_name == "f32" || type_name == "f64";
+
+    let quote_value = |value: &syn::Lit| match *value {
+        syn::Lit::Str(ref text) if date.is_some() => {
+            let (name, pattern) = date.unwrap();
+            if !Regex::new(pattern).unwrap().is_match(&text.value()) {
+                abort!(
+                    text.span(),
+                    "`range` expects the bounds of a `{}` in ISO 8601",
+                    name
+                );
+            }
+            quote!(#text)
+        }
+        syn::Lit::Str(ref text) => quote!(#text),
+        // Integer bounds of float fields, `0` rather than `0.0`
+        syn::Lit::Int(ref n) if float => {
+            let digits = n.base10_digits();
+            let sign = digits.strip_prefix('-').map(|_| quote!(-));
+            let n = syn::LitFloat::new(&format!("{}.0", digits.trim_start_matches('-')), n.span());
+            quote!(&#sign #n)
+        }
+        ref lit => quote!(&#lit),
+    };
+    let quote_bound = |bound: Option<&RangeBound>| match bound {
+        Some(bound) if bound.exclusive => {
+            let value = quote_value(&bound.value);
+            quote!(::std::ops::Bound::Excluded(#value))
+        }
+        Some(bound) => {
+            let value = quote_value(&bound.value);
+            quote!(::std::ops::Bound::Included(#value))
+        }
+        None => quote!(::std::ops::Bound::Unbounded),
+    };
+    let (min_bound, max_bound) = (quote_bound(min), quote_bound(max));
+
+    let text = matches!(
+        min.or(max),
+        Some(RangeBound {
+            value: syn::Lit::Str(_),
+            ..
+        })
+    );
+    let check = if text && date.is_some() {
+        quote!(_validation::is_within_temporal(#validate_parameter, #min_bound, #max_bound))
+    } else if text {
+        quote!(_validation::is_within::<str>(#validate_parameter, #min_bound, #max_bound))
+    } else {
+        quote!(_validation::is_within(#validate_parameter, #min_bound, #max_bound))
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+// Describes a bound of `range` as JSON, chars as strings and integers wide enough for any field
+fn quote_range_param(name: &str, bound: &syn::Lit) -> proc_macro2::TokenStream {
+    match *bound {
+        syn::Lit::Char(ref c) => {
+            let c = c.value().to_string();
+            quote!(.param(#name, #c))
+        }
+        syn::Lit::Int(ref n) => match n.base10_parse::<i64>() {
+            Ok(n) => quote!(.param(#name, #n)),
+            Err(_) => {
+                let n = n.base10_parse::<u64>().unwrap_or(u64::MAX);
+                quote!(.param(#name, #n))
+            }
+        },
+        syn::Lit::Float(ref n) => {
+            let n = n.base10_parse::<f64>().unwrap_or_default();
+            quote!(.param(#name, #n))
+        }
+        ref lit => quote!(.param(#name, #lit)),
+    }
+}
+
+/// Generates the validation rule `one_of`, strings compared per the collation and integers
+/// as they are
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `values` - the string or integer literals the field must be one of
+/// * `collation` - name of the `validation::Collation` of strings
+pub fn create_one_of_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    values: &[syn::Lit],
+    collation: &str,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let check = if let Some(syn::Lit::Str(_)) = values.first() {
+        let collation = quote_collation(collation);
+        quote!(_validation::is_one_of(#validate_parameter, &[#(#values),*], #collation))
+    } else {
+        quote!(_validation::is_in_collection(#validate_parameter, ::std::vec![#(#values),*]))
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the validation rule `recently_seen`, awaiting the `SeenStore` of the environment
+/// for no longer than the rule timeout of the context
+/// The key is prefixed with the struct, per the `SEEN_KEY_PREFIX` of `field_errors_async`, and
+/// the field, so keys of other rules cannot clash
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+/// * `window` - seconds for which a key is remembered
+/// * `key_fn` - path of the function returning the key from `&self`, if not the field value
+pub fn create_recently_seen_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+    window: u64,
+    key_fn: &Option<String>,
+) -> proc_macro2::TokenStream {
+    let field_name = &field_quoter.name;
+    let key = match *key_fn {
+        Some(ref key_fn) => {
+            let key_fn = syn::parse_str::<syn::ExprPath>(key_fn).unwrap();
+            quote!(#key_fn(self))
+        }
+        None => {
+            let validate_parameter = field_quoter.quote_validate_parameter();
+            quote!(::std::string::ToString::to_string(#validate_parameter))
+        }
+    };
+
+    let quoted_error = quote_state_err(validation);
+    let quoted = quote!(
+        let key = ::std::format!("{}.{}:{}", SEEN_KEY_PREFIX, #field_name, #key);
+        let seen = _validation::is_recently_seen(ctx, &key, ::std::time::Duration::from_secs(#window));
+        match _validation::with_rule_timeout(ctx, seen).await {
+            Ok(true) => {
+                #quoted_error
+                errors.push(err)
+            }
+            Ok(false) => {}
+            Err(err) => errors.push(err),
+        }
+    );
+
+    // The key of `key_fn` does not read the value, the field only needs to hold one
+    match (key_fn, field_quoter.quote_has_value()) {
+        (Some(_), Some(has_value)) => quote!(if #has_value { #quoted }),
+        _ => field_quoter.wrap_if_option(quote!({ #quoted })),
+    }
+}
+
+/// Generates the validation rules of `serde_json::Value` fields: `json_type`, `max_depth`,
+/// `max_keys` and `required_keys`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_json_value_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let check = match validation.validator {
+        ValidationType::JsonType(ref json_type) => {
+            quote!(_validation::is_json_type(#validate_parameter, #json_type))
+        }
+        ValidationType::JsonMaxDepth(max_depth) => {
+            let max_depth = max_depth as usize;
+            quote!(_validation::is_json_max_depth(#validate_parameter, #max_depth))
+        }
+        ValidationType::JsonMaxKeys(max_keys) => {
+            let max_keys = max_keys as usize;
+            quote!(_validation::is_json_max_keys(#validate_parameter, #max_keys))
+        }
+        ValidationType::RequiredKeys(ref keys) => {
+            quote!(_validation::has_json_keys(#validate_parameter, &[#(#keys),*]))
+        }
+        _ => unreachable!("Only the rules of JSON values are generated here"),
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+/// Generates the localized validation rules `date_format` and `number_format`
+/// Without an explicit locale the rules read the locale of the `ValidationContext`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_locale_format_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let validate_parameter = field_quoter.quote_validate_parameter();
+    let quote_locale = |locale: &Option<String>| match *locale {
+        Some(ref l) => quote!(#l),
+        None => quote!(ctx.environment().locale()),
+    };
+    let check = match validation.validator {
+        ValidationType::DateFormat {
+            ref style,
+            ref locale,
+        } => {
+            let locale = quote_locale(locale);
+            quote!(_validation::is_date_format(#validate_parameter, #style, #locale))
+        }
+        ValidationType::NumberFormat { ref locale } => {
+            let locale = quote_locale(locale);
+            quote!(_validation::is_number_format(#validate_parameter, #locale))
+        }
+        _ => unreachable!("Only localized rules are generated here"),
+    };
+
+    let quoted_error = quote_err(validation);
+    let quoted = quote!(
+        if !#check {
+            #quoted_error
+            errors.push(err)
+        }
+    );
+
+    field_quoter.wrap_if_option(quoted)
+}
+
+// Quotes `.param(name, value)` for an optional parameter, nothing when it is not set
+fn quote_param_option(name: &str, value: &Option<String>) -> proc_macro2::TokenStream {
+    match *value {
+        Some(ref v) => quote!(.param(#name, #v)),
+        None => quote!(),
+    }
+}
+
+/// Generates the update validation rules `immutable` and `monotonic_increase`, comparing the
+/// field with the one of `previous`
+///
+/// # Arguments
+///
+/// * `field_quoter` - `FieldQuoter` to help with validation generation
+/// * `validation` - `FieldValidation` to add
+pub fn create_update_validation(
+    field_quoter: &FieldQuoter,
+    validation: &FieldValidation,
+) -> proc_macro2::TokenStream {
+    let ident = &field_quoter.ident;
+    let check = match validation.validator {
+        ValidationType::Immutable => quote!(self.#ident == previous.#ident),
+        ValidationType::MonotonicIncrease => quote!(self.#ident >= previous.#ident),
+        _ => unreachable!("Only update rules are generated here"),
+    };
+
+    let quoted_error = quote_state_err(validation);
+    quote!(
+        if !(#check) {
+            #quoted_error
+            errors.push(err)
+        }
+    )
+}
+
+/// Generates one lazily compiled static per distinct inline regex pattern
+/// Fields sharing a pattern share the static, patterns are already checked by the derive
+///
+/// # Arguments
+///
+/// * `patterns` - `&[String]` of the distinct patterns used by the struct
+pub fn create_regex_statics(patterns: &[String]) -> proc_macro2::TokenStream {
+    if patterns.is_empty() {
+        return quote!();
+    }
+
+    let statics = patterns.iter().map(|pattern| {
+        let ident = regex_static_ident(pattern);
+        quote!(
+            static ref #ident: _validation::__private::Regex =
+                _validation::__private::Regex::new(#pattern).unwrap();
+        )
+    });
+
+    quote!(_validation::__private::lazy_static! {
+        #(#statics)*
+    })
+}
+
+// Identical patterns always map to the same ident, which is what lets fields share a static
+fn regex_static_ident(pattern: &str) -> syn::Ident {
+    let mut hasher = DefaultHasher::new();
+    pattern.hash(&mut hasher);
+    syn::Ident::new(
+        &format!("__VALIDATION_REGEX_{:016X}", hasher.finish()),
+        Span::call_site(),
+    )
+}
+
+/// Generates `assert_unchanged_except`, comparing every given field with the one of `previous`
+/// unless it is allowed to change
+///
+/// # Arguments
+///
+/// * `fields` - the ident, the serialized name followed by the serde aliases and the type of every
+///   field of the struct with an update rule
+pub fn create_unchanged_except(
+    fields: &[(syn::Ident, Vec<String>, syn::Type)],
+) -> proc_macro2::TokenStream {
+    let idents = fields.iter().map(|(ident, _, _)| ident);
+    let names = fields.iter().map(|(_, names, _)| names);
+    let types = fields.iter().map(|(_, _, field_type)| field_type);
+
+    quote!(
+        /// Fails with `immutable` when a field with an update rule other than the `allowed` ones,
+        /// named as it is serialized or by one of its serde aliases, differs from the one of
+        /// `previous`
+        pub fn assert_unchanged_except(
+            &self,
+            previous: &Self,
+            allowed: &[&str],
+        ) -> _validation::ValidationResult<()>
+        where
+            #(#types: ::std::cmp::PartialEq,)*
+        {
+            #(
+                if ![#(#names),*].iter().any(|name| allowed.contains(name)) && self.#idents != previous.#idents {
+                    return Err(_validation::ValidationError::InvalidState("immutable".to_string()));
+                }
+            )*
+            Ok(())
+        }
+    )
+}
+
+fn quote_state_err(validation: &FieldValidation) -> proc_macro2::TokenStream {
+    let message = validation.message.as_ref().unwrap_or(&validation.code);
+
+    quote!(
+        let err = _validation::ValidationError::InvalidState(#message.to_string());
+    )
+}
+
+fn quote_err(validation: &FieldValidation) -> proc_macro2::TokenStream {
+    let message = validation.message.as_ref().unwrap_or(&validation.code);
 
     quote!(
-        let mut err = ::validation::ValidationError::FieldMismatch(#code.to_string());
+        let err = _validation::ValidationError::FieldMismatch(#message.to_string());
     )
 }
@@ -0,0 +1,192 @@
+//! raw
+//!
+//! Module generating the raw twin of a struct, per `#[validation(raw = "...")]`: the same
+//! fields, each one optional, and a `TryFrom` of the twin into the struct that only succeeds
+//! once every required field is present and the rules of the struct hold
+
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse_quote;
+
+use crate::container::ContainerOptions;
+
+/// Options of `#[serde]` forwarded to the raw twin, the ones changing the names it reads
+/// Others (`with`, `default`...) expect the type of the field, not its `Option`
+const FORWARDED_SERDE_OPTIONS: [&str; 4] = ["rename", "rename_all", "alias", "deny_unknown_fields"];
+
+/// Field of the struct carried over to its raw twin
+pub struct RawField {
+    pub field: syn::Field,
+    //Name of the field, as it is serialized
+    pub name: String,
+    //Whether or not the field is already an `Option`, kept as it is in the twin
+    pub optional: bool,
+}
+
+/// Generates the raw twin of the struct, to be placed next to it
+///
+/// # Arguments
+///
+/// * `syntax` - the `DeriveInput` of the struct
+/// * `raw` - name of the raw twin
+/// * `fields` - the `RawField`s of the struct
+/// * `options` - `ContainerOptions` of the struct
+pub fn create_raw_struct(
+    syntax: &syn::DeriveInput,
+    raw: &syn::Ident,
+    fields: &[RawField],
+    options: &ContainerOptions,
+) -> proc_macro2::TokenStream {
+    let identity = &syntax.ident;
+    let visibility = &syntax.vis;
+    let generics = &syntax.generics;
+    let where_clause = &syntax.generics.where_clause;
+    let crate_path = &options.crate_path;
+    let serde_path = syn::LitStr::new(
+        &format!("{}::__private::serde", quote!(#crate_path)).replace(' ', ""),
+        Span::call_site(),
+    );
+    let container_serde = forwarded_serde_attributes(&syntax.attrs);
+    let doc = format!(
+        "Raw twin of [`{}`], every field optional, converted with `TryFrom`",
+        identity
+    );
+
+    let raw_fields = fields.iter().map(|raw_field| {
+        let ident = &raw_field.field.ident;
+        let visibility = &raw_field.field.vis;
+        let field_type = &raw_field.field.ty;
+        let field_serde = forwarded_serde_attributes(&raw_field.field.attrs);
+        let raw_type = if raw_field.optional {
+            quote!(#field_type)
+        } else {
+            quote!(::std::option::Option<#field_type>)
+        };
+
+        quote!(
+            #(#field_serde)*
+            #visibility #ident: #raw_type
+        )
+    });
+
+    quote!(
+        #[doc = #doc]
+        #[derive(Clone, Debug, Default, #crate_path::__private::serde_derive::Deserialize)]
+        #[serde(crate = #serde_path)]
+        #(#container_serde)*
+        #visibility struct #raw #generics #where_clause {
+            #(#raw_fields),*
+        }
+    )
+}
+
+/// Generates the `TryFrom` of the raw twin into the struct
+/// Missing required fields fail with `not_null`, the struct is then checked with its
+/// `FieldValidation` against the default `ValidationContext`
+///
+/// # Arguments
+///
+/// * `syntax` - the `DeriveInput` of the struct
+/// * `raw` - name of the raw twin
+/// * `fields` - the `RawField`s of the struct
+pub fn create_raw_conversion(
+    syntax: &syn::DeriveInput,
+    raw: &syn::Ident,
+    fields: &[RawField],
+) -> proc_macro2::TokenStream {
+    let identity = &syntax.ident;
+    let (implementation_generics, type_generics, where_clause) = syntax.generics.split_for_impl();
+
+    let (required, optional): (Vec<&RawField>, Vec<&RawField>) =
+        fields.iter().partition(|raw_field| !raw_field.optional);
+    let required_idents = required
+        .iter()
+        .map(|raw_field| raw_field.field.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    // Bindings of the required values, none of them can shadow `raw`. `r#type` binds `raw_type`
+    let required_bindings = required_idents
+        .iter()
+        .map(|ident| syn::Ident::new(&format!("raw_{}", ident.unraw()), Span::call_site()))
+        .collect::<Vec<_>>();
+    let required_names = required.iter().map(|raw_field| &raw_field.name);
+    let optional_idents = optional
+        .iter()
+        .map(|raw_field| raw_field.field.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+
+    let construct = quote!(
+        #identity {
+            #(#required_idents: #required_bindings,)*
+            #(#optional_idents: raw.#optional_idents,)*
+        }
+    );
+    let value = if required.is_empty() {
+        construct
+    } else {
+        quote!(
+            match (#(raw.#required_idents,)*) {
+                (#(Some(#required_bindings),)*) => #construct,
+                (#(#required_bindings,)*) => {
+                    let mut missing = _validation::ValidationErrors::new();
+                    #(
+                        if #required_bindings.is_none() {
+                            missing.push(_validation::FieldError::new(
+                                #required_names,
+                                _validation::ValidationError::FieldMismatch("not_null".to_string()),
+                            ));
+                        }
+                    )*
+                    return Err(missing);
+                }
+            }
+        )
+    };
+
+    quote!(
+        impl #implementation_generics ::std::convert::TryFrom<#raw #type_generics> for #identity #type_generics #where_clause {
+            type Error = _validation::ValidationErrors;
+
+            fn try_from(raw: #raw #type_generics) -> ::std::result::Result<Self, Self::Error> {
+                let value = #value;
+                let errors = _validation::FieldValidation::field_errors(
+                    &value,
+                    &_validation::ValidationContext::default(),
+                );
+                if errors.is_empty() {
+                    Ok(value)
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+    )
+}
+
+// Returns the `#[serde]` attributes holding only the options in `FORWARDED_SERDE_OPTIONS`
+fn forwarded_serde_attributes(attrs: &[syn::Attribute]) -> Vec<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path == parse_quote!(serde))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(syn::MetaList { nested, .. })) => {
+                let forwarded = nested
+                    .iter()
+                    .filter(|meta| match **meta {
+                        syn::NestedMeta::Meta(ref meta) => {
+                            meta.path().get_ident().is_some_and(|ident| {
+                                FORWARDED_SERDE_OPTIONS.contains(&ident.to_string().as_str())
+                            })
+                        }
+                        syn::NestedMeta::Lit(_) => false,
+                    })
+                    .collect::<Vec<_>>();
+                if forwarded.is_empty() {
+                    None
+                } else {
+                    Some(quote!(#[serde(#(#forwarded),*)]))
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
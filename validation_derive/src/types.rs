@@ -4,17 +4,250 @@
 //! This enum is meant be a way to help with labeling the rule that
 //! the derive should find and mapping what it attributes to
 
+use crate::field_validation::FieldValidation;
+
 ///Enum providing the mapping that allows the derive to determine which validation rule to generate
 #[derive(Clone, Debug, PartialEq)]
 pub enum ValidationType {
     //Indicates that the field cannot be None, or 'null' in the case of a DTO field.
     NotNull,
+    //Indicates that the field must match the inline regex literal, checked at expansion time
+    RegexInline(String),
+    //Indicates that the field must be a point in time before the time of the validation
+    Past,
+    //Indicates that the field must be a point in time after the time of the validation
+    Future,
+    //Indicates that at least the given years passed since the field, typically a birth date
+    MinAge(u32),
+    //Indicates that the field must be a date in the given style, for the given locale or the
+    //locale of the validation context
+    DateFormat {
+        style: String,
+        locale: Option<String>,
+    },
+    //Indicates that the field must be a number as written in the given locale or the locale
+    //of the validation context
+    NumberFormat {
+        locale: Option<String>,
+    },
+    //Indicates that the length of the field must be within the bounds, characters for text and
+    //elements for collections, per `validation::HasLen`
+    Length {
+        min: Option<LengthBound>,
+        max: Option<LengthBound>,
+    },
+    //Indicates that the text of the field must be an integer that fits in the named integer
+    //type, `integer_overflow` reported apart from text that is not an integer
+    FitsIn(String),
+    //Indicates that the integer field can only set the bits of the flags of the given path,
+    //per `validation::KnownFlags`
+    FlagsOf(String),
+    //Indicates that the float field cannot be NaN or an infinity
+    Finite,
+    //Indicates that the text field must be a canonical identifier of at most the given
+    //characters: trimmed, lowercase, NFC and of letters, digits and `_`, `-` or `.`
+    CanonicalIdentifier {
+        max: u64,
+    },
+    //Indicates that the `uuid::Uuid` field must be of the given version, from 1 to 5
+    UuidVersion(u8),
+    //Indicates that the `uuid::Uuid` field cannot be the nil UUID
+    UuidNotNil,
+    //Indicates that the field must be within the bounds, numbers, chars and text compared per
+    //`PartialOrd` and the text of dates parsed as the type of the field
+    Range {
+        min: Option<RangeBound>,
+        max: Option<RangeBound>,
+    },
+    //Indicates that the field is validated with its own `FieldValidation`, its errors
+    //prefixed by the field name, up to the given nesting depth
+    Nested {
+        max_depth: Option<u64>,
+    },
+    //Indicates that the rules apply to the value of the variant of the enum field, when the
+    //field holds that variant
+    OnVariant {
+        variant: String,
+        rules: Vec<FieldValidation>,
+    },
+    //Indicates that the `serde_json::Value` field must be of the given JSON type
+    JsonType(String),
+    //Indicates that the `serde_json::Value` field cannot be nested deeper than the given levels
+    JsonMaxDepth(u64),
+    //Indicates that the `serde_json::Value` field cannot have more keys than the given number
+    JsonMaxKeys(u64),
+    //Indicates that the `serde_json::Value` field must be an object holding all the given keys
+    RequiredKeys(Vec<String>),
+    //Indicates that the field must be one of the literals, strings compared per the named
+    //collation of `validation::Collation`, with the nearest of them as the `suggestion` param
+    //of the error when `suggest` is set
+    OneOf {
+        values: Vec<syn::Lit>,
+        collation: String,
+        suggest: bool,
+    },
+    //Indicates that the field is a signature of the payload fields, per the verifier of the
+    //`#[validate(signed(...))]` attribute of the struct. The payload fields are the names
+    //they are serialized as, along with their Rust identity
+    Signed {
+        payload_fields: Vec<(String, syn::Ident)>,
+        verifier: String,
+    },
+    //Indicates that the key of the field, its value or the result of `key_fn`, cannot be seen
+    //twice within the window in seconds, checked against the `SeenStore` of the environment
+    RecentlySeen {
+        window: u64,
+        key_fn: Option<String>,
+    },
+    //Indicates that the field cannot change on an update
+    Immutable,
+    //Indicates that the field cannot decrease on an update
+    MonotonicIncrease,
+}
+
+///Bound of the rule `range`, `min` and `max` included in the range, `exclusive_min` and
+///`exclusive_max` excluded from it
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeBound {
+    pub value: syn::Lit,
+    pub exclusive: bool,
+}
+
+impl RangeBound {
+    /// Name of the parameter of the bound, `min` or `max` prefixed by `exclusive_` or not
+    pub fn param_name(&self, name: &str) -> String {
+        if self.exclusive {
+            format!("exclusive_{}", name)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+///Bound of the rule `length`, a number or the path of a const read when the rule runs, like
+///`Self::MAX_NAME` for the associated consts of the validated type
+#[derive(Clone, Debug, PartialEq)]
+pub enum LengthBound {
+    Value(u64),
+    Const(Box<syn::ExprPath>),
+}
+
+impl LengthBound {
+    /// Returns the bound as a `usize` expression, for `validation::is_length`
+    pub fn quote_usize(&self) -> proc_macro2::TokenStream {
+        match *self {
+            LengthBound::Value(value) => {
+                let value = value as usize;
+                quote!(#value)
+            }
+            LengthBound::Const(ref path) => quote!((#path as usize)),
+        }
+    }
+
+    /// Returns the bound as a `u64` expression, for the params of descriptions
+    pub fn quote_u64(&self) -> proc_macro2::TokenStream {
+        match *self {
+            LengthBound::Value(value) => quote!(#value),
+            LengthBound::Const(ref path) => quote!((#path as u64)),
+        }
+    }
+}
+
+///Cost of a rule, rules without an `order` run from the cheapest to the most expensive
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RuleCost {
+    //Checks the value as it is: presence, length, pattern, shape of a JSON value
+    Syntactic,
+    //Parses the value or reads the context: formats, points in time
+    Parsing,
+    //Runs the rules of another value
+    Nested,
+    //Waits on I/O, only run by `AsyncFieldValidation` once every other rule ran
+    Async,
 }
 
 impl ValidationType {
+    /// Error code of the rule, every code needs an entry in `validation::catalog::ERROR_CATALOG`
     pub fn code(&self) -> &'static str {
         match *self {
             ValidationType::NotNull => "not_null",
+            ValidationType::RegexInline(_) => "regex_inline",
+            ValidationType::Past => "past",
+            ValidationType::Future => "future",
+            ValidationType::MinAge(_) => "min_age",
+            ValidationType::DateFormat { .. } => "date_format",
+            ValidationType::NumberFormat { .. } => "number_format",
+            ValidationType::Length { .. } => "length",
+            ValidationType::Finite => "finite",
+            ValidationType::CanonicalIdentifier { .. } => "canonical_identifier",
+            ValidationType::UuidVersion(_) => "uuid_version",
+            ValidationType::UuidNotNil => "uuid_not_nil",
+            ValidationType::FitsIn(_) => "fits_in",
+            ValidationType::FlagsOf(_) => "flags_of",
+            ValidationType::Range { .. } => "range",
+            ValidationType::Nested { .. } => "nested",
+            // Never reported, the errors are the ones of the rules of the variant
+            ValidationType::OnVariant { .. } => "on_variant",
+            ValidationType::JsonType(_) => "json_type",
+            ValidationType::JsonMaxDepth(_) => "max_depth",
+            ValidationType::JsonMaxKeys(_) => "max_keys",
+            ValidationType::RequiredKeys(_) => "required_keys",
+            ValidationType::OneOf { .. } => "one_of",
+            ValidationType::Signed { .. } => "signed",
+            ValidationType::RecentlySeen { .. } => "recently_seen",
+            ValidationType::Immutable => "immutable",
+            ValidationType::MonotonicIncrease => "monotonic_increase",
         }
     }
+
+    /// Cost of the rule, rules on a variant cost as much as the most expensive of them
+    pub fn cost(&self) -> RuleCost {
+        match *self {
+            ValidationType::NotNull
+            | ValidationType::RegexInline(_)
+            | ValidationType::Length { .. }
+            | ValidationType::Finite
+            | ValidationType::UuidVersion(_)
+            | ValidationType::UuidNotNil
+            | ValidationType::FlagsOf(_)
+            | ValidationType::Range { .. }
+            | ValidationType::JsonType(_)
+            | ValidationType::JsonMaxDepth(_)
+            | ValidationType::JsonMaxKeys(_)
+            | ValidationType::RequiredKeys(_)
+            | ValidationType::Immutable
+            | ValidationType::MonotonicIncrease => RuleCost::Syntactic,
+            ValidationType::Past
+            | ValidationType::Future
+            | ValidationType::MinAge(_)
+            | ValidationType::FitsIn(_)
+            // Lowercases the value and checks its normalization
+            | ValidationType::CanonicalIdentifier { .. }
+            // Serializes the payload fields, then computes the signature
+            | ValidationType::Signed { .. }
+            | ValidationType::DateFormat { .. }
+            | ValidationType::NumberFormat { .. } => RuleCost::Parsing,
+            // Collations other than `exact` normalize every value
+            ValidationType::OneOf { ref collation, .. } if collation == "exact" => {
+                RuleCost::Syntactic
+            }
+            ValidationType::OneOf { .. } => RuleCost::Parsing,
+            ValidationType::Nested { .. } => RuleCost::Nested,
+            ValidationType::RecentlySeen { .. } => RuleCost::Async,
+            ValidationType::OnVariant { ref rules, .. } => rules
+                .iter()
+                .map(|rule| rule.validator.cost())
+                .max()
+                .unwrap_or(RuleCost::Syntactic),
+        }
+    }
+
+    /// Returns whether or not the rule compares an update with the previous value,
+    /// in which case it is generated by `UpdateValidate` rather than `FieldValidate`
+    pub fn is_update_rule(&self) -> bool {
+        matches!(
+            *self,
+            ValidationType::Immutable | ValidationType::MonotonicIncrease
+        )
+    }
 }
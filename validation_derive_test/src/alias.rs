@@ -0,0 +1,77 @@
+#[derive(Debug, DeserializeValidated, FieldValidate, PartialEq)]
+struct Contact {
+    #[serde(rename = "emailAddress", alias = "email", alias = "mail")]
+    #[validate(regex_inline = "^[^@]+@[^@]+$")]
+    email_address: String,
+    #[validate(length(max = 8))]
+    name: String,
+}
+
+#[derive(Debug, Deserialize, FieldValidate, UpdateValidate)]
+struct Profile {
+    #[serde(alias = "nick")]
+    #[validate(length(max = 8), immutable)]
+    nickname: String,
+    #[validate(immutable)]
+    id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alias::{Contact, Profile};
+    use validation::{body, FieldValidation, ValidationError, ValidationErrors};
+
+    fn error_fields(body: &[u8]) -> Vec<String> {
+        let response = body::decode_body::<Profile>("application/json", body).unwrap_err();
+        ValidationErrors::from_embedded(&response.error_message)
+            .unwrap()
+            .iter()
+            .filter_map(|err| err.field.clone())
+            .collect()
+    }
+
+    #[test]
+    fn field_aliases() {
+        assert_eq!(
+            &[("email", "emailAddress"), ("mail", "emailAddress")],
+            Contact::field_aliases()
+        );
+        assert_eq!(&[("nick", "nickname")], Profile::field_aliases());
+    }
+
+    #[test]
+    fn alias_attributes_errors() {
+        assert_eq!(
+            vec!["nick"],
+            error_fields(br#"{ "nick": "josephorme", "id": 1 }"#)
+        );
+        assert_eq!(
+            vec!["nickname"],
+            error_fields(br#"{ "nickname": "josephorme", "id": 1 }"#)
+        );
+
+        let response = body::decode_body::<Contact>(
+            "application/x-www-form-urlencoded",
+            b"mail=joseph&name=joseph",
+        )
+        .unwrap_err();
+        let errors = ValidationErrors::from_embedded(&response.error_message).unwrap();
+        assert_eq!(Some("mail"), errors.first().unwrap().field.as_deref());
+    }
+
+    #[test]
+    fn alias_unchanged_except() {
+        let profile = |nickname: &str| Profile {
+            nickname: nickname.to_string(),
+            id: 1,
+        };
+
+        assert!(profile("joseph")
+            .assert_unchanged_except(&profile("jo"), &["nick"])
+            .is_ok());
+        assert_eq!(
+            Err(ValidationError::InvalidState("immutable".to_string())),
+            profile("joseph").assert_unchanged_except(&profile("jo"), &["id"])
+        );
+    }
+}
@@ -0,0 +1,106 @@
+/// A library type shipping its own limits
+struct Username;
+
+impl Username {
+    const MIN_LEN: usize = 3;
+    const MAX_LEN: usize = 16;
+}
+
+#[derive(Default, FieldValidate)]
+#[validation(invalid_examples)]
+struct Profile {
+    #[validate(length(max = "Self::MAX_NAME"))]
+    name: String,
+    #[validate(length(min = "Username::MIN_LEN", max = "Username::MAX_LEN"))]
+    username: String,
+    #[validate(length(min = 1, max = "Self::MAX_TAGS"))]
+    tags: Option<Vec<String>>,
+}
+
+impl Profile {
+    const MAX_NAME: u32 = 8;
+    const MAX_TAGS: usize = 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::associated_consts::Profile;
+    use validation::{FieldConstraintDescription, FieldValidation, ValidationError};
+
+    #[test]
+    fn validate_length_of_associated_consts() {
+        let p = Profile {
+            name: "Ada".to_string(),
+            username: "ada".to_string(),
+            tags: Some(vec!["admin".to_string()]),
+        };
+
+        assert!(p.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_length_of_associated_consts_failed() {
+        let p = Profile {
+            name: "Augusta Ada".to_string(),
+            username: "ada".to_string(),
+            tags: Some(vec!["admin".to_string()]),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            p.validate_fields()
+        );
+    }
+
+    #[test]
+    fn validate_length_of_foreign_associated_consts_failed() {
+        let p = Profile {
+            name: "Ada".to_string(),
+            username: "ad".to_string(),
+            tags: Some(vec!["admin".to_string()]),
+        };
+
+        assert!(p.validate_fields().is_err());
+    }
+
+    #[test]
+    fn validate_length_of_associated_consts_collection_failed() {
+        let p = Profile {
+            name: "Ada".to_string(),
+            username: "ada".to_string(),
+            tags: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+        };
+
+        assert!(p.validate_fields().is_err());
+    }
+
+    #[test]
+    fn describe_length_of_associated_consts() {
+        let constraints = Profile::describe_constraints();
+        assert_eq!(
+            FieldConstraintDescription::new("name", "length", None).param("max", 8),
+            constraints[0]
+        );
+        assert_eq!(
+            FieldConstraintDescription::new("username", "length", None)
+                .param("min", 3)
+                .param("max", 16),
+            constraints[1]
+        );
+    }
+
+    #[test]
+    fn invalid_examples_of_associated_consts() {
+        let examples = Profile::invalid_examples();
+        assert_eq!(3, examples.len());
+        assert_eq!("a".repeat(9), examples[0].0.name);
+        assert_eq!("", examples[1].0.username);
+        assert_eq!(Some(vec![]), examples[2].0.tags);
+        for (example, code) in examples {
+            assert_eq!(
+                Err(ValidationError::FieldMismatch(code.to_string())),
+                example.validate_fields()
+            );
+        }
+    }
+}
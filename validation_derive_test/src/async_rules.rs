@@ -0,0 +1,155 @@
+#[derive(FieldValidate)]
+#[validation(async_rules = "concurrent", max_concurrency = 2)]
+struct Signup {
+    #[validate(recently_seen(window = 60))]
+    email: String,
+    #[validate(recently_seen(window = 60))]
+    username: String,
+    #[validate(recently_seen(window = 60))]
+    phone: String,
+}
+
+#[derive(FieldValidate)]
+#[validation(async_failures = "first")]
+struct Invite {
+    #[validate(recently_seen(window = 60))]
+    email: String,
+    #[validate(recently_seen(window = 60))]
+    code: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::async_rules::{Invite, Signup};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use std::time::{Duration, SystemTime};
+    use validation::{
+        AsyncFieldValidation, BoxFuture, InMemorySeenStore, SeenStore, ValidationContext,
+        ValidationEnvironment,
+    };
+
+    // Store answering on the second poll, like one over the network, recording the keys it
+    // was asked for in the order it was
+    #[derive(Default)]
+    struct Remote {
+        seen: InMemorySeenStore,
+        asked: Mutex<Vec<String>>,
+    }
+
+    impl Remote {
+        fn asked(&self) -> Vec<String> {
+            self.asked.lock().unwrap().clone()
+        }
+    }
+
+    // Pending on its first poll
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    impl SeenStore for Remote {
+        fn check_and_record<'a>(
+            &'a self,
+            key: &'a str,
+            window: Duration,
+            now: SystemTime,
+        ) -> BoxFuture<'a, bool> {
+            self.asked.lock().unwrap().push(key.to_string());
+            let seen = self.seen.check_and_record(key, window, now);
+            Box::pin(async move {
+                YieldOnce(false).await;
+                seen.await
+            })
+        }
+    }
+
+    fn run<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future
+                .as_mut()
+                .poll(&mut Context::from_waker(Waker::noop()))
+            {
+                return output;
+            }
+        }
+    }
+
+    fn context(store: &Arc<Remote>) -> ValidationContext {
+        ValidationContext::new(ValidationEnvironment::new().with_seen_store(store.clone()))
+    }
+
+    #[test]
+    fn async_rules_concurrent() {
+        let store = Arc::new(Remote::default());
+        let ctx = context(&store);
+
+        // Two rules at most are started before any of them is done
+        let first = Signup {
+            email: "ada@example.com".to_string(),
+            username: "ada".to_string(),
+            phone: "+44 20 7946 0000".to_string(),
+        };
+        let mut validation = first.field_errors_async(&ctx);
+        assert!(validation
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+            .is_pending());
+        assert_eq!(2, store.asked().len());
+        assert!(run(validation).is_empty());
+        assert_eq!(
+            vec![
+                "Signup.email:ada@example.com".to_string(),
+                "Signup.username:ada".to_string(),
+                "Signup.phone:+44 20 7946 0000".to_string(),
+            ],
+            store.asked()
+        );
+
+        let second = Signup {
+            email: "ada@example.com".to_string(),
+            username: "ada".to_string(),
+            phone: "+44 20 7946 0000".to_string(),
+        };
+        let errors = run(second.field_errors_async(&ctx));
+        assert_eq!(
+            "email: recently_seen; username: recently_seen; phone: recently_seen",
+            errors.to_string()
+        );
+    }
+
+    #[test]
+    fn async_rules_first_failure() {
+        let store = Arc::new(Remote::default());
+        let ctx = context(&store);
+        let invite = |code: &str| Invite {
+            email: "ada@example.com".to_string(),
+            code: code.to_string(),
+        };
+
+        assert!(run(invite("a").field_errors_async(&ctx)).is_empty());
+        assert_eq!(
+            "email: recently_seen",
+            run(invite("b").field_errors_async(&ctx)).to_string()
+        );
+        // The code of the second invite is never checked, nor recorded
+        assert_eq!(3, store.asked().len());
+        assert!(run(invite("b").field_errors_async(&ctx))
+            .iter()
+            .all(|err| err.field.as_deref() == Some("email")));
+    }
+}
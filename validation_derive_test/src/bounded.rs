@@ -0,0 +1,76 @@
+use validation::{BoundedString, BoundedVec, NonEmpty};
+
+#[derive(Deserialize, FieldValidate)]
+struct Tag {
+    #[validate(regex_inline = "^[a-z]+$")]
+    label: String,
+}
+
+#[derive(Deserialize, FieldValidate)]
+struct Article {
+    #[validate(regex_inline = "^[A-Z]")]
+    title: BoundedString<1, 16>,
+    #[validate(nested)]
+    tags: BoundedVec<Tag, 2>,
+    #[validate(nested)]
+    authors: NonEmpty<Tag>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bounded::Article;
+    use validation::{FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    #[test]
+    fn validate_bounded_successful() {
+        let article: Article = serde_json::from_str(
+            r#"{ "title": "Rust", "tags": [{ "label": "lang" }], "authors": [{ "label": "jo" }] }"#,
+        )
+        .unwrap();
+        assert!(article.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_bounded_failed() {
+        let article: Article =
+            serde_json::from_str(r#"{ "title": "rust", "tags": [{ "label": "Lang" }], "authors": [{ "label": "jo" }, { "label": "Al" }] }"#).unwrap();
+        assert_eq!(
+            vec![
+                FieldError::new(
+                    "title",
+                    ValidationError::FieldMismatch("regex_inline".to_string())
+                ),
+                FieldError::new(
+                    "tags[0].label",
+                    ValidationError::FieldMismatch("regex_inline".to_string())
+                ),
+                FieldError::new(
+                    "authors[1].label",
+                    ValidationError::FieldMismatch("regex_inline".to_string())
+                ),
+            ],
+            article
+                .field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn deserialize_bounded_failed() {
+        let err = serde_json::from_str::<Article>(
+            r#"{ "title": "", "tags": [], "authors": [{ "label": "jo" }] }"#,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().starts_with("length"));
+        assert!(serde_json::from_str::<Article>(
+            r#"{ "title": "Rust", "tags": [{ "label": "a" }, { "label": "b" }, { "label": "c" }], "authors": [{ "label": "jo" }] }"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Article>(
+            r#"{ "title": "Rust", "tags": [], "authors": [] }"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use validation::{
+    StateValidation, Validation, ValidationError, ValidationErrors, ValidationResult,
+};
+
+#[derive(Builder, Debug, FieldValidate)]
+#[builder(build_fn(private, name = "build_unvalidated", error = "ValidationErrors"))]
+struct Shipment {
+    #[validate(length(min = 1, max = 8))]
+    reference: String,
+    #[builder(default)]
+    parcels: u32,
+}
+
+impl StateValidation for Shipment {
+    fn validate_state(&self) -> ValidationResult<()> {
+        if self.parcels > 0 {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidState("parcels".to_string()))
+        }
+    }
+}
+
+impl Validation for Shipment {
+    fn validate(&self) -> ValidationResult<()> {
+        validation::FieldValidation::validate_fields(self).and_then(|_| self.validate_state())
+    }
+}
+
+impl ShipmentBuilder {
+    fn build(&self) -> Result<Shipment, ValidationErrors> {
+        validation::builder::validated(self.build_unvalidated()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::ShipmentBuilder;
+    use validation::{FieldError, ValidationError};
+
+    #[test]
+    fn build_successful() {
+        let shipment = ShipmentBuilder::default()
+            .reference("S-1".to_string())
+            .parcels(2)
+            .build()
+            .unwrap();
+        assert_eq!("S-1", shipment.reference);
+    }
+
+    #[test]
+    fn build_missing_field() {
+        let errors = ShipmentBuilder::default().parcels(2).build().unwrap_err();
+        assert_eq!(
+            Some(&FieldError::new(
+                "reference",
+                ValidationError::FieldMismatch("not_null".to_string())
+            )),
+            errors.first()
+        );
+    }
+
+    #[test]
+    fn build_invalid() {
+        let errors = ShipmentBuilder::default()
+            .reference("S-123456789".to_string())
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            Some(&FieldError::new(
+                "reference",
+                ValidationError::FieldMismatch("length".to_string())
+            )),
+            errors.first()
+        );
+
+        let errors = ShipmentBuilder::default()
+            .reference("S-1".to_string())
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            Some(&FieldError::unattached(ValidationError::InvalidState(
+                "parcels".to_string()
+            ))),
+            errors.first()
+        );
+    }
+}
@@ -0,0 +1,55 @@
+#[derive(FieldValidate)]
+struct Customer {
+    #[validate(bundle = "person_name")]
+    name: String,
+    #[validate(bundle = "address")]
+    street: Option<String>,
+    #[validate(bundle = "contact_info", length(max = 64))]
+    contact: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bundle::Customer;
+    use validation::{bundles, FieldValidation, ValidationError};
+
+    fn customer(name: &str, street: Option<&str>, contact: &str) -> Customer {
+        Customer {
+            name: name.to_string(),
+            street: street.map(str::to_string),
+            contact: contact.to_string(),
+        }
+    }
+
+    #[test]
+    fn validate_bundle_successful() {
+        assert!(
+            customer("Joseph", Some("221B Baker Street"), "joseph@example.com")
+                .validate_fields()
+                .is_ok()
+        );
+        assert!(customer("Joseph", None, "+44 20 7946 0958")
+            .validate_fields()
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_bundle_failed() {
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("regex_inline".to_string())),
+            customer("R2D2", None, "joseph@example.com").validate_fields()
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            customer("Joseph", None, &format!("{}@example.com", "j".repeat(60))).validate_fields()
+        );
+    }
+
+    #[test]
+    fn describe_bundle() {
+        let mut expected = bundles::person_name("name");
+        expected.extend(bundles::address("street"));
+        expected.extend(bundles::contact_info("contact"));
+        assert_eq!(expected, Customer::describe_constraints()[..6].to_vec());
+    }
+}
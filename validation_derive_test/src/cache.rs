@@ -0,0 +1,43 @@
+#[derive(FieldValidate, Serialize)]
+struct Delivery {
+    #[validate(length(min = 1, max = 16))]
+    event: String,
+    #[validate(range(min = 1))]
+    attempt: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::Delivery;
+    use std::sync::Arc;
+    use validation::cache::{self, LruOutcomeCache, OutcomeCache, OutcomeKey};
+    use validation::{ValidationContext, ValidationEnvironment};
+
+    #[test]
+    fn cache_outcome_of_derived() {
+        let store = Arc::new(LruOutcomeCache::new(16));
+        let ctx =
+            ValidationContext::new(ValidationEnvironment::new().with_outcome_cache(store.clone()));
+        let delivery = Delivery {
+            event: "order.created.v2.retry".to_string(),
+            attempt: 0,
+        };
+
+        let errors = cache::validate_cached(&delivery, &ctx);
+        assert_eq!("event: length; attempt: range", errors.to_string());
+        assert_eq!(
+            Some(errors),
+            store.get(&OutcomeKey::new(&delivery, &ctx).unwrap())
+        );
+        assert_eq!(
+            "event: length; attempt: range",
+            cache::validate_cached(&delivery, &ctx).to_string()
+        );
+        assert_eq!(1, store.len());
+
+        // The tenant is part of the key, its outcome is stored apart
+        let tenant = ctx.clone().with_tenant("acme");
+        cache::validate_cached(&delivery, &tenant);
+        assert_eq!(2, store.len());
+    }
+}
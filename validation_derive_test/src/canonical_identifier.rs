@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+
+#[derive(Default, FieldValidate)]
+#[validation(invalid_examples)]
+struct Workspace<'a> {
+    #[validate(canonical_identifier)]
+    slug: String,
+    #[validate(canonical_identifier(max = 8))]
+    team: Option<String>,
+    #[validate(canonical_identifier)]
+    region: &'a str,
+    #[validate(canonical_identifier)]
+    owner: Cow<'a, str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canonical_identifier::Workspace;
+    use std::borrow::Cow;
+    use validation::{validate, FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    fn mismatch(field: &str) -> FieldError {
+        FieldError::new(
+            field,
+            ValidationError::FieldMismatch("canonical_identifier".to_string()),
+        )
+    }
+
+    #[test]
+    fn validate_canonical_identifier_successful() {
+        let w = Workspace {
+            slug: "acme.billing-v2".to_string(),
+            team: Some("payments".to_string()),
+            region: "eu_west",
+            owner: Cow::Borrowed("zoë"),
+        };
+        assert!(w.validate_fields().is_ok());
+
+        let w = Workspace { team: None, ..w };
+        assert!(w.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_canonical_identifier_failed() {
+        let w = Workspace {
+            slug: " acme".to_string(),
+            team: Some("payments-eu".to_string()),
+            region: "EU_WEST",
+            owner: Cow::Owned("zoe\u{308}".to_string()),
+        };
+
+        assert_eq!(
+            vec![
+                mismatch("slug"),
+                mismatch("team"),
+                mismatch("region"),
+                mismatch("owner"),
+            ],
+            w.field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn canonical_identifier_described_with_its_max() {
+        let rules = Workspace::describe_constraints();
+
+        assert_eq!(Some(&64.into()), rules[0].params.get("max"));
+        assert_eq!(Some(&8.into()), rules[1].params.get("max"));
+    }
+
+    #[test]
+    fn canonical_identifier_invalid_example() {
+        let examples = Workspace::invalid_examples();
+
+        assert_eq!(
+            vec!["canonical_identifier"; 2],
+            examples.iter().map(|(_, code)| *code).collect::<Vec<_>>()
+        );
+        assert_eq!("", examples[0].0.slug);
+    }
+
+    #[test]
+    fn canonical_identifier_inline() {
+        let team = Some("payments-eu".to_string());
+        let errors = validate! {
+            field("slug", &" acme".to_string()) => [canonical_identifier];
+            optional("team", &team) => [canonical_identifier(max = 8)];
+        };
+
+        assert_eq!(
+            vec![mismatch("slug"), mismatch("team")],
+            errors.into_iter().collect::<Vec<_>>()
+        );
+    }
+}
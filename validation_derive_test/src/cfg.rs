@@ -0,0 +1,58 @@
+#[derive(FieldValidate, UpdateValidate)]
+struct Tenant {
+    #[validate(length(max = 64))]
+    #[validate(cfg(test), length(max = 32))]
+    #[validate(cfg(not(test)), regex_inline = "^[a-z]+$")]
+    name: String,
+    #[validate(length(max = 8))]
+    #[validate(cfg(all(test, not(test))), immutable)]
+    region: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cfg::Tenant;
+    use validation::{FieldValidation, UpdateValidation, ValidationError};
+
+    fn tenant(name: &str, region: &str) -> Tenant {
+        Tenant {
+            name: name.to_string(),
+            region: region.to_string(),
+        }
+    }
+
+    #[test]
+    fn validate_cfg_compiled() {
+        let long = tenant(&"a".repeat(40), "eu");
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            long.validate_fields()
+        );
+        // The regex is only compiled into the builds without tests
+        assert!(tenant("Ops-2", "eu").validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_cfg_skipped_update() {
+        assert!(tenant("ops", "us")
+            .validate_update(&tenant("ops", "eu"))
+            .is_ok());
+    }
+
+    #[test]
+    fn describe_cfg() {
+        let rules = Tenant::describe_constraints()
+            .into_iter()
+            .map(|c| (c.field, c.rule))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("name".to_string(), "length".to_string()),
+                ("name".to_string(), "length".to_string()),
+                ("region".to_string(), "length".to_string())
+            ],
+            rules
+        );
+        assert_eq!(3, tenant("ops", "eu").explain().to_run().count());
+    }
+}
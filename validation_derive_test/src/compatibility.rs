@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use validation::{catalog, FieldError, ValidationError, ValidationMode};
+
+    // Codes of the 1.0 catalog, which a minor release may deprecate but never remove
+    const STABLE_CODES: &[&str] = &[
+        "not_null",
+        "regex_inline",
+        "past",
+        "future",
+        "min_age",
+        "date_format",
+        "number_format",
+        "length",
+        "range",
+        "finite",
+        "fits_in",
+        "uuid_version",
+        "uuid_not_nil",
+        "integer_overflow",
+        "flags_of",
+        "nested",
+        "api_version",
+        "one_of",
+        "json_type",
+        "max_depth",
+        "max_keys",
+        "required_keys",
+        "unknown_field",
+        "duplicate_key",
+        "number_overflow",
+        "invalid_json",
+        "invalid_body",
+        "unsupported_media_type",
+        "max_size",
+        "max_items",
+        "max_length",
+        "signed",
+        "recently_seen",
+        "validation_timeout",
+        "immutable",
+        "monotonic_increase",
+    ];
+
+    // Outside of the crate, matches on its enums need a wildcard arm
+    fn audience(err: &ValidationError) -> &'static str {
+        match *err {
+            ValidationError::FieldMismatch(_) => "client",
+            ValidationError::InvalidState(_) | ValidationError::InvalidTransition(_) => "workflow",
+            _ => "unknown",
+        }
+    }
+
+    #[test]
+    fn compatibility_stable_codes() {
+        for code in STABLE_CODES {
+            assert!(catalog::find(code).is_some(), "{} left the catalog", code);
+        }
+    }
+
+    #[test]
+    fn compatibility_wire_format() {
+        assert_eq!(
+            r#"{"kind":"field_mismatch","code":"length"}"#,
+            serde_json::to_string(&ValidationError::field_mismatch("length")).unwrap()
+        );
+        assert_eq!(
+            r#"{"kind":"invalid_state","code":"immutable"}"#,
+            serde_json::to_string(&ValidationError::invalid_state("immutable")).unwrap()
+        );
+        assert_eq!(
+            r#"{"kind":"invalid_transition","code":{"entity":"order","from":"SHIPPED","to":"DRAFT","allowed":["RETURNED"]}}"#,
+            serde_json::to_string(&ValidationError::invalid_transition(
+                "order",
+                "SHIPPED",
+                "DRAFT",
+                &["RETURNED"]
+            ))
+            .unwrap()
+        );
+        assert_eq!(
+            ValidationError::field_mismatch("length"),
+            serde_json::from_str(r#"{"kind":"field_mismatch","code":"length"}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn compatibility_constructors() {
+        assert_eq!(
+            ValidationError::FieldMismatch("length".to_string()),
+            ValidationError::field_mismatch("length")
+        );
+        assert_eq!(
+            ValidationError::InvalidState("immutable".to_string()),
+            ValidationError::invalid_state("immutable")
+        );
+        assert_eq!(ValidationMode::CollectAll, ValidationMode::default());
+    }
+
+    #[test]
+    fn compatibility_matches_code() {
+        let err = FieldError::new("name", ValidationError::field_mismatch("length"));
+        assert!(err.matches_code("length"));
+        assert!(!err.matches_code("range"));
+        assert!(
+            ValidationError::invalid_transition("order", "SHIPPED", "DRAFT", &[])
+                .matches_code("invalid_transition")
+        );
+
+        assert_eq!("client", audience(&err.error));
+        assert_eq!(
+            "workflow",
+            audience(&ValidationError::invalid_state("immutable"))
+        );
+    }
+}
@@ -0,0 +1,5 @@
+// Each case of `ui` fails to derive, its `.stderr` holding the error and the span it points at
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("ui/*.rs");
+}
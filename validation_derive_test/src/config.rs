@@ -0,0 +1,85 @@
+use validation::{FieldValidation, StateValidation, Validation, ValidationError, ValidationResult};
+
+#[derive(Deserialize, FieldValidate)]
+struct ServiceConfig {
+    #[validate(length(min = 1, max = 32))]
+    name: String,
+    #[validate(regex_inline = "^https://")]
+    endpoint: String,
+    replicas: u32,
+}
+
+impl StateValidation for ServiceConfig {
+    fn validate_state(&self) -> ValidationResult<()> {
+        if self.replicas == 0 {
+            Err(ValidationError::InvalidState("replicas".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Validation for ServiceConfig {
+    fn validate(&self) -> ValidationResult<()> {
+        self.validate_fields()?;
+        self.validate_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ServiceConfig;
+    use validation::config::{from_toml_str, from_yaml_str, ConfigError, Location};
+    use validation::ValidationError;
+
+    #[test]
+    fn toml_config_valid() {
+        let source = "name = \"billing\"\nendpoint = \"https://billing\"\nreplicas = 2\n";
+        assert!(from_toml_str::<ServiceConfig>(source).is_ok());
+    }
+
+    #[test]
+    fn toml_config_field_located() {
+        let source = "name = \"billing\"\nendpoint = \"http://billing\"\nreplicas = 2\n";
+        match from_toml_str::<ServiceConfig>(source) {
+            Err(ConfigError::Invalid {
+                error,
+                field,
+                location,
+            }) => {
+                assert_eq!(
+                    ValidationError::FieldMismatch("regex_inline".to_string()),
+                    error
+                );
+                assert_eq!(Some("endpoint".to_string()), field);
+                assert_eq!(Some(Location { line: 2, column: 1 }), location);
+            }
+            other => panic!("unexpected {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn toml_config_state_invalid() {
+        let source = "name = \"billing\"\nendpoint = \"https://billing\"\nreplicas = 0\n";
+        let err = from_toml_str::<ServiceConfig>(source).err().unwrap();
+        assert_eq!("replicas", err.to_string());
+    }
+
+    #[test]
+    fn yaml_config_field_located() {
+        let source = "name: \"\"\nendpoint: https://billing\nreplicas: 2\n";
+        let err = from_yaml_str::<ServiceConfig>(source).err().unwrap();
+        assert_eq!("name: length at line 1 column 1", err.to_string());
+    }
+
+    #[test]
+    fn yaml_config_parse_located() {
+        let source = "name: billing\nendpoint: https://billing\nreplicas: many\n";
+        match from_yaml_str::<ServiceConfig>(source) {
+            Err(ConfigError::Parse { location, .. }) => {
+                assert_eq!(Some(3), location.map(|l| l.line))
+            }
+            other => panic!("unexpected {:?}", other.err()),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Stands for the `validation` crate renamed in Cargo.toml, or re-exported by another crate
+pub mod reexported {
+    pub use ::validation::*;
+}
+
+#[derive(FieldValidate, UpdateValidate)]
+#[validation(crate = "crate::crate_path::reexported")]
+struct Account {
+    #[validate(length(min = 3), regex_inline = "^[a-z]+$")]
+    login: String,
+    #[validate(immutable)]
+    id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crate_path::reexported::{FieldValidation, UpdateValidation, ValidationError};
+    use crate::crate_path::Account;
+
+    #[test]
+    fn validate_with_crate_path() {
+        let account = Account {
+            login: "jo".to_string(),
+            id: 1,
+        };
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            account.validate_fields()
+        );
+        assert_eq!(3, Account::describe_constraints().len());
+    }
+
+    #[test]
+    fn validate_update_with_crate_path() {
+        let previous = Account {
+            login: "joseph".to_string(),
+            id: 1,
+        };
+        let account = Account { id: 2, ..previous };
+        assert!(account.validate_update(&account).is_ok());
+        assert!(account
+            .validate_update(&Account {
+                login: "joseph".to_string(),
+                id: 1
+            })
+            .is_err());
+    }
+}
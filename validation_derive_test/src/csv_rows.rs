@@ -0,0 +1,56 @@
+#[derive(Deserialize, FieldValidate)]
+struct Contact {
+    #[validate(length(min = 1, max = 16))]
+    name: String,
+    #[validate(regex_inline = "^[0-9]{5}$")]
+    zip: String,
+    #[allow(dead_code)]
+    age: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::csv_rows::Contact;
+    use validation::{validate_csv, CsvRowError, ValidationError};
+
+    #[test]
+    fn validate_csv_reports_row_and_column() {
+        let data = "name,zip,age\nJoseph,12345,30\n,1234,41\nAnna,54321,old\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+
+        let report = validate_csv::<Contact, _>(&mut reader).unwrap();
+
+        assert!(!report.passed());
+        assert_eq!(3, report.rows);
+        assert_eq!(0, report.errors_for_row(1).count());
+        assert_eq!(
+            vec![
+                CsvRowError {
+                    row: 2,
+                    column: Some("name".to_string()),
+                    error: ValidationError::FieldMismatch("length".to_string()),
+                },
+                CsvRowError {
+                    row: 2,
+                    column: Some("zip".to_string()),
+                    error: ValidationError::FieldMismatch("regex_inline".to_string()),
+                },
+            ],
+            report.errors_for_row(2).cloned().collect::<Vec<_>>()
+        );
+        let row3 = report.errors_for_row(3).collect::<Vec<_>>();
+        assert_eq!(1, row3.len());
+        assert_eq!(Some("age"), row3[0].column.as_deref());
+    }
+
+    #[test]
+    fn validate_csv_passed() {
+        let data = "name,zip,age\nJoseph,12345,30\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+
+        let report = validate_csv::<Contact, _>(&mut reader).unwrap();
+
+        assert!(report.passed());
+        assert_eq!(1, report.rows);
+    }
+}
@@ -0,0 +1,90 @@
+#[derive(FieldValidate)]
+struct Contact {
+    #[validate(
+        length(max = 10),
+        deprecated(since = "1.4", note = "phone numbers are E.164")
+    )]
+    phone: String,
+    #[validate(not_null, deprecated(since = "2.0"), enforcement = "warn")]
+    fax: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deprecated::Contact;
+    use std::sync::{Arc, Mutex};
+    use validation::{
+        catalog, Deprecation, FieldValidation, ValidationContext, ValidationEnvironment,
+        ValidationError, ValidationObserver,
+    };
+
+    #[derive(Default)]
+    struct Deprecations(Mutex<Vec<String>>);
+
+    impl ValidationObserver for Deprecations {
+        fn on_warning(&self, _field: &str, _error: &ValidationError) {}
+
+        fn on_deprecated(&self, field: &str, rule: &str, deprecation: &Deprecation) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}.{} {}", field, rule, deprecation));
+        }
+    }
+
+    fn observed() -> (Arc<Deprecations>, ValidationContext) {
+        let deprecations = Arc::new(Deprecations::default());
+        let ctx = ValidationContext::new(
+            ValidationEnvironment::new().with_observer(deprecations.clone()),
+        );
+        (deprecations, ctx)
+    }
+
+    #[test]
+    fn validate_deprecated_reports_failures() {
+        let (deprecations, ctx) = observed();
+        let c = Contact {
+            phone: "+33 1 23 45 67 89".to_string(),
+            fax: None,
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            c.validate_fields_with(&ctx)
+        );
+        assert_eq!(
+            vec![
+                "phone.length deprecated since 1.4: phone numbers are E.164".to_string(),
+                "fax.not_null deprecated since 2.0".to_string(),
+            ],
+            *deprecations.0.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_deprecated_silent_when_passing() {
+        let (deprecations, ctx) = observed();
+        let c = Contact {
+            phone: "0123456789".to_string(),
+            fax: Some("0123456789".to_string()),
+        };
+
+        assert!(c.validate_fields_with(&ctx).is_ok());
+        assert!(deprecations.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn describe_deprecated() {
+        let descriptions = Contact::describe_constraints();
+        assert_eq!(
+            Some(Deprecation::new("1.4", Some("phone numbers are E.164"))),
+            descriptions[0].deprecated
+        );
+
+        catalog::register_constraints("Contact", descriptions);
+        assert_eq!(
+            Some(Deprecation::new("2.0", None)),
+            catalog::registered_constraints("Contact")[1].deprecated
+        );
+    }
+}
@@ -0,0 +1,48 @@
+#[derive(FieldValidate, Serialize)]
+struct Profile {
+    #[validate(not_null, regex_inline = "^[a-z]+$")]
+    #[serde(rename = "userName")]
+    name: Option<String>,
+    #[validate(not_null)]
+    age: Option<u8>,
+    nickname: Option<String>,
+}
+
+#[derive(FieldValidate)]
+struct Unvalidated {
+    _name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::describe_constraints::{Profile, Unvalidated};
+    use validation::FieldConstraintDescription;
+
+    #[test]
+    fn describe_constraints_in_declaration_order() {
+        assert_eq!(
+            vec![
+                FieldConstraintDescription::new("userName", "not_null", None),
+                FieldConstraintDescription::new("userName", "regex_inline", None)
+                    .param("pattern", "^[a-z]+$"),
+                FieldConstraintDescription::new("age", "not_null", None),
+            ],
+            Profile::describe_constraints()
+        );
+    }
+
+    #[test]
+    fn describe_constraints_default_messages() {
+        let descriptions = Profile::describe_constraints();
+        assert_eq!("userName: value is required", descriptions[0].to_string());
+        assert_eq!(
+            Some("value is required".to_string()),
+            descriptions[2].message
+        );
+    }
+
+    #[test]
+    fn describe_constraints_empty() {
+        assert!(Unvalidated::describe_constraints().is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+#[derive(Debug, DeserializeValidated, FieldValidate, PartialEq)]
+struct Signup {
+    #[serde(rename = "userName")]
+    #[validate(length(min = 3, max = 16))]
+    user_name: String,
+    #[validate(regex_inline = "^[^@]+@[^@]+$")]
+    email: Option<String>,
+    #[serde(default)]
+    newsletter: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deserialize_validated::Signup;
+    use validation::{FieldError, ValidationError, ValidationErrors};
+
+    #[test]
+    fn deserialize_validated_successful() {
+        assert_eq!(
+            Signup {
+                user_name: "joseph".to_string(),
+                email: None,
+                newsletter: false,
+            },
+            serde_json::from_str(r#"{ "userName": "joseph" }"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_validated_failed() {
+        let err =
+            serde_json::from_str::<Signup>(r#"{ "userName": "jo", "email": "nope" }"#).unwrap_err();
+        let errors = ValidationErrors::from_embedded(&err.to_string()).unwrap();
+        assert_eq!(
+            vec![
+                FieldError::new(
+                    "userName",
+                    ValidationError::FieldMismatch("length".to_string())
+                ),
+                FieldError::new(
+                    "email",
+                    ValidationError::FieldMismatch("regex_inline".to_string())
+                ),
+            ],
+            errors.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn deserialize_validated_serde_error() {
+        let err = serde_json::from_str::<Signup>(r#"{ "email": "a@b" }"#).unwrap_err();
+        assert_eq!(None, ValidationErrors::from_embedded(&err.to_string()));
+    }
+}
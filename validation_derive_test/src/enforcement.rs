@@ -0,0 +1,97 @@
+#[derive(FieldValidate)]
+struct Message {
+    #[validate(not_null)]
+    #[validate(length(max = 10), enforcement = "warn")]
+    body: Option<String>,
+    #[validate(regex_inline = "^[a-z]+$")]
+    channel: String,
+}
+
+#[derive(FieldValidate)]
+#[validation(enforcement = "warn")]
+struct Draft {
+    #[validate(length(max = 5))]
+    title: String,
+    #[validate(not_null, enforcement = "deny")]
+    author: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enforcement::{Draft, Message};
+    use std::sync::{Arc, Mutex};
+    use validation::{
+        FieldValidation, ValidationContext, ValidationEnvironment, ValidationError,
+        ValidationObserver,
+    };
+
+    #[derive(Default)]
+    struct Warnings(Mutex<Vec<(String, ValidationError)>>);
+
+    impl ValidationObserver for Warnings {
+        fn on_warning(&self, field: &str, error: &ValidationError) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.to_string(), error.clone()));
+        }
+    }
+
+    fn observed() -> (Arc<Warnings>, ValidationContext) {
+        let warnings = Arc::new(Warnings::default());
+        let ctx =
+            ValidationContext::new(ValidationEnvironment::new().with_observer(warnings.clone()));
+        (warnings, ctx)
+    }
+
+    #[test]
+    fn validate_warn_rule_reports_and_succeeds() {
+        let (warnings, ctx) = observed();
+        let m = Message {
+            body: Some("a body over ten characters".to_string()),
+            channel: "sms".to_string(),
+        };
+
+        assert!(m.validate_fields_with(&ctx).is_ok());
+        assert!(m.validate_fields().is_ok());
+        assert_eq!(
+            vec![(
+                "body".to_string(),
+                ValidationError::FieldMismatch("length".to_string())
+            )],
+            *warnings.0.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_deny_rules_next_to_warn_rules() {
+        let (warnings, ctx) = observed();
+        let m = Message {
+            body: None,
+            channel: "SMS".to_string(),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("not_null".to_string())),
+            m.validate_fields_with(&ctx)
+        );
+        assert!(warnings.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn validate_container_warn_with_rule_deny() {
+        let (warnings, ctx) = observed();
+        let d = Draft {
+            title: "a long title".to_string(),
+            author: Some("Jo".to_string()),
+        };
+        assert!(d.validate_fields_with(&ctx).is_ok());
+        assert_eq!(1, warnings.0.lock().unwrap().len());
+
+        let d = Draft { author: None, ..d };
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("not_null".to_string())),
+            d.validate_fields_with(&ctx)
+        );
+    }
+}
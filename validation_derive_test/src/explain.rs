@@ -0,0 +1,55 @@
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(length(min = 1))]
+    name: String,
+    #[validate(not_null, regex_inline = "^[a-z]+$")]
+    referral: Option<String>,
+    #[validate(length(max = 3))]
+    nickname: Option<Option<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::explain::Signup;
+    use validation::{PlannedRule, PlannedRuleStatus, SkipReason, ValidationPlan};
+
+    #[test]
+    fn explain_all_rules_run() {
+        let s = Signup {
+            name: String::new(),
+            referral: Some("friend".to_string()),
+            nickname: Some(Some("Jo".to_string())),
+        };
+
+        let plan = s.explain();
+        assert_eq!(4, plan.to_run().count());
+        assert_eq!(0, plan.skipped().count());
+    }
+
+    #[test]
+    fn explain_skipped_without_value() {
+        let s = Signup {
+            name: String::new(),
+            referral: None,
+            nickname: Some(None),
+        };
+
+        assert_eq!(
+            ValidationPlan::new(vec![
+                PlannedRule::new("name", "length", PlannedRuleStatus::Run),
+                PlannedRule::new("referral", "not_null", PlannedRuleStatus::Run),
+                PlannedRule::new(
+                    "referral",
+                    "regex_inline",
+                    PlannedRuleStatus::Skipped(SkipReason::NoValue)
+                ),
+                PlannedRule::new(
+                    "nickname",
+                    "length",
+                    PlannedRuleStatus::Skipped(SkipReason::NoValue)
+                ),
+            ]),
+            s.explain()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(not_null, length(min = 3))]
+    name: Option<String>,
+    #[validate(regex_inline = "^[^@]+@[^@]+$")]
+    email: String,
+    #[validate(length(max = 4), enforcement = "warn")]
+    nickname: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::field_errors::Signup;
+    use validation::{FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    #[test]
+    fn field_errors_lists_every_failure_with_its_field() {
+        let signup = Signup {
+            name: Some("Jo".to_string()),
+            email: "joseph".to_string(),
+            nickname: "Joseph".to_string(),
+        };
+
+        let errors = signup.field_errors(&ValidationContext::default());
+
+        assert_eq!(
+            vec![
+                FieldError::new("name", ValidationError::FieldMismatch("length".to_string())),
+                FieldError::new(
+                    "email",
+                    ValidationError::FieldMismatch("regex_inline".to_string())
+                ),
+            ],
+            errors.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            signup.validate_fields()
+        );
+    }
+
+    #[test]
+    fn field_errors_empty_when_valid() {
+        let signup = Signup {
+            name: Some("Joseph".to_string()),
+            email: "joseph@example.com".to_string(),
+            nickname: "Jo".to_string(),
+        };
+
+        assert!(signup
+            .field_errors(&ValidationContext::default())
+            .is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+enum Reading {
+    Celsius(f64),
+    Missing(String),
+}
+
+#[derive(FieldValidate)]
+struct Sample {
+    #[validate(finite)]
+    latitude: f32,
+    #[validate(finite, range(min = 0))]
+    rainfall: Option<f64>,
+    #[validate(on_variant(Celsius, finite), on_variant(Missing, length(min = 1)))]
+    reading: Reading,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite::{Reading, Sample};
+    use validation::{FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    #[test]
+    fn validate_finite_successful() {
+        let s = Sample {
+            latitude: 48.85,
+            rainfall: Some(0.0),
+            reading: Reading::Celsius(-12.5),
+        };
+        assert!(s.validate_fields().is_ok());
+
+        let s = Sample {
+            rainfall: None,
+            reading: Reading::Missing("sensor offline".to_string()),
+            ..s
+        };
+        assert!(s.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_finite_failed() {
+        let sample = Sample {
+            latitude: f32::INFINITY,
+            rainfall: Some(f64::NAN),
+            reading: Reading::Celsius(f64::NEG_INFINITY),
+        };
+        let mismatch = |field: &str, code: &str| {
+            FieldError::new(field, ValidationError::FieldMismatch(code.to_string()))
+        };
+
+        assert_eq!(
+            vec![
+                mismatch("latitude", "finite"),
+                mismatch("rainfall", "finite"),
+                mismatch("rainfall", "range"),
+                mismatch("reading", "finite"),
+            ],
+            sample
+                .field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+}
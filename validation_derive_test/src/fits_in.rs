@@ -0,0 +1,53 @@
+#[derive(FieldValidate)]
+struct Frame<'a> {
+    #[validate(fits_in = "u32")]
+    sequence: String,
+    #[validate(fits_in = "i16")]
+    offset: Option<String>,
+    #[validate(fits_in = "u8")]
+    channel: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fits_in::Frame;
+    use validation::{FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    fn frame(sequence: &str, offset: Option<&str>) -> Frame<'static> {
+        Frame {
+            sequence: sequence.to_string(),
+            offset: offset.map(str::to_string),
+            channel: "7",
+        }
+    }
+
+    #[test]
+    fn validate_fits_in_successful() {
+        assert!(frame("4294967295", Some("-32768"))
+            .validate_fields()
+            .is_ok());
+        assert!(frame("0", None).validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_fits_in_overflow_apart_from_format() {
+        let mismatch = |field: &str, code: &str| {
+            FieldError::new(field, ValidationError::FieldMismatch(code.to_string()))
+        };
+
+        assert_eq!(
+            vec![
+                mismatch("sequence", "integer_overflow"),
+                mismatch("offset", "fits_in"),
+                mismatch("channel", "integer_overflow"),
+            ],
+            Frame {
+                channel: "256",
+                ..frame("4294967296", Some("0x10"))
+            }
+            .field_errors(&ValidationContext::default())
+            .into_iter()
+            .collect::<Vec<_>>()
+        );
+    }
+}
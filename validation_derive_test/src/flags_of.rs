@@ -0,0 +1,64 @@
+use validation::KnownFlags;
+
+struct Permissions;
+
+impl Permissions {
+    const READ: u16 = 1;
+    const WRITE: u16 = 1 << 1;
+    const EXECUTE: u16 = 1 << 2;
+}
+
+impl KnownFlags for Permissions {
+    type Bits = u16;
+
+    fn known_bits() -> u16 {
+        Permissions::READ | Permissions::WRITE | Permissions::EXECUTE
+    }
+}
+
+#[derive(Deserialize, FieldValidate)]
+struct Grant {
+    #[validate(flags_of = "Permissions")]
+    owner: u16,
+    #[validate(flags_of = "crate::flags_of::Permissions")]
+    group: Option<u16>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flags_of::Grant;
+    use serde_json::json;
+    use validation::{FieldConstraintDescription, FieldValidation, ValidationError};
+
+    fn grant(value: serde_json::Value) -> Grant {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn validate_flags_of_successful() {
+        assert!(grant(json!({ "owner": 7, "group": 5 }))
+            .validate_fields()
+            .is_ok());
+        assert!(grant(json!({ "owner": 0 })).validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_flags_of_unknown_bits() {
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("flags_of".to_string())),
+            grant(json!({ "owner": 8 })).validate_fields()
+        );
+        assert!(grant(json!({ "owner": 1, "group": 65535 }))
+            .validate_fields()
+            .is_err());
+    }
+
+    #[test]
+    fn describe_flags_of() {
+        assert_eq!(
+            FieldConstraintDescription::new("owner", "flags_of", None)
+                .param("flags", "Permissions"),
+            Grant::describe_constraints()[0]
+        );
+    }
+}
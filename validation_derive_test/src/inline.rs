@@ -0,0 +1,86 @@
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(not_null, length(min = 3, max = 16))]
+    username: Option<String>,
+    #[validate(range(min = 18, max = 120))]
+    age: u8,
+    #[validate(one_of("free", "pro"))]
+    plan: String,
+    #[validate(fits_in = "u16")]
+    port: String,
+    #[validate(finite)]
+    weight: f64,
+    #[validate(uuid_version = 4, uuid_not_nil)]
+    id: uuid::Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inline::Signup;
+    use validation::{validate, FieldValidation, ValidationContext, ValidationErrors};
+
+    fn validate_inline(signup: &Signup) -> ValidationErrors {
+        validate! {
+            optional("username", &signup.username) => [not_null, length(3, 16)];
+            field("age", &signup.age) => [range(min = 18, max = 120)];
+            field("plan", &signup.plan) => [one_of("free", "pro")];
+            field("port", &signup.port) => [fits_in("u16")];
+            field("weight", &signup.weight) => [finite];
+            field("id", &signup.id) => [uuid_version(4), uuid_not_nil];
+        }
+    }
+
+    fn assert_same_errors(signup: Signup) {
+        assert_eq!(
+            signup.field_errors(&ValidationContext::default()),
+            validate_inline(&signup)
+        );
+    }
+
+    #[test]
+    fn inline_valid() {
+        let signup = Signup {
+            username: Some("ada".to_string()),
+            age: 36,
+            plan: "pro".to_string(),
+            port: "8080".to_string(),
+            weight: 60.0,
+            id: uuid::Uuid::new_v4(),
+        };
+
+        assert!(validate_inline(&signup).is_empty());
+    }
+
+    #[test]
+    fn inline_same_errors_as_derive() {
+        assert_same_errors(Signup {
+            username: None,
+            age: 16,
+            plan: "team".to_string(),
+            port: "70000".to_string(),
+            weight: f64::NAN,
+            id: uuid::Uuid::nil(),
+        });
+        assert_same_errors(Signup {
+            username: Some("ad".to_string()),
+            age: 36,
+            plan: "pro".to_string(),
+            port: "http".to_string(),
+            weight: 60.0,
+            id: uuid::Uuid::new_v4(),
+        });
+    }
+
+    #[test]
+    fn inline_one_bound() {
+        let (quantity, note) = (0u32, "x".repeat(300));
+        let errors = validate! {
+            field("quantity", &quantity) => [range(min = 1)];
+            field("note", &note) => [length(max = 256)];
+        };
+        assert_eq!(
+            vec!["quantity: range", "note: length"],
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+    }
+}
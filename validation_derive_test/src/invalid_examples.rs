@@ -0,0 +1,94 @@
+use serde_json::Value;
+
+#[derive(Default, FieldValidate)]
+#[validation(invalid_examples)]
+struct Signup {
+    #[validate(length(min = 3, max = 16))]
+    username: String,
+    #[validate(not_null)]
+    email: Option<String>,
+    #[validate(range(min = 13, max = 120))]
+    age: u8,
+    #[validate(range(exclusive_min = 0.0))]
+    height: Option<f64>,
+    #[validate(finite)]
+    weight: f64,
+    #[validate(one_of("free", "pro"), length(max = 4))]
+    plan: String,
+    #[validate(fits_in = "u16")]
+    port: String,
+    #[validate(json_type = "object")]
+    preferences: Value,
+    // No value fails a pattern in general, nor a warning
+    #[validate(regex_inline = "^[a-z]+$")]
+    #[validate(length(max = 2), enforcement = "warn")]
+    referrer: String,
+    #[validate(length(max = 64), when = "self.age > 100")]
+    note: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::invalid_examples::Signup;
+    use serde_json::json;
+    use validation::{FieldValidation, ValidationError};
+
+    #[test]
+    fn invalid_examples_codes() {
+        assert_eq!(
+            vec![
+                "length",
+                "not_null",
+                "range",
+                "range",
+                "finite",
+                "one_of",
+                "length",
+                "fits_in",
+                "json_type"
+            ],
+            Signup::invalid_examples()
+                .iter()
+                .map(|(_, code)| *code)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn invalid_examples_fail_their_rule() {
+        let signup = Signup {
+            username: "ada".to_string(),
+            email: Some("ada@example.com".to_string()),
+            age: 36,
+            height: Some(1.65),
+            weight: 60.0,
+            plan: "pro".to_string(),
+            port: "8080".to_string(),
+            preferences: json!({}),
+            referrer: "ab".to_string(),
+            note: String::new(),
+        };
+
+        assert!(signup.validate_fields().is_ok());
+        for (example, code) in Signup::invalid_examples() {
+            let errors = example.field_errors(&Default::default());
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.error == ValidationError::FieldMismatch(code.to_string())),
+                "{} has no example failing it",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_examples_change_a_field() {
+        let (example, _) = Signup::invalid_examples().remove(0);
+        assert_eq!("", example.username);
+        let (example, _) = Signup::invalid_examples().remove(2);
+        assert_eq!(12, example.age);
+        let (example, _) = Signup::invalid_examples().remove(6);
+        assert_eq!(5, example.plan.len());
+    }
+}
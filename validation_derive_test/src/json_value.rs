@@ -0,0 +1,68 @@
+use serde_json::Value;
+
+#[derive(FieldValidate)]
+struct Event {
+    #[validate(json_type = "object", max_depth = 2, max_keys = 3)]
+    metadata: Value,
+    #[validate(required_keys("id", "source"))]
+    origin: Option<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_value::Event;
+    use serde_json::json;
+    use validation::{FieldConstraintDescription, FieldValidation, ValidationError};
+
+    fn event(metadata: serde_json::Value, origin: Option<serde_json::Value>) -> Event {
+        Event { metadata, origin }
+    }
+
+    #[test]
+    fn validate_json_value_successful() {
+        assert!(event(json!({ "tags": ["a", "b"], "region": "eu" }), None)
+            .validate_fields()
+            .is_ok());
+        assert!(event(json!({}), Some(json!({ "id": 1, "source": null })))
+            .validate_fields()
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_json_value_failed() {
+        let mismatch = |code: &str| Err(ValidationError::FieldMismatch(code.to_string()));
+
+        assert_eq!(
+            mismatch("json_type"),
+            event(json!(["a"]), None).validate_fields()
+        );
+        assert_eq!(
+            mismatch("max_depth"),
+            event(json!({ "a": { "b": { "c": 1 } } }), None).validate_fields()
+        );
+        assert_eq!(
+            mismatch("max_keys"),
+            event(json!({ "a": 1, "b": 2, "c": 3, "d": 4 }), None).validate_fields()
+        );
+        assert_eq!(
+            mismatch("required_keys"),
+            event(json!({}), Some(json!({ "id": 1 }))).validate_fields()
+        );
+    }
+
+    #[test]
+    fn describe_json_value() {
+        assert_eq!(
+            vec![
+                FieldConstraintDescription::new("metadata", "json_type", None)
+                    .param("type", "object"),
+                FieldConstraintDescription::new("metadata", "max_depth", None)
+                    .param("max_depth", 2),
+                FieldConstraintDescription::new("metadata", "max_keys", None).param("max_keys", 3),
+                FieldConstraintDescription::new("origin", "required_keys", None)
+                    .param("keys", vec!["id", "source"]),
+            ],
+            Event::describe_constraints()
+        );
+    }
+}
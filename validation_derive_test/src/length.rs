@@ -0,0 +1,135 @@
+#[derive(FieldValidate, Serialize)]
+struct Customer {
+    #[validate(length(min = 1, max = 64))]
+    name: String,
+    #[validate(length(max = 3))]
+    initials: Option<String>,
+}
+
+struct Segments(Vec<String>);
+
+impl validation::HasLen for Segments {
+    fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(FieldValidate)]
+struct Upload<'a> {
+    #[validate(length(min = 4, max = 4))]
+    checksum: Vec<u8>,
+    #[validate(length(max = 8))]
+    file_name: std::ffi::OsString,
+    #[validate(length(min = 1))]
+    segments: Segments,
+    #[validate(length(max = 8))]
+    label: std::borrow::Cow<'a, str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::length::{Customer, Segments, Upload};
+    use serde_json::json;
+    use validation::schema::{RuleOverrides, Schema};
+    use validation::{
+        FieldConstraintDescription, FieldValidation, ValidationContext, ValidationError,
+    };
+
+    fn customer(name: &str) -> Customer {
+        Customer {
+            name: name.to_string(),
+            initials: Some("JO".to_string()),
+        }
+    }
+
+    #[test]
+    fn validate_length_successful() {
+        assert!(customer("Joseph").validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_length_failed() {
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            customer("").validate_fields()
+        );
+        assert!(customer(&"x".repeat(65)).validate_fields().is_err());
+        assert!(Customer {
+            initials: Some("JOSE".to_string()),
+            ..customer("Joseph")
+        }
+        .validate_fields()
+        .is_err());
+    }
+
+    #[test]
+    fn validate_length_of_has_len() {
+        let upload = || Upload {
+            checksum: vec![0xDE, 0xAD, 0xBE, 0xEF],
+            file_name: "a.txt".into(),
+            segments: Segments(vec!["tmp".to_string()]),
+            label: "report".into(),
+        };
+        assert!(upload().validate_fields().is_ok());
+        assert!(Upload {
+            checksum: vec![0xDE, 0xAD],
+            ..upload()
+        }
+        .validate_fields()
+        .is_err());
+        assert!(Upload {
+            file_name: "long-name.txt".into(),
+            ..upload()
+        }
+        .validate_fields()
+        .is_err());
+        assert!(Upload {
+            segments: Segments(vec![]),
+            ..upload()
+        }
+        .validate_fields()
+        .is_err());
+        assert!(Upload {
+            label: "quarterly report".into(),
+            ..upload()
+        }
+        .validate_fields()
+        .is_err());
+    }
+
+    #[test]
+    fn describe_length() {
+        assert_eq!(
+            FieldConstraintDescription::new("name", "length", None)
+                .param("min", 1)
+                .param("max", 64),
+            Customer::describe_constraints()[0]
+        );
+    }
+
+    #[test]
+    fn validate_length_tenant_override() {
+        let schema = Schema::new(Customer::describe_constraints()).with_tenant(
+            "tenant-a",
+            RuleOverrides::new().param("name", "length", "max", 128),
+        );
+        let long_name = customer(&"x".repeat(100));
+
+        assert!(long_name.validate_fields().is_err());
+        assert!(schema
+            .validate_serialized(&long_name, &ValidationContext::default())
+            .is_err());
+        assert!(schema
+            .validate_serialized(
+                &long_name,
+                &ValidationContext::default().with_tenant("tenant-a")
+            )
+            .is_ok());
+        assert!(schema
+            .validate(
+                &json!({ "name": "" }),
+                &ValidationContext::default().with_tenant("tenant-a")
+            )
+            .is_err());
+    }
+}
@@ -1,8 +1,69 @@
+// This crate only exercises the derive, everything in it is a test
+#![cfg(test)]
+
+extern crate chrono;
+extern crate csv;
+#[macro_use]
+extern crate derive_builder;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate time;
+extern crate uuid;
 extern crate validation;
+extern crate validator;
 #[macro_use]
 extern crate validation_derive;
 
+mod alias;
+mod associated_consts;
+mod async_rules;
+mod bounded;
+mod builder;
+mod bundle;
+mod cache;
+mod canonical_identifier;
+mod cfg;
+mod compatibility;
+mod compile_fail;
+mod config;
+mod crate_path;
+mod csv_rows;
+mod deprecated;
+mod describe_constraints;
+mod deserialize_validated;
+mod enforcement;
+mod explain;
+mod field_errors;
+mod finite;
+mod fits_in;
+mod flags_of;
+mod inline;
+mod invalid_examples;
+mod json_value;
+mod length;
+mod locale_format;
+mod nested;
 mod not_null;
+mod on_variant;
+mod one_of;
+mod order;
+mod range;
+mod raw;
+mod recently_seen;
+mod reflect;
+mod regex_inline;
+mod scoring;
+mod serde_matrix;
+mod signed;
+mod sources;
+mod strict_serde;
+mod temporal;
+mod time_types;
+mod unknown_fields;
+mod update;
+mod uuids;
+mod validation_config;
+mod validator_compat;
+mod versions;
+mod when;
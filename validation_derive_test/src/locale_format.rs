@@ -0,0 +1,102 @@
+#[derive(FieldValidate)]
+struct Invoice {
+    #[validate(date_format = "short")]
+    issued_on: String,
+    #[validate(date_format(style = "short", locale = "en-US"))]
+    due_on: Option<String>,
+    #[validate(date_format = "iso")]
+    paid_on: Option<&'static str>,
+    #[validate(number_format)]
+    amount: String,
+    #[validate(number_format(locale = "de-DE"))]
+    amount_eur: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale_format::Invoice;
+    use validation::{FieldValidation, ValidationContext, ValidationEnvironment, ValidationError};
+
+    fn ctx(locale: &str) -> ValidationContext {
+        ValidationContext::new(ValidationEnvironment::new().with_locale(locale))
+    }
+
+    #[test]
+    fn validate_locale_format_from_context() {
+        let i = Invoice {
+            issued_on: "31/12/2024".to_string(),
+            due_on: Some("01/31/2025".to_string()),
+            paid_on: Some("2025-01-15"),
+            amount: "1,234.50".to_string(),
+            amount_eur: Some("1.234,50".to_string()),
+        };
+
+        assert!(i.validate_fields_with(&ctx("en-GB")).is_ok());
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("date_format".to_string())),
+            i.validate_fields_with(&ctx("en-US"))
+        );
+    }
+
+    #[test]
+    fn validate_locale_format_default_locale() {
+        let i = Invoice {
+            issued_on: "12/31/2024".to_string(),
+            due_on: Some("01/31/2025".to_string()),
+            paid_on: Some("2025-01-15"),
+            amount: "1,234.50".to_string(),
+            amount_eur: Some("1.234,50".to_string()),
+        };
+
+        assert!(i.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_date_format_explicit_locale() {
+        let i = Invoice {
+            issued_on: "31/12/2024".to_string(),
+            due_on: Some("31/01/2025".to_string()),
+            paid_on: Some("2025-01-15"),
+            amount: "1,234.50".to_string(),
+            amount_eur: Some("1.234,50".to_string()),
+        };
+
+        assert!(i.validate_fields_with(&ctx("en-GB")).is_err());
+    }
+
+    #[test]
+    fn validate_date_format_iso() {
+        let i = Invoice {
+            issued_on: "31/12/2024".to_string(),
+            due_on: Some("01/31/2025".to_string()),
+            paid_on: Some("15/01/2025"),
+            amount: "1,234.50".to_string(),
+            amount_eur: Some("1.234,50".to_string()),
+        };
+
+        assert!(i.validate_fields_with(&ctx("en-GB")).is_err());
+    }
+
+    #[test]
+    fn validate_number_format() {
+        let i = Invoice {
+            issued_on: "31/12/2024".to_string(),
+            due_on: Some("01/31/2025".to_string()),
+            paid_on: Some("2025-01-15"),
+            amount: "1.234,50".to_string(),
+            amount_eur: Some("1.234,50".to_string()),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("number_format".to_string())),
+            i.validate_fields_with(&ctx("en-GB"))
+        );
+        assert!(i.validate_fields_with(&ctx("de-DE")).is_err());
+        assert!(Invoice {
+            issued_on: "31.12.2024".to_string(),
+            ..i
+        }
+        .validate_fields_with(&ctx("de-DE"))
+        .is_ok());
+    }
+}
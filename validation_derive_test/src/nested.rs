@@ -0,0 +1,106 @@
+#[derive(FieldValidate)]
+struct Category {
+    #[validate(length(min = 1, max = 16))]
+    name: String,
+    #[validate(nested)]
+    parent: Option<Box<Category>>,
+    #[validate(nested(max_depth = 2))]
+    children: Vec<Category>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nested::Category;
+    use validation::{
+        FieldConstraintDescription, FieldError, FieldValidation, ValidationContext, ValidationError,
+    };
+
+    fn category(name: &str, children: Vec<Category>) -> Category {
+        Category {
+            name: name.to_string(),
+            parent: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn nested_valid_tree() {
+        let tree = category("root", vec![category("a", vec![category("b", vec![])])]);
+        assert!(tree.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn nested_errors_have_their_path() {
+        let tree = Category {
+            parent: Some(Box::new(category("", vec![]))),
+            ..category("root", vec![category("a", vec![]), category("", vec![])])
+        };
+
+        assert_eq!(
+            vec![
+                FieldError::new(
+                    "parent.name",
+                    ValidationError::FieldMismatch("length".to_string())
+                ),
+                FieldError::new(
+                    "children[1].name",
+                    ValidationError::FieldMismatch("length".to_string())
+                ),
+            ],
+            tree.field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nested_deeper_than_max_depth() {
+        let tree = category(
+            "root",
+            vec![category(
+                "a",
+                vec![category("b", vec![category("c", vec![])])],
+            )],
+        );
+
+        assert_eq!(
+            vec![FieldError::new(
+                "children[0].children[0].children[0]",
+                ValidationError::InvalidState("nested".to_string())
+            )],
+            tree.field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nested_max_depth_of_context() {
+        let tree = Category {
+            parent: Some(Box::new(Category {
+                parent: Some(Box::new(category("grandparent", vec![]))),
+                ..category("parent", vec![])
+            })),
+            ..category("root", vec![])
+        };
+
+        assert!(tree.validate_fields().is_ok());
+        assert_eq!(
+            Err(ValidationError::InvalidState("nested".to_string())),
+            tree.validate_fields_with(&ValidationContext::default().with_max_depth(1))
+        );
+    }
+
+    #[test]
+    fn describe_nested() {
+        let descriptions = Category::describe_constraints();
+        assert_eq!(
+            FieldConstraintDescription::new("parent", "nested", None),
+            descriptions[1]
+        );
+        assert_eq!(
+            FieldConstraintDescription::new("children", "nested", None).param("max_depth", 2),
+            descriptions[2]
+        );
+    }
+}
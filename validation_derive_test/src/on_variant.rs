@@ -0,0 +1,97 @@
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(f64),
+}
+
+#[derive(Deserialize, FieldValidate)]
+struct Product {
+    #[validate(on_variant(String, length(max = 8), regex_inline = "^[A-Z0-9-]+$"))]
+    sku: StringOrNumber,
+    #[validate(on_variant(String, length(min = 1)), enforcement = "warn")]
+    #[validate(on_variant(String, length(max = 16)))]
+    label: Option<StringOrNumber>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::on_variant::{Product, StringOrNumber};
+    use serde_json::json;
+    use validation::{
+        FieldConstraintDescription, FieldValidation, PlannedRuleStatus, SkipReason, ValidationError,
+    };
+
+    fn product(value: serde_json::Value) -> Product {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn on_variant_rules_apply_to_the_variant() {
+        assert!(product(json!({ "sku": "AB-12", "label": "Shoes" }))
+            .validate_fields()
+            .is_ok());
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            product(json!({ "sku": "AB-123456" })).validate_fields()
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("regex_inline".to_string())),
+            product(json!({ "sku": "ab" })).validate_fields()
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            product(json!({ "sku": "AB", "label": "x".repeat(17) })).validate_fields()
+        );
+    }
+
+    #[test]
+    fn on_variant_rules_skipped_for_other_variants() {
+        let numbers = product(json!({ "sku": 123456789.0, "label": 12 }));
+        assert!(matches!(numbers.sku, StringOrNumber::Number(n) if n > 1e8));
+        assert!(numbers.validate_fields().is_ok());
+        // The `min` of `label` is only a warning
+        assert!(product(json!({ "sku": "AB", "label": "" }))
+            .validate_fields()
+            .is_ok());
+    }
+
+    #[test]
+    fn describe_on_variant() {
+        assert_eq!(
+            vec![
+                FieldConstraintDescription::new("sku", "length", None)
+                    .param("max", 8)
+                    .param("variant", "String"),
+                FieldConstraintDescription::new("sku", "regex_inline", None)
+                    .param("pattern", "^[A-Z0-9-]+$")
+                    .param("variant", "String"),
+                FieldConstraintDescription::new("label", "length", None)
+                    .param("min", 1)
+                    .param("variant", "String"),
+                FieldConstraintDescription::new("label", "length", None)
+                    .param("max", 16)
+                    .param("variant", "String"),
+            ],
+            Product::describe_constraints()
+        );
+    }
+
+    #[test]
+    fn explain_on_variant() {
+        let plan = Product {
+            sku: StringOrNumber::Number(1.0),
+            label: None,
+        }
+        .explain();
+        assert_eq!(
+            PlannedRuleStatus::Skipped(SkipReason::OtherVariant("String".to_string())),
+            plan.rules[0].status
+        );
+        assert_eq!(
+            PlannedRuleStatus::Skipped(SkipReason::NoValue),
+            plan.rules[2].status
+        );
+        assert!(product(json!({ "sku": "AB" })).explain().rules[0].will_run());
+    }
+}
@@ -0,0 +1,110 @@
+#[derive(FieldValidate)]
+struct Transfer {
+    #[validate(one_of("EUR", "USD"))]
+    currency: String,
+    #[validate(one_of("Ärzte", "Zürich", collation = "case_fold"))]
+    payee: String,
+    #[validate(one_of("ﬁle", "ledger", collation = "nfkc_case_fold"))]
+    source: Option<String>,
+    #[validate(one_of(1, 2, 3))]
+    priority: u8,
+}
+
+#[derive(FieldValidate)]
+struct Subscription {
+    #[validate(one_of("basic", "business", "enterprise", suggest))]
+    plan: String,
+    #[validate(one_of("EUR", "USD", collation = "case_fold", suggest))]
+    currency: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::one_of::{Subscription, Transfer};
+    use validation::{FieldError, FieldValidation, ValidationContext, ValidationError};
+
+    #[test]
+    fn validate_one_of() {
+        let t = Transfer {
+            currency: "EUR".to_string(),
+            payee: "zürich".to_string(),
+            source: Some("FILE".to_string()),
+            priority: 2,
+        };
+        assert!(t.validate_fields().is_ok());
+
+        let t = Transfer { source: None, ..t };
+        assert!(t.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_one_of_exact() {
+        let t = Transfer {
+            currency: "eur".to_string(),
+            payee: "zürich".to_string(),
+            source: Some("FILE".to_string()),
+            priority: 2,
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("one_of".to_string())),
+            t.validate_fields()
+        );
+    }
+
+    #[test]
+    fn validate_one_of_collation() {
+        let transfer = Transfer {
+            currency: "EUR".to_string(),
+            payee: "ZURICH".to_string(),
+            source: Some("file.".to_string()),
+            priority: 4,
+        };
+
+        assert_eq!(
+            vec!["payee", "source", "priority"],
+            transfer
+                .field_errors(&ValidationContext::default())
+                .into_iter()
+                .filter_map(|error| error.field)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_one_of_suggestion() {
+        let subscription = Subscription {
+            plan: "buisness".to_string(),
+            currency: Some("eru".to_string()),
+        };
+        let mismatch = |field: &str| {
+            FieldError::new(field, ValidationError::FieldMismatch("one_of".to_string()))
+        };
+
+        assert_eq!(
+            vec![
+                mismatch("plan").with_param("suggestion", "business"),
+                mismatch("currency"),
+            ],
+            subscription
+                .field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_one_of_suggestion_in_response() {
+        let subscription = Subscription {
+            plan: "basic".to_string(),
+            currency: Some("usdd".to_string()),
+        };
+
+        assert_eq!(
+            r#"[{"field":"currency","kind":"field_mismatch","code":"one_of","params":{"suggestion":"USD"}}]"#,
+            subscription
+                .field_errors(&ValidationContext::default())
+                .to_json()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+#[derive(FieldValidate)]
+struct Booking {
+    #[validate(date_format = "iso")]
+    #[validate(length(max = 10))]
+    date: String,
+    #[validate(length(max = 8))]
+    code: String,
+    #[validate(regex_inline = "^[A-Z]{3}$", order = 2)]
+    #[validate(length(min = 1), order = 1)]
+    airport: String,
+    #[validate(nested)]
+    legs: Vec<Leg>,
+}
+
+#[derive(FieldValidate)]
+struct Leg {
+    #[validate(length(min = 1))]
+    flight: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::{Booking, Leg};
+    use validation::{FieldValidation, ValidationContext, ValidationError};
+
+    fn errors(b: &Booking) -> Vec<String> {
+        b.field_errors(&ValidationContext::default())
+            .iter()
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn validate_fields_in_declaration_order() {
+        let b = Booking {
+            date: "tomorrow".to_string(),
+            code: "a code over eight characters".to_string(),
+            airport: String::new(),
+            legs: vec![],
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("date_format".to_string())),
+            b.validate_fields()
+        );
+        assert_eq!(
+            vec![
+                "date: date_format",
+                "code: length",
+                "airport: length",
+                "airport: regex_inline",
+            ],
+            errors(&b)
+        );
+    }
+
+    #[test]
+    fn validate_cheap_rules_before_expensive_ones() {
+        let b = Booking {
+            date: "the day after tomorrow".to_string(),
+            code: "a code over eight characters".to_string(),
+            airport: "LHR".to_string(),
+            legs: vec![],
+        };
+
+        assert_eq!(
+            vec!["date: length", "date: date_format", "code: length"],
+            errors(&b)
+        );
+    }
+
+    #[test]
+    fn validate_collection_in_index_order() {
+        let leg = |flight: &str| Leg {
+            flight: flight.to_string(),
+        };
+        let b = Booking {
+            date: "2024-06-01".to_string(),
+            code: "ABC".to_string(),
+            airport: "LHR".to_string(),
+            legs: vec![leg(""), leg("BA117"), leg("")],
+        };
+
+        // The same on every run, whatever the values
+        for _ in 0..3 {
+            assert_eq!(
+                vec!["legs[0].flight: length", "legs[2].flight: length"],
+                errors(&b)
+            );
+        }
+    }
+
+    #[test]
+    fn explain_in_run_order() {
+        let b = Booking {
+            date: "tomorrow".to_string(),
+            code: "a code over eight characters".to_string(),
+            airport: String::new(),
+            legs: vec![],
+        };
+        let rules = b
+            .explain()
+            .to_run()
+            .map(|rule| format!("{}: {}", rule.field, rule.rule))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                "date: length",
+                "date: date_format",
+                "code: length",
+                "airport: length",
+                "airport: regex_inline",
+                "legs: nested",
+            ],
+            rules
+        );
+    }
+}
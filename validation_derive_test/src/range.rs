@@ -0,0 +1,156 @@
+use chrono::NaiveDate;
+
+enum Quantity {
+    Count(u32),
+    Label(String),
+}
+
+#[derive(FieldValidate)]
+struct Shipment {
+    #[validate(range(min = 1, max = 99))]
+    parcels: u8,
+    #[validate(range(min = -40, max = 60))]
+    temperature: Option<i16>,
+    #[validate(range(min = 0, max = 1))]
+    insured_ratio: f64,
+    #[validate(range(exclusive_min = 0))]
+    weight: f32,
+    #[validate(range(min = 0.0, exclusive_max = 1.0))]
+    discount: Option<f64>,
+    #[validate(range(min = 'A', max = 'F'))]
+    zone: char,
+    #[validate(range(min = "A000", max = "M999"))]
+    route: String,
+    #[validate(range(min = "2024-01-01", max = "2024-12-31"))]
+    shipped_on: Option<NaiveDate>,
+    #[validate(
+        on_variant(Count, range(max = 500)),
+        on_variant(Label, range(min = "a", max = "z"))
+    )]
+    quantity: Quantity,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::range::{Quantity, Shipment};
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use validation::{FieldValidation, ValidationContext, ValidationError};
+
+    fn invalid_fields(shipment: &Shipment) -> Vec<String> {
+        shipment
+            .field_errors(&ValidationContext::default())
+            .into_iter()
+            .filter_map(|error| error.field)
+            .collect()
+    }
+
+    #[test]
+    fn validate_range_successful() {
+        let s = Shipment {
+            parcels: 3,
+            temperature: Some(-40),
+            insured_ratio: 1.0,
+            weight: 0.25,
+            discount: Some(0.0),
+            zone: 'C',
+            route: "B120".to_string(),
+            shipped_on: NaiveDate::from_ymd_opt(2024, 12, 31),
+            quantity: Quantity::Count(500),
+        };
+        assert!(s.validate_fields().is_ok());
+
+        let s = Shipment {
+            temperature: None,
+            shipped_on: None,
+            quantity: Quantity::Label("pallet".to_string()),
+            ..s
+        };
+        assert!(s.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_range_failed() {
+        let s = Shipment {
+            parcels: 0,
+            temperature: Some(-40),
+            insured_ratio: 1.0,
+            weight: 0.25,
+            discount: Some(0.0),
+            zone: 'C',
+            route: "B120".to_string(),
+            shipped_on: NaiveDate::from_ymd_opt(2024, 12, 31),
+            quantity: Quantity::Count(500),
+        };
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("range".to_string())),
+            s.validate_fields()
+        );
+
+        let s = Shipment {
+            parcels: 3,
+            temperature: Some(61),
+            insured_ratio: f64::NAN,
+            zone: 'a',
+            route: "N000".to_string(),
+            shipped_on: NaiveDate::from_ymd_opt(2025, 1, 1),
+            quantity: Quantity::Count(501),
+            ..s
+        };
+        assert_eq!(
+            vec![
+                "temperature",
+                "insured_ratio",
+                "zone",
+                "route",
+                "shipped_on",
+                "quantity"
+            ],
+            invalid_fields(&s)
+        );
+    }
+
+    #[test]
+    fn validate_range_exclusive() {
+        let s = Shipment {
+            parcels: 3,
+            temperature: Some(-40),
+            insured_ratio: 1.0,
+            weight: 0.0,
+            discount: Some(1.0),
+            zone: 'C',
+            route: "B120".to_string(),
+            shipped_on: NaiveDate::from_ymd_opt(2024, 12, 31),
+            quantity: Quantity::Count(500),
+        };
+        assert_eq!(vec!["weight", "discount"], invalid_fields(&s));
+
+        let s = Shipment {
+            weight: -0.5,
+            discount: Some(f64::NAN),
+            ..s
+        };
+        assert_eq!(vec!["weight", "discount"], invalid_fields(&s));
+    }
+
+    #[test]
+    fn describe_range() {
+        let descriptions = Shipment::describe_constraints();
+        let params = |field: &str| {
+            let description = descriptions.iter().find(|d| d.field == field).unwrap();
+            json!(description.params)
+        };
+
+        assert_eq!(json!({ "min": -40, "max": 60 }), params("temperature"));
+        assert_eq!(json!({ "exclusive_min": 0 }), params("weight"));
+        assert_eq!(
+            json!({ "min": 0.0, "exclusive_max": 1.0 }),
+            params("discount")
+        );
+        assert_eq!(json!({ "min": "A", "max": "F" }), params("zone"));
+        assert_eq!(
+            json!({ "min": "2024-01-01", "max": "2024-12-31" }),
+            params("shipped_on")
+        );
+    }
+}
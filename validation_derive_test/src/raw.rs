@@ -0,0 +1,85 @@
+use std::convert::TryFrom;
+
+#[derive(Debug, Deserialize, FieldValidate, PartialEq)]
+#[validation(raw = "RawOrder")]
+struct Order {
+    #[validate(length(min = 1, max = 8))]
+    reference: String,
+    #[serde(rename = "qty")]
+    quantity: u32,
+    #[validate(length(max = 16))]
+    note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, FieldValidate, PartialEq)]
+#[validation(raw = "RawPayment")]
+struct Payment {
+    #[validate(one_of("card", "cash"))]
+    r#type: String,
+}
+
+impl Order {
+    fn parse(json: &str) -> Result<Order, validation::ValidationErrors> {
+        Order::try_from(serde_json::from_str::<RawOrder>(json).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::raw::{Order, Payment, RawOrder, RawPayment};
+    use std::convert::TryFrom;
+    use validation::{FieldError, ValidationError};
+
+    #[test]
+    fn try_from_raw_successful() {
+        assert_eq!(
+            Ok(Order {
+                reference: "A-1".to_string(),
+                quantity: 3,
+                note: None,
+            }),
+            Order::parse(r#"{ "reference": "A-1", "qty": 3 }"#)
+        );
+    }
+
+    #[test]
+    fn try_from_raw_missing_fields() {
+        let errors = Order::parse(r#"{ "note": "fragile" }"#).unwrap_err();
+        assert_eq!(
+            vec![
+                FieldError::new(
+                    "reference",
+                    ValidationError::FieldMismatch("not_null".to_string())
+                ),
+                FieldError::new(
+                    "qty",
+                    ValidationError::FieldMismatch("not_null".to_string())
+                ),
+            ],
+            errors.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn try_from_raw_invalid_fields() {
+        let raw = RawOrder {
+            reference: Some("A-123456789".to_string()),
+            quantity: Some(1),
+            note: Some("x".repeat(20)),
+        };
+        let errors = Order::try_from(raw).unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!(Some("reference"), errors.first().unwrap().field.as_deref());
+    }
+
+    #[test]
+    fn try_from_raw_identifier_field() {
+        let missing = Payment::try_from(RawPayment { r#type: None }).unwrap_err();
+        assert_eq!(Some("type"), missing.first().unwrap().field.as_deref());
+
+        let raw = serde_json::from_str::<RawPayment>(r#"{ "type": "cheque" }"#).unwrap();
+        let errors = Payment::try_from(raw).unwrap_err();
+        assert_eq!(Some("type"), errors.first().unwrap().field.as_deref());
+        assert_eq!("type", Payment::describe_constraints()[0].field);
+    }
+}
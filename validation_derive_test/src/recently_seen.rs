@@ -0,0 +1,167 @@
+#[derive(FieldValidate)]
+struct Payment {
+    #[validate(recently_seen(window = 60))]
+    idempotency_key: String,
+    #[validate(length(max = 3))]
+    currency: String,
+    #[validate(recently_seen(window = 60, key_fn = "Payment::submission"))]
+    amount: Option<u64>,
+}
+
+impl Payment {
+    fn submission(&self) -> String {
+        format!("{}:{:?}", self.currency, self.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::recently_seen::Payment;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use validation::{
+        AsyncFieldValidation, BoxFuture, FieldValidation, FixedClock, InMemorySeenStore, SeenStore,
+        Timer, ValidationContext, ValidationEnvironment, ValidationError, ValidationErrorResponse,
+    };
+
+    // Store that never answers, like one whose connection hangs
+    struct Hanging;
+
+    impl SeenStore for Hanging {
+        fn check_and_record<'a>(
+            &'a self,
+            _: &'a str,
+            _: Duration,
+            _: SystemTime,
+        ) -> BoxFuture<'a, bool> {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    // Timer whose every sleep has already elapsed
+    struct Elapsed;
+
+    impl Timer for Elapsed {
+        fn sleep(&self, _: Duration) -> BoxFuture<'static, ()> {
+            Box::pin(async {})
+        }
+    }
+
+    // Polls the future once, the in memory store never waits
+    fn ready<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the future is not ready"),
+        }
+    }
+
+    fn context(store: &Arc<InMemorySeenStore>, seconds: u64) -> ValidationContext {
+        ValidationContext::new(
+            ValidationEnvironment::new()
+                .with_clock(FixedClock(UNIX_EPOCH + Duration::from_secs(seconds)))
+                .with_seen_store(store.clone()),
+        )
+    }
+
+    fn payment(key: &str) -> Payment {
+        Payment {
+            idempotency_key: key.to_string(),
+            currency: "EUR".to_string(),
+            amount: None,
+        }
+    }
+
+    #[test]
+    fn validate_recently_seen_duplicate() {
+        let store = Arc::new(InMemorySeenStore::new());
+
+        assert!(ready(payment("a").validate_fields_async(&context(&store, 0))).is_ok());
+        assert!(ready(payment("b").validate_fields_async(&context(&store, 1))).is_ok());
+
+        let result = ready(payment("a").validate_fields_async(&context(&store, 2)));
+        assert_eq!(
+            Err(ValidationError::InvalidState("recently_seen".to_string())),
+            result
+        );
+        assert_eq!(
+            409,
+            ValidationErrorResponse::from(result.unwrap_err()).error_code
+        );
+
+        assert!(ready(payment("a").validate_fields_async(&context(&store, 61))).is_ok());
+    }
+
+    #[test]
+    fn validate_recently_seen_key_fn() {
+        let store = Arc::new(InMemorySeenStore::new());
+        let p = Payment {
+            amount: Some(100),
+            ..payment("a")
+        };
+        assert!(ready(p.field_errors_async(&context(&store, 0))).is_empty());
+
+        let errors = ready(
+            Payment {
+                amount: Some(100),
+                ..payment("b")
+            }
+            .field_errors_async(&context(&store, 1)),
+        );
+        assert_eq!(
+            vec!["amount: recently_seen".to_string()],
+            errors.iter().map(|err| err.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_sync_rules_before_recently_seen() {
+        let store = Arc::new(InMemorySeenStore::new());
+        let p = Payment {
+            currency: "EURO".to_string(),
+            ..payment("a")
+        };
+        ready(p.field_errors_async(&context(&store, 0)));
+
+        let errors = ready(p.field_errors_async(&context(&store, 1)));
+        assert_eq!(
+            vec![
+                "currency: length".to_string(),
+                "idempotency_key: recently_seen".to_string()
+            ],
+            errors.iter().map(|err| err.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            p.validate_fields()
+        );
+        assert_eq!(1, p.field_errors(&context(&store, 2)).len());
+    }
+
+    #[test]
+    fn validate_recently_seen_timeout() {
+        let ctx = ValidationContext::new(
+            ValidationEnvironment::new()
+                .with_seen_store(Arc::new(Hanging))
+                .with_timer(Arc::new(Elapsed)),
+        )
+        .with_rule_timeout(Duration::from_millis(100));
+
+        let errors = ready(payment("a").field_errors_async(&ctx));
+        assert_eq!(
+            vec!["idempotency_key: validation_timeout".to_string()],
+            errors.iter().map(|err| err.to_string()).collect::<Vec<_>>()
+        );
+
+        let result = ready(payment("a").validate_fields_async(&ctx));
+        assert_eq!(
+            503,
+            ValidationErrorResponse::from(result.unwrap_err()).error_code
+        );
+    }
+}
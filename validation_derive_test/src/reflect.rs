@@ -0,0 +1,65 @@
+#[derive(Deserialize, FieldValidate)]
+struct Signup {
+    #[serde(rename = "userName")]
+    #[validate(length(min = 3, max = 16), groups("signup", "profile"))]
+    #[validate(regex_inline = "^[a-z0-9_]+$")]
+    username: String,
+    #[validate(not_null, groups("signup"))]
+    email: Option<String>,
+    #[validate(range(min = 13))]
+    age: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reflect::Signup;
+    use serde_json::json;
+    use validation::reflect::{self, Reflect, REFLECT_VERSION};
+
+    #[test]
+    fn reflect_type_info() {
+        let info = reflect::type_info::<Signup>();
+        assert_eq!(REFLECT_VERSION, info.reflect_version);
+        assert_eq!("Signup", info.name);
+        assert_eq!(
+            vec!["userName", "email", "age"],
+            info.fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let username = &info.fields[0];
+        assert_eq!("length", username.rules[0].name);
+        assert_eq!(Some(&json!(16)), username.rules[0].params.get("max"));
+        assert_eq!(vec!["signup", "profile"], username.rules[0].groups);
+        assert!(username.rules[1].groups.is_empty());
+        assert_eq!(
+            Some("value is out of the allowed range".to_string()),
+            info.fields[2].rules[0].message
+        );
+    }
+
+    #[test]
+    fn reflect_groups() {
+        let signup = Signup::type_info().in_group("signup");
+        assert_eq!(2, signup.fields.len());
+        assert_eq!(1, signup.fields[0].rules.len());
+        assert!(Signup::type_info().in_group("admin").fields.is_empty());
+    }
+
+    #[test]
+    fn reflect_json() {
+        let json: serde_json::Value = serde_json::from_str(&Signup::type_info().to_json()).unwrap();
+        assert_eq!(json!(1), json["reflectVersion"]);
+        assert_eq!(
+            json!({
+                "name": "not_null",
+                "params": {},
+                "message": "value is required",
+                "groups": ["signup"]
+            }),
+            json["fields"][1]["rules"][0]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+
+#[derive(FieldValidate)]
+struct Slug<'a> {
+    #[validate(regex_inline = "^[a-z]+$")]
+    name: String,
+    #[validate(regex_inline = "^[a-z]+$")]
+    alias: Option<String>,
+    #[validate(regex_inline = "^[0-9]{3}$")]
+    code: &'a str,
+    #[validate(not_null, regex_inline = "^[0-9]{3}$")]
+    area: Option<Cow<'a, str>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex_inline::Slug;
+    use std::borrow::Cow;
+    use validation::{FieldValidation, ValidationError};
+
+    #[test]
+    fn validate_regex_inline_successful() {
+        let s = Slug {
+            name: "sql".to_string(),
+            alias: Some("mongo".to_string()),
+            code: "404",
+            area: Some(Cow::from("212")),
+        };
+
+        assert!(s.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_regex_inline_skips_none() {
+        let s = Slug {
+            name: "sql".to_string(),
+            alias: None,
+            code: "404",
+            area: Some(Cow::from("212")),
+        };
+
+        assert!(s.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_regex_inline_failed() {
+        let s = Slug {
+            name: "NoSQL".to_string(),
+            alias: Some("mongo".to_string()),
+            code: "404",
+            area: Some(Cow::from("212")),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("regex_inline".to_string())),
+            s.validate_fields()
+        );
+    }
+
+    #[test]
+    fn validate_regex_inline_failed_shared_pattern() {
+        let s = Slug {
+            name: "sql".to_string(),
+            alias: Some("Mongo".to_string()),
+            code: "404",
+            area: Some(Cow::from("212")),
+        };
+
+        assert!(s.validate_fields().is_err());
+    }
+
+    #[test]
+    fn validate_regex_inline_failed_reference() {
+        let s = Slug {
+            name: "sql".to_string(),
+            alias: Some("mongo".to_string()),
+            code: "40",
+            area: Some(Cow::from("212")),
+        };
+
+        assert!(s.validate_fields().is_err());
+    }
+
+    #[test]
+    fn validate_regex_inline_failed_alongside_not_null() {
+        let s = Slug {
+            name: "sql".to_string(),
+            alias: Some("mongo".to_string()),
+            code: "404",
+            area: None,
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("not_null".to_string())),
+            s.validate_fields()
+        );
+    }
+}
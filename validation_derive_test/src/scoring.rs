@@ -0,0 +1,93 @@
+#[derive(FieldValidate)]
+struct Listing {
+    #[validate(not_null, weight = 5)]
+    title: Option<String>,
+    #[validate(length(min = 20), weight = 2)]
+    description: String,
+    #[validate(regex_inline = "^https://")]
+    photo_url: Option<String>,
+    #[validate(nested)]
+    seller: Seller,
+}
+
+#[derive(FieldValidate)]
+struct Seller {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoring::{Listing, Seller};
+    use validation::ValidationContext;
+
+    fn listing(title: Option<&str>, description: &str, photo_url: Option<&str>) -> Listing {
+        Listing {
+            title: title.map(str::to_string),
+            description: description.to_string(),
+            photo_url: photo_url.map(str::to_string),
+            seller: Seller {
+                name: "Joseph".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn scored_complete() {
+        let scored = listing(
+            Some("Bike"),
+            "A red bike, barely ridden",
+            Some("https://img"),
+        )
+        .validate_scored();
+
+        assert_eq!(1.0, scored.score);
+        assert_eq!(9, scored.total_weight);
+        assert!(scored.is_valid());
+    }
+
+    #[test]
+    fn scored_weighted_failures() {
+        // The photo rule is skipped without a value, it does not count
+        let scored = listing(Some("Bike"), "Red bike", None).validate_scored();
+        assert_eq!((6, 8), (scored.passed_weight, scored.total_weight));
+        assert_eq!(0.75, scored.score);
+        assert_eq!(1, scored.errors.len());
+
+        let mut untitled = listing(None, "Red bike", Some("http://img"));
+        untitled.seller.name = String::new();
+        let scored = untitled.validate_scored_with(&ValidationContext::default());
+        assert_eq!((0, 9), (scored.passed_weight, scored.total_weight));
+        assert_eq!(0.0, scored.score);
+    }
+
+    #[test]
+    fn scored_ranking() {
+        let mut records = [
+            listing(None, "Red bike", None),
+            listing(Some("Bike"), "A red bike, barely ridden", None),
+            listing(Some("Bike"), "Red bike", None),
+        ];
+        records.sort_by(|a, b| {
+            b.validate_scored()
+                .score
+                .partial_cmp(&a.validate_scored().score)
+                .unwrap()
+        });
+
+        let scores = records
+            .iter()
+            .map(|record| record.validate_scored().score)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1.0, 0.75, 1.0 / 8.0], scores);
+    }
+
+    #[test]
+    fn scored_weights_described() {
+        let weights = Listing::describe_constraints()
+            .into_iter()
+            .map(|constraint| constraint.weight)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Some(5), Some(2), None, None], weights);
+    }
+}
@@ -0,0 +1,271 @@
+#[derive(Serialize, FieldValidate)]
+struct Renamed {
+    #[serde(rename = "displayName")]
+    #[validate(not_null)]
+    display_name: Option<String>,
+    #[serde(rename = "nick")]
+    #[validate(length(max = 4))]
+    nickname: String,
+    #[serde(rename = "years")]
+    #[validate(range(max = 120))]
+    age: u32,
+    #[serde(rename = "mail")]
+    #[validate(regex_inline = "^[^@]+@[^@]+$")]
+    email: String,
+    #[serde(rename = "ccy")]
+    #[validate(one_of("EUR", "USD"))]
+    currency: String,
+    #[serde(rename = "home")]
+    #[validate(nested)]
+    address: Address,
+}
+
+#[derive(Serialize, FieldValidate)]
+struct Address {
+    #[validate(length(min = 1))]
+    street_name: String,
+}
+
+#[derive(Serialize, FieldValidate)]
+#[serde(rename_all = "camelCase")]
+struct Camel {
+    #[validate(length(max = 4))]
+    first_name: String,
+    #[serde(rename = "surname")]
+    #[validate(length(max = 4))]
+    last_name: String,
+}
+
+#[derive(Serialize, FieldValidate)]
+#[serde(rename_all = "kebab-case")]
+struct Kebab {
+    #[validate(range(min = 1))]
+    max_retries: u32,
+}
+
+#[derive(Serialize, FieldValidate)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Screaming {
+    #[validate(not_null)]
+    api_key: Option<String>,
+}
+
+#[derive(Serialize, FieldValidate)]
+#[serde(rename_all = "PascalCase")]
+struct Pascal {
+    #[validate(one_of("EUR", "USD"))]
+    base_currency: String,
+}
+
+#[derive(Serialize, FieldValidate)]
+#[serde(rename_all(serialize = "camelCase"))]
+struct SplitRenameAll {
+    #[serde(rename(serialize = "zip", deserialize = "postcode"))]
+    #[validate(length(max = 5))]
+    post_code: String,
+    #[validate(length(max = 5))]
+    city_name: String,
+}
+
+#[derive(Serialize, FieldValidate)]
+struct Flattened {
+    #[validate(length(max = 4))]
+    name: String,
+    #[serde(flatten)]
+    #[validate(nested)]
+    address: Address,
+}
+
+#[derive(Serialize, FieldValidate)]
+struct Skipped {
+    #[serde(skip)]
+    #[validate(length(max = 4))]
+    internal_note: String,
+}
+
+#[derive(Debug, DeserializeValidated, FieldValidate, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Defaulted {
+    #[serde(default = "default_page_size")]
+    #[validate(range(min = 1, max = 100))]
+    page_size: u32,
+    #[serde(default)]
+    #[validate(length(max = 8))]
+    sort_by: String,
+}
+
+fn default_page_size() -> u32 {
+    500
+}
+
+#[derive(Debug, DeserializeValidated, FieldValidate, PartialEq)]
+#[serde(tag = "type")]
+struct Tagged {
+    #[validate(length(max = 4))]
+    label: String,
+}
+
+#[derive(Debug, DeserializeValidated, FieldValidate, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct Strict {
+    #[validate(length(max = 4))]
+    user_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde_matrix::*;
+    use serde::Serialize;
+    use validation::{FieldValidation, ValidationContext, ValidationErrors};
+
+    fn error_fields<T: FieldValidation>(value: &T) -> Vec<String> {
+        value
+            .field_errors(&ValidationContext::default())
+            .iter()
+            .filter_map(|err| err.field.clone())
+            .collect()
+    }
+
+    // Every field an error is reported on must be a key of the serialized value
+    fn assert_serialized_names<T: FieldValidation + Serialize>(value: &T, expected: &[&str]) {
+        let serialized = serde_json::to_value(value).unwrap();
+        let fields = error_fields(value);
+        assert_eq!(expected, fields.as_slice());
+        for field in &fields {
+            let top = field.split('.').next().unwrap();
+            assert!(serialized.get(top).is_some(), "{} is not serialized", field);
+        }
+    }
+
+    fn embedded_fields(err: serde_json::Error) -> Vec<String> {
+        ValidationErrors::from_embedded(&err.to_string())
+            .unwrap()
+            .iter()
+            .filter_map(|err| err.field.clone())
+            .collect()
+    }
+
+    #[test]
+    fn matrix_rename() {
+        let renamed = Renamed {
+            display_name: None,
+            nickname: "joseph".to_string(),
+            age: 130,
+            email: "nope".to_string(),
+            currency: "GBP".to_string(),
+            address: Address {
+                street_name: String::new(),
+            },
+        };
+
+        assert_serialized_names(
+            &renamed,
+            &[
+                "displayName",
+                "nick",
+                "years",
+                "mail",
+                "ccy",
+                "home.street_name",
+            ],
+        );
+    }
+
+    #[test]
+    fn matrix_rename_all() {
+        assert_serialized_names(
+            &Camel {
+                first_name: "joseph".to_string(),
+                last_name: "ormerod".to_string(),
+            },
+            &["firstName", "surname"],
+        );
+        assert_serialized_names(&Kebab { max_retries: 0 }, &["max-retries"]);
+        assert_serialized_names(&Screaming { api_key: None }, &["API_KEY"]);
+        assert_serialized_names(
+            &Pascal {
+                base_currency: "GBP".to_string(),
+            },
+            &["BaseCurrency"],
+        );
+    }
+
+    #[test]
+    fn matrix_split_rename() {
+        assert_serialized_names(
+            &SplitRenameAll {
+                post_code: "123456".to_string(),
+                city_name: "Amsterdam".to_string(),
+            },
+            &["zip", "cityName"],
+        );
+    }
+
+    #[test]
+    fn matrix_flatten() {
+        assert_serialized_names(
+            &Flattened {
+                name: "joseph".to_string(),
+                address: Address {
+                    street_name: String::new(),
+                },
+            },
+            &["name", "street_name"],
+        );
+    }
+
+    #[test]
+    fn matrix_skip() {
+        let skipped = Skipped {
+            internal_note: "too long".to_string(),
+        };
+
+        assert_eq!(vec!["internal_note"], error_fields(&skipped));
+        assert_eq!(
+            serde_json::json!({}),
+            serde_json::to_value(&skipped).unwrap()
+        );
+    }
+
+    #[test]
+    fn matrix_default() {
+        let err = serde_json::from_str::<Defaulted>("{}").unwrap_err();
+        assert_eq!(vec!["pageSize"], embedded_fields(err));
+
+        assert_eq!(
+            Defaulted {
+                page_size: 20,
+                sort_by: String::new(),
+            },
+            serde_json::from_str(r#"{ "pageSize": 20 }"#).unwrap()
+        );
+        let err =
+            serde_json::from_str::<Defaulted>(r#"{ "pageSize": 20, "sortBy": "created_at" }"#)
+                .unwrap_err();
+        assert_eq!(vec!["sortBy"], embedded_fields(err));
+    }
+
+    #[test]
+    fn matrix_tag() {
+        assert_eq!(
+            Tagged {
+                label: "new".to_string(),
+            },
+            serde_json::from_str(r#"{ "type": "Tagged", "label": "new" }"#).unwrap()
+        );
+        let err = serde_json::from_str::<Tagged>(r#"{ "type": "Tagged", "label": "urgent" }"#)
+            .unwrap_err();
+        assert_eq!(vec!["label"], embedded_fields(err));
+    }
+
+    #[test]
+    fn matrix_deny_unknown_fields() {
+        let err =
+            serde_json::from_str::<Strict>(r#"{ "userName": "jo", "role": "admin" }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `role`"));
+        assert_eq!(None, ValidationErrors::from_embedded(&err.to_string()));
+
+        let err = serde_json::from_str::<Strict>(r#"{ "userName": "joseph" }"#).unwrap_err();
+        assert_eq!(vec!["userName"], embedded_fields(err));
+    }
+}
@@ -0,0 +1,92 @@
+use validation::{constant_time_eq, ValidationContext};
+
+// Stand-in for an HMAC, a keyed checksum of the payload in hex
+fn sign(payload: &[u8]) -> String {
+    let checksum = b"webhook-secret"
+        .iter()
+        .chain(payload)
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100_0000_01b3)
+        });
+    format!("{:016x}", checksum)
+}
+
+fn verify_signature(payload: &[u8], signature: &String, _ctx: &ValidationContext) -> bool {
+    constant_time_eq(sign(payload).as_bytes(), signature.as_bytes())
+}
+
+#[derive(Deserialize, FieldValidate)]
+#[validate(signed(
+    field = "signature",
+    payload_fields(event, amount),
+    verifier = "crate::signed::verify_signature"
+))]
+struct Webhook {
+    #[serde(rename = "type")]
+    event: String,
+    amount: u64,
+    // Not signed, the sender can set it freely
+    #[validate(length(max = 64))]
+    note: String,
+    signature: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signed::{sign, Webhook};
+    use serde_json::json;
+    use validation::{FieldConstraintDescription, FieldValidation, ValidationError};
+
+    fn webhook(signature: Option<String>) -> Webhook {
+        serde_json::from_value(json!({
+            "type": "payment.settled",
+            "amount": 1250,
+            "note": "first order",
+            "signature": signature,
+        }))
+        .unwrap()
+    }
+
+    fn signature() -> String {
+        sign(br#"{"amount":1250,"type":"payment.settled"}"#)
+    }
+
+    #[test]
+    fn validate_signed_successful() {
+        assert!(webhook(Some(signature())).validate_fields().is_ok());
+
+        let mut unsigned_change = webhook(Some(signature()));
+        unsigned_change.note = "second order".to_string();
+        assert!(unsigned_change.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_signed_tampered() {
+        let mut tampered = webhook(Some(signature()));
+        tampered.amount = 125_000;
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("signed".to_string())),
+            tampered.validate_fields()
+        );
+
+        let forged = webhook(Some("0".repeat(16)));
+        assert!(forged.validate_fields().is_err());
+    }
+
+    #[test]
+    fn validate_signed_missing_signature() {
+        let errors = webhook(None).field_errors(&Default::default());
+        assert_eq!(
+            vec![Some("signature".to_string())],
+            errors.iter().map(|e| e.field.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn describe_signed() {
+        assert!(Webhook::describe_constraints().contains(
+            &FieldConstraintDescription::new("signature", "signed", None)
+                .param("payload_fields", vec!["type", "amount"])
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+#[derive(Deserialize, FieldValidate)]
+struct CreateOrder {
+    #[validate(source = "header", length(min = 1))]
+    #[serde(rename = "X-Tenant-Id")]
+    tenant_id: String,
+    #[validate(source = "path", length(max = 8))]
+    order_id: String,
+    #[validate(source = "query", range(max = 100))]
+    limit: u32,
+    #[validate(length(min = 1))]
+    note: String,
+    #[validate(source = "body", nested)]
+    items: Vec<Item>,
+    #[validate(source = "header")]
+    #[serde(rename = "X-Request-Id")]
+    #[allow(dead_code)]
+    request_id: String,
+}
+
+#[derive(Deserialize, FieldValidate)]
+struct Item {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sources::{CreateOrder, Item};
+    use validation::{FieldSource, FieldValidation, ValidationContext, ValidationErrors};
+
+    #[test]
+    fn field_sources() {
+        assert_eq!(
+            &[
+                ("X-Tenant-Id", FieldSource::Header),
+                ("order_id", FieldSource::Path),
+                ("limit", FieldSource::Query),
+                ("items", FieldSource::Body),
+                ("X-Request-Id", FieldSource::Header),
+            ],
+            CreateOrder::field_sources()
+        );
+    }
+
+    #[test]
+    fn errors_attributed_to_sources() {
+        let order = CreateOrder {
+            tenant_id: String::new(),
+            order_id: "order-000001".to_string(),
+            limit: 500,
+            note: String::new(),
+            items: vec![Item {
+                name: String::new(),
+            }],
+            request_id: String::new(),
+        };
+        let errors = order.field_errors(&ValidationContext::default());
+
+        assert_eq!(
+            vec![
+                Some("header:X-Tenant-Id".to_string()),
+                Some("path:order_id".to_string()),
+                Some("query:limit".to_string()),
+                None,
+                Some("body:/items/0/name".to_string()),
+            ],
+            errors.iter().map(|err| err.location()).collect::<Vec<_>>()
+        );
+
+        let json = errors.to_json();
+        assert!(json.contains(
+            r#""field":"X-Tenant-Id","kind":"field_mismatch","code":"length","source":"header""#
+        ));
+        let round_trip: ValidationErrors = serde_json::from_str(&json).unwrap();
+        assert_eq!(errors, round_trip);
+    }
+}
@@ -0,0 +1,47 @@
+#[derive(Debug, DeserializeValidated, FieldValidate)]
+#[serde(deny_unknown_fields)]
+#[validation(strict_serde = "deny")]
+struct Payment {
+    #[validate(range(min = 1))]
+    amount: u64,
+}
+
+// Drifts are compile warnings, allowed here to keep the build clean
+#[allow(deprecated)]
+mod drifting {
+    #[derive(Deserialize, FieldValidate)]
+    #[validation(strict_serde = "warn")]
+    pub struct Refund {
+        #[validate(length(max = 64))]
+        pub reason: String,
+        #[serde(skip_deserializing)]
+        #[validate(range(max = 3))]
+        pub attempts: u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strict_serde::drifting::Refund;
+    use crate::strict_serde::Payment;
+
+    #[test]
+    fn strict_serde_matching() {
+        assert!(Payment::serde_drift().is_empty());
+
+        let err =
+            serde_json::from_str::<Payment>(r#"{ "amount": 5, "currency": "EUR" }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `currency`"));
+    }
+
+    #[test]
+    fn strict_serde_drift() {
+        assert_eq!(
+            &[
+                "`Refund` accepts unknown fields, add #[serde(deny_unknown_fields)] or deny them with `unknown_fields = \"deny_unknown\"`",
+                "field `attempts` has rules but serde never deserializes it",
+            ],
+            Refund::serde_drift()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::SystemTime;
+
+#[derive(FieldValidate)]
+struct Booking {
+    #[validate(past)]
+    created_at: SystemTime,
+    #[validate(future)]
+    starts_at: Option<SystemTime>,
+    #[validate(not_null, min_age = 18)]
+    guest_born_on: Option<SystemTime>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::temporal::Booking;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use validation::{
+        FieldValidation, FixedClock, ValidationContext, ValidationEnvironment, ValidationError,
+    };
+
+    const DAY: u64 = 86_400;
+
+    // 2020-09-13T12:26:40Z
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_600_000_000)
+    }
+
+    fn ctx() -> ValidationContext {
+        ValidationContext::new(ValidationEnvironment::new().with_clock(FixedClock(now())))
+    }
+
+    #[test]
+    fn validate_temporal_successful() {
+        let b = Booking {
+            created_at: now() - Duration::from_secs(DAY),
+            starts_at: Some(now() + Duration::from_secs(DAY)),
+            // 2000-01-01
+            guest_born_on: Some(UNIX_EPOCH + Duration::from_secs(946_684_800)),
+        };
+
+        assert!(b.validate_fields_with(&ctx()).is_ok());
+    }
+
+    #[test]
+    fn validate_temporal_uses_system_clock_by_default() {
+        let b = Booking {
+            created_at: now() - Duration::from_secs(DAY),
+            starts_at: Some(now() + Duration::from_secs(DAY)),
+            guest_born_on: Some(UNIX_EPOCH + Duration::from_secs(946_684_800)),
+        };
+
+        // Fine against the fixed clock, but 2020 is long gone for the system clock
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("future".to_string())),
+            b.validate_fields()
+        );
+    }
+
+    #[test]
+    fn validate_past_failed() {
+        let b = Booking {
+            created_at: now() + Duration::from_secs(1),
+            starts_at: Some(now() + Duration::from_secs(DAY)),
+            guest_born_on: Some(UNIX_EPOCH + Duration::from_secs(946_684_800)),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("past".to_string())),
+            b.validate_fields_with(&ctx())
+        );
+    }
+
+    #[test]
+    fn validate_future_deterministic_with_fixed_clock() {
+        let b = Booking {
+            created_at: now() - Duration::from_secs(DAY),
+            starts_at: Some(now()),
+            guest_born_on: Some(UNIX_EPOCH + Duration::from_secs(946_684_800)),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("future".to_string())),
+            b.validate_fields_with(&ctx())
+        );
+        assert!(Booking {
+            starts_at: None,
+            ..b
+        }
+        .validate_fields_with(&ctx())
+        .is_ok());
+    }
+
+    #[test]
+    fn validate_min_age_failed() {
+        let b = Booking {
+            created_at: now() - Duration::from_secs(DAY),
+            starts_at: Some(now() + Duration::from_secs(DAY)),
+            // 2003-01-01
+            guest_born_on: Some(UNIX_EPOCH + Duration::from_secs(1_041_379_200)),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("min_age".to_string())),
+            b.validate_fields_with(&ctx())
+        );
+    }
+}
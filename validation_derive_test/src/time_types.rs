@@ -0,0 +1,121 @@
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+#[derive(FieldValidate)]
+struct Reservation {
+    #[validate(past)]
+    booked_at: OffsetDateTime,
+    #[validate(future, range(max = "2021-12-31T23:59:59"))]
+    check_in: PrimitiveDateTime,
+    #[validate(not_null, min_age = 18)]
+    guest_born_on: Option<Date>,
+    #[validate(range(min = "2020-01-01", exclusive_max = "2021-01-01"))]
+    season_starts_on: Option<Date>,
+    #[validate(range(min = "2020-09-13T14:00:00+02:00"))]
+    confirmed_at: OffsetDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::time_types::Reservation;
+    use std::time::{Duration, UNIX_EPOCH};
+    use time::{Date, Month, OffsetDateTime};
+    use validation::{
+        FieldValidation, FixedClock, ValidationContext, ValidationEnvironment, ValidationError,
+    };
+
+    // 2020-09-13T12:26:40Z
+    const NOW: i64 = 1_600_000_000;
+
+    fn ctx() -> ValidationContext {
+        ValidationContext::new(
+            ValidationEnvironment::new()
+                .with_clock(FixedClock(UNIX_EPOCH + Duration::from_secs(NOW as u64))),
+        )
+    }
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn at(timestamp: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(timestamp).unwrap()
+    }
+
+    #[test]
+    fn validate_time_types_successful() {
+        let r = Reservation {
+            booked_at: at(NOW - 86_400),
+            check_in: date(2020, Month::October, 1).with_hms(15, 0, 0).unwrap(),
+            guest_born_on: Some(date(2000, Month::January, 1)),
+            season_starts_on: Some(date(2020, Month::June, 21)),
+            confirmed_at: at(NOW),
+        };
+
+        assert!(r.validate_fields_with(&ctx()).is_ok());
+    }
+
+    #[test]
+    fn validate_time_past_failed() {
+        let r = Reservation {
+            booked_at: at(NOW + 1),
+            check_in: date(2020, Month::October, 1).with_hms(15, 0, 0).unwrap(),
+            guest_born_on: Some(date(2000, Month::January, 1)),
+            season_starts_on: Some(date(2020, Month::June, 21)),
+            confirmed_at: at(NOW),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("past".to_string())),
+            r.validate_fields_with(&ctx())
+        );
+    }
+
+    #[test]
+    fn validate_time_min_age_failed() {
+        let r = Reservation {
+            booked_at: at(NOW - 86_400),
+            check_in: date(2020, Month::October, 1).with_hms(15, 0, 0).unwrap(),
+            guest_born_on: Some(date(2002, Month::September, 14)),
+            season_starts_on: Some(date(2020, Month::June, 21)),
+            confirmed_at: at(NOW),
+        };
+
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("min_age".to_string())),
+            r.validate_fields_with(&ctx())
+        );
+    }
+
+    #[test]
+    fn validate_time_range_failed() {
+        let late = Reservation {
+            booked_at: at(NOW - 86_400),
+            check_in: date(2022, Month::January, 1).midnight(),
+            guest_born_on: Some(date(2000, Month::January, 1)),
+            season_starts_on: Some(date(2020, Month::June, 21)),
+            confirmed_at: at(NOW),
+        };
+        let next_season = Reservation {
+            booked_at: at(NOW - 86_400),
+            check_in: date(2020, Month::October, 1).with_hms(15, 0, 0).unwrap(),
+            guest_born_on: Some(date(2000, Month::January, 1)),
+            season_starts_on: Some(date(2021, Month::January, 1)),
+            confirmed_at: at(NOW),
+        };
+        // 2020-09-13T11:59:59Z, a second before the bound in UTC+2
+        let early = Reservation {
+            booked_at: at(NOW - 86_400),
+            check_in: date(2020, Month::October, 1).with_hms(15, 0, 0).unwrap(),
+            guest_born_on: Some(date(2000, Month::January, 1)),
+            season_starts_on: Some(date(2020, Month::June, 21)),
+            confirmed_at: at(1_599_998_399),
+        };
+
+        for r in [late, next_season, early] {
+            assert_eq!(
+                Err(ValidationError::FieldMismatch("range".to_string())),
+                r.validate_fields_with(&ctx())
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::Value;
+
+#[derive(Deserialize, FieldValidate)]
+#[validation(unknown_fields = "deny_unknown")]
+struct OrderV2 {
+    #[validate(length(max = 8))]
+    reference: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Deserialize, FieldValidate)]
+#[validation(unknown_fields = "warn_unknown")]
+struct OrderV1 {
+    #[validate(length(max = 8))]
+    reference: String,
+    #[serde(flatten)]
+    extra: BTreeMap<String, Value>,
+}
+
+#[derive(Deserialize, FieldValidate)]
+struct OrderBeta {
+    #[validate(length(max = 8))]
+    reference: String,
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unknown_fields::{OrderBeta, OrderV1, OrderV2};
+    use std::sync::{Arc, Mutex};
+    use validation::{
+        FieldError, FieldValidation, ValidationContext, ValidationEnvironment, ValidationError,
+        ValidationObserver,
+    };
+
+    const PAYLOAD: &str = r#"{ "reference": "A-1", "priority": 1, "coupon": "X" }"#;
+
+    #[derive(Default)]
+    struct Warnings(Mutex<Vec<String>>);
+
+    impl ValidationObserver for Warnings {
+        fn on_warning(&self, field: &str, _error: &ValidationError) {
+            self.0.lock().unwrap().push(field.to_string());
+        }
+    }
+
+    #[test]
+    fn validate_deny_unknown() {
+        let order: OrderV2 = serde_json::from_str(PAYLOAD).unwrap();
+        let unknown = |field: &str| {
+            FieldError::new(
+                field,
+                ValidationError::FieldMismatch("unknown_field".to_string()),
+            )
+        };
+        assert_eq!(
+            vec![unknown("coupon"), unknown("priority")],
+            order
+                .field_errors(&ValidationContext::default())
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+
+        let order: OrderV2 = serde_json::from_str(r#"{ "reference": "A-1" }"#).unwrap();
+        assert!(order.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_warn_unknown() {
+        let warnings = Arc::new(Warnings::default());
+        let ctx =
+            ValidationContext::new(ValidationEnvironment::new().with_observer(warnings.clone()));
+        let order: OrderV1 = serde_json::from_str(PAYLOAD).unwrap();
+
+        assert!(order.validate_fields_with(&ctx).is_ok());
+        assert_eq!(
+            vec!["coupon".to_string(), "priority".to_string()],
+            *warnings.0.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_allow_unknown() {
+        let order: OrderBeta = serde_json::from_str(PAYLOAD).unwrap();
+        assert!(order.validate_fields().is_ok());
+        assert_eq!(2, order.extra.len());
+    }
+}
@@ -0,0 +1,160 @@
+#[derive(FieldValidate, UpdateValidate, Serialize)]
+struct Order {
+    #[validate(immutable)]
+    id: u64,
+    #[validate(immutable, length(max = 8))]
+    currency: String,
+    #[validate(monotonic_increase)]
+    #[serde(rename = "revision")]
+    version: u32,
+    #[validate(monotonic_increase)]
+    shipped_items: Option<u32>,
+}
+
+// Not `PartialEq`, only fields with an update rule are compared
+#[derive(Serialize)]
+struct Note(String);
+
+#[derive(FieldValidate, UpdateValidate, Serialize)]
+struct Ticket {
+    #[validate(immutable)]
+    id: u64,
+    note: Note,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::update::{Note, Order, Ticket};
+    use validation::{FieldValidation, UpdateValidation, ValidationError};
+
+    #[test]
+    fn validate_update_successful() {
+        let previous = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+        let updated = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 2,
+            shipped_items: Some(3),
+        };
+
+        assert!(updated.validate_update(&previous).is_ok());
+        assert!(previous.validate_update(&previous).is_ok());
+    }
+
+    #[test]
+    fn validate_update_immutable() {
+        let previous = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+        let updated = Order {
+            id: 1,
+            currency: "USD".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+
+        assert_eq!(
+            Err(ValidationError::InvalidState("immutable".to_string())),
+            updated.validate_update(&previous)
+        );
+    }
+
+    #[test]
+    fn validate_update_monotonic_increase() {
+        let previous = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+        let updated = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 0,
+            shipped_items: Some(2),
+        };
+        assert_eq!(
+            Err(ValidationError::InvalidState(
+                "monotonic_increase".to_string()
+            )),
+            updated.validate_update(&previous)
+        );
+
+        let updated = Order {
+            version: 1,
+            shipped_items: None,
+            ..updated
+        };
+        assert!(updated.validate_update(&previous).is_err());
+    }
+
+    #[test]
+    fn validate_unchanged_except_allowed() {
+        let previous = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+        let updated = Order {
+            id: 1,
+            currency: "USD".to_string(),
+            version: 2,
+            shipped_items: Some(2),
+        };
+
+        assert!(updated
+            .assert_unchanged_except(&previous, &["currency", "revision"])
+            .is_ok());
+        assert!(previous.assert_unchanged_except(&previous, &[]).is_ok());
+        assert_eq!(
+            Err(ValidationError::InvalidState("immutable".to_string())),
+            updated.assert_unchanged_except(&previous, &["currency"])
+        );
+        assert!(updated
+            .assert_unchanged_except(&previous, &["currency", "version"])
+            .is_err());
+    }
+
+    #[test]
+    fn validate_unchanged_except_skips_fields_without_update_rules() {
+        let previous = Ticket {
+            id: 1,
+            note: Note("pending".to_string()),
+        };
+        let updated = Ticket {
+            id: 1,
+            note: Note("shipped".to_string()),
+        };
+        assert!(updated.assert_unchanged_except(&previous, &[]).is_ok());
+
+        let updated = Ticket { id: 2, ..updated };
+        assert!(updated.validate_update(&previous).is_err());
+        assert_eq!(
+            Err(ValidationError::InvalidState("immutable".to_string())),
+            updated.assert_unchanged_except(&previous, &[])
+        );
+    }
+
+    #[test]
+    fn validate_fields_ignores_update_rules() {
+        let o = Order {
+            id: 1,
+            currency: "EUR".to_string(),
+            version: 1,
+            shipped_items: Some(2),
+        };
+
+        assert!(o.validate_fields().is_ok());
+        assert_eq!(1, o.explain().rules.len());
+        assert_eq!(5, Order::describe_constraints().len());
+    }
+}
@@ -0,0 +1,71 @@
+use uuid::Uuid;
+
+#[derive(Default, FieldValidate)]
+#[validation(invalid_examples)]
+struct Device {
+    #[validate(uuid_version = 4)]
+    id: Uuid,
+    #[validate(uuid_not_nil)]
+    owner: Option<Uuid>,
+    #[validate(uuid_version = 1, uuid_not_nil)]
+    batch: Option<Option<Uuid>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::uuids::Device;
+    use uuid::Uuid;
+    use validation::{FieldValidation, ValidationError};
+
+    // A version 1 UUID, from RFC 4122
+    const V1: &str = "c232ab00-9414-11ec-b3c8-9f6bdeced846";
+
+    #[test]
+    fn validate_uuid_successful() {
+        let d = Device {
+            id: Uuid::new_v4(),
+            owner: Some(Uuid::new_v4()),
+            batch: Some(Some(Uuid::parse_str(V1).unwrap())),
+        };
+        assert!(d.validate_fields().is_ok());
+
+        let d = Device {
+            owner: None,
+            batch: Some(None),
+            ..d
+        };
+        assert!(d.validate_fields().is_ok());
+    }
+
+    #[test]
+    fn validate_uuid_failed() {
+        let nil = Device {
+            id: Uuid::nil(),
+            owner: Some(Uuid::nil()),
+            batch: Some(Some(Uuid::nil())),
+        };
+        let codes = nil
+            .field_errors(&Default::default())
+            .iter()
+            .map(|err| (err.field.clone().unwrap(), err.error.clone()))
+            .collect::<Vec<_>>();
+        let mismatch = |code: &str| ValidationError::FieldMismatch(code.to_string());
+        assert_eq!(
+            vec![
+                ("id".to_string(), mismatch("uuid_version")),
+                ("owner".to_string(), mismatch("uuid_not_nil")),
+                ("batch".to_string(), mismatch("uuid_version")),
+                ("batch".to_string(), mismatch("uuid_not_nil")),
+            ],
+            codes
+        );
+    }
+
+    #[test]
+    fn describe_uuid() {
+        let rules = Device::describe_constraints();
+        assert_eq!("uuid_version", rules[0].rule);
+        assert_eq!(Some(&serde_json::json!(4)), rules[0].params.get("version"));
+        assert_eq!(4, Device::invalid_examples().len());
+    }
+}
@@ -0,0 +1,166 @@
+#[derive(FieldValidate)]
+struct Account {
+    #[validate(length(min = 3))]
+    username: String,
+    #[validate(not_null)]
+    email: Option<String>,
+    #[validate(range(min = 18), enforcement = "warn")]
+    age: u8,
+    #[validate(one_of("basic", "pro"))]
+    plan: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation_config::Account;
+    use std::sync::{Arc, Mutex};
+    use validation::{
+        FieldError, FieldValidation, MessageResolver, ValidationConfig, ValidationContext,
+        ValidationError, ValidationMode, ValidationObserver,
+    };
+
+    #[derive(Default)]
+    struct Warnings(Mutex<Vec<String>>);
+
+    impl ValidationObserver for Warnings {
+        fn on_warning(&self, field: &str, _error: &ValidationError) {
+            self.0.lock().unwrap().push(field.to_string());
+        }
+    }
+
+    struct French;
+
+    impl MessageResolver for French {
+        fn resolve(&self, error: &FieldError, locale: &str) -> Option<String> {
+            match error.error {
+                ValidationError::FieldMismatch(ref code)
+                    if code == "length" && locale == "fr-FR" =>
+                {
+                    Some("longueur invalide".to_string())
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn fields(account: &Account, ctx: &ValidationContext) -> Vec<Option<String>> {
+        account
+            .field_errors(ctx)
+            .into_iter()
+            .map(|err| err.field)
+            .collect()
+    }
+
+    #[test]
+    fn config_collects_all_by_default() {
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+
+        assert_eq!(3, fields(&account, &ValidationContext::default()).len());
+    }
+
+    #[test]
+    fn config_mode_and_max_errors() {
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+        let fail_fast = ValidationConfig::new().with_mode(ValidationMode::FailFast);
+        assert_eq!(
+            vec![Some("username".to_string())],
+            fields(
+                &account,
+                &ValidationContext::default().with_config(fail_fast)
+            )
+        );
+
+        let two = ValidationConfig::new().with_max_errors(2);
+        assert_eq!(
+            vec![Some("username".to_string()), Some("email".to_string())],
+            fields(&account, &ValidationContext::default().with_config(two))
+        );
+    }
+
+    #[test]
+    fn config_max_errors_zero_reports_the_first() {
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+        let ctx =
+            ValidationContext::default().with_config(ValidationConfig::new().with_max_errors(0));
+        assert!(account.validate_fields_with(&ctx).is_err());
+        assert_eq!(vec![Some("username".to_string())], fields(&account, &ctx));
+    }
+
+    #[test]
+    fn config_rules_left_not_run() {
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+        // The warning on `age` is only reported when its rule runs
+        let run = |config: ValidationConfig| {
+            let warnings = Arc::new(Warnings::default());
+            let config = config.with_observer(warnings.clone());
+            account.field_errors(&ValidationContext::default().with_config(config));
+            let warned = warnings.0.lock().unwrap().clone();
+            warned
+        };
+
+        assert!(run(ValidationConfig::new().with_mode(ValidationMode::FailFast)).is_empty());
+        assert!(run(ValidationConfig::new().with_max_errors(2)).is_empty());
+        assert_eq!(
+            vec!["age".to_string()],
+            run(ValidationConfig::new().with_max_errors(3))
+        );
+    }
+
+    #[test]
+    fn config_observer() {
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+        let warnings = Arc::new(Warnings::default());
+        let config = ValidationConfig::new().with_observer(warnings.clone());
+        account.field_errors(&ValidationContext::default().with_config(config));
+        assert_eq!(vec!["age".to_string()], *warnings.0.lock().unwrap());
+    }
+
+    #[test]
+    fn config_message_resolver() {
+        let config = ValidationConfig::new().with_message_resolver(Arc::new(French));
+        let account = Account {
+            username: "ab".to_string(),
+            email: None,
+            age: 16,
+            plan: "gold".to_string(),
+        };
+        let errors = account.field_errors(&ValidationContext::default());
+        let messages = errors
+            .iter()
+            .map(|err| config.message(err, "fr-FR"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "longueur invalide",
+                "value is required",
+                "value is not one of the allowed values"
+            ],
+            messages
+        );
+    }
+}
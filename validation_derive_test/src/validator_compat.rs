@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+#[derive(FieldValidate, Serialize)]
+struct Address {
+    #[validate(required)]
+    city: Option<String>,
+    #[validate(length(equal = 5))]
+    zip: String,
+}
+
+#[derive(FieldValidate, Serialize)]
+struct Customer {
+    #[validate(length(min = 1, max = 16))]
+    name: String,
+    #[validate(nested)]
+    address: Address,
+    #[validate(nested)]
+    contacts: Vec<Address>,
+    #[validate(length(min = 1))]
+    tags: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validator_compat::{Address, Customer};
+    use std::collections::HashMap;
+    use validation::{
+        FieldError, FieldValidation, ValidationContext, ValidationError, ValidationErrors,
+    };
+    use validator::ValidationErrorsKind;
+
+    fn address(zip: &str) -> Address {
+        Address {
+            city: Some("Lyon".to_string()),
+            zip: zip.to_string(),
+        }
+    }
+
+    #[test]
+    fn validator_attribute_names() {
+        assert!(address("69001").validate_fields().is_ok());
+        assert_eq!(
+            Err(ValidationError::field_mismatch("length")),
+            address("6900").validate_fields()
+        );
+        assert_eq!(
+            Err(ValidationError::field_mismatch("not_null")),
+            Address {
+                city: None,
+                ..address("69001")
+            }
+            .validate_fields()
+        );
+    }
+
+    #[test]
+    fn validator_errors_nested() {
+        let customer = Customer {
+            name: String::new(),
+            address: Address {
+                city: None,
+                zip: "69001".to_string(),
+            },
+            contacts: vec![address("69001"), address("690")],
+            tags: HashMap::new(),
+        };
+        let errors = customer.field_errors(&ValidationContext::default());
+        let converted = validator::ValidationErrors::from(errors);
+
+        assert_eq!("length", converted.field_errors()["name"][0].code);
+        match converted.errors()["address"] {
+            ValidationErrorsKind::Struct(ref address) => {
+                assert_eq!("required", address.field_errors()["city"][0].code)
+            }
+            ref kind => panic!("address is not a struct: {:?}", kind),
+        }
+        match converted.errors()["contacts"] {
+            ValidationErrorsKind::List(ref contacts) => {
+                assert_eq!(vec![&1], contacts.keys().collect::<Vec<_>>());
+                assert_eq!("length", contacts[&1].field_errors()["zip"][0].code);
+            }
+            ref kind => panic!("contacts is not a list: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn validator_errors_round_trip() {
+        let customer = Customer {
+            name: String::new(),
+            address: Address {
+                city: None,
+                zip: "69001".to_string(),
+            },
+            contacts: vec![address("69001"), address("690")],
+            tags: HashMap::new(),
+        };
+        let mut errors = customer.field_errors(&ValidationContext::default());
+        errors.push(
+            FieldError::new("name", ValidationError::field_mismatch("one_of"))
+                .with_param("suggestion", "Ada"),
+        );
+        errors.push(FieldError::unattached(ValidationError::invalid_state(
+            "closed",
+        )));
+        errors.push(FieldError::new(
+            "status",
+            ValidationError::invalid_transition("order", "SHIPPED", "DRAFT", &["RETURNED"]),
+        ));
+        // Element errors have no place of their own in `validator`
+        errors.push(FieldError::new(
+            "contacts[0]",
+            ValidationError::field_mismatch("length"),
+        ));
+
+        let mut expected = errors.iter().cloned().collect::<Vec<_>>();
+        let mut round_trip = ValidationErrors::from(validator::ValidationErrors::from(errors))
+            .into_iter()
+            .collect::<Vec<_>>();
+        let key = |err: &FieldError| serde_json::to_string(err).unwrap();
+        expected.sort_by_key(key);
+        round_trip.sort_by_key(key);
+        assert_eq!(expected, round_trip);
+    }
+
+    #[test]
+    fn validator_errors_message() {
+        let mut errors = validator::ValidationErrors::new();
+        errors.add(
+            "email",
+            validator::ValidationError::new("email").with_message("not an email".into()),
+        );
+
+        let converted = ValidationErrors::from(errors);
+        let error = converted.iter().next().unwrap();
+        assert_eq!(ValidationError::field_mismatch("email"), error.error);
+        assert_eq!(
+            Some(&serde_json::json!("not an email")),
+            error.params.get("message")
+        );
+        assert_eq!(
+            Some("not an email"),
+            validator::ValidationErrors::from(converted).field_errors()["email"][0]
+                .message
+                .as_deref()
+        );
+    }
+}
@@ -0,0 +1,98 @@
+#[derive(FieldValidate)]
+#[validation(version = 2)]
+struct Customer {
+    #[validate(length(max = 8), until_version = 1)]
+    #[validate(length(max = 16), since_version = 2)]
+    name: String,
+    #[validate(not_null, since_version = 2)]
+    email: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::versions::Customer;
+    use validation::{
+        parse_api_version, FieldConstraintDescription, FieldValidation, PlannedRuleStatus,
+        SkipReason, ValidationContext, ValidationError,
+    };
+
+    fn customer(name: &str, email: Option<&str>) -> Customer {
+        Customer {
+            name: name.to_string(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    fn version(header: &str) -> ValidationContext {
+        ValidationContext::default().with_api_version(parse_api_version(header).unwrap())
+    }
+
+    #[test]
+    fn validate_versions_per_api_version() {
+        let v1_customer = customer("Ada Lovelace", None);
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            v1_customer.validate_fields_with(&version("v1"))
+        );
+
+        let v2_customer = customer("Ada Lovelace", Some("ada@example.com"));
+        assert!(v2_customer.validate_fields_with(&version("v2")).is_ok());
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("not_null".to_string())),
+            customer("Ada", None).validate_fields_with(&version("v2"))
+        );
+        assert!(customer("Ada", None)
+            .validate_fields_with(&version("v1"))
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_versions_latest_by_default() {
+        assert!(customer("Ada Lovelace", Some("ada@example.com"))
+            .validate_fields()
+            .is_ok());
+        assert!(customer("Ada", None).validate_fields().is_err());
+    }
+
+    #[test]
+    fn validate_versions_unsupported() {
+        let errors = customer("Ada", Some("ada@example.com")).field_errors(&version("v3"));
+        let error = errors.iter().next().unwrap();
+        assert_eq!(
+            ValidationError::InvalidState("api_version".to_string()),
+            error.error
+        );
+        assert_eq!(Some(&serde_json::json!(2)), error.params.get("latest"));
+        assert_eq!(1, errors.iter().count());
+    }
+
+    #[test]
+    fn describe_versions() {
+        assert_eq!(
+            FieldConstraintDescription::new("name", "length", None)
+                .param("max", 8)
+                .versions(None, Some(1)),
+            Customer::describe_constraints()[0]
+        );
+        assert!(Customer::describe_constraints()[1].applies_to(2));
+        assert!(!Customer::describe_constraints()[1].applies_to(1));
+    }
+
+    #[test]
+    fn explain_versions() {
+        let plan = customer("Ada", Some("ada@example.com")).explain();
+        let statuses = plan
+            .rules
+            .iter()
+            .map(|rule| rule.status.clone())
+            .collect::<Vec<_>>();
+        assert!(statuses.contains(&PlannedRuleStatus::Skipped(SkipReason::ApiVersion(2))));
+        assert_eq!(
+            2,
+            statuses
+                .iter()
+                .filter(|status| **status == PlannedRuleStatus::Run)
+                .count()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+#[derive(PartialEq)]
+enum Channel {
+    Sms,
+    Email,
+}
+
+#[derive(FieldValidate)]
+struct Notification {
+    channel: Channel,
+    #[validate(length(max = 10), when = "self.channel == Channel::Sms")]
+    #[validate(length(min = 1))]
+    body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::when::{Channel, Notification};
+    use validation::{
+        FieldConstraintDescription, FieldValidation, PlannedRuleStatus, SkipReason, ValidationError,
+    };
+
+    fn notification(channel: Channel, body: &str) -> Notification {
+        Notification {
+            channel,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn when_condition_holds() {
+        assert_eq!(
+            Err(ValidationError::FieldMismatch("length".to_string())),
+            notification(Channel::Sms, "Hello from the team").validate_fields()
+        );
+        assert!(notification(Channel::Sms, "Hello")
+            .validate_fields()
+            .is_ok());
+    }
+
+    #[test]
+    fn when_condition_does_not_hold() {
+        assert!(notification(Channel::Email, "Hello from the team")
+            .validate_fields()
+            .is_ok());
+        // Rules of other attributes are not guarded
+        assert!(notification(Channel::Email, "").validate_fields().is_err());
+    }
+
+    #[test]
+    fn describe_when() {
+        assert_eq!(
+            FieldConstraintDescription::new("body", "length", None)
+                .param("max", 10)
+                .when("self.channel == Channel::Sms"),
+            Notification::describe_constraints()[0]
+        );
+    }
+
+    #[test]
+    fn explain_when() {
+        let plan = notification(Channel::Email, "Hello").explain();
+        assert_eq!(
+            PlannedRuleStatus::Skipped(SkipReason::Condition(
+                "self.channel == Channel::Sms".to_string()
+            )),
+            plan.rules[0].status
+        );
+        assert!(notification(Channel::Sms, "Hello").explain().rules[0].will_run());
+    }
+}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(canonical_identifier(max = 0))]
+    team: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(date_format(style = "lunar"))]
+    birthday: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(length(minimum = 1))]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(one_of("basic", "pro", collation = "klingon"))]
+    plan: String,
+}
+
+fn main() {}
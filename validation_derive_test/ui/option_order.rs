@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(not_null, order = "first")]
+    email: Option<String>,
+}
+
+fn main() {}
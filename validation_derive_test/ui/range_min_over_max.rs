@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(range(min = 65, exclusive_max = 18))]
+    age: u8,
+}
+
+fn main() {}
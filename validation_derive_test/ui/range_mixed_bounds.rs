@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(range(min = 1, max = "z"))]
+    age: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(recently_seen(window = 60, key_fn = "not a path"))]
+    request_id: String,
+}
+
+fn main() {}
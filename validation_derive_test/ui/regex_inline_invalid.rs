@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(regex_inline = "^[a-z+$")]
+    name: String,
+}
+
+fn main() {}
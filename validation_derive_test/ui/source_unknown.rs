@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Signup {
+    #[validate(source = "cookie", length(max = 8))]
+    session: String,
+}
+
+fn main() {}
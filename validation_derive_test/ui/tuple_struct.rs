@@ -0,0 +1,7 @@
+#[macro_use]
+extern crate validation_derive;
+
+#[derive(FieldValidate)]
+struct Code(#[validate(length(max = 8))] String);
+
+fn main() {}